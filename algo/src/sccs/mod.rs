@@ -113,7 +113,7 @@ impl Sccs {
         self.components
             .iter_mut()
             .for_each(|node_component| *node_component = inv_perm[*node_component]);
-        sizes.sort_by(|&x, &y| y.cmp(&x));
+        sizes.sort_by_key(|&x| std::cmp::Reverse(x));
         sizes
     }
 
@@ -132,7 +132,7 @@ impl Sccs {
         self.components
             .par_iter_mut()
             .for_each(|node_component| *node_component = inv_perm[*node_component]);
-        sizes.sort_by(|&x, &y| y.cmp(&x));
+        sizes.sort_by_key(|&x| std::cmp::Reverse(x));
         sizes
     }
 }
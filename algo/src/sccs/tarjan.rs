@@ -51,6 +51,7 @@ pub fn tarjan(graph: impl RandomAccessGraph, pl: &mut impl ProgressLog) -> Sccs
                     index -= 1;
                     lead.push(true);
                 }
+                #[allow(clippy::collapsible_match)]
                 EventPred::Revisit { node, pred, .. } => {
                     // curr has not been emitted yet but it has a higher link
                     if high_link[pred] < high_link[node] {
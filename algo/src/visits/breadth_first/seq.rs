@@ -278,7 +278,7 @@ pub struct BfsOrder<'a, G: RandomAccessGraph> {
 }
 
 impl<G: RandomAccessGraph> BfsOrder<'_, G> {
-    pub fn new(visit: &mut Seq<G>) -> BfsOrder<G> {
+    pub fn new(visit: &mut Seq<G>) -> BfsOrder<'_, G> {
         visit.reset(); // ensure we start from a clean state
         BfsOrder {
             visit,
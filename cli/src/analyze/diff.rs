@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::{GlobalArgs, NumThreadsArg};
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::prelude::*;
+use std::io::Write;
+use std::path::PathBuf;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "diff", about = "Writes to standard out the differences between two graphs as a list of arcs. Each arc comprises a pair of nodes followed by `-` if the arc appears only in the first graph, or by `+` if it appears only in the second graph, separated by a TAB (but the separator is customizable). The number of differences is logged at the end.", long_about = None)]
+pub struct CliArgs {
+    /// The basename of the first graph.
+    pub first: PathBuf,
+    /// The basename of the second graph.
+    pub second: PathBuf,
+
+    #[arg(long, default_value_t = '\t')]
+    /// The separator between the fields of each line.
+    pub separator: char,
+
+    #[clap(flatten)]
+    pub num_threads: NumThreadsArg,
+}
+
+pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    let endianness = get_endianness(&args.first)?;
+    let second_endianness = get_endianness(&args.second)?;
+    ensure!(
+        endianness == second_endianness,
+        "The graphs have different endianness: {} != {}",
+        endianness,
+        second_endianness
+    );
+    match endianness.as_str() {
+        #[cfg(feature = "be_bins")]
+        BE::NAME => diff::<BE>(global_args, args),
+        #[cfg(feature = "le_bins")]
+        LE::NAME => diff::<LE>(global_args, args),
+        e => panic!("Unknown endianness: {}", e),
+    }
+}
+
+pub fn diff<E: Endianness + 'static>(_global_args: GlobalArgs, args: CliArgs) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Send + Sync + Clone,
+{
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+
+    if args.first.with_extension(EF_EXTENSION).exists()
+        && args.second.with_extension(EF_EXTENSION).exists()
+    {
+        let first = BvGraph::with_basename(&args.first)
+            .endianness::<E>()
+            .load()
+            .with_context(|| format!("Could not load graph {}", args.first.display()))?;
+        let second = BvGraph::with_basename(&args.second)
+            .endianness::<E>()
+            .load()
+            .with_context(|| format!("Could not load graph {}", args.second.display()))?;
+        write_diff(&args, &first, &second, &thread_pool)
+    } else {
+        let first = BvGraphSeq::with_basename(&args.first)
+            .endianness::<E>()
+            .load()
+            .with_context(|| format!("Could not load graph {}", args.first.display()))?;
+        let second = BvGraphSeq::with_basename(&args.second)
+            .endianness::<E>()
+            .load()
+            .with_context(|| format!("Could not load graph {}", args.second.display()))?;
+        write_diff(&args, &first, &second, &thread_pool)
+    }
+}
+
+fn write_diff<G0, G1>(
+    args: &CliArgs,
+    first: &G0,
+    second: &G1,
+    thread_pool: &rayon::ThreadPool,
+) -> Result<()>
+where
+    G0: SequentialGraph + SplitLabeling,
+    G1: SequentialGraph + SplitLabeling,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+{
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    let sep = args.separator;
+    let mut write_node = |node_diff: &NodeDiff<()>| -> Result<()> {
+        for (dst, _) in &node_diff.removed {
+            writeln!(stdout, "{}{}{}{}-", node_diff.node, sep, dst, sep)?;
+        }
+        for (dst, _) in &node_diff.added {
+            writeln!(stdout, "{}{}{}{}+", node_diff.node, sep, dst, sep)?;
+        }
+        Ok(())
+    };
+
+    // The differences are written as soon as they are computed
+    let stats = if first.num_nodes() == second.num_nodes() {
+        webgraph::traits::diff_split_with(first, second, thread_pool, |part| {
+            part.nodes.iter().try_for_each(&mut write_node)
+        })?
+    } else {
+        log::warn!(
+            "The graphs have a different number of nodes ({} != {}): computing the differences sequentially",
+            first.num_nodes(),
+            second.num_nodes()
+        );
+        webgraph::traits::diff_with(first, second, |node_diff| write_node(&node_diff))?
+    };
+    stdout.flush()?;
+
+    log::info!(
+        "{} nodes differ: {} arcs removed, {} arcs added",
+        stats.nodes,
+        stats.removed,
+        stats.added
+    );
+    Ok(())
+}
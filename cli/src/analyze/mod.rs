@@ -9,6 +9,7 @@ use anyhow::Result;
 use clap::Subcommand;
 
 pub mod codes;
+pub mod diff;
 
 pub const COMMAND_NAME: &str = "";
 
@@ -17,10 +18,12 @@ pub const COMMAND_NAME: &str = "";
 /// Compute statistics on a graphs.
 pub enum SubCommands {
     Codes(codes::CliArgs),
+    Diff(diff::CliArgs),
 }
 
 pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
     match subcommand {
         SubCommands::Codes(args) => codes::main(global_args, args),
+        SubCommands::Diff(args) => diff::main(global_args, args),
     }
 }
@@ -0,0 +1,176 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::*;
+use anyhow::{bail, ensure, Context, Result};
+use clap::Parser;
+use dsi_bitstream::{dispatch::factory::CodesReaderFactoryHelper, prelude::*};
+use dsi_progress_logger::prelude::*;
+use itertools::Itertools;
+use std::fs::File;
+use std::io::{BufRead, BufWriter};
+use std::path::PathBuf;
+use tempfile::Builder;
+use webgraph::graphs::arc_list_graph::ArcListGraph;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Read from standard input a list of arcs and append their source nodes to an existing BvGraph, without recompressing it. Each arc is specified by a pair of numerical node identifiers separated by a TAB (but the format is customizable), and all sources must be new nodes, that is, at least the number of nodes of the graph (targets can be arbitrary). The graph must have an .offsets file; the .offsets, .ef, and .properties files are updated accordingly.",
+    long_about = None
+)]
+pub struct CliArgs {
+    /// The basename of the graph.
+    pub basename: PathBuf,
+
+    #[arg(long)]
+    /// The number of nodes of the resulting graph; if specified this will be
+    /// used instead of the number inferred. This is useful if you want to add
+    /// disconnected nodes at the end of the graph.
+    pub num_nodes: Option<usize>,
+
+    #[clap(flatten)]
+    pub arcs_args: ArcsArgs,
+
+    #[clap(flatten)]
+    pub batch_size: BatchSizeArg,
+}
+
+pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    match get_endianness(&args.basename)?.as_str() {
+        #[cfg(feature = "be_bins")]
+        BE::NAME => append::<BE>(global_args, args, std::io::stdin().lock()),
+        #[cfg(feature = "le_bins")]
+        LE::NAME => append::<LE>(global_args, args, std::io::stdin().lock()),
+        e => panic!("Unknown endianness: {}", e),
+    }
+}
+
+pub fn append<E: Endianness>(
+    global_args: GlobalArgs,
+    args: CliArgs,
+    file: impl BufRead,
+) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek,
+    for<'a> MemBufReader<'a, E>: BitRead<E>,
+    BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: CodesWrite<E>,
+{
+    ensure!(
+        args.arcs_args.label_column.is_none(),
+        "Arcs cannot be appended to a labeled graph"
+    );
+    let properties_path = args.basename.with_extension(PROPERTIES_EXTENSION);
+    let (first_node, _, _) = parse_properties::<E>(&properties_path)
+        .with_context(|| format!("Could not load {}", properties_path.display()))?;
+
+    let dir = Builder::new().prefix("append_sort_").tempdir()?;
+    let mut group_by = SortPairs::new(args.batch_size.batch_size, &dir)?;
+
+    let mut pl = ProgressLogger::default();
+    pl.display_memory(true)
+        .item_name("lines")
+        .expected_updates(args.arcs_args.max_arcs);
+    if let Some(duration) = global_args.log_interval {
+        pl.log_interval(duration);
+    }
+    pl.start("Reading arcs CSV");
+
+    let mut iter = file.lines();
+    // skip the first few lines
+    for _ in 0..args.arcs_args.lines_to_skip {
+        let _ = iter.next();
+    }
+    let biggest_idx = args
+        .arcs_args
+        .source_column
+        .max(args.arcs_args.target_column);
+    let mut num_nodes = first_node;
+    let mut num_arcs = 0;
+    for (line_num, line) in iter.enumerate() {
+        // break if we reached the end
+        if let Some(max_arcs) = args.arcs_args.max_arcs {
+            if num_arcs > max_arcs {
+                break;
+            }
+        }
+        let line = line.context("Could not read line")?;
+        // skip comment
+        if line.trim().starts_with(args.arcs_args.line_comment_symbol) {
+            continue;
+        }
+
+        // split the csv line into the args
+        let vals = line.split(args.arcs_args.separator).collect::<Vec<_>>();
+
+        if vals.get(biggest_idx).is_none() {
+            log::warn!(
+                "Line {}: {:?} from stdin does not have enough columns: got {} columns but expected at least {} columns separated by {:?} (you can change the separator using the --separator option)",
+                line_num,
+                line,
+                vals.len(),
+                biggest_idx + 1,
+                args.arcs_args.separator,
+            );
+            continue;
+        }
+
+        let src = vals[args.arcs_args.source_column];
+        let dst = vals[args.arcs_args.target_column];
+        let (Ok(src_id), Ok(dst_id)) = (src.parse::<usize>(), dst.parse::<usize>()) else {
+            bail!(
+                "Line {}: {:?} does not contain numerical node identifiers",
+                line_num,
+                line
+            );
+        };
+        ensure!(
+            src_id >= first_node,
+            "Line {}: source {} is not a new node, as the graph has {} nodes",
+            line_num,
+            src_id,
+            first_node
+        );
+
+        num_nodes = num_nodes.max(src_id.max(dst_id) + 1);
+        group_by.push(src_id, dst_id)?;
+        pl.light_update();
+        num_arcs += 1;
+    }
+    pl.done();
+
+    if let Some(user_num_nodes) = args.num_nodes {
+        if user_num_nodes < num_nodes {
+            log::warn!(
+                "The number of nodes specified by --num-nodes={} is smaller than the number of nodes found in the arcs: {}",
+                user_num_nodes,
+                num_nodes
+            );
+        }
+        num_nodes = user_num_nodes.max(first_node);
+    }
+
+    log::info!(
+        "Arcs read: {} Nodes to append: {}",
+        num_arcs,
+        num_nodes - first_node
+    );
+
+    // convert the iter to a graph
+    let g = ArcListGraph::new(
+        num_nodes,
+        group_by
+            .iter()
+            .context("Could not read sorted arcs")?
+            .map(|(src, dst, _)| (src, dst))
+            .dedup(),
+    );
+
+    BvComp::append::<E, _>(&args.basename, g.iter_from(first_node))?;
+    Ok(())
+}
@@ -0,0 +1,635 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::{GlobalArgs, NumThreadsArg};
+use anyhow::{bail, Result};
+use clap::Parser;
+use dsi_bitstream::{dispatch::factory::CodesReaderFactoryHelper, prelude::*};
+use dsi_progress_logger::prelude::*;
+use lender::prelude::*;
+use std::any::Any;
+use std::cell::Cell;
+use std::ops::Range;
+use std::panic::{catch_unwind, AssertUnwindSafe, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "graph", about = "Decodes a graph in parallel checking its integrity.", long_about = None)]
+pub struct CliArgs {
+    /// The basename of the graph.
+    pub src: PathBuf,
+
+    #[clap(flatten)]
+    pub num_threads: NumThreadsArg,
+}
+
+pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    match get_endianness(&args.src)?.as_str() {
+        #[cfg(feature = "be_bins")]
+        BE::NAME => check_graph::<BE>(global_args, args),
+        #[cfg(feature = "le_bins")]
+        LE::NAME => check_graph::<LE>(global_args, args),
+        e => panic!("Unknown endianness: {}", e),
+    }
+}
+
+/// Decodes a graph in parallel, checking the decoded values, the number of
+/// arcs and the checksums recorded in the `.properties` file.
+///
+/// Note that checksums are computed on the decoded successor lists, not on
+/// the bitstream: corruptions of the bitstream that do not change the
+/// successor lists are not detected, and a graph recompressed with different
+/// flags has the same checksums.
+pub fn check_graph<E: Endianness>(global_args: GlobalArgs, args: CliArgs) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Clone + Send + Sync,
+{
+    let problems = find_problems::<E>(
+        &args.src,
+        args.num_threads.num_threads,
+        global_args.log_interval,
+    )?;
+
+    if problems.is_empty() {
+        log::info!("No problems found");
+        return Ok(());
+    }
+    for problem in &problems {
+        log::error!("{}", problem);
+    }
+    bail!(
+        "Found {} problems in {}",
+        problems.len(),
+        args.src.display()
+    );
+}
+
+/// A problem found by [`find_problems`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// The node the problem refers to, if any.
+    pub node: Option<usize>,
+    /// A description of the problem.
+    pub msg: String,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+/// Decodes the graph with the given basename using `num_threads` threads and
+/// returns the problems found, as described in [`check_graph`].
+///
+/// Problems referring to a node come first, sorted by node.
+pub fn find_problems<E: Endianness>(
+    src: &Path,
+    num_threads: usize,
+    log_interval: Option<Duration>,
+) -> Result<Vec<Problem>>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Clone + Send + Sync,
+{
+    let properties_path = src.with_extension(PROPERTIES_EXTENSION);
+    let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(&properties_path)?;
+    let checksums = Checksums::load(&properties_path)?;
+    let block_size = checksums
+        .as_ref()
+        .map_or(CHECKSUM_BLOCK_SIZE, |c| c.block_size());
+    let has_ef = src.with_extension(EF_EXTENSION).is_file();
+
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()?;
+    let mut pl = concurrent_progress_logger![item_name = "node"];
+    pl.display_memory(true).expected_updates(Some(num_nodes));
+    if let Some(duration) = log_interval {
+        pl.log_interval(duration);
+    }
+    let check = Check {
+        num_nodes,
+        block_size,
+        how_many: num_threads,
+        thread_pool: &thread_pool,
+        pl,
+    };
+    let window = comp_flags.compression_window;
+    let min_interval = comp_flags.min_interval_length;
+
+    // Decoding errors are reported as panics, which we catch and log
+    let _quiet_hook = QuietPanicHook::install();
+    let report = if has_ef {
+        log::info!(
+            "Checking the graph in parallel using {} threads",
+            num_threads
+        );
+        let graph = BvGraph::with_basename(src).endianness::<E>().load()?;
+        let factory = CheckFactory::new(graph.into_inner(), num_nodes, window);
+        check.random(&BvGraph::new(
+            factory,
+            num_nodes,
+            num_arcs,
+            window,
+            min_interval,
+        ))
+    } else {
+        log::info!(
+            "No Elias-Fano offsets found, splits will be computed sequentially; you can build them with `webgraph build ef {}`",
+            src.display()
+        );
+        check.sequential(
+            &BvGraphSeq::with_basename(src)
+                .endianness::<E>()
+                .load()?
+                .map_factory(|f| CheckFactory::new(f, num_nodes, window)),
+        )
+    };
+
+    let mut problems = report.problems;
+    problems.sort_by_key(|problem| problem.node);
+    let mut problem = |msg| problems.push(Problem { node: None, msg });
+
+    if report.arcs != num_arcs {
+        problem(format!(
+            "The graph has {} arcs, but {} declares {}",
+            report.arcs,
+            properties_path.display(),
+            num_arcs
+        ));
+    }
+
+    if let Some(checksums) = checksums {
+        let num_blocks = num_nodes.div_ceil(checksums.block_size());
+        if checksums.sums().len() != num_blocks {
+            problem(format!(
+                "{} contains {} checksums, but the graph has {} blocks of {} nodes",
+                properties_path.display(),
+                checksums.sums().len(),
+                num_blocks,
+                checksums.block_size()
+            ));
+        }
+        for block in 0..num_blocks {
+            let expected = checksums.sums().get(block).copied().unwrap_or(0);
+            let actual = report.checksums.sums().get(block).copied().unwrap_or(0);
+            if expected != actual {
+                let range = checksums.block_range(block, num_nodes);
+                problem(format!(
+                    "Checksum mismatch for block {} (nodes [{}..{})): expected {:016x}, found {:016x}",
+                    block, range.start, range.end, expected, actual
+                ));
+            }
+        }
+    } else {
+        log::warn!(
+            "{} contains no checksums; recompress the graph to add them",
+            properties_path.display()
+        );
+    }
+
+    Ok(problems)
+}
+
+/// A decoder checking that the values it reads are consistent with the
+/// graph.
+///
+/// The decoder keeps track of the current node by counting the calls to
+/// [`Decode::read_outdegree`], and panics with a descriptive message when a
+/// reference offset is outside the compression window or points before the
+/// first node, or when a value is too large to be valid; in the latter case,
+/// a corrupted bitstream might otherwise lead to very long decoding times.
+#[derive(Debug, Clone)]
+pub struct CheckDecoder<D: Decode> {
+    decoder: D,
+    next_node: usize,
+    num_nodes: usize,
+    window: usize,
+}
+
+impl<D: Decode> CheckDecoder<D> {
+    /// Panics if `value` is larger than `max`.
+    fn check(&self, value: u64, max: usize, what: &str) -> u64 {
+        if value > max as u64 {
+            panic!(
+                "Node {} has {} {}, but the graph has {} nodes",
+                self.next_node - 1,
+                what,
+                value,
+                self.num_nodes
+            );
+        }
+        value
+    }
+}
+
+impl<D: Decode> Decode for CheckDecoder<D> {
+    fn read_outdegree(&mut self) -> u64 {
+        self.next_node += 1;
+        let outdegree = self.decoder.read_outdegree();
+        self.check(outdegree, self.num_nodes, "outdegree")
+    }
+
+    fn read_reference_offset(&mut self) -> u64 {
+        let node = self.next_node - 1;
+        let reference_offset = self.decoder.read_reference_offset();
+        if reference_offset > self.window as u64 || reference_offset > node as u64 {
+            panic!(
+                "Node {} has reference offset {}, but the compression window is {}",
+                node, reference_offset, self.window
+            );
+        }
+        reference_offset
+    }
+
+    fn read_block_count(&mut self) -> u64 {
+        let block_count = self.decoder.read_block_count();
+        self.check(block_count, self.num_nodes, "block count")
+    }
+
+    fn read_block(&mut self) -> u64 {
+        let block = self.decoder.read_block();
+        self.check(block, self.num_nodes, "block")
+    }
+
+    fn read_interval_count(&mut self) -> u64 {
+        let interval_count = self.decoder.read_interval_count();
+        self.check(interval_count, self.num_nodes, "interval count")
+    }
+
+    fn read_interval_start(&mut self) -> u64 {
+        // The first start is a signed difference with the current node
+        let interval_start = self.decoder.read_interval_start();
+        self.check(interval_start, 2 * self.num_nodes, "interval start")
+    }
+
+    fn read_interval_len(&mut self) -> u64 {
+        let interval_len = self.decoder.read_interval_len();
+        self.check(interval_len, self.num_nodes, "interval length")
+    }
+
+    fn read_first_residual(&mut self) -> u64 {
+        // The first residual is a signed difference with the current node
+        let first_residual = self.decoder.read_first_residual();
+        self.check(first_residual, 2 * self.num_nodes, "first residual")
+    }
+
+    fn read_residual(&mut self) -> u64 {
+        let residual = self.decoder.read_residual();
+        self.check(residual, self.num_nodes, "residual")
+    }
+}
+
+/// A factory wrapping the decoders of another factory in [`CheckDecoder`]s.
+#[derive(Debug)]
+pub struct CheckFactory<F> {
+    factory: F,
+    num_nodes: usize,
+    window: usize,
+}
+
+impl<F> CheckFactory<F> {
+    pub fn new(factory: F, num_nodes: usize, window: usize) -> Self {
+        Self {
+            factory,
+            num_nodes,
+            window,
+        }
+    }
+}
+
+impl<F: RandomAccessDecoderFactory> RandomAccessDecoderFactory for CheckFactory<F> {
+    type Decoder<'a>
+        = CheckDecoder<F::Decoder<'a>>
+    where
+        Self: 'a;
+
+    fn new_decoder(&self, node: usize) -> Result<Self::Decoder<'_>> {
+        Ok(CheckDecoder {
+            decoder: self.factory.new_decoder(node)?,
+            next_node: node,
+            num_nodes: self.num_nodes,
+            window: self.window,
+        })
+    }
+}
+
+impl<F: SequentialDecoderFactory> SequentialDecoderFactory for CheckFactory<F> {
+    type Decoder<'a>
+        = CheckDecoder<F::Decoder<'a>>
+    where
+        Self: 'a;
+
+    fn new_decoder(&self) -> Result<Self::Decoder<'_>> {
+        Ok(CheckDecoder {
+            decoder: self.factory.new_decoder()?,
+            next_node: 0,
+            num_nodes: self.num_nodes,
+            window: self.window,
+        })
+    }
+}
+
+/// The results of checking a set of nodes.
+struct Report {
+    arcs: u64,
+    checksums: Checksums,
+    problems: Vec<Problem>,
+}
+
+impl Report {
+    fn new(block_size: usize) -> Self {
+        Self {
+            arcs: 0,
+            checksums: Checksums::new(block_size),
+            problems: Vec::new(),
+        }
+    }
+
+    fn problem(&mut self, node: usize, msg: String) {
+        self.problems.push(Problem {
+            node: Some(node),
+            msg,
+        });
+    }
+
+    fn merge(&mut self, other: Report) {
+        self.arcs += other.arcs;
+        self.checksums.merge(&other.checksums);
+        self.problems.extend(other.problems);
+    }
+}
+
+thread_local! {
+    /// Whether the current thread is decoding within [`catch_decoding_errors`].
+    static DECODING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls `f`, catching the panics reporting decoding errors.
+///
+/// Such panics are not reported by the hook installed by
+/// [`QuietPanicHook`].
+fn catch_decoding_errors<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    DECODING.set(true);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    DECODING.set(false);
+    result
+}
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Send + Sync;
+
+/// A guard installing a panic hook that does not report the panics caught by
+/// [`catch_decoding_errors`], delegating the other ones to the previous hook,
+/// which is restored when the guard is dropped.
+struct QuietPanicHook {
+    previous: Arc<PanicHook>,
+}
+
+impl QuietPanicHook {
+    fn install() -> Self {
+        let previous: Arc<PanicHook> = std::panic::take_hook().into();
+        let hook = previous.clone();
+        std::panic::set_hook(Box::new(move |info| {
+            if !DECODING.get() {
+                hook(info)
+            }
+        }));
+        Self { previous }
+    }
+}
+
+impl Drop for QuietPanicHook {
+    fn drop(&mut self) {
+        // The hook cannot be changed while panicking
+        if !std::thread::panicking() {
+            let previous = self.previous.clone();
+            std::panic::set_hook(Box::new(move |info| previous(info)));
+        }
+    }
+}
+
+/// Returns the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown error"
+    }
+}
+
+struct Check<'a, P> {
+    num_nodes: usize,
+    block_size: usize,
+    how_many: usize,
+    thread_pool: &'a rayon::ThreadPool,
+    pl: P,
+}
+
+impl<P: ConcurrentProgressLog + Send + Sync> Check<'_, P> {
+    /// Returns the range of nodes of the given split.
+    fn split_range(&self, split: usize) -> Range<usize> {
+        let nodes_per_split = self.num_nodes.div_ceil(self.how_many);
+        let start = (split * nodes_per_split).min(self.num_nodes);
+        start..(start + nodes_per_split).min(self.num_nodes)
+    }
+
+    /// Checks the nodes returned by a lender, which should be those in
+    /// `range`, returning the first node that could not be decoded, if any.
+    fn check_lender<L>(
+        &self,
+        mut lender: L,
+        range: Range<usize>,
+        report: &mut Report,
+        pl: &mut P,
+    ) -> Option<usize>
+    where
+        L: Lender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    {
+        let mut next_node = range.start;
+        loop {
+            let result = catch_decoding_errors(|| {
+                let Some((node, succ)) = lender.next() else {
+                    return false;
+                };
+                if node != next_node {
+                    report.problem(
+                        node,
+                        format!("Expected node {} but got node {}", next_node, node),
+                    );
+                }
+                let mut hasher = NodeHasher::new(node);
+                let mut last = None;
+                for s in succ {
+                    if s >= self.num_nodes {
+                        report.problem(
+                            node,
+                            format!(
+                                "Node {} has successor {}, but the graph has {} nodes",
+                                node, s, self.num_nodes
+                            ),
+                        );
+                    }
+                    if let Some(last) = last.filter(|&last| s <= last) {
+                        report.problem(
+                            node,
+                            format!(
+                                "Successors of node {} are not strictly increasing: {} is followed by {}",
+                                node, last, s
+                            ),
+                        );
+                    }
+                    last = Some(s);
+                    hasher.push(s);
+                    report.arcs += 1;
+                }
+                report.checksums.add(node, hasher.finish());
+                next_node = node + 1;
+                pl.light_update();
+                true
+            });
+            match result {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(payload) => {
+                    report.problem(
+                        next_node,
+                        format!(
+                            "Node {} could not be decoded: {}",
+                            next_node,
+                            panic_message(payload.as_ref())
+                        ),
+                    );
+                    return Some(next_node);
+                }
+            }
+        }
+        if next_node < range.end {
+            report.problem(
+                next_node,
+                format!("Nodes [{}..{}) are missing", next_node, range.end),
+            );
+        }
+        None
+    }
+
+    /// Checks a random-access graph, resuming decoding after each node that
+    /// cannot be decoded.
+    fn random<F>(mut self, graph: &BvGraph<F>) -> Report
+    where
+        F: RandomAccessDecoderFactory + Sync,
+        for<'a> F::Decoder<'a>: Send + Sync,
+    {
+        let total = Mutex::new(Report::new(self.block_size));
+        self.pl.start("Checking...");
+        self.thread_pool.in_place_scope(|s| {
+            for (split, lender) in graph.split_iter(self.how_many).enumerate() {
+                let (this, total) = (&self, &total);
+                s.spawn(move |_| {
+                    let mut report = Report::new(this.block_size);
+                    let mut pl = this.pl.clone();
+                    let mut range = this.split_range(split);
+                    let mut failed = this.check_lender(lender, range.clone(), &mut report, &mut pl);
+                    while let Some(node) = failed {
+                        range.start = node + 1;
+                        failed = None;
+                        while !range.is_empty() {
+                            let start = range.start;
+                            match catch_decoding_errors(|| graph.iter_from(start).take(range.len()))
+                            {
+                                Ok(lender) => {
+                                    failed = this.check_lender(
+                                        lender,
+                                        range.clone(),
+                                        &mut report,
+                                        &mut pl,
+                                    );
+                                    break;
+                                }
+                                Err(payload) => {
+                                    report.problem(
+                                        start,
+                                        format!(
+                                            "Node {} could not be decoded: {}",
+                                            start,
+                                            panic_message(payload.as_ref())
+                                        ),
+                                    );
+                                    range.start += 1;
+                                }
+                            }
+                        }
+                    }
+                    total.lock().unwrap().merge(report);
+                });
+            }
+        });
+        self.pl.done();
+        total.into_inner().unwrap()
+    }
+
+    /// Checks a sequential graph; since splits are computed by decoding the
+    /// graph, nodes following a node that cannot be decoded are not checked.
+    fn sequential<F>(mut self, graph: &BvGraphSeq<F>) -> Report
+    where
+        F: SequentialDecoderFactory + Sync,
+        for<'a> F::Decoder<'a>: Clone + Send + Sync,
+    {
+        let total = Mutex::new(Report::new(self.block_size));
+        self.pl.start("Checking...");
+        self.thread_pool.in_place_scope(|s| {
+            let mut splits = graph.split_iter(self.how_many);
+            for split in 0.. {
+                let (this, total) = (&self, &total);
+                match catch_decoding_errors(|| splits.next()) {
+                    Ok(Some(lender)) => s.spawn(move |_| {
+                        let mut report = Report::new(this.block_size);
+                        let mut pl = this.pl.clone();
+                        let range = this.split_range(split);
+                        if let Some(node) =
+                            this.check_lender(lender, range.clone(), &mut report, &mut pl)
+                        {
+                            if node + 1 < range.end {
+                                report.problem(
+                                    node + 1,
+                                    format!(
+                                        "Nodes [{}..{}) could not be checked",
+                                        node + 1,
+                                        range.end
+                                    ),
+                                );
+                            }
+                        }
+                        total.lock().unwrap().merge(report);
+                    }),
+                    Ok(None) => break,
+                    Err(payload) => {
+                        let start = this.split_range(split).start;
+                        total.lock().unwrap().problem(
+                            start,
+                            format!(
+                                "Nodes [{}..{}) could not be decoded: {}",
+                                start,
+                                this.num_nodes,
+                                panic_message(payload.as_ref())
+                            ),
+                        );
+                        break;
+                    }
+                }
+            }
+        });
+        self.pl.done();
+        total.into_inner().unwrap()
+    }
+}
@@ -10,16 +10,19 @@ use clap::Subcommand;
 use super::GlobalArgs;
 
 pub mod ef;
+pub mod graph;
 
 #[derive(Subcommand, Debug)]
 #[command(name = "check")]
 /// Check coherence of files.
 pub enum SubCommands {
     Ef(ef::CliArgs),
+    Graph(graph::CliArgs),
 }
 
 pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
     match subcommand {
         SubCommands::Ef(args) => ef::main(global_args, args),
+        SubCommands::Graph(args) => graph::main(global_args, args),
     }
 }
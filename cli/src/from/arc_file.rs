@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::*;
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+use tempfile::Builder;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Compresses in parallel a file of arcs sorted by source and target into a BvGraph. The file is read multiple times, and it is split at arc boundaries, so no sorting pass is needed. Text files contain a pair of numerical node identifiers separated by whitespace on each line (empty lines and lines starting with # are ignored); binary files contain pairs of big-endian integers."
+)]
+pub struct CliArgs {
+    /// The file of arcs.
+    pub src: PathBuf,
+    /// The basename of the graph.
+    pub dst: PathBuf,
+
+    #[arg(long)]
+    /// The number of nodes in the graph; it must be larger than all sources.
+    pub num_nodes: usize,
+
+    #[arg(long, value_enum, default_value_t = FileFormat::Text)]
+    /// The format of the file of arcs.
+    pub format: FileFormat,
+
+    #[clap(flatten)]
+    pub num_threads: NumThreadsArg,
+
+    #[clap(flatten)]
+    pub ca: CompressArgs,
+
+    #[arg(long)]
+    /// Choose the codes using a statistics pass on the graph; the codes
+    /// specified by the other options are used only during the statistics pass.
+    pub auto_codes: bool,
+}
+
+/// The format of a file of arcs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FileFormat {
+    /// One arc per line, with source and target separated by whitespace.
+    Text,
+    /// Pairs of big-endian 32-bit integers.
+    U32,
+    /// Pairs of big-endian 64-bit integers.
+    U64,
+}
+
+impl From<FileFormat> for ArcFileFormat {
+    fn from(format: FileFormat) -> Self {
+        match format {
+            FileFormat::Text => ArcFileFormat::Text,
+            FileFormat::U32 => ArcFileFormat::U32,
+            FileFormat::U64 => ArcFileFormat::U64,
+        }
+    }
+}
+
+pub fn main(_global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    let graph = FileArcListGraph::new(&args.src, args.format.into(), args.num_nodes)?;
+
+    create_parent_dir(&args.dst)?;
+    let target_endianness = args.ca.endianness.clone();
+    let dir = Builder::new().prefix("from_arc_file_").tempdir()?;
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+    BvComp::parallel_endianness(
+        &args.dst,
+        &graph,
+        args.num_nodes,
+        comp_flags(args.ca, args.auto_codes, &graph, &thread_pool)?,
+        &thread_pool,
+        dir,
+        &target_endianness.unwrap_or_else(|| BE::NAME.into()),
+    )?;
+    Ok(())
+}
@@ -8,21 +8,22 @@
 use crate::create_parent_dir;
 use crate::*;
 use anyhow::Result;
-use clap::Parser;
-use dsi_bitstream::prelude::{Endianness, BE};
+use clap::{Parser, ValueEnum};
 use dsi_progress_logger::prelude::*;
 use itertools::Itertools;
 use rayon::prelude::ParallelSliceMut;
 use std::collections::HashMap;
 use std::io::{BufRead, Write};
-use std::path::PathBuf;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
 use tempfile::Builder;
 use webgraph::graphs::arc_list_graph::ArcListGraph;
+use webgraph::labels::serializers::CodedInt;
 use webgraph::prelude::*;
 
 #[derive(Parser, Debug)]
 #[command(
-    about = "Read from standard input a list of arcs and create a BvGraph. Each arc is specified by a pair of labels separated by a TAB (but the format is customizable), and numerical identifiers will be assigned to the labels in appearance order. The final list of node labels will be saved in a file with the same basename of the graph and extension .nodes. The option --exact can be used to use the labels directly as node identifiers. Note that in that case nodes are numbered starting from zero."
+    about = "Read from standard input a list of arcs and create a BvGraph. Each arc is specified by a pair of labels separated by a TAB (but the format is customizable), and numerical identifiers will be assigned to the labels in appearance order. The final list of node labels will be saved in a file with the same basename of the graph and extension .nodes. The option --exact can be used to use the labels directly as node identifiers. Note that in that case nodes are numbered starting from zero. If --label-column is specified, arcs are labeled with the content of the column, and a labeled graph is created: the labels are stored as a bitstream with a separate basename (by default, the basename of the graph followed by -labelled)."
 )]
 pub struct CliArgs {
     /// The basename of the graph.
@@ -40,6 +41,18 @@ pub struct CliArgs {
     #[clap(flatten)]
     pub arcs_args: ArcsArgs,
 
+    #[clap(flatten)]
+    pub label_args: LabelArgs,
+
+    #[arg(long, value_enum, default_value_t = DuplicatePolicy::First)]
+    /// How to combine the labels of duplicate arcs.
+    pub duplicates: DuplicatePolicy,
+
+    #[arg(long)]
+    /// The basename of the labels (by default, the basename of the graph
+    /// followed by -labelled).
+    pub labels_basename: Option<PathBuf>,
+
     #[clap(flatten)]
     pub num_threads: NumThreadsArg,
 
@@ -48,6 +61,26 @@ pub struct CliArgs {
 
     #[clap(flatten)]
     pub ca: CompressArgs,
+
+    #[arg(long)]
+    /// Choose the codes using a statistics pass on the graph; the codes
+    /// specified by the other options are used only during the statistics pass.
+    pub auto_codes: bool,
+}
+
+/// How to combine the labels of duplicate arcs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DuplicatePolicy {
+    /// Keep the label appearing first in the input.
+    First,
+    /// Keep the label appearing last in the input.
+    Last,
+    /// Sum the labels.
+    Sum,
+    /// Keep the smallest label.
+    Min,
+    /// Keep the largest label.
+    Max,
 }
 
 pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
@@ -57,9 +90,76 @@ pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
 }
 
 pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> Result<()> {
-    let dir = Builder::new().prefix("from_arcs_sort_").tempdir()?;
+    if args.arcs_args.label_column.is_some() {
+        return match args.label_args.label_type {
+            LabelType::U32 => from_csv_labeled::<u32>(global_args, args, file),
+            LabelType::U64 => from_csv_labeled::<u64>(global_args, args, file),
+            LabelType::F32 => from_csv_labeled::<f32>(global_args, args, file),
+            LabelType::F64 => from_csv_labeled::<f64>(global_args, args, file),
+        };
+    }
 
+    let dir = Builder::new().prefix("from_arcs_sort_").tempdir()?;
     let mut group_by = SortPairs::new(args.batch_size.batch_size, &dir)?;
+
+    let Some((num_nodes, nodes)) = read_arcs(&global_args, &args, file, |src, dst, _, _| {
+        group_by.push(src, dst)
+    })?
+    else {
+        return Ok(());
+    };
+
+    // convert the iter to a graph
+    let g = ArcListGraph::new(
+        num_nodes,
+        group_by
+            .iter()
+            .unwrap()
+            .map(|(src, dst, _)| (src, dst))
+            .dedup(),
+    );
+
+    create_parent_dir(&args.dst)?;
+
+    // compress it
+    let target_endianness = args.ca.endianness.clone();
+    let dir = Builder::new().prefix("from_arcs_compress_").tempdir()?;
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+    BvComp::parallel_endianness(
+        &args.dst,
+        &g,
+        num_nodes,
+        comp_flags(args.ca, args.auto_codes, &g, &thread_pool)?,
+        &thread_pool,
+        dir,
+        &target_endianness.unwrap_or_else(|| BE::NAME.into()),
+    )
+    .unwrap();
+
+    if !args.arcs_args.exact {
+        store_nodes(
+            &global_args,
+            &args.arcs_args,
+            args.num_arcs,
+            &args.dst,
+            nodes,
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads the arcs in `file`, passing to `push` the source and target of each
+/// arc, the columns of its line and the line number.
+///
+/// Returns the number of nodes and the map from node labels to node
+/// identifiers (empty if `--exact` was specified), or `None` if no graph
+/// should be built.
+fn read_arcs(
+    global_args: &GlobalArgs,
+    args: &CliArgs,
+    file: impl BufRead,
+    mut push: impl FnMut(usize, usize, &[&str], usize) -> Result<()>,
+) -> Result<Option<(usize, HashMap<String, usize>)>> {
     let mut nodes = HashMap::new();
 
     // read the csv and put it inside the sort pairs
@@ -81,7 +181,8 @@ pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> R
     let biggest_idx = args
         .arcs_args
         .source_column
-        .max(args.arcs_args.target_column);
+        .max(args.arcs_args.target_column)
+        .max(args.arcs_args.label_column.unwrap_or(0));
     let mut num_nodes = 0;
     let mut num_arcs = 0;
     for (line_num, line) in iter.enumerate() {
@@ -126,7 +227,7 @@ pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> R
                         line_num,
                         err
                     );
-                    return Ok(());
+                    return Ok(None);
                 }
             }
         } else {
@@ -143,7 +244,7 @@ pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> R
                         line_num,
                         err
                     );
-                    return Ok(());
+                    return Ok(None);
                 }
             }
         } else {
@@ -152,7 +253,7 @@ pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> R
         };
 
         num_nodes = num_nodes.max(src_id.max(dst_id) + 1);
-        group_by.push(src_id, dst_id).unwrap();
+        push(src_id, dst_id, &vals, line_num)?;
         pl.light_update();
         num_arcs += 1;
     }
@@ -185,59 +286,236 @@ pub fn from_csv(global_args: GlobalArgs, args: CliArgs, file: impl BufRead) -> R
             args.arcs_args.source_column,
             args.arcs_args.target_column
         );
-        return Ok(());
+        return Ok(None);
     }
+    Ok(Some((num_nodes, nodes)))
+}
 
-    // convert the iter to a graph
-    let g = Left(ArcListGraph::new(
-        num_nodes,
-        group_by
-            .iter()
-            .unwrap()
-            .map(|(src, dst, _)| (src, dst))
-            .dedup(),
-    ));
+/// Saves the node labels, in the order of their identifiers, in a file with
+/// extension `.nodes`.
+fn store_nodes(
+    global_args: &GlobalArgs,
+    arcs_args: &ArcsArgs,
+    num_arcs: Option<usize>,
+    dst: &Path,
+    nodes: HashMap<String, usize>,
+) -> Result<()> {
+    let nodes_file = dst.with_extension("nodes");
+    let mut pl = ProgressLogger::default();
+    pl.display_memory(true)
+        .item_name("lines")
+        .expected_updates(arcs_args.max_arcs.or(num_arcs));
+    if let Some(duration) = global_args.log_interval {
+        pl.log_interval(duration);
+    }
 
-    create_parent_dir(&args.dst)?;
+    let mut file = std::fs::File::create(&nodes_file).unwrap();
+    let mut buf = std::io::BufWriter::new(&mut file);
+    let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+    // sort based on the idx
+    nodes.par_sort_by(|(_, a), (_, b)| a.cmp(b));
+    pl.start(format!("Storing the nodes to {}", nodes_file.display()));
+    for (node, _) in nodes {
+        buf.write_all(node.as_bytes()).unwrap();
+        buf.write_all(b"\n").unwrap();
+        pl.light_update();
+    }
+    pl.done();
+    Ok(())
+}
+
+/// An iterator over sorted labeled arcs merging the labels of duplicate
+/// arcs using a [`DuplicatePolicy`].
+///
+/// Each label is paired with the input line it comes from. Since sums might
+/// overflow, with [`DuplicatePolicy::Sum`] the merged arcs must be
+/// [checked](MergeDuplicates::check) before iterating on them.
+struct MergeDuplicates<I: Iterator> {
+    iter: Peekable<I>,
+    policy: DuplicatePolicy,
+}
+
+impl<I: Iterator<Item: Clone> + Clone> Clone for MergeDuplicates<I> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            policy: self.policy,
+        }
+    }
+}
+
+impl<T: ArcLabel, I: Iterator<Item = (usize, usize, (u64, T))>> MergeDuplicates<I> {
+    /// Returns the next merged arc, or an error reporting the input line
+    /// at which the sum of the labels overflows.
+    fn try_next(&mut self) -> Result<Option<(usize, usize, T)>> {
+        let Some((src, dst, (mut line, mut label))) = self.iter.next() else {
+            return Ok(None);
+        };
+        while let Some((_, _, (next_line, next_label))) =
+            self.iter.next_if(|(s, d, _)| *s == src && *d == dst)
+        {
+            match self.policy {
+                DuplicatePolicy::First if next_line < line => {
+                    (line, label) = (next_line, next_label)
+                }
+                DuplicatePolicy::Last if next_line > line => {
+                    (line, label) = (next_line, next_label)
+                }
+                DuplicatePolicy::Sum => {
+                    label = label.checked_add(next_label).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "The sum of the labels of arc {} -> {} overflows at line {}",
+                            src,
+                            dst,
+                            next_line
+                        )
+                    })?
+                }
+                DuplicatePolicy::Min if next_label < label => label = next_label,
+                DuplicatePolicy::Max if next_label > label => label = next_label,
+                _ => {}
+            }
+        }
+        Ok(Some((src, dst, label)))
+    }
+
+    /// Consumes the iterator, returning an error if merging fails.
+    fn check(mut self) -> Result<()> {
+        while self.try_next()?.is_some() {}
+        Ok(())
+    }
+}
+
+impl<T: ArcLabel, I: Iterator<Item = (usize, usize, (u64, T))>> Iterator for MergeDuplicates<I> {
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next()
+            .expect("Merged arcs should be checked before iterating")
+    }
+}
+
+fn from_csv_labeled<T: ArcLabel>(
+    global_args: GlobalArgs,
+    args: CliArgs,
+    file: impl BufRead,
+) -> Result<()> {
+    let label_column = args.arcs_args.label_column.unwrap();
+    let code = args.label_args.code()?;
+    let labels_basename = labels_basename(&args.dst, args.labels_basename.as_deref())?;
+
+    // Labels are paired with the line of the arc
+    let batch_size = args.batch_size.labeled_batch_size::<(u64, T)>();
+    let dir = Builder::new().prefix("from_arcs_sort_").tempdir()?;
+    let mut group_by = SortPairs::new_labeled(
+        batch_size,
+        &dir,
+        (CodedInt::<u64>::new(Codes::Delta), T::serde(code)),
+        (CodedInt::<u64>::new(Codes::Delta), T::serde(code)),
+    )?;
+
+    let Some((num_nodes, nodes)) =
+        read_arcs(&global_args, &args, file, |src, dst, vals, line_num| {
+            let label = vals[label_column].parse::<T>().map_err(|err| {
+                anyhow::anyhow!(
+                    "Error parsing as {:?} label column value {:?} at line {}: {:?}",
+                    args.label_args.label_type,
+                    vals[label_column],
+                    line_num,
+                    err
+                )
+            })?;
+            group_by.push_labeled(src, dst, (line_num as u64, label))
+        })?
+    else {
+        return Ok(());
+    };
+
+    let merged = MergeDuplicates {
+        iter: group_by.iter()?.peekable(),
+        policy: args.duplicates,
+    };
+    if args.duplicates == DuplicatePolicy::Sum {
+        merged.clone().check()?;
+    }
+    let g = ArcListGraph::new_labeled(num_nodes, merged);
 
-    // compress it
-    let target_endianness = args.ca.endianness.clone();
-    let dir = Builder::new().prefix("from_arcs_compress_").tempdir()?;
     let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
-    BvComp::parallel_endianness(
+    let target_endianness = args.ca.endianness.clone();
+    let flags = comp_flags(args.ca, args.auto_codes, &Left(g.clone()), &thread_pool)?;
+    let label_spec = args.label_args.label_spec()?;
+    compress_labeled(
         &args.dst,
+        &labels_basename,
         &g,
-        num_nodes,
-        args.ca.into(),
+        T::serde(code),
+        &label_spec,
+        flags,
         &thread_pool,
-        dir,
-        &target_endianness.unwrap_or_else(|| BE::NAME.into()),
-    )
-    .unwrap();
+        target_endianness.as_deref().unwrap_or(BE::NAME),
+    )?;
 
-    // save the nodes
     if !args.arcs_args.exact {
-        let nodes_file = args.dst.with_extension("nodes");
-        let mut pl = ProgressLogger::default();
-        pl.display_memory(true)
-            .item_name("lines")
-            .expected_updates(args.arcs_args.max_arcs.or(args.num_arcs));
-        if let Some(duration) = global_args.log_interval {
-            pl.log_interval(duration);
-        }
+        store_nodes(
+            &global_args,
+            &args.arcs_args,
+            args.num_arcs,
+            &args.dst,
+            nodes,
+        )?;
+    }
+    Ok(())
+}
 
-        let mut file = std::fs::File::create(&nodes_file).unwrap();
-        let mut buf = std::io::BufWriter::new(&mut file);
-        let mut nodes = nodes.into_iter().collect::<Vec<_>>();
-        // sort based on the idx
-        nodes.par_sort_by(|(_, a), (_, b)| a.cmp(b));
-        pl.start(format!("Storing the nodes to {}", nodes_file.display()));
-        for (node, _) in nodes {
-            buf.write_all(node.as_bytes()).unwrap();
-            buf.write_all(b"\n").unwrap();
-            pl.light_update();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The arcs of the tests, sorted, with their lines.
+    const ARCS: [(usize, usize, (u64, u32)); 7] = [
+        (0, 1, (2, 9)),
+        (0, 1, (0, 6)),
+        (0, 1, (6, 5)),
+        (0, 1, (1, 3)),
+        (0, 2, (3, 1)),
+        (1, 0, (5, 4)),
+        (1, 0, (4, 6)),
+    ];
+
+    fn merge(policy: DuplicatePolicy, arcs: &[(usize, usize, (u64, u32))]) -> Result<Vec<u32>> {
+        let mut merged = MergeDuplicates {
+            iter: arcs.iter().copied().peekable(),
+            policy,
+        };
+        let mut labels = vec![];
+        while let Some((_, _, label)) = merged.try_next()? {
+            labels.push(label);
         }
-        pl.done();
+        Ok(labels)
+    }
+
+    #[test]
+    fn test_policies() -> Result<()> {
+        assert_eq!(merge(DuplicatePolicy::First, &ARCS)?, vec![6, 1, 6]);
+        assert_eq!(merge(DuplicatePolicy::Last, &ARCS)?, vec![5, 1, 4]);
+        assert_eq!(merge(DuplicatePolicy::Sum, &ARCS)?, vec![23, 1, 10]);
+        assert_eq!(merge(DuplicatePolicy::Min, &ARCS)?, vec![3, 1, 4]);
+        assert_eq!(merge(DuplicatePolicy::Max, &ARCS)?, vec![9, 1, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let arcs = [(0, 1, (0, u32::MAX - 1)), (0, 1, (1, 1)), (0, 1, (7, 1))];
+        let err = merge(DuplicatePolicy::Sum, &arcs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The sum of the labels of arc 0 -> 1 overflows at line 7"
+        );
+        let merged = MergeDuplicates {
+            iter: arcs.iter().copied().peekable(),
+            policy: DuplicatePolicy::Sum,
+        };
+        assert!(merged.check().is_err());
     }
-    Ok(())
 }
@@ -9,17 +9,20 @@ use clap::Subcommand;
 
 use super::GlobalArgs;
 
+pub mod arc_file;
 pub mod arcs;
 
 #[derive(Subcommand, Debug)]
 #[command(name = "from")]
 /// Ingest data into graphs.
 pub enum SubCommands {
+    ArcFile(arc_file::CliArgs),
     Arcs(arcs::CliArgs),
 }
 
 pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
     match subcommand {
+        SubCommands::ArcFile(args) => arc_file::main(global_args, args),
         SubCommands::Arcs(args) => arcs::main(global_args, args),
     }
 }
@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::{compress, OutputArgs};
+use crate::GlobalArgs;
+use anyhow::Result;
+use clap::Parser;
+use webgraph::graphs::families::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "bipartite", about = "Generates a complete bipartite graph.", long_about = None)]
+pub struct BipartiteArgs {
+    /// The number of nodes on the left side.
+    pub left: usize,
+    /// The number of nodes on the right side.
+    pub right: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn bipartite(_global_args: GlobalArgs, args: BipartiteArgs) -> Result<()> {
+    compress(args.output, &CompleteBipartite::new(args.left, args.right))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "complete", about = "Generates a complete graph.", long_about = None)]
+pub struct CompleteArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn complete(_global_args: GlobalArgs, args: CompleteArgs) -> Result<()> {
+    compress(args.output, &Complete::new(args.num_nodes))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "cycle", about = "Generates an undirected cycle.", long_about = None)]
+pub struct CycleArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn cycle(_global_args: GlobalArgs, args: CycleArgs) -> Result<()> {
+    compress(args.output, &Cycle::new(args.num_nodes))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "grid", about = "Generates an undirected two-dimensional grid.", long_about = None)]
+pub struct GridArgs {
+    /// The number of rows.
+    pub rows: usize,
+    /// The number of columns.
+    pub cols: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn grid(_global_args: GlobalArgs, args: GridArgs) -> Result<()> {
+    compress(args.output, &Grid::new(args.rows, args.cols))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "path", about = "Generates an undirected path.", long_about = None)]
+pub struct PathArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn path(_global_args: GlobalArgs, args: PathArgs) -> Result<()> {
+    compress(args.output, &Path::new(args.num_nodes))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "star", about = "Generates an undirected star with center 0.", long_about = None)]
+pub struct StarArgs {
+    /// The number of nodes, including the center.
+    pub num_nodes: usize,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn star(_global_args: GlobalArgs, args: StarArgs) -> Result<()> {
+    compress(args.output, &Star::new(args.num_nodes))
+}
@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::*;
+use anyhow::Result;
+use clap::Subcommand;
+use std::path::PathBuf;
+use tempfile::Builder;
+
+pub mod families;
+pub mod random;
+
+#[derive(Subcommand, Debug)]
+#[command(name = "gen")]
+/// Generates random graphs and graphs from deterministic families, compressing
+/// them directly as BvGraphs.
+pub enum SubCommands {
+    Ba(random::BaArgs),
+    Config(random::ConfigArgs),
+    Er(random::ErArgs),
+    Geometric(random::GeometricArgs),
+    Rmat(random::RmatArgs),
+    Ws(random::WsArgs),
+    Bipartite(families::BipartiteArgs),
+    Complete(families::CompleteArgs),
+    Cycle(families::CycleArgs),
+    Grid(families::GridArgs),
+    Path(families::PathArgs),
+    Star(families::StarArgs),
+}
+
+pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
+    match subcommand {
+        SubCommands::Ba(args) => random::ba(global_args, args),
+        SubCommands::Config(args) => random::config(global_args, args),
+        SubCommands::Er(args) => random::er(global_args, args),
+        SubCommands::Geometric(args) => random::geometric(global_args, args),
+        SubCommands::Rmat(args) => random::rmat(global_args, args),
+        SubCommands::Ws(args) => random::ws(global_args, args),
+        SubCommands::Bipartite(args) => families::bipartite(global_args, args),
+        SubCommands::Complete(args) => families::complete(global_args, args),
+        SubCommands::Cycle(args) => families::cycle(global_args, args),
+        SubCommands::Grid(args) => families::grid(global_args, args),
+        SubCommands::Path(args) => families::path(global_args, args),
+        SubCommands::Star(args) => families::star(global_args, args),
+    }
+}
+
+/// Shared CLI arguments for the output of graph generators.
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// The basename of the generated graph.
+    pub dst: PathBuf,
+
+    #[clap(flatten)]
+    pub num_threads: NumThreadsArg,
+
+    #[clap(flatten)]
+    pub ca: CompressArgs,
+}
+
+/// Compresses a generated graph in parallel.
+pub fn compress<G: SplitLabeling + SequentialGraph>(args: OutputArgs, graph: &G) -> Result<()>
+where
+    for<'a> <G as SplitLabeling>::SplitLender<'a>: Send + Sync,
+{
+    create_parent_dir(&args.dst)?;
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+    let target_endianness = args.ca.endianness.clone();
+    let dir = Builder::new().prefix("gen_").tempdir()?;
+    BvComp::parallel_endianness(
+        &args.dst,
+        graph,
+        graph.num_nodes(),
+        args.ca.into(),
+        &thread_pool,
+        dir,
+        &target_endianness.unwrap_or_else(|| BE::NAME.into()),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::{compress, OutputArgs};
+use crate::GlobalArgs;
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use webgraph::graphs::random::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "ba", about = "Generates an undirected Barabási–Albert preferential-attachment random graph.", long_about = None)]
+pub struct BaArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The number of edges attaching each new node to the previous ones.
+    pub m: usize,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn ba(_global_args: GlobalArgs, args: BaArgs) -> Result<()> {
+    ensure!(
+        args.m >= 1 && args.m < args.num_nodes,
+        "m must be positive and smaller than the number of nodes"
+    );
+    compress(
+        args.output,
+        &BarabasiAlbert::new(args.num_nodes, args.m, args.seed),
+    )
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "config", about = "Generates an undirected random graph with a given degree sequence using the configuration model.", long_about = None)]
+pub struct ConfigArgs {
+    /// A file containing the degree of each node, one per line; their sum
+    /// must be even.
+    pub degrees: PathBuf,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn config(_global_args: GlobalArgs, args: ConfigArgs) -> Result<()> {
+    let file = std::fs::File::open(&args.degrees)
+        .with_context(|| format!("Could not open {}", args.degrees.display()))?;
+    let mut degrees = vec![];
+    for (line_num, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.context("Could not read line")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        degrees.push(
+            line.parse::<usize>().with_context(|| {
+                format!("Line {}: {:?} is not a numerical degree", line_num, line)
+            })?,
+        );
+    }
+    ensure!(
+        degrees.iter().sum::<usize>().is_multiple_of(2),
+        "The sum of the degrees must be even"
+    );
+    compress(args.output, &ConfigurationModel::new(&degrees, args.seed))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "er", about = "Generates an Erdös–Rényi random graph.", long_about = None)]
+pub struct ErArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The probability of an arc between any two distinct nodes.
+    pub p: f64,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn er(_global_args: GlobalArgs, args: ErArgs) -> Result<()> {
+    ensure!((0.0..=1.0).contains(&args.p), "p must be in [0..1]");
+    compress(
+        args.output,
+        &ErdosRenyi::new(args.num_nodes, args.p, args.seed),
+    )
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "geometric", about = "Generates an undirected random geometric graph in the unit square.", long_about = None)]
+pub struct GeometricArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The maximum distance between adjacent nodes.
+    pub radius: f64,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn geometric(_global_args: GlobalArgs, args: GeometricArgs) -> Result<()> {
+    ensure!(args.radius >= 0.0, "The radius must be nonnegative");
+    compress(
+        args.output,
+        &RandomGeometric::new(args.num_nodes, args.radius, args.seed),
+    )
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "rmat", about = "Generates an R-MAT random graph.", long_about = None)]
+pub struct RmatArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The number of arcs to sample (multiple arcs are discarded).
+    pub num_samples: u64,
+
+    #[arg(long, value_delimiter = ',', default_values_t = Rmat::GRAPH500)]
+    /// The probabilities a,b,c,d of the four quadrants of the adjacency
+    /// matrix (top left, top right, bottom left, bottom right).
+    pub probabilities: Vec<f64>,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn rmat(_global_args: GlobalArgs, args: RmatArgs) -> Result<()> {
+    let probabilities: [f64; 4] = args
+        .probabilities
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Exactly four probabilities must be specified"))?;
+    ensure!(
+        probabilities.iter().all(|p| (0.0..=1.0).contains(p)),
+        "Probabilities must be in [0..1]"
+    );
+    ensure!(
+        (probabilities.iter().sum::<f64>() - 1.0).abs() < 1E-9,
+        "Probabilities must sum to one"
+    );
+    compress(
+        args.output,
+        &Rmat::new(args.num_nodes, args.num_samples, probabilities, args.seed),
+    )
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "ws", about = "Generates an undirected Watts–Strogatz small-world random graph.", long_about = None)]
+pub struct WsArgs {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The (even) degree of the initial ring lattice.
+    pub k: usize,
+    /// The rewiring probability.
+    pub beta: f64,
+
+    #[arg(short, long, default_value_t = 0)]
+    /// The seed of the pseudorandom number generator.
+    pub seed: u64,
+
+    #[clap(flatten)]
+    pub output: OutputArgs,
+}
+
+pub fn ws(_global_args: GlobalArgs, args: WsArgs) -> Result<()> {
+    ensure!(args.k.is_multiple_of(2), "k must be even");
+    ensure!(
+        args.k < args.num_nodes || args.k == 0,
+        "k must be smaller than the number of nodes"
+    );
+    ensure!((0.0..=1.0).contains(&args.beta), "beta must be in [0..1]");
+    compress(
+        args.output,
+        &WattsStrogatz::new(args.num_nodes, args.k, args.beta, args.seed),
+    )
+}
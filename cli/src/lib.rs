@@ -15,6 +15,7 @@ use anyhow::{anyhow, bail, ensure, Context, Result};
 use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use common_traits::{ToBytes, UnsignedInt};
 use dsi_bitstream::dispatch::Codes;
+use dsi_bitstream::prelude::{CodesRead, Endianness, BE, LE, NE};
 use epserde::ser::Serialize;
 use jiff::fmt::friendly::{Designator, Spacing, SpanPrinter};
 use jiff::SpanRound;
@@ -24,7 +25,14 @@ use std::time::Duration;
 use std::time::SystemTime;
 use sux::bits::BitFieldVec;
 use sysinfo::System;
-use webgraph::prelude::CompFlags;
+use webgraph::graphs::bvgraph::GRAPH_EXTENSION;
+use webgraph::labels::serializers::{CodedInt, FixedWidthFloat};
+use webgraph::prelude::{
+    BitDeserializer, BitSerializer, BvComp, BvGraphSeq, CompFlags, LabeledSequentialGraph,
+    LabelsBitWriter, LoadModeCodesReader, Mmap, RefSelection, SequentialGraph, SortLabel,
+    SplitLabeling, StreamBufReader, StreamDecoderFactory,
+};
+use webgraph::utils::sort_pairs::{BitReader, BitWriter};
 use webgraph::utils::Granularity;
 
 #[cfg(not(any(feature = "le_bins", feature = "be_bins")))]
@@ -57,10 +65,17 @@ build info: built on {} for {} with {}",
 /// Enum for instantaneous codes.
 ///
 /// It is used to implement [`ValueEnum`] here instead of in [`dsi_bitstream`].
+/// Parameterized codes are limited to the parameters supported by dynamic
+/// dispatch.
 pub enum PrivCode {
     Unary,
     Gamma,
     Delta,
+    Omega,
+    #[value(name = "vbyte-be")]
+    VByteBe,
+    #[value(name = "vbyte-le")]
+    VByteLe,
     Zeta1,
     Zeta2,
     Zeta3,
@@ -68,6 +83,59 @@ pub enum PrivCode {
     Zeta5,
     Zeta6,
     Zeta7,
+    Zeta8,
+    Zeta9,
+    Zeta10,
+    Pi1,
+    Pi2,
+    Pi3,
+    Pi4,
+    Pi5,
+    Pi6,
+    Pi7,
+    Pi8,
+    Pi9,
+    Pi10,
+    Golomb1,
+    Golomb2,
+    Golomb3,
+    Golomb4,
+    Golomb5,
+    Golomb6,
+    Golomb7,
+    Golomb8,
+    Golomb9,
+    Golomb10,
+    #[value(name = "expgolomb1")]
+    ExpGolomb1,
+    #[value(name = "expgolomb2")]
+    ExpGolomb2,
+    #[value(name = "expgolomb3")]
+    ExpGolomb3,
+    #[value(name = "expgolomb4")]
+    ExpGolomb4,
+    #[value(name = "expgolomb5")]
+    ExpGolomb5,
+    #[value(name = "expgolomb6")]
+    ExpGolomb6,
+    #[value(name = "expgolomb7")]
+    ExpGolomb7,
+    #[value(name = "expgolomb8")]
+    ExpGolomb8,
+    #[value(name = "expgolomb9")]
+    ExpGolomb9,
+    #[value(name = "expgolomb10")]
+    ExpGolomb10,
+    Rice1,
+    Rice2,
+    Rice3,
+    Rice4,
+    Rice5,
+    Rice6,
+    Rice7,
+    Rice8,
+    Rice9,
+    Rice10,
 }
 
 impl From<PrivCode> for Codes {
@@ -76,6 +144,9 @@ impl From<PrivCode> for Codes {
             PrivCode::Unary => Codes::Unary,
             PrivCode::Gamma => Codes::Gamma,
             PrivCode::Delta => Codes::Delta,
+            PrivCode::Omega => Codes::Omega,
+            PrivCode::VByteBe => Codes::VByteBe,
+            PrivCode::VByteLe => Codes::VByteLe,
             PrivCode::Zeta1 => Codes::Zeta { k: 1 },
             PrivCode::Zeta2 => Codes::Zeta { k: 2 },
             PrivCode::Zeta3 => Codes::Zeta { k: 3 },
@@ -83,6 +154,49 @@ impl From<PrivCode> for Codes {
             PrivCode::Zeta5 => Codes::Zeta { k: 5 },
             PrivCode::Zeta6 => Codes::Zeta { k: 6 },
             PrivCode::Zeta7 => Codes::Zeta { k: 7 },
+            PrivCode::Zeta8 => Codes::Zeta { k: 8 },
+            PrivCode::Zeta9 => Codes::Zeta { k: 9 },
+            PrivCode::Zeta10 => Codes::Zeta { k: 10 },
+            PrivCode::Pi1 => Codes::Pi { k: 1 },
+            PrivCode::Pi2 => Codes::Pi { k: 2 },
+            PrivCode::Pi3 => Codes::Pi { k: 3 },
+            PrivCode::Pi4 => Codes::Pi { k: 4 },
+            PrivCode::Pi5 => Codes::Pi { k: 5 },
+            PrivCode::Pi6 => Codes::Pi { k: 6 },
+            PrivCode::Pi7 => Codes::Pi { k: 7 },
+            PrivCode::Pi8 => Codes::Pi { k: 8 },
+            PrivCode::Pi9 => Codes::Pi { k: 9 },
+            PrivCode::Pi10 => Codes::Pi { k: 10 },
+            PrivCode::Golomb1 => Codes::Golomb { b: 1 },
+            PrivCode::Golomb2 => Codes::Golomb { b: 2 },
+            PrivCode::Golomb3 => Codes::Golomb { b: 3 },
+            PrivCode::Golomb4 => Codes::Golomb { b: 4 },
+            PrivCode::Golomb5 => Codes::Golomb { b: 5 },
+            PrivCode::Golomb6 => Codes::Golomb { b: 6 },
+            PrivCode::Golomb7 => Codes::Golomb { b: 7 },
+            PrivCode::Golomb8 => Codes::Golomb { b: 8 },
+            PrivCode::Golomb9 => Codes::Golomb { b: 9 },
+            PrivCode::Golomb10 => Codes::Golomb { b: 10 },
+            PrivCode::ExpGolomb1 => Codes::ExpGolomb { k: 1 },
+            PrivCode::ExpGolomb2 => Codes::ExpGolomb { k: 2 },
+            PrivCode::ExpGolomb3 => Codes::ExpGolomb { k: 3 },
+            PrivCode::ExpGolomb4 => Codes::ExpGolomb { k: 4 },
+            PrivCode::ExpGolomb5 => Codes::ExpGolomb { k: 5 },
+            PrivCode::ExpGolomb6 => Codes::ExpGolomb { k: 6 },
+            PrivCode::ExpGolomb7 => Codes::ExpGolomb { k: 7 },
+            PrivCode::ExpGolomb8 => Codes::ExpGolomb { k: 8 },
+            PrivCode::ExpGolomb9 => Codes::ExpGolomb { k: 9 },
+            PrivCode::ExpGolomb10 => Codes::ExpGolomb { k: 10 },
+            PrivCode::Rice1 => Codes::Rice { log2_b: 1 },
+            PrivCode::Rice2 => Codes::Rice { log2_b: 2 },
+            PrivCode::Rice3 => Codes::Rice { log2_b: 3 },
+            PrivCode::Rice4 => Codes::Rice { log2_b: 4 },
+            PrivCode::Rice5 => Codes::Rice { log2_b: 5 },
+            PrivCode::Rice6 => Codes::Rice { log2_b: 6 },
+            PrivCode::Rice7 => Codes::Rice { log2_b: 7 },
+            PrivCode::Rice8 => Codes::Rice { log2_b: 8 },
+            PrivCode::Rice9 => Codes::Rice { log2_b: 9 },
+            PrivCode::Rice10 => Codes::Rice { log2_b: 10 },
         }
     }
 }
@@ -115,11 +229,197 @@ pub struct ArcsArgs {
     /// The index of the column containing the target node of an arc.
     pub target_column: usize,
 
+    #[arg(long)]
+    /// The index of the column containing the label of an arc, if any.
+    pub label_column: Option<usize>,
+
     #[arg(long, default_value_t = false)]
     /// Source and destinations are node identifiers.
     pub exact: bool,
 }
 
+/// The type of arc labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LabelType {
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+#[derive(Args, Debug)]
+/// Shared CLI arguments for commands handling arc labels.
+pub struct LabelArgs {
+    #[arg(long, value_enum, default_value_t = LabelType::U32)]
+    /// The type of the labels.
+    pub label_type: LabelType,
+
+    #[arg(long, value_enum)]
+    /// The code used to write integer labels (by default, γ); floating-point
+    /// labels are always written as fixed-width bit patterns.
+    pub label_code: Option<PrivCode>,
+}
+
+impl LabelArgs {
+    /// Returns the code used to write the labels, or `None` for
+    /// floating-point labels.
+    pub fn code(&self) -> Result<Option<Codes>> {
+        match (self.label_type, self.label_code) {
+            (LabelType::F32 | LabelType::F64, Some(code)) => bail!(
+                "Floating-point labels are written as fixed-width bit patterns and cannot use code {:?}",
+                code
+            ),
+            (LabelType::F32 | LabelType::F64, None) => Ok(None),
+            (_, code) => Ok(Some(code.map(Codes::from).unwrap_or(Codes::Gamma))),
+        }
+    }
+
+    /// Returns the label specification to store in the `.properties` file of
+    /// the labels: the Java one, if Java has a corresponding label class, or
+    /// a descriptive one.
+    pub fn label_spec(&self) -> Result<String> {
+        let code = self.code()?;
+        Ok(match (self.label_type, code) {
+            (LabelType::U32, Some(Codes::Gamma)) => {
+                "it.unimi.dsi.webgraph.labelling.GammaCodedIntLabel(label)".to_string()
+            }
+            _ => {
+                log::warn!(
+                    "Labels of type {:?} written with {:?} have no Java equivalent",
+                    self.label_type,
+                    code
+                );
+                format!("{:?}({:?})", self.label_type, code)
+            }
+        })
+    }
+}
+
+/// A label type that can be read from an arc list and stored in a label
+/// bitstream.
+pub trait ArcLabel:
+    std::str::FromStr<Err: std::fmt::Debug> + Copy + PartialOrd + SortLabel + 'static
+{
+    /// The (de)serializer of the type.
+    type Serde: Copy
+        + Send
+        + Sync
+        + BitSerializer<NE, BitWriter, SerType = Self>
+        + BitDeserializer<NE, BitReader, DeserType = Self>
+        + BitSerializer<BE, LabelsBitWriter<BE>, SerType = Self>
+        + BitSerializer<LE, LabelsBitWriter<LE>, SerType = Self>
+        + for<'a> BitDeserializer<BE, LoadModeCodesReader<'a, BE, Mmap>, DeserType = Self>
+        + for<'a> BitDeserializer<LE, LoadModeCodesReader<'a, LE, Mmap>, DeserType = Self>;
+    /// Returns a (de)serializer using `code`, which is `None` exactly for
+    /// floating-point types.
+    fn serde(code: Option<Codes>) -> Self::Serde;
+    /// Returns the sum of two labels, or `None` if it overflows.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+impl ArcLabel for u32 {
+    type Serde = CodedInt<u32>;
+    fn serde(code: Option<Codes>) -> Self::Serde {
+        CodedInt::new(code.unwrap())
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u32::checked_add(self, other)
+    }
+}
+
+impl ArcLabel for u64 {
+    type Serde = CodedInt<u64>;
+    fn serde(code: Option<Codes>) -> Self::Serde {
+        CodedInt::new(code.unwrap())
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u64::checked_add(self, other)
+    }
+}
+
+impl ArcLabel for f32 {
+    type Serde = FixedWidthFloat<f32>;
+    fn serde(_code: Option<Codes>) -> Self::Serde {
+        FixedWidthFloat::new()
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        // Finite labels overflow to infinity
+        let sum = self + other;
+        (sum.is_finite() || !self.is_finite() || !other.is_finite()).then_some(sum)
+    }
+}
+
+impl ArcLabel for f64 {
+    type Serde = FixedWidthFloat<f64>;
+    fn serde(_code: Option<Codes>) -> Self::Serde {
+        FixedWidthFloat::new()
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        // Finite labels overflow to infinity
+        let sum = self + other;
+        (sum.is_finite() || !self.is_finite() || !other.is_finite()).then_some(sum)
+    }
+}
+
+/// Returns `labels_basename`, if present, or the basename of the labels of a
+/// graph with basename `basename`, that is, `basename` followed by
+/// `-labelled`.
+pub fn labels_basename(basename: &Path, labels_basename: Option<&Path>) -> Result<PathBuf> {
+    if let Some(labels_basename) = labels_basename {
+        return Ok(labels_basename.to_owned());
+    }
+    let mut file_name = basename
+        .file_name()
+        .with_context(|| format!("Basename {} has no file name", basename.display()))?
+        .to_owned();
+    file_name.push("-labelled");
+    Ok(basename.with_file_name(file_name))
+}
+
+/// Compresses in parallel a labeled graph with given basenames for the graph
+/// and its labels, using the given endianness.
+#[allow(clippy::too_many_arguments)]
+pub fn compress_labeled<T: ArcLabel>(
+    basename: &Path,
+    labels_basename: &Path,
+    graph: &(impl LabeledSequentialGraph<T> + SplitLabeling),
+    serializer: T::Serde,
+    label_spec: &str,
+    comp_flags: CompFlags,
+    thread_pool: &rayon::ThreadPool,
+    endianness: &str,
+) -> Result<()> {
+    create_parent_dir(basename)?;
+    create_parent_dir(labels_basename)?;
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("compress_labeled_")
+        .tempdir()?;
+    match endianness {
+        BE::NAME => BvComp::parallel_labeled::<BE, _, _>(
+            basename,
+            labels_basename,
+            graph,
+            serializer,
+            label_spec,
+            comp_flags,
+            thread_pool,
+            tmp_dir,
+        )?,
+        LE::NAME => BvComp::parallel_labeled::<LE, _, _>(
+            basename,
+            labels_basename,
+            graph,
+            serializer,
+            label_spec,
+            comp_flags,
+            thread_pool,
+            tmp_dir,
+        )?,
+        e => bail!("Unknown endianness: {}", e),
+    };
+    Ok(())
+}
+
 /// Parses the number of threads from a string.
 ///
 /// This function is meant to be used with `#[arg(...,  value_parser =
@@ -174,6 +474,59 @@ pub struct BatchSizeArg {
     pub batch_size: usize,
 }
 
+impl BatchSizeArg {
+    /// Returns the batch size to use when sorting triples with labels of type
+    /// `L`, which are larger than the pairs the batch size is expressed in,
+    /// so that batches use the same amount of memory.
+    pub fn labeled_batch_size<L>(&self) -> usize {
+        (self.batch_size * std::mem::size_of::<(usize, usize)>()
+            / std::mem::size_of::<(usize, usize, L)>())
+        .max(1)
+    }
+}
+
+/// Shared CLI arguments for commands that read a graph sequentially.
+#[derive(Args, Debug)]
+pub struct GraphInputArg {
+    #[arg(long)]
+    /// Read the bitstream from this file rather than from BASENAME.graph; use
+    /// `-` for standard input. Zstandard-compressed data is decompressed
+    /// transparently. If this option is missing and BASENAME.graph does not
+    /// exist, BASENAME.graph.zst is used, if present.
+    pub graph: Option<PathBuf>,
+}
+
+impl GraphInputArg {
+    /// Returns a graph reading the bitstream from the input specified by this
+    /// argument, or `None` if the graph should be loaded as usual from
+    /// `basename`.
+    pub fn load_stream<E: Endianness>(
+        &self,
+        basename: impl AsRef<Path>,
+    ) -> Result<Option<BvGraphSeq<StreamDecoderFactory<E>>>>
+    where
+        StreamBufReader<E>: CodesRead<E>,
+    {
+        let basename = basename.as_ref();
+        let config = BvGraphSeq::with_basename(basename).endianness::<E>();
+        match &self.graph {
+            Some(path) if path.as_os_str() == "-" => {
+                Ok(Some(config.load_from_reader(std::io::stdin())?))
+            }
+            Some(path) => Ok(Some(config.load_from_path(path)?)),
+            None => {
+                let graph = basename.with_extension(GRAPH_EXTENSION);
+                let zst = basename.with_extension(format!("{}.zst", GRAPH_EXTENSION));
+                if !graph.exists() && zst.exists() {
+                    Ok(Some(config.load_from_path(zst)?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 /// How to store vectors of floats.
 pub enum FloatVectorFormat {
@@ -448,13 +801,48 @@ pub struct CompressArgs {
 
     #[arg(value_enum)]
     #[clap(long, default_value = "gamma")]
-    /// The code to use for the blocks
+    /// The code to use for the block counts and the blocks
     pub blocks: PrivCode,
 
+    #[arg(value_enum)]
+    #[clap(long, default_value = "gamma")]
+    /// The code to use for the interval counts, starts and lengths
+    pub intervals: PrivCode,
+
     #[arg(value_enum)]
     #[clap(long, default_value = "zeta3")]
-    /// The code to use for the residuals
+    /// The code to use for the first residuals and the residuals
     pub residuals: PrivCode,
+
+    #[arg(value_enum)]
+    #[clap(long)]
+    /// The code to use for the block counts, overriding --blocks
+    pub block_counts: Option<PrivCode>,
+
+    #[arg(value_enum)]
+    #[clap(long)]
+    /// The code to use for the interval counts, overriding --intervals
+    pub interval_counts: Option<PrivCode>,
+
+    #[arg(value_enum)]
+    #[clap(long)]
+    /// The code to use for the interval starts, overriding --intervals
+    pub interval_starts: Option<PrivCode>,
+
+    #[arg(value_enum)]
+    #[clap(long)]
+    /// The code to use for the interval lengths, overriding --intervals
+    pub interval_lens: Option<PrivCode>,
+
+    #[arg(value_enum)]
+    #[clap(long)]
+    /// The code to use for the first residuals, overriding --residuals
+    pub first_residuals: Option<PrivCode>,
+
+    /// Select references optimally on chunks of the given number of nodes
+    /// instead of greedily (slower, but yields smaller graphs)
+    #[clap(long)]
+    pub optimal_refs: Option<usize>,
 }
 
 impl From<CompressArgs> for CompFlags {
@@ -462,8 +850,12 @@ impl From<CompressArgs> for CompFlags {
         CompFlags {
             outdegrees: value.outdegrees.into(),
             references: value.references.into(),
+            block_counts: value.block_counts.unwrap_or(value.blocks).into(),
             blocks: value.blocks.into(),
-            intervals: PrivCode::Gamma.into(),
+            interval_counts: value.interval_counts.unwrap_or(value.intervals).into(),
+            interval_starts: value.interval_starts.unwrap_or(value.intervals).into(),
+            interval_lens: value.interval_lens.unwrap_or(value.intervals).into(),
+            first_residuals: value.first_residuals.unwrap_or(value.residuals).into(),
             residuals: value.residuals.into(),
             min_interval_length: value.min_interval_length,
             compression_window: value.compression_window,
@@ -471,10 +863,32 @@ impl From<CompressArgs> for CompFlags {
                 -1 => usize::MAX,
                 _ => value.max_ref_count as usize,
             },
+            ref_selection: match value.optimal_refs {
+                Some(chunk_size) => RefSelection::Optimal { chunk_size },
+                None => RefSelection::Greedy,
+            },
+            huffman_coding: false,
         }
     }
 }
 
+/// Returns the compression flags specified by `ca`; if `auto_codes` is true,
+/// the codes are replaced by those chosen by a statistics pass on `graph`.
+pub fn comp_flags(
+    ca: CompressArgs,
+    auto_codes: bool,
+    graph: &(impl SequentialGraph + SplitLabeling),
+    thread_pool: &rayon::ThreadPool,
+) -> Result<CompFlags> {
+    let comp_flags = ca.into();
+    if auto_codes {
+        log::info!("Choosing the best codes with a statistics pass");
+        BvComp::parallel_best_codes(graph, comp_flags, thread_pool)
+    } else {
+        Ok(comp_flags)
+    }
+}
+
 /// Creates a [`ThreadPool`](rayon::ThreadPool) with the given number of threads.
 pub fn get_thread_pool(num_threads: usize) -> rayon::ThreadPool {
     rayon::ThreadPoolBuilder::new()
@@ -603,6 +1017,7 @@ pub struct GlobalArgs {
 pub enum SubCommands {
     #[command(subcommand)]
     Analyze(analyze::SubCommands),
+    Append(append::CliArgs),
     #[command(subcommand)]
     Bench(bench::SubCommands),
     #[command(subcommand)]
@@ -612,6 +1027,8 @@ pub enum SubCommands {
     #[command(subcommand)]
     From(from::SubCommands),
     #[command(subcommand)]
+    Gen(gen::SubCommands),
+    #[command(subcommand)]
     Perm(perm::SubCommands),
     #[command(subcommand)]
     Run(run::SubCommands),
@@ -645,10 +1062,12 @@ pub mod dist;
 pub mod sccs;
 
 pub mod analyze;
+pub mod append;
 pub mod bench;
 pub mod build;
 pub mod check;
 pub mod from;
+pub mod gen;
 pub mod perm;
 pub mod run;
 pub mod to;
@@ -666,6 +1085,9 @@ where
         SubCommands::Analyze(args) => {
             analyze::main(cli.args, args)?;
         }
+        SubCommands::Append(args) => {
+            append::main(cli.args, args)?;
+        }
         SubCommands::Bench(args) => {
             bench::main(cli.args, args)?;
         }
@@ -678,6 +1100,9 @@ where
         SubCommands::From(args) => {
             from::main(cli.args, args)?;
         }
+        SubCommands::Gen(args) => {
+            gen::main(cli.args, args)?;
+        }
         SubCommands::Perm(args) => {
             perm::main(cli.args, args)?;
         }
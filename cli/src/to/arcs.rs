@@ -5,7 +5,7 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
-use crate::GlobalArgs;
+use crate::{GlobalArgs, GraphInputArg};
 use anyhow::Result;
 use clap::Parser;
 use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
@@ -15,7 +15,8 @@ use lender::*;
 use std::io::Write;
 use std::path::PathBuf;
 use webgraph::graphs::bvgraph::get_endianness;
-use webgraph::traits::SequentialLabeling;
+use webgraph::graphs::bvgraph::StreamBufReader;
+use webgraph::traits::SequentialGraph;
 use webgraph::utils::MmapHelper;
 
 #[derive(Parser, Debug)]
@@ -24,6 +25,9 @@ pub struct CliArgs {
     /// The basename of the graph.
     pub src: PathBuf,
 
+    #[clap(flatten)]
+    pub input: GraphInputArg,
+
     #[arg(long, default_value_t = '\t')]
     /// The separator between source and target nodes.
     pub separator: char,
@@ -48,10 +52,19 @@ pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
 pub fn to_csv<E: Endianness + 'static>(global_args: GlobalArgs, args: CliArgs) -> Result<()>
 where
     MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    StreamBufReader<E>: CodesRead<E>,
 {
-    let graph = webgraph::graphs::bvgraph::sequential::BvGraphSeq::with_basename(args.src)
-        .endianness::<E>()
-        .load()?;
+    if let Some(graph) = args.input.load_stream::<E>(&args.src)? {
+        write_arcs(global_args, args, graph)
+    } else {
+        let graph = webgraph::graphs::bvgraph::sequential::BvGraphSeq::with_basename(&args.src)
+            .endianness::<E>()
+            .load()?;
+        write_arcs(global_args, args, graph)
+    }
+}
+
+fn write_arcs(global_args: GlobalArgs, args: CliArgs, graph: impl SequentialGraph) -> Result<()> {
     let num_nodes = graph.num_nodes();
 
     let labels = if let Some(labels) = args.labels {
@@ -7,7 +7,7 @@
 
 use crate::create_parent_dir;
 use crate::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
 use dsi_bitstream::prelude::*;
 
@@ -34,8 +34,35 @@ pub struct CliArgs {
     #[clap(flatten)]
     pub batch_size: BatchSizeArg,
 
+    #[arg(long)]
+    /// The basename of the labels of the source graph, if it is labeled; the
+    /// labels are then permuted and recompressed together with the graph,
+    /// albeit the compression is sequential. The graph and the labels must
+    /// have an .ef file.
+    pub labels: Option<PathBuf>,
+
+    #[arg(long, requires = "labels")]
+    /// The basename of the labels of the destination graph (by default, the
+    /// basename of the destination graph followed by -labelled).
+    pub dst_labels: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub label_args: LabelArgs,
+
     #[clap(flatten)]
     pub ca: CompressArgs,
+
+    #[arg(long)]
+    /// Choose the codes using a statistics pass on the graph; the codes
+    /// specified by the other options are used only during the statistics pass.
+    pub auto_codes: bool,
+
+    #[arg(long, conflicts_with = "labels")]
+    /// Huffman-code the graph using models built by a statistics pass on the
+    /// graph; the codes specified by the other options are used only to select
+    /// references. The result is smaller, but slower to decode, and cannot be
+    /// read by the Java version.
+    pub huffman: bool,
 }
 
 pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
@@ -68,6 +95,23 @@ where
     MmapHelper<u32>: CodesReaderFactoryHelper<E>,
     for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Send + Sync + Clone,
 {
+    if args.labels.is_some() {
+        return match args.label_args.label_type {
+            LabelType::U32 => {
+                compress_labeled_graph::<E, u32>(args, target_endianness, permutation)
+            }
+            LabelType::U64 => {
+                compress_labeled_graph::<E, u64>(args, target_endianness, permutation)
+            }
+            LabelType::F32 => {
+                compress_labeled_graph::<E, f32>(args, target_endianness, permutation)
+            }
+            LabelType::F64 => {
+                compress_labeled_graph::<E, f64>(args, target_endianness, permutation)
+            }
+        };
+    }
+
     let dir = Builder::new().prefix("to_bvgraph_").tempdir()?;
 
     let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
@@ -86,21 +130,21 @@ where
                 "Permuted the graph. It took {:.3} seconds",
                 start.elapsed().as_secs_f64()
             );
-            BvComp::parallel_endianness(
+            parallel_compress(
                 args.dst,
                 &sorted,
-                sorted.num_nodes(),
-                args.ca.into(),
+                comp_flags(args.ca, args.auto_codes, &sorted, &thread_pool)?,
+                args.huffman,
                 &thread_pool,
                 dir,
                 &target_endianness.unwrap_or_else(|| E::NAME.into()),
             )?;
         } else {
-            BvComp::parallel_endianness(
+            parallel_compress(
                 args.dst,
                 &graph,
-                graph.num_nodes(),
-                args.ca.into(),
+                comp_flags(args.ca, args.auto_codes, &graph, &thread_pool)?,
+                args.huffman,
                 &thread_pool,
                 dir,
                 &target_endianness.unwrap_or_else(|| E::NAME.into()),
@@ -125,21 +169,21 @@ where
                 start.elapsed().as_secs_f64()
             );
 
-            BvComp::parallel_endianness(
+            parallel_compress(
                 args.dst,
                 &permuted,
-                permuted.num_nodes(),
-                args.ca.into(),
+                comp_flags(args.ca, args.auto_codes, &permuted, &thread_pool)?,
+                args.huffman,
                 &thread_pool,
                 dir,
                 &target_endianness.unwrap_or_else(|| E::NAME.into()),
             )?;
         } else {
-            BvComp::parallel_endianness(
+            parallel_compress(
                 args.dst,
                 &seq_graph,
-                seq_graph.num_nodes(),
-                args.ca.into(),
+                comp_flags(args.ca, args.auto_codes, &seq_graph, &thread_pool)?,
+                args.huffman,
                 &thread_pool,
                 dir,
                 &target_endianness.unwrap_or_else(|| E::NAME.into()),
@@ -148,3 +192,110 @@ where
     }
     Ok(())
 }
+
+/// Compresses a graph in parallel, Huffman coding it if `huffman` is true.
+fn parallel_compress<G: SequentialGraph + SplitLabeling>(
+    dst: PathBuf,
+    graph: &G,
+    flags: CompFlags,
+    huffman: bool,
+    thread_pool: &rayon::ThreadPool,
+    tmp_dir: tempfile::TempDir,
+    endianness: &str,
+) -> Result<u64>
+where
+    for<'a> <G as SplitLabeling>::SplitLender<'a>: Send + Sync,
+{
+    if huffman {
+        log::info!("Building Huffman models");
+        let models = BvComp::parallel_huffman_models(graph, flags, thread_pool)?;
+        BvComp::parallel_huffman_endianness(
+            dst,
+            graph,
+            flags,
+            &models,
+            thread_pool,
+            tmp_dir,
+            endianness,
+        )
+    } else {
+        BvComp::parallel_endianness(
+            dst,
+            graph,
+            graph.num_nodes(),
+            flags,
+            thread_pool,
+            tmp_dir,
+            endianness,
+        )
+    }
+}
+
+/// Recompresses a labeled graph, possibly applying a permutation.
+fn compress_labeled_graph<E: Endianness, T: ArcLabel>(
+    args: CliArgs,
+    target_endianness: Option<String>,
+    permutation: Option<JavaPermutation>,
+) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Send + Sync + Clone,
+    T::Serde: for<'a> BitDeserializer<E, LoadModeCodesReader<'a, E, Mmap>, DeserType = T>,
+{
+    let labels = args.labels.as_deref().unwrap();
+    let dst_labels = labels_basename(&args.dst, args.dst_labels.as_deref())?;
+    let code = args.label_args.code()?;
+    let label_spec = args.label_args.label_spec()?;
+    let endianness = target_endianness.unwrap_or_else(|| E::NAME.into());
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+
+    let graph = load_labeled::<E, _, _>(labels, T::serde(code))
+        .with_context(|| format!("Could not load labels {}", labels.display()))?;
+
+    if let Some(permutation) = permutation {
+        let batch_size = args.batch_size.labeled_batch_size::<T>();
+        log::info!("Permuting labeled graph with batch size {}", batch_size);
+        let start = std::time::Instant::now();
+        let permuted = webgraph::transform::permute_labeled_split(
+            &graph,
+            &permutation,
+            batch_size,
+            T::serde(code),
+            T::serde(code),
+            &thread_pool,
+        )?;
+        log::info!(
+            "Permuted the graph. It took {:.3} seconds",
+            start.elapsed().as_secs_f64()
+        );
+
+        let flags = comp_flags(
+            args.ca,
+            args.auto_codes,
+            &Left(permuted.clone()),
+            &thread_pool,
+        )?;
+        compress_labeled(
+            &args.dst,
+            &dst_labels,
+            &permuted,
+            T::serde(code),
+            &label_spec,
+            flags,
+            &thread_pool,
+            &endianness,
+        )
+    } else {
+        let flags = comp_flags(args.ca, args.auto_codes, &graph.0, &thread_pool)?;
+        compress_labeled(
+            &args.dst,
+            &dst_labels,
+            &graph,
+            T::serde(code),
+            &label_spec,
+            flags,
+            &thread_pool,
+            &endianness,
+        )
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::{create_parent_dir, GlobalArgs};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::prelude::*;
+use std::path::PathBuf;
+use webgraph::graphs::bvgraph::get_endianness;
+use webgraph::graphs::efgraph::DEFAULT_LOG2_QUANTUM;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "efgraph", about = "Converts a BvGraph to the Java EFGraph format (quasi-succinct successor lists with skip pointers).", long_about = None)]
+pub struct CliArgs {
+    /// The basename of the source graph.
+    pub src: PathBuf,
+    /// The basename of the destination graph.
+    pub dst: PathBuf,
+    /// The base-two logarithm of the indexing quantum.
+    #[clap(short = 'q', long, default_value_t = DEFAULT_LOG2_QUANTUM)]
+    pub log2_quantum: usize,
+    /// The byte order of the graph to write (by default, little endian).
+    #[clap(short = 'E', long)]
+    pub endianness: Option<String>,
+}
+
+pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    create_parent_dir(&args.dst)?;
+
+    match get_endianness(&args.src)?.as_str() {
+        #[cfg(feature = "be_bins")]
+        BE::NAME => convert::<BE>(global_args, args),
+        #[cfg(feature = "le_bins")]
+        LE::NAME => convert::<LE>(global_args, args),
+        e => panic!("Unknown endianness: {}", e),
+    }
+}
+
+pub fn convert<E: Endianness + 'static>(_global_args: GlobalArgs, args: CliArgs) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+{
+    let seq_graph = BvGraphSeq::with_basename(&args.src)
+        .endianness::<E>()
+        .load()
+        .with_context(|| format!("Could not load graph {}", args.src.display()))?;
+
+    match args.endianness.as_deref().unwrap_or(LE::NAME) {
+        BE::NAME => EfGraph::store::<BE>(&args.dst, &seq_graph, args.log2_quantum)?,
+        LE::NAME => EfGraph::store::<LE>(&args.dst, &seq_graph, args.log2_quantum)?,
+        e => bail!("Unknown endianness: {}", e),
+    };
+    Ok(())
+}
@@ -67,12 +67,14 @@ macro_rules! impl_convert {
             .endianness::<$src>()
             .load()
             .with_context(|| format!("Could not load graph {}", $args.src.display()))?;
+        // checksums do not depend on the endianness
+        let mut properties =
+            comp_flags.to_properties::<$dst>(num_nodes, num_arcs, bitstream_len)?;
+        if let Some(checksums) = Checksums::from_properties(&map)? {
+            properties.push_str(&checksums.to_properties());
+        }
         // build the encoder with the opposite endianness
-        std::fs::write(
-            &properties_path,
-            comp_flags.to_properties::<$dst>(num_nodes, num_arcs, bitstream_len)?,
-        )
-        .with_context(|| {
+        std::fs::write(&properties_path, properties).with_context(|| {
             format!(
                 "Could not write properties to {}",
                 properties_path.display()
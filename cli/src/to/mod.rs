@@ -12,6 +12,7 @@ use super::GlobalArgs;
 pub mod arcs;
 pub mod ascii;
 pub mod bvgraph;
+pub mod efgraph;
 pub mod endianness;
 
 #[derive(Subcommand, Debug)]
@@ -20,6 +21,7 @@ pub mod endianness;
 pub enum SubCommands {
     Ascii(ascii::CliArgs),
     Bvgraph(bvgraph::CliArgs),
+    Efgraph(efgraph::CliArgs),
     Arcs(arcs::CliArgs),
     Endianness(endianness::CliArgs),
 }
@@ -28,6 +30,7 @@ pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
     match subcommand {
         SubCommands::Ascii(args) => ascii::main(global_args, args),
         SubCommands::Bvgraph(args) => bvgraph::main(global_args, args),
+        SubCommands::Efgraph(args) => efgraph::main(global_args, args),
         SubCommands::Arcs(args) => arcs::main(global_args, args),
         SubCommands::Endianness(args) => endianness::main(global_args, args),
     }
@@ -10,6 +10,7 @@ use clap::Subcommand;
 use super::GlobalArgs;
 
 pub mod simplify;
+pub mod subgraph;
 pub mod transpose;
 
 #[derive(Subcommand, Debug)]
@@ -17,12 +18,14 @@ pub mod transpose;
 /// Apply a transformation to a graph.
 pub enum SubCommands {
     Simplify(simplify::CliArgs),
+    Subgraph(subgraph::CliArgs),
     Transpose(transpose::CliArgs),
 }
 
 pub fn main(global_args: GlobalArgs, subcommand: SubCommands) -> Result<()> {
     match subcommand {
         SubCommands::Simplify(args) => simplify::main(global_args, args),
+        SubCommands::Subgraph(args) => subgraph::main(global_args, args),
         SubCommands::Transpose(args) => transpose::main(global_args, args),
     }
 }
@@ -0,0 +1,116 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::*;
+use anyhow::{ensure, Context, Result};
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::prelude::*;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use sux::bits::BitVec;
+use sux::traits::NumBits;
+use tempfile::Builder;
+use webgraph::prelude::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "subgraph", about = "Compresses the subgraph of a BvGraph induced by a set of nodes.", long_about = None)]
+pub struct CliArgs {
+    /// The basename of the graph.
+    pub src: PathBuf,
+    /// The basename of the subgraph.
+    pub dst: PathBuf,
+
+    #[arg(long)]
+    /// A file containing the nodes of the subgraph, one per line, in any
+    /// order.
+    pub nodes: PathBuf,
+
+    #[arg(long)]
+    /// Keep the original node IDs, leaving the nodes not in the subgraph
+    /// isolated, rather than renumbering the nodes of the subgraph densely.
+    pub keep_ids: bool,
+
+    #[clap(flatten)]
+    pub num_threads: NumThreadsArg,
+
+    #[clap(flatten)]
+    pub ca: CompressArgs,
+}
+
+pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
+    create_parent_dir(&args.dst)?;
+
+    match get_endianness(&args.src)?.as_str() {
+        #[cfg(feature = "be_bins")]
+        BE::NAME => subgraph::<BE>(global_args, args),
+        #[cfg(feature = "le_bins")]
+        LE::NAME => subgraph::<LE>(global_args, args),
+        e => panic!("Unknown endianness: {}", e),
+    }
+}
+
+/// Reads a set of nodes, one per line, returning it as a mask.
+fn read_nodes(path: &Path, num_nodes: usize) -> Result<BitVec> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Could not open {}", path.display()))?;
+    let mut mask = BitVec::new(num_nodes);
+    for (line_num, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.context("Could not read line")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let node = line.parse::<usize>().with_context(|| {
+            format!(
+                "Line {}: {:?} is not a numerical node identifier",
+                line_num, line
+            )
+        })?;
+        ensure!(
+            node < num_nodes,
+            "Line {}: node {} does not exist, as the graph has {} nodes",
+            line_num,
+            node,
+            num_nodes
+        );
+        mask.set(node, true);
+    }
+    Ok(mask)
+}
+
+pub fn subgraph<E: Endianness>(_global_args: GlobalArgs, args: CliArgs) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Send + Sync + Clone,
+{
+    let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+    let target_endianness = args.ca.endianness.clone().unwrap_or_else(|| E::NAME.into());
+
+    let graph = BvGraphSeq::with_basename(&args.src)
+        .endianness::<E>()
+        .load()?;
+    let mask = read_nodes(&args.nodes, graph.num_nodes())?;
+    let sub = Subgraph::from_mask(graph, mask, !args.keep_ids);
+    log::info!(
+        "The subgraph contains {} nodes out of {}",
+        sub.nodes().num_ones(),
+        sub.graph().num_nodes()
+    );
+
+    let dir = Builder::new().prefix("transform_subgraph_").tempdir()?;
+    BvComp::parallel_endianness(
+        &args.dst,
+        &sub,
+        sub.num_nodes(),
+        args.ca.into(),
+        &thread_pool,
+        dir,
+        &target_endianness,
+    )?;
+
+    Ok(())
+}
@@ -21,6 +21,9 @@ pub struct CliArgs {
     /// The basename of the transposed graph.
     pub dst: PathBuf,
 
+    #[clap(flatten)]
+    pub input: GraphInputArg,
+
     #[clap(flatten)]
     pub num_threads: NumThreadsArg,
 
@@ -46,25 +49,42 @@ pub fn main(global_args: GlobalArgs, args: CliArgs) -> Result<()> {
 pub fn transpose<E: Endianness>(_global_args: GlobalArgs, args: CliArgs) -> Result<()>
 where
     MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek + Send + Sync + Clone,
+    StreamBufReader<E>: CodesRead<E>,
 {
     let thread_pool = crate::get_thread_pool(args.num_threads.num_threads);
+    let batch_size = args.batch_size.batch_size;
 
-    // TODO!: speed it up by using random access graph if possible
-    let seq_graph = webgraph::graphs::bvgraph::sequential::BvGraphSeq::with_basename(&args.src)
-        .endianness::<E>()
-        .load()?;
-
-    // transpose the graph
-    let sorted = webgraph::transform::transpose(&seq_graph, args.batch_size.batch_size).unwrap();
+    if let Some(seq_graph) = args.input.load_stream::<E>(&args.src)? {
+        // streams cannot be split, so we must transpose sequentially
+        let sorted = webgraph::transform::transpose(&seq_graph, batch_size)?;
+        compress_transpose::<E>(args, &sorted, &thread_pool)
+    } else if args.src.with_extension(EF_EXTENSION).exists() {
+        let graph = BvGraph::with_basename(&args.src).endianness::<E>().load()?;
+        let sorted = webgraph::transform::transpose_split(&graph, batch_size, &thread_pool)?;
+        compress_transpose::<E>(args, &sorted, &thread_pool)
+    } else {
+        let seq_graph = BvGraphSeq::with_basename(&args.src)
+            .endianness::<E>()
+            .load()?;
+        let sorted = webgraph::transform::transpose_split(&seq_graph, batch_size, &thread_pool)?;
+        compress_transpose::<E>(args, &sorted, &thread_pool)
+    }
+}
 
+fn compress_transpose<E: Endianness>(
+    args: CliArgs,
+    sorted: &(impl SequentialGraph + SplitLabeling),
+    thread_pool: &rayon::ThreadPool,
+) -> Result<()> {
     let target_endianness = args.ca.endianness.clone();
     let dir = Builder::new().prefix("transform_transpose_").tempdir()?;
     BvComp::parallel_endianness(
         &args.dst,
-        &sorted,
+        sorted,
         sorted.num_nodes(),
         args.ca.into(),
-        &thread_pool,
+        thread_pool,
         dir,
         &target_endianness.unwrap_or_else(|| E::NAME.into()),
     )?;
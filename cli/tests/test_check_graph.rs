@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::BE;
+use std::path::{Path, PathBuf};
+use webgraph::prelude::*;
+use webgraph_cli::check::graph::{find_problems, Problem};
+
+const TEST_GRAPH: &str = "../data/cnr-2000";
+/// The nodes whose outdegree we corrupt.
+const CORRUPTED: [usize; 2] = [1000, 200_000];
+
+/// Copies cnr-2000 to a temporary directory, optionally with its Elias–Fano
+/// offsets, and clears the 48 bits starting at the offset of each node in
+/// [`CORRUPTED`], so that its outdegree is decoded as a huge value.
+fn corrupted_copy(dir: &Path, with_ef: bool) -> Result<PathBuf> {
+    let src = PathBuf::from(TEST_GRAPH);
+    let basename = dir.join("cnr-2000");
+    let mut extensions = vec![GRAPH_EXTENSION, PROPERTIES_EXTENSION];
+    if with_ef {
+        extensions.push(EF_EXTENSION);
+    }
+    for extension in extensions {
+        std::fs::copy(
+            src.with_extension(extension),
+            basename.with_extension(extension),
+        )?;
+    }
+
+    let graph = BvGraphSeq::with_basename(&src).endianness::<BE>().load()?;
+    let offsets = graph.offset_deg_iter().map(|(offset, _)| offset as usize);
+    let offsets = offsets.take(CORRUPTED[1] + 1).collect::<Vec<_>>();
+    let graph_path = basename.with_extension(GRAPH_EXTENSION);
+    let mut bytes = std::fs::read(&graph_path)?;
+    for node in CORRUPTED {
+        // The bitstream is big endian, so bit 0 is the highest bit
+        for bit in offsets[node]..offsets[node] + 48 {
+            bytes[bit / 8] &= !(0x80 >> (bit % 8));
+        }
+    }
+    std::fs::write(&graph_path, bytes)?;
+    Ok(basename)
+}
+
+/// Returns the problems referring to a node, checking that they come first.
+fn node_problems(problems: &[Problem]) -> Vec<&Problem> {
+    let node_problems = problems
+        .iter()
+        .take_while(|p| p.node.is_some())
+        .collect::<Vec<_>>();
+    assert!(problems[node_problems.len()..]
+        .iter()
+        .all(|p| p.node.is_none()));
+    node_problems
+}
+
+#[test]
+fn test_intact() -> Result<()> {
+    let problems = find_problems::<BE>(Path::new(TEST_GRAPH), 4, None)?;
+    assert_eq!(problems, vec![]);
+    Ok(())
+}
+
+#[test]
+fn test_corrupted_random() -> Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let basename = corrupted_copy(tmp.path(), true)?;
+    let problems = find_problems::<BE>(&basename, 4, None)?;
+    let node_problems = node_problems(&problems);
+
+    // Each corrupted node is reported; decoding then resumes, possibly
+    // failing on the following nodes referencing it
+    for node in CORRUPTED {
+        let first = node_problems.iter().find(|p| p.node >= Some(node)).unwrap();
+        assert_eq!(first.node, Some(node));
+        assert!(
+            first.msg.contains(&format!("Node {} has outdegree", node)),
+            "{}",
+            first.msg
+        );
+    }
+    for p in node_problems {
+        let node = p.node.unwrap();
+        assert!(
+            CORRUPTED.iter().any(|&c| (c..c + 100).contains(&node)),
+            "{}",
+            p.msg
+        );
+    }
+    // The missing arcs are reported, too
+    assert!(problems.last().unwrap().msg.contains("arcs"));
+    Ok(())
+}
+
+#[test]
+fn test_corrupted_sequential() -> Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let basename = corrupted_copy(tmp.path(), false)?;
+    let problems = find_problems::<BE>(&basename, 4, None)?;
+    let node_problems = node_problems(&problems);
+
+    // Splits are computed sequentially, so decoding stops at the first
+    // corrupted node
+    assert!(!node_problems.is_empty());
+    assert!(node_problems
+        .iter()
+        .all(|p| p.node.unwrap() >= CORRUPTED[0]));
+    assert!(
+        node_problems.iter().any(|p| p
+            .msg
+            .contains(&format!("Node {} has outdegree", CORRUPTED[0]))),
+        "{:?}",
+        node_problems
+    );
+    assert!(problems.last().unwrap().msg.contains("arcs"));
+    Ok(())
+}
@@ -0,0 +1,113 @@
+#!/usr/bin/env python3
+#
+# SPDX-FileCopyrightText: 2025 Inria
+# SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+#
+# SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+#
+# Writes the test-ef.graph, test-ef.offsets and test-ef.properties fixtures,
+# which follow the layout of Java's it.unimi.dsi.webgraph.EFGraph with a
+# quantum of 4. The files have been generated by this script, independently
+# of the Rust code, and checked by hand, as no Java installation was
+# available; the properties file mimics the output of
+# java.util.Properties.store, including the date comment and the unordered
+# keys.
+
+import sys
+
+NUM_NODES = 40
+LOG2_QUANTUM = 2
+ARCS = {
+    0: [1, 2, 3, 5, 8, 13, 21, 34],
+    1: [0],
+    3: [39],
+    5: list(range(40)),
+    6: [2, 4, 6, 7, 9, 10, 30, 31, 32, 33, 38],
+    39: [0, 39],
+}
+
+
+def msb(x):
+    return x.bit_length() - 1
+
+
+def successor_list(succ, upper_bound):
+    """Returns the bits of a successor list, in writing order."""
+    bits = []
+
+    def append(value, width):
+        bits.extend((value >> i) & 1 for i in range(width))
+
+    # γ code, with the little-endian conventions: msb zeros, a one, and the
+    # msb lower bits of the length plus one
+    n = len(succ) + 1
+    bits.extend([0] * msb(n) + [1])
+    append(n & ~(1 << msb(n)), msb(n))
+
+    if not succ:
+        return bits
+    l = max(0, msb(upper_bound // len(succ))) if upper_bound >= len(succ) else 0
+    upper = []
+    for i, s in enumerate(succ):
+        pos = (s >> l) + i
+        upper.extend([0] * (pos - len(upper)) + [1])
+
+    # Skip pointers: the position following the kq-th zero of the upper bits,
+    # or zero if there are fewer zeros
+    pointer_size = (len(succ) + (upper_bound >> l) - 1).bit_length()
+    for k in range(1, ((upper_bound >> l) >> LOG2_QUANTUM) + 1):
+        zeros, pointer = 0, 0
+        for pos, bit in enumerate(upper):
+            zeros += bit == 0
+            if zeros == k << LOG2_QUANTUM:
+                pointer = pos + 1
+                break
+        append(pointer, pointer_size)
+
+    for s in succ:
+        append(s & ((1 << l) - 1), l)
+    return bits + upper
+
+
+def main(basename):
+    graph = []
+    lengths = []
+    for node in range(NUM_NODES):
+        bits = successor_list(ARCS.get(node, []), NUM_NODES)
+        graph += bits
+        lengths.append(len(bits))
+
+    # Little-endian 64-bit words, filled from the least significant bit
+    graph += [0] * (-len(graph) % 64)
+    with open(basename + ".graph", "wb") as f:
+        for w in range(0, len(graph), 64):
+            word = sum(bit << i for i, bit in enumerate(graph[w : w + 64]))
+            f.write(word.to_bytes(8, "little"))
+
+    # γ-coded offset deltas, big endian, padded to a byte as in
+    # OutputBitStream
+    offsets = []
+    for delta in [0] + lengths:
+        n = delta + 1
+        offsets += [0] * msb(n) + [int(b) for b in bin(n)[2:]]
+    offsets += [0] * (-len(offsets) % 8)
+    with open(basename + ".offsets", "wb") as f:
+        f.write(bytes(int("".join(map(str, offsets[i : i + 8])), 2) for i in range(0, len(offsets), 8)))
+
+    with open(basename + ".properties", "w") as f:
+        f.write(
+            "#EFGraph properties\n"
+            "#Sat Oct 17 10:24:31 CEST 2026\n"
+            "upperbound=%d\n"
+            "byteorder=LITTLE_ENDIAN\n"
+            "graphclass=it.unimi.dsi.webgraph.EFGraph\n"
+            "arcs=%d\n"
+            "version=0\n"
+            "quantum=%d\n"
+            "nodes=%d\n"
+            % (NUM_NODES, sum(map(len, ARCS.values())), 1 << LOG2_QUANTUM, NUM_NODES)
+        )
+
+
+if __name__ == "__main__":
+    main(sys.argv[1] if len(sys.argv) > 1 else "test-ef")
@@ -10,16 +10,16 @@
 use anyhow::{Context, Result};
 use bitstream::Supply;
 use clap::Parser;
-use dsi_bitstream::codes::GammaRead;
-use dsi_bitstream::impls::{BufBitReader, MemWordReader};
-use dsi_bitstream::traits::{BitRead, BitSeek, Endianness, BE};
+use dsi_bitstream::dispatch::Codes;
+use dsi_bitstream::traits::BE;
 use dsi_progress_logger::prelude::*;
 use epserde::deser::{DeserType, Deserialize, Flags, MemCase};
 use lender::*;
 use mmap_rs::MmapFlags;
 use std::hint::black_box;
 use std::path::{Path, PathBuf};
-use webgraph::prelude::bitstream::BitStreamLabeling;
+use webgraph::prelude::bitstream::{BitStreamLabeling, MmapReaderSupplier};
+use webgraph::prelude::serializers::{FixedWidthInt, VecOf};
 use webgraph::prelude::*;
 
 #[derive(Parser, Debug)]
@@ -32,54 +32,6 @@ struct Args {
     width: usize,
 }
 
-/// A [`BitDeserializer`] for the labels stored in the bitstream.
-///
-/// Labels are deserialized as a sequence of `u64` values, each of which is
-/// `width` bits wide. The length of the sequence is read using a [γ
-/// code](GammaRead), and then each value is obtained by reading `width` bits.
-struct SwhDeserializer {
-    width: usize,
-}
-
-impl SwhDeserializer {
-    /// Creates a new [`SwhDeserializer`] with the given width.
-    pub fn new(width: usize) -> Self {
-        Self { width }
-    }
-}
-
-impl<BR: BitRead<BE> + BitSeek + GammaRead<BE>> BitDeserializer<BE, BR> for SwhDeserializer {
-    type DeserType = Vec<u64>;
-
-    fn deserialize(
-        &self,
-        bitstream: &mut BR,
-    ) -> std::result::Result<Self::DeserType, <BR as BitRead<BE>>::Error> {
-        let num_labels = bitstream.read_gamma().unwrap() as usize;
-        let mut labels = Vec::with_capacity(num_labels);
-        for _ in 0..num_labels {
-            labels.push(bitstream.read_bits(self.width)?);
-        }
-        Ok(labels)
-    }
-}
-
-pub struct MmapReaderSupplier<E: Endianness> {
-    backend: MmapHelper<u32>,
-    _marker: std::marker::PhantomData<E>,
-}
-
-impl Supply for MmapReaderSupplier<BE> {
-    type Item<'a>
-        = MemBufReader<'a, BE>
-    where
-        Self: 'a;
-
-    fn request(&self) -> Self::Item<'_> {
-        BufBitReader::<BE, _>::new(MemWordReader::new(self.backend.as_ref()))
-    }
-}
-
 pub fn mmap<D>(
     path: impl AsRef<Path>,
     bit_deser: D,
@@ -91,11 +43,7 @@ where
     let labels_path = path.with_extension("labels");
     let ef_path = path.with_extension("ef");
     Ok(BitStreamLabeling::new(
-        MmapReaderSupplier {
-            backend: MmapHelper::<u32>::mmap(&labels_path, MmapFlags::empty())
-                .with_context(|| format!("Could not mmap {}", labels_path.display()))?,
-            _marker: std::marker::PhantomData,
-        },
+        MmapReaderSupplier::mmap(&labels_path, MmapFlags::empty())?,
         bit_deser,
         EF::mmap(&ef_path, Flags::empty())
             .with_context(|| format!("Could not parse {}", ef_path.display()))?,
@@ -109,7 +57,12 @@ pub fn main() -> Result<()> {
         .filter_level(log::LevelFilter::Info)
         .try_init()?;
 
-    let labels = mmap(&args.basename, SwhDeserializer::new(args.width))?;
+    // Labels are sequences of u64 values, each of which is width bits wide,
+    // preceded by their γ-coded length
+    let labels = mmap(
+        &args.basename,
+        VecOf::new(Codes::Gamma, FixedWidthInt::<u64>::new(args.width)),
+    )?;
 
     for _ in 0..10 {
         let mut pl = ProgressLogger::default();
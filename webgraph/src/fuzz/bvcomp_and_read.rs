@@ -43,16 +43,19 @@ pub struct CompFlagsFuzz {
 
 impl From<CompFlagsFuzz> for CompFlags {
     fn from(value: CompFlagsFuzz) -> Self {
-        CompFlags {
+        let mut cf = CompFlags {
             outdegrees: value.outdegrees.into(),
             references: value.references.into(),
-            blocks: value.blocks.into(),
-            intervals: value.intervals.into(),
-            residuals: value.residuals.into(),
             min_interval_length: value.min_interval_length as usize,
             compression_window: value.compression_window as usize,
             max_ref_count: value.max_ref_count as usize,
-        }
+            ref_selection: RefSelection::Greedy,
+            ..CompFlags::default()
+        };
+        cf.set_blocks(value.blocks.into());
+        cf.set_intervals(value.intervals.into());
+        cf.set_residuals(value.residuals.into());
+        cf
     }
 }
 
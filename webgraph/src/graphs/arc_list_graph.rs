@@ -18,7 +18,7 @@ pub struct ArcListGraph<I: Clone> {
     into_iter: I,
 }
 
-impl<L: Clone + Copy + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> ArcListGraph<I> {
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> ArcListGraph<I> {
     /// Creates a new arc-list graph from the given [`IntoIterator`].
     #[inline(always)]
     pub fn new_labeled(num_nodes: usize, iter: I) -> Self {
@@ -89,6 +89,17 @@ impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)>> Iter<L, I> {
             iter: iter.peekable(),
         }
     }
+
+    /// Creates a new lender returning the nodes in the given range.
+    ///
+    /// The sources of the arcs returned by `iter` must lie in `nodes`.
+    pub fn with_range(nodes: core::ops::Range<usize>, iter: I::IntoIter) -> Self {
+        Iter {
+            num_nodes: nodes.end,
+            next_node: nodes.start,
+            iter: iter.peekable(),
+        }
+    }
 }
 
 impl<'succ, L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone>
@@ -184,6 +195,11 @@ impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> Sequ
     }
 }
 
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone>
+    LabeledSequentialGraph<L> for ArcListGraph<I>
+{
+}
+
 pub struct Succ<'succ, L, I: IntoIterator<Item = (usize, usize, L)>> {
     node_iter: &'succ mut Iter<L, I>,
 }
@@ -210,3 +226,185 @@ impl<L, I: IntoIterator<Item = (usize, usize, L)>> Iterator for Succ<'_, L, I> {
         Some((succ, label))
     }
 }
+
+/// An adapter exhibiting lists of labeled arcs, each sorted by source and
+/// containing the arcs whose sources lie in a range of nodes, as a [labeled
+/// sequential graph](LabeledSequentialGraph) that can be
+/// [split](SplitLabeling) along the ranges.
+///
+/// This is the structure returned by parallel transformations such as
+/// [`transpose_labeled_split`](crate::transform::transpose_labeled_split):
+/// each part can be compressed independently by
+/// [`BvComp::parallel_iter`](crate::graphs::bvgraph::BvComp::parallel_iter).
+#[derive(Clone)]
+pub struct SplitArcListGraph<I: Clone> {
+    num_nodes: usize,
+    /// The first node of each part, followed by the number of nodes.
+    boundaries: Box<[usize]>,
+    into_iters: Box<[I]>,
+}
+
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> SplitArcListGraph<I> {
+    /// Creates a new split arc-list graph from the given [`IntoIterator`]s.
+    ///
+    /// The `boundaries` must be nondecreasing, must start with zero and end
+    /// with `num_nodes`, and must contain one element more than `iters`; the
+    /// sources of the arcs returned by the `i`-th iterator must lie in the
+    /// range `boundaries[i]..boundaries[i + 1]`.
+    pub fn new_labeled(
+        num_nodes: usize,
+        boundaries: impl Into<Box<[usize]>>,
+        iters: impl Into<Box<[I]>>,
+    ) -> Self {
+        let boundaries = boundaries.into();
+        let into_iters = iters.into();
+        assert_eq!(boundaries.len(), into_iters.len() + 1);
+        assert_eq!(boundaries.first(), Some(&0));
+        assert_eq!(boundaries.last(), Some(&num_nodes));
+        assert!(boundaries.windows(2).all(|w| w[0] <= w[1]));
+        Self {
+            num_nodes,
+            boundaries,
+            into_iters,
+        }
+    }
+
+    /// Returns the number of parts.
+    pub fn num_parts(&self) -> usize {
+        self.into_iters.len()
+    }
+
+    /// Returns a lender on the nodes of the given range of parts.
+    fn parts_iter(&self, parts: core::ops::Range<usize>) -> Iter<L, PartsIter<'_, I>> {
+        Iter {
+            num_nodes: self.boundaries[parts.end],
+            next_node: self.boundaries[parts.start],
+            iter: PartsIter {
+                into_iters: self.into_iters[parts].iter(),
+                iter: None,
+            }
+            .peekable(),
+        }
+    }
+}
+
+/// An iterator chaining the arcs of consecutive parts of a
+/// [`SplitArcListGraph`].
+pub struct PartsIter<'a, I: IntoIterator> {
+    into_iters: core::slice::Iter<'a, I>,
+    iter: Option<I::IntoIter>,
+}
+
+impl<I: IntoIterator<IntoIter: Clone>> Clone for PartsIter<'_, I> {
+    fn clone(&self) -> Self {
+        Self {
+            into_iters: self.into_iters.clone(),
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<I: IntoIterator + Clone> Iterator for PartsIter<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.iter.as_mut().and_then(Iterator::next) {
+                return Some(item);
+            }
+            self.iter = Some(self.into_iters.next()?.clone().into_iter());
+        }
+    }
+}
+
+// Parts contain disjoint, increasing ranges of sources
+unsafe impl<I: IntoIterator + Clone> SortedIterator for PartsIter<'_, I> where
+    I::IntoIter: SortedIterator
+{
+}
+
+impl<'lend, L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> Lending<'lend>
+    for &SplitArcListGraph<I>
+where
+    I::IntoIter: Clone,
+{
+    type Lend = (usize, Succ<'lend, L, PartsIter<'lend, I>>);
+}
+
+impl<'a, L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> IntoLender
+    for &'a SplitArcListGraph<I>
+where
+    I::IntoIter: Clone,
+{
+    type Lender = Iter<L, PartsIter<'a, I>>;
+
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> SequentialLabeling
+    for SplitArcListGraph<I>
+where
+    I::IntoIter: Clone,
+{
+    type Label = (usize, L);
+    type Lender<'node>
+        = Iter<L, PartsIter<'node, I>>
+    where
+        Self: 'node;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        None
+    }
+
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        // The last part whose first node is at most from
+        let part = self.boundaries[1..].partition_point(|&b| b <= from);
+        let mut iter = self.parts_iter(part.min(self.num_parts())..self.num_parts());
+        while iter.next_node < from {
+            iter.next();
+        }
+        iter
+    }
+}
+
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone>
+    LabeledSequentialGraph<L> for SplitArcListGraph<I>
+where
+    I::IntoIter: Clone,
+{
+}
+
+impl<L: Clone + 'static, I: IntoIterator<Item = (usize, usize, L)> + Clone> SplitLabeling
+    for SplitArcListGraph<I>
+where
+    I: Sync,
+    I::IntoIter: Clone + Send + Sync,
+    L: Send + Sync,
+{
+    type SplitLender<'a>
+        = Iter<L, PartsIter<'a, I>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = Vec<Self::SplitLender<'a>>
+    where
+        Self: 'a;
+
+    /// Returns `how_many` lenders, each covering a range of consecutive
+    /// parts; if `how_many` is larger than the number of parts, some lenders
+    /// will be empty.
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        let num_parts = self.num_parts();
+        (0..how_many)
+            .map(|i| self.parts_iter(i * num_parts / how_many..(i + 1) * num_parts / how_many))
+            .collect()
+    }
+}
@@ -287,6 +287,11 @@ impl BTreeGraph {
         self.0.add_arc(u, v, ())
     }
 
+    /// Remove an arc from the graph and return whether it was present or not.
+    pub fn remove_arc(&mut self, u: usize, v: usize) -> bool {
+        self.0.remove_arc(u, v)
+    }
+
     /// Add nodes and successors from an [`IntoLender`] yielding a
     /// [`NodeLabelsLender`].
     pub fn add_lender<I: IntoLender>(&mut self, iter_nodes: I) -> &mut Self
@@ -425,7 +430,7 @@ impl<L: Clone + 'static> Eq for Successor<L> {}
 impl<L: Clone + 'static> PartialOrd for Successor<L> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        Some(self.0.cmp(&other.0))
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,187 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Checksums of the successor lists of a graph.
+//!
+//! Compression methods record in the `.properties` file a checksum for each
+//! block of [`CHECKSUM_BLOCK_SIZE`] consecutive nodes, so that corruption can
+//! be detected and located by decoding the graph (e.g., using `webgraph check
+//! graph`).
+//!
+//! The checksum of a block is the wrapping sum of the hashes of the successor
+//! lists of its nodes, as computed by a [`NodeHasher`]; thus, checksums can be
+//! computed in any order, and partial checksums of the same block can be
+//! [merged](Checksums::merge).
+
+use anyhow::{ensure, Context, Result};
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::ops::Range;
+use std::path::Path;
+
+/// The default number of nodes in a checksum block.
+pub const CHECKSUM_BLOCK_SIZE: usize = 1 << 16;
+
+/// The SplitMix64 finalizer.
+#[inline(always)]
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Computes incrementally the hash of the successor list of a node.
+#[derive(Debug, Clone)]
+pub struct NodeHasher {
+    state: u64,
+    len: u64,
+}
+
+impl NodeHasher {
+    /// Creates a new hasher for the successors of the given node.
+    pub fn new(node: usize) -> Self {
+        Self {
+            state: mix(node as u64),
+            len: 0,
+        }
+    }
+
+    /// Adds a successor to the hash.
+    #[inline(always)]
+    pub fn push(&mut self, succ: usize) {
+        self.state = mix(self.state ^ succ as u64);
+        self.len += 1;
+    }
+
+    /// Returns the hash of the successor list.
+    pub fn finish(&self) -> u64 {
+        mix(self.state ^ self.len)
+    }
+}
+
+/// Checksums of the successor lists of a graph, one for each block of
+/// consecutive nodes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checksums {
+    block_size: usize,
+    sums: Vec<u64>,
+}
+
+impl Default for Checksums {
+    fn default() -> Self {
+        Self::new(CHECKSUM_BLOCK_SIZE)
+    }
+}
+
+impl Checksums {
+    /// Creates empty checksums with the given block size.
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size > 0, "The block size must be positive");
+        Self {
+            block_size,
+            sums: Vec::new(),
+        }
+    }
+
+    /// Returns the number of nodes in a block.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the checksums of the blocks.
+    pub fn sums(&self) -> &[u64] {
+        &self.sums
+    }
+
+    /// Returns the range of nodes of the given block in a graph with the
+    /// given number of nodes.
+    pub fn block_range(&self, block: usize, num_nodes: usize) -> Range<usize> {
+        let start = (block * self.block_size).min(num_nodes);
+        start..(start + self.block_size).min(num_nodes)
+    }
+
+    /// Adds the hash of the successor list of a node, as returned by
+    /// [`NodeHasher::finish`], to the checksum of its block.
+    #[inline(always)]
+    pub fn add(&mut self, node: usize, hash: u64) {
+        let block = node / self.block_size;
+        if block >= self.sums.len() {
+            self.sums.resize(block + 1, 0);
+        }
+        self.sums[block] = self.sums[block].wrapping_add(hash);
+    }
+
+    /// Adds the hash of the successor list of a node.
+    pub fn add_successors(&mut self, node: usize, succ: impl IntoIterator<Item = usize>) {
+        let mut hasher = NodeHasher::new(node);
+        succ.into_iter().for_each(|s| hasher.push(s));
+        self.add(node, hasher.finish());
+    }
+
+    /// Merges into these checksums other checksums computed on a disjoint
+    /// set of nodes.
+    pub fn merge(&mut self, other: &Checksums) {
+        assert_eq!(
+            self.block_size, other.block_size,
+            "Cannot merge checksums with different block sizes"
+        );
+        if other.sums.len() > self.sums.len() {
+            self.sums.resize(other.sums.len(), 0);
+        }
+        for (sum, other) in self.sums.iter_mut().zip(&other.sums) {
+            *sum = sum.wrapping_add(*other);
+        }
+    }
+
+    /// Returns the lines describing these checksums in a `.properties` file.
+    pub fn to_properties(&self) -> String {
+        format!(
+            "checksumblocksize={}\nchecksums={}\n",
+            self.block_size,
+            self.sums
+                .iter()
+                .map(|sum| format!("{:016x}", sum))
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    /// Parses the checksums from a map of properties, returning `None` if
+    /// the properties do not contain checksums.
+    pub fn from_properties(map: &HashMap<String, String>) -> Result<Option<Self>> {
+        let (Some(block_size), Some(sums)) = (map.get("checksumblocksize"), map.get("checksums"))
+        else {
+            return Ok(None);
+        };
+        let block_size = block_size
+            .parse::<usize>()
+            .with_context(|| format!("Could not parse checksum block size {}", block_size))?;
+        ensure!(block_size > 0, "The checksum block size must be positive");
+        let sums = sums
+            .split(',')
+            .filter(|sum| !sum.is_empty())
+            .map(|sum| {
+                u64::from_str_radix(sum.trim(), 16)
+                    .with_context(|| format!("Could not parse checksum {}", sum))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Self { block_size, sums }))
+    }
+
+    /// Loads the checksums from a `.properties` file, returning `None` if
+    /// the file does not contain checksums.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        let f = std::fs::File::open(path)
+            .with_context(|| format!("Cannot open property file {}", path.display()))?;
+        let map = java_properties::read(BufReader::new(f)).with_context(|| {
+            format!("cannot parse {} as a java properties file", path.display())
+        })?;
+        Self::from_properties(&map)
+    }
+}
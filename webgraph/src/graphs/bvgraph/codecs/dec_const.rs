@@ -18,6 +18,9 @@ use sux::traits::IndexedSeq;
 
 #[repr(transparent)]
 /// An implementation of [`Decode`]  with compile-time defined codes.
+///
+/// The meaning of the parameters is the same as in
+/// [`Static`](crate::graphs::bvgraph::Static).
 #[derive(Debug, Clone)]
 pub struct ConstCodesDecoder<
     E: Endianness,
@@ -27,6 +30,10 @@ pub struct ConstCodesDecoder<
     const BLOCKS: usize = { code_consts::GAMMA },
     const INTERVALS: usize = { code_consts::GAMMA },
     const RESIDUALS: usize = { code_consts::ZETA3 },
+    const BLOCK_COUNTS: usize = BLOCKS,
+    const INTERVAL_COUNTS: usize = INTERVALS,
+    const INTERVAL_STARTS: usize = INTERVALS,
+    const FIRST_RESIDUALS: usize = RESIDUALS,
 > {
     /// The inner codes reader we will dispatch to
     pub(crate) code_reader: CR,
@@ -43,7 +50,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > BitSeek for ConstCodesDecoder<E, CR, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > BitSeek
+    for ConstCodesDecoder<
+        E,
+        CR,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Error = <CR as BitSeek>::Error;
 
@@ -64,7 +88,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > ConstCodesDecoder<E, CR, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    ConstCodesDecoder<
+        E,
+        CR,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     /// Creates a new [`ConstCodesEncoder`] from a [`CodesRead`] implementation.
     /// and a [`CompFlags`] struct
@@ -75,13 +116,25 @@ impl<
             bail!("Code for outdegrees does not match");
         }
         if comp_flags.references.to_code_const()? != REFERENCES {
-            bail!("Cod for references does not match");
+            bail!("Code for references does not match");
+        }
+        if comp_flags.block_counts.to_code_const()? != BLOCK_COUNTS {
+            bail!("Code for block counts does not match");
         }
         if comp_flags.blocks.to_code_const()? != BLOCKS {
             bail!("Code for blocks does not match");
         }
-        if comp_flags.intervals.to_code_const()? != INTERVALS {
-            bail!("Code for intervals does not match");
+        if comp_flags.interval_counts.to_code_const()? != INTERVAL_COUNTS {
+            bail!("Code for interval counts does not match");
+        }
+        if comp_flags.interval_starts.to_code_const()? != INTERVAL_STARTS {
+            bail!("Code for interval starts does not match");
+        }
+        if comp_flags.interval_lens.to_code_const()? != INTERVALS {
+            bail!("Code for interval lengths does not match");
+        }
+        if comp_flags.first_residuals.to_code_const()? != FIRST_RESIDUALS {
+            bail!("Code for first residuals does not match");
         }
         if comp_flags.residuals.to_code_const()? != RESIDUALS {
             bail!("Code for residuals does not match");
@@ -101,7 +154,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > Decode for ConstCodesDecoder<E, CR, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > Decode
+    for ConstCodesDecoder<
+        E,
+        CR,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     #[inline(always)]
     fn read_outdegree(&mut self) -> u64 {
@@ -115,7 +185,9 @@ impl<
 
     #[inline(always)]
     fn read_block_count(&mut self) -> u64 {
-        ConstCode::<BLOCKS>.read(&mut self.code_reader).unwrap()
+        ConstCode::<BLOCK_COUNTS>
+            .read(&mut self.code_reader)
+            .unwrap()
     }
     #[inline(always)]
     fn read_block(&mut self) -> u64 {
@@ -124,11 +196,15 @@ impl<
 
     #[inline(always)]
     fn read_interval_count(&mut self) -> u64 {
-        ConstCode::<INTERVALS>.read(&mut self.code_reader).unwrap()
+        ConstCode::<INTERVAL_COUNTS>
+            .read(&mut self.code_reader)
+            .unwrap()
     }
     #[inline(always)]
     fn read_interval_start(&mut self) -> u64 {
-        ConstCode::<INTERVALS>.read(&mut self.code_reader).unwrap()
+        ConstCode::<INTERVAL_STARTS>
+            .read(&mut self.code_reader)
+            .unwrap()
     }
     #[inline(always)]
     fn read_interval_len(&mut self) -> u64 {
@@ -137,7 +213,9 @@ impl<
 
     #[inline(always)]
     fn read_first_residual(&mut self) -> u64 {
-        ConstCode::<RESIDUALS>.read(&mut self.code_reader).unwrap()
+        ConstCode::<FIRST_RESIDUALS>
+            .read(&mut self.code_reader)
+            .unwrap()
     }
     #[inline(always)]
     fn read_residual(&mut self) -> u64 {
@@ -154,6 +232,10 @@ pub struct ConstCodesDecoderFactory<
     const BLOCKS: usize = { code_consts::GAMMA },
     const INTERVALS: usize = { code_consts::GAMMA },
     const RESIDUALS: usize = { code_consts::ZETA3 },
+    const BLOCK_COUNTS: usize = BLOCKS,
+    const INTERVAL_COUNTS: usize = INTERVALS,
+    const INTERVAL_STARTS: usize = INTERVALS,
+    const FIRST_RESIDUALS: usize = RESIDUALS,
 > {
     /// The owned data
     factory: F,
@@ -173,7 +255,25 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > ConstCodesDecoderFactory<E, F, OFF, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    ConstCodesDecoderFactory<
+        E,
+        F,
+        OFF,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 where
     for<'a> &'a OFF: IntoIterator<Item = usize>, // This dependence can soon be removed, as there will be a IndexedSeq::iter method
 {
@@ -195,6 +295,10 @@ where
         BLOCKS,
         INTERVALS,
         RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
     > {
         ConstCodesDecoderFactory {
             factory: self.factory,
@@ -219,21 +323,55 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > ConstCodesDecoderFactory<E, F, OFF, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    ConstCodesDecoderFactory<
+        E,
+        F,
+        OFF,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     /// Creates a new builder from the given data and compression flags.
     pub fn new(factory: F, offsets: MemCase<OFF>, comp_flags: CompFlags) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !comp_flags.huffman_coding,
+            "The graph is Huffman-coded, so it must be loaded using dynamic or Huffman dispatch"
+        );
         if comp_flags.outdegrees.to_code_const()? != OUTDEGREES {
             bail!("Code for outdegrees does not match");
         }
         if comp_flags.references.to_code_const()? != REFERENCES {
-            bail!("Cod for references does not match");
+            bail!("Code for references does not match");
+        }
+        if comp_flags.block_counts.to_code_const()? != BLOCK_COUNTS {
+            bail!("Code for block counts does not match");
         }
         if comp_flags.blocks.to_code_const()? != BLOCKS {
             bail!("Code for blocks does not match");
         }
-        if comp_flags.intervals.to_code_const()? != INTERVALS {
-            bail!("Code for intervals does not match");
+        if comp_flags.interval_counts.to_code_const()? != INTERVAL_COUNTS {
+            bail!("Code for interval counts does not match");
+        }
+        if comp_flags.interval_starts.to_code_const()? != INTERVAL_STARTS {
+            bail!("Code for interval starts does not match");
+        }
+        if comp_flags.interval_lens.to_code_const()? != INTERVALS {
+            bail!("Code for interval lengths does not match");
+        }
+        if comp_flags.first_residuals.to_code_const()? != FIRST_RESIDUALS {
+            bail!("Code for first residuals does not match");
         }
         if comp_flags.residuals.to_code_const()? != RESIDUALS {
             bail!("Code for residuals does not match");
@@ -255,13 +393,42 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
     > RandomAccessDecoderFactory
-    for ConstCodesDecoderFactory<E, F, OFF, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+    for ConstCodesDecoderFactory<
+        E,
+        F,
+        OFF,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 where
     for<'a> <F as CodesReaderFactory<E>>::CodesReader<'a>: BitSeek,
 {
     type Decoder<'a>
-        = ConstCodesDecoder<E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+        = ConstCodesDecoder<
+        E,
+        <F as CodesReaderFactory<E>>::CodesReader<'a>,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
     where
         Self: 'a;
 
@@ -285,11 +452,40 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
     > SequentialDecoderFactory
-    for ConstCodesDecoderFactory<E, F, OFF, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+    for ConstCodesDecoderFactory<
+        E,
+        F,
+        OFF,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Decoder<'a>
-        = ConstCodesDecoder<E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+        = ConstCodesDecoder<
+        E,
+        <F as CodesReaderFactory<E>>::CodesReader<'a>,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
     where
         Self: 'a;
 
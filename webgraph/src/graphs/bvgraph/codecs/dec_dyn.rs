@@ -55,12 +55,12 @@ impl<E: Endianness, CR: CodesRead<E>> DynCodesDecoder<E, CR> {
             code_reader,
             read_outdegree: FuncCodeReader::new(cf.outdegrees)?,
             read_reference_offset: FuncCodeReader::new(cf.references)?,
-            read_block_count: FuncCodeReader::new(cf.blocks)?,
+            read_block_count: FuncCodeReader::new(cf.block_counts)?,
             read_block: FuncCodeReader::new(cf.blocks)?,
-            read_interval_count: FuncCodeReader::new(cf.intervals)?,
-            read_interval_start: FuncCodeReader::new(cf.intervals)?,
-            read_interval_len: FuncCodeReader::new(cf.intervals)?,
-            read_first_residual: FuncCodeReader::new(cf.residuals)?,
+            read_interval_count: FuncCodeReader::new(cf.interval_counts)?,
+            read_interval_start: FuncCodeReader::new(cf.interval_starts)?,
+            read_interval_len: FuncCodeReader::new(cf.interval_lens)?,
+            read_first_residual: FuncCodeReader::new(cf.first_residuals)?,
             read_residual: FuncCodeReader::new(cf.residuals)?,
             _marker: core::marker::PhantomData,
         })
@@ -132,6 +132,73 @@ impl<E: Endianness, CR: CodesRead<E>> Decode for DynCodesDecoder<E, CR> {
     }
 }
 
+/// The decoder returned by a [`DynCodesDecoderFactory`] or a
+/// [`StreamDecoderFactory`].
+///
+/// The variant is chosen by the factory: graphs using instantaneous codes are
+/// read by a [`DynCodesDecoder`], whereas
+/// [Huffman-coded](CompFlags::huffman_coding) graphs are read by a
+/// [`HuffmanDecoder`].
+#[derive(Debug, Clone)]
+pub enum DynDecoder<'a, E: Endianness, CR: CodesRead<E>> {
+    Codes(DynCodesDecoder<E, CR>),
+    Huffman(HuffmanDecoder<'a, E, CR>),
+}
+
+impl<E: Endianness, CR: CodesRead<E> + BitSeek> BitSeek for DynDecoder<'_, E, CR> {
+    type Error = <CR as BitSeek>::Error;
+
+    #[inline(always)]
+    fn set_bit_pos(&mut self, bit_index: u64) -> Result<(), Self::Error> {
+        match self {
+            DynDecoder::Codes(decoder) => decoder.set_bit_pos(bit_index),
+            DynDecoder::Huffman(decoder) => decoder.set_bit_pos(bit_index),
+        }
+    }
+
+    #[inline(always)]
+    fn bit_pos(&mut self) -> Result<u64, Self::Error> {
+        match self {
+            DynDecoder::Codes(decoder) => decoder.bit_pos(),
+            DynDecoder::Huffman(decoder) => decoder.bit_pos(),
+        }
+    }
+}
+
+macro_rules! impl_dyn_decoder_read {
+    ($($method:ident),*) => {
+        impl<E: Endianness, CR: CodesRead<E>> Decode for DynDecoder<'_, E, CR> {
+            $(
+                #[inline(always)]
+                fn $method(&mut self) -> u64 {
+                    match self {
+                        DynDecoder::Codes(decoder) => decoder.$method(),
+                        DynDecoder::Huffman(decoder) => decoder.$method(),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+impl_dyn_decoder_read!(
+    read_outdegree,
+    read_reference_offset,
+    read_block_count,
+    read_block,
+    read_interval_count,
+    read_interval_start,
+    read_interval_len,
+    read_first_residual,
+    read_residual
+);
+
+/// A factory of [`DynDecoder`]s whose codes are chosen at runtime from the
+/// [compression flags](CompFlags).
+///
+/// If the graph is [Huffman-coded](CompFlags::huffman_coding), the factory
+/// must be built with [`with_models`](DynCodesDecoderFactory::with_models),
+/// and returns [`HuffmanDecoder`]s.
 #[derive(Debug)]
 pub struct DynCodesDecoderFactory<
     E: Endianness,
@@ -144,6 +211,8 @@ pub struct DynCodesDecoderFactory<
     offsets: MemCase<OFF>,
     /// The compression flags.
     compression_flags: CompFlags,
+    /// The models of a Huffman-coded graph.
+    models: Option<HuffmanModels>,
     // The cached functions to read the codes.
     read_outdegree: FactoryFuncCodeReader<E, F>,
     read_reference_offset: FactoryFuncCodeReader<E, F>,
@@ -186,6 +255,7 @@ where
             )
             .into(),
             compression_flags: self.compression_flags,
+            models: self.models,
             read_outdegree: self.read_outdegree,
             read_reference_offset: self.read_reference_offset,
             read_block_count: self.read_block_count,
@@ -213,23 +283,65 @@ impl<
     }
 
     /// Creates a new builder from the data and the compression flags.
+    ///
+    /// Huffman-coded graphs require
+    /// [`with_models`](DynCodesDecoderFactory::with_models).
     pub fn new(factory: F, offsets: MemCase<OFF>, cf: CompFlags) -> anyhow::Result<Self> {
+        Self::with_models(factory, offsets, cf, None)
+    }
+
+    /// Creates a new builder from the data, the compression flags and, if
+    /// the graph is [Huffman-coded](CompFlags::huffman_coding), its models.
+    pub fn with_models(
+        factory: F,
+        offsets: MemCase<OFF>,
+        cf: CompFlags,
+        models: Option<HuffmanModels>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            cf.huffman_coding == models.is_some(),
+            "Huffman models must be provided if and only if the graph is Huffman-coded"
+        );
         Ok(Self {
             factory,
             offsets,
+            models,
             read_outdegree: FactoryFuncCodeReader::new(cf.outdegrees)?,
             read_reference_offset: FactoryFuncCodeReader::new(cf.references)?,
-            read_block_count: FactoryFuncCodeReader::new(cf.blocks)?,
+            read_block_count: FactoryFuncCodeReader::new(cf.block_counts)?,
             read_blocks: FactoryFuncCodeReader::new(cf.blocks)?,
-            read_interval_count: FactoryFuncCodeReader::new(cf.intervals)?,
-            read_interval_start: FactoryFuncCodeReader::new(cf.intervals)?,
-            read_interval_len: FactoryFuncCodeReader::new(cf.intervals)?,
-            read_first_residual: FactoryFuncCodeReader::new(cf.residuals)?,
+            read_interval_count: FactoryFuncCodeReader::new(cf.interval_counts)?,
+            read_interval_start: FactoryFuncCodeReader::new(cf.interval_starts)?,
+            read_interval_len: FactoryFuncCodeReader::new(cf.interval_lens)?,
+            read_first_residual: FactoryFuncCodeReader::new(cf.first_residuals)?,
             read_residual: FactoryFuncCodeReader::new(cf.residuals)?,
             compression_flags: cf,
             _marker: core::marker::PhantomData,
         })
     }
+
+    #[inline(always)]
+    fn new_dyn_decoder<'a>(
+        &'a self,
+        code_reader: <F as CodesReaderFactory<E>>::CodesReader<'a>,
+    ) -> DynDecoder<'a, E, <F as CodesReaderFactory<E>>::CodesReader<'a>> {
+        match &self.models {
+            Some(models) => DynDecoder::Huffman(HuffmanDecoder::new(code_reader, models)),
+            None => DynDecoder::Codes(DynCodesDecoder {
+                code_reader,
+                read_outdegree: self.read_outdegree.get(),
+                read_reference_offset: self.read_reference_offset.get(),
+                read_block_count: self.read_block_count.get(),
+                read_block: self.read_blocks.get(),
+                read_interval_count: self.read_interval_count.get(),
+                read_interval_start: self.read_interval_start.get(),
+                read_interval_len: self.read_interval_len.get(),
+                read_first_residual: self.read_first_residual.get(),
+                read_residual: self.read_residual.get(),
+                _marker: PhantomData,
+            }),
+        }
+    }
 }
 
 impl<
@@ -241,7 +353,7 @@ where
     for<'a> <F as CodesReaderFactory<E>>::CodesReader<'a>: BitSeek,
 {
     type Decoder<'a>
-        = DynCodesDecoder<E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+        = DynDecoder<'a, E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
     where
         Self: 'a;
 
@@ -249,20 +361,7 @@ where
     fn new_decoder(&self, node: usize) -> anyhow::Result<Self::Decoder<'_>> {
         let mut code_reader = self.factory.new_reader();
         code_reader.set_bit_pos(self.offsets.get(node) as u64)?;
-
-        Ok(DynCodesDecoder {
-            code_reader,
-            read_outdegree: self.read_outdegree.get(),
-            read_reference_offset: self.read_reference_offset.get(),
-            read_block_count: self.read_block_count.get(),
-            read_block: self.read_blocks.get(),
-            read_interval_count: self.read_interval_count.get(),
-            read_interval_start: self.read_interval_start.get(),
-            read_interval_len: self.read_interval_len.get(),
-            read_first_residual: self.read_first_residual.get(),
-            read_residual: self.read_residual.get(),
-            _marker: PhantomData,
-        })
+        Ok(self.new_dyn_decoder(code_reader))
     }
 }
 
@@ -273,24 +372,12 @@ impl<
     > SequentialDecoderFactory for DynCodesDecoderFactory<E, F, OFF>
 {
     type Decoder<'a>
-        = DynCodesDecoder<E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+        = DynDecoder<'a, E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
     where
         Self: 'a;
 
     #[inline(always)]
     fn new_decoder(&self) -> anyhow::Result<Self::Decoder<'_>> {
-        Ok(DynCodesDecoder {
-            code_reader: self.factory.new_reader(),
-            read_outdegree: self.read_outdegree.get(),
-            read_reference_offset: self.read_reference_offset.get(),
-            read_block_count: self.read_block_count.get(),
-            read_block: self.read_blocks.get(),
-            read_interval_count: self.read_interval_count.get(),
-            read_interval_start: self.read_interval_start.get(),
-            read_interval_len: self.read_interval_len.get(),
-            read_first_residual: self.read_first_residual.get(),
-            read_residual: self.read_residual.get(),
-            _marker: PhantomData,
-        })
+        Ok(self.new_dyn_decoder(self.factory.new_reader()))
     }
 }
@@ -0,0 +1,188 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::huffman::{bucket, Component};
+use super::{Decode, HuffmanModels, RandomAccessDecoderFactory, SequentialDecoderFactory};
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::dispatch::CodesReaderFactory;
+use dsi_bitstream::prelude::*;
+use epserde::deser::MemCase;
+use sux::traits::IndexedSeq;
+
+/// A decoder reading each component using the [Huffman
+/// models](HuffmanModels) of its context.
+#[derive(Debug, Clone)]
+pub struct HuffmanDecoder<'a, E: Endianness, CR: BitRead<E>> {
+    /// The bit reader used to read the compressed data.
+    code_reader: CR,
+    /// The models used to read the components.
+    models: &'a HuffmanModels,
+    /// The bucket of the outdegree of the current node.
+    bucket: usize,
+    _marker: core::marker::PhantomData<E>,
+}
+
+impl<'a, E: Endianness, CR: BitRead<E>> HuffmanDecoder<'a, E, CR> {
+    pub fn new(code_reader: CR, models: &'a HuffmanModels) -> Self {
+        Self {
+            code_reader,
+            models,
+            bucket: 0,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn read(&mut self, component: Component) -> u64 {
+        self.models
+            .read(&mut self.code_reader, component, self.bucket)
+            .unwrap()
+    }
+}
+
+impl<E: Endianness, CR: BitRead<E> + BitSeek> BitSeek for HuffmanDecoder<'_, E, CR> {
+    type Error = <CR as BitSeek>::Error;
+
+    #[inline(always)]
+    fn set_bit_pos(&mut self, bit_index: u64) -> Result<(), Self::Error> {
+        self.code_reader.set_bit_pos(bit_index)
+    }
+
+    #[inline(always)]
+    fn bit_pos(&mut self) -> Result<u64, Self::Error> {
+        self.code_reader.bit_pos()
+    }
+}
+
+impl<E: Endianness, CR: BitRead<E>> Decode for HuffmanDecoder<'_, E, CR> {
+    #[inline(always)]
+    fn read_outdegree(&mut self) -> u64 {
+        let outdegree = self.read(Component::Outdegree);
+        self.bucket = bucket(outdegree);
+        outdegree
+    }
+
+    #[inline(always)]
+    fn read_reference_offset(&mut self) -> u64 {
+        self.read(Component::ReferenceOffset)
+    }
+
+    #[inline(always)]
+    fn read_block_count(&mut self) -> u64 {
+        self.read(Component::BlockCount)
+    }
+
+    #[inline(always)]
+    fn read_block(&mut self) -> u64 {
+        self.read(Component::Block)
+    }
+
+    #[inline(always)]
+    fn read_interval_count(&mut self) -> u64 {
+        self.read(Component::IntervalCount)
+    }
+
+    #[inline(always)]
+    fn read_interval_start(&mut self) -> u64 {
+        self.read(Component::IntervalStart)
+    }
+
+    #[inline(always)]
+    fn read_interval_len(&mut self) -> u64 {
+        self.read(Component::IntervalLen)
+    }
+
+    #[inline(always)]
+    fn read_first_residual(&mut self) -> u64 {
+        self.read(Component::FirstResidual)
+    }
+
+    #[inline(always)]
+    fn read_residual(&mut self) -> u64 {
+        self.read(Component::Residual)
+    }
+}
+
+/// A factory of [`HuffmanDecoder`]s, providing both sequential and random
+/// access.
+///
+/// Huffman-coded graphs can be loaded using the
+/// [`Huffman`](crate::graphs::bvgraph::Huffman) dispatch.
+#[derive(Debug)]
+pub struct HuffmanDecoderFactory<
+    E: Endianness,
+    F: CodesReaderFactoryHelper<E>,
+    OFF: IndexedSeq<Input = usize, Output = usize>,
+> {
+    /// The owned data we will read as a bitstream.
+    factory: F,
+    /// The offsets into the data.
+    offsets: MemCase<OFF>,
+    /// The models used to read the components.
+    models: HuffmanModels,
+    _marker: core::marker::PhantomData<E>,
+}
+
+impl<
+        E: Endianness,
+        F: CodesReaderFactoryHelper<E>,
+        OFF: IndexedSeq<Input = usize, Output = usize>,
+    > HuffmanDecoderFactory<E, F, OFF>
+{
+    /// Creates a new factory from the data, the offsets and the models.
+    pub fn new(factory: F, offsets: MemCase<OFF>, models: HuffmanModels) -> Self {
+        Self {
+            factory,
+            offsets,
+            models,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the models used to read the components.
+    pub fn models(&self) -> &HuffmanModels {
+        &self.models
+    }
+}
+
+impl<
+        E: Endianness,
+        F: CodesReaderFactoryHelper<E>,
+        OFF: IndexedSeq<Input = usize, Output = usize>,
+    > RandomAccessDecoderFactory for HuffmanDecoderFactory<E, F, OFF>
+where
+    for<'a> <F as CodesReaderFactory<E>>::CodesReader<'a>: BitSeek,
+{
+    type Decoder<'a>
+        = HuffmanDecoder<'a, E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn new_decoder(&self, node: usize) -> anyhow::Result<Self::Decoder<'_>> {
+        let mut code_reader = self.factory.new_reader();
+        code_reader.set_bit_pos(self.offsets.get(node) as u64)?;
+        Ok(HuffmanDecoder::new(code_reader, &self.models))
+    }
+}
+
+impl<
+        E: Endianness,
+        F: CodesReaderFactoryHelper<E>,
+        OFF: IndexedSeq<Input = usize, Output = usize>,
+    > SequentialDecoderFactory for HuffmanDecoderFactory<E, F, OFF>
+{
+    type Decoder<'a>
+        = HuffmanDecoder<'a, E, <F as CodesReaderFactory<E>>::CodesReader<'a>>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn new_decoder(&self) -> anyhow::Result<Self::Decoder<'_>> {
+        Ok(HuffmanDecoder::new(self.factory.new_reader(), &self.models))
+    }
+}
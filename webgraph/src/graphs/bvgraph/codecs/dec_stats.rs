@@ -6,7 +6,11 @@
  */
 
 use crate::prelude::*;
-use dsi_bitstream::{prelude::CodesStats, traits::BitSeek};
+use dsi_bitstream::{
+    dispatch::FuncCodeLen,
+    prelude::{Codes, CodesStats},
+    traits::BitSeek,
+};
 
 /// A struct that keeps track of how much bits each piece would take
 /// using different codes for compression.
@@ -44,6 +48,62 @@ impl DecoderStats {
         self.first_residuals.add(&rhs.first_residuals);
         self.residuals.add(&rhs.residuals);
     }
+
+    /// Returns a copy of the given compression flags in which the codes are
+    /// replaced by those minimizing the space used by the values recorded in
+    /// these statistics, and the corresponding total space in bits.
+    ///
+    /// All codes tracked by [`CodesStats`] that are supported by dynamic
+    /// dispatch are considered, and each component gets its own code, so the
+    /// resulting flags might not be readable by the Java version of the
+    /// library (see [`CompFlags::is_java_compatible`]). In case of ties, the
+    /// codes of `cf` are preferred.
+    pub fn best_codes(&self, cf: &CompFlags) -> (CompFlags, u64) {
+        let mut total = 0;
+        let mut best = |stats: &CodesStats, curr: Codes| {
+            // the current code comes first, so it is kept in case of ties
+            let (code, len) = std::iter::once(curr)
+                .chain(stats.get_codes().into_iter().map(|(code, _)| code))
+                .filter(|&code| FuncCodeLen::new(code).is_ok())
+                .filter_map(|code| code_len(stats, code).map(|len| (code, len)))
+                .min_by_key(|&(_, len)| len)
+                .unwrap();
+            total += len;
+            code
+        };
+
+        let best_cf = CompFlags {
+            outdegrees: best(&self.outdegrees, cf.outdegrees),
+            references: best(&self.reference_offsets, cf.references),
+            block_counts: best(&self.block_counts, cf.block_counts),
+            blocks: best(&self.blocks, cf.blocks),
+            interval_counts: best(&self.interval_counts, cf.interval_counts),
+            interval_starts: best(&self.interval_starts, cf.interval_starts),
+            interval_lens: best(&self.interval_lens, cf.interval_lens),
+            first_residuals: best(&self.first_residuals, cf.first_residuals),
+            residuals: best(&self.residuals, cf.residuals),
+            ..*cf
+        };
+        (best_cf, total)
+    }
+}
+
+/// Returns the space used by the values recorded in `stats` using `code`, or
+/// `None` if `code` is not tracked by [`CodesStats`].
+fn code_len(stats: &CodesStats, code: Codes) -> Option<u64> {
+    match code {
+        Codes::Unary => Some(stats.unary),
+        Codes::Gamma => Some(stats.gamma),
+        Codes::Delta => Some(stats.delta),
+        Codes::Omega => Some(stats.omega),
+        Codes::VByteBe | Codes::VByteLe => Some(stats.vbyte),
+        Codes::Zeta { k } => stats.zeta.get(k.checked_sub(1)?).copied(),
+        Codes::Golomb { b } => stats.golomb.get((b as usize).checked_sub(1)?).copied(),
+        Codes::ExpGolomb { k } => stats.exp_golomb.get(k).copied(),
+        Codes::Rice { log2_b } => stats.rice.get(log2_b).copied(),
+        Codes::Pi { k } => stats.pi.get(k.checked_sub(2)?).copied(),
+        _ => None,
+    }
 }
 
 impl std::ops::AddAssign<&Self> for DecoderStats {
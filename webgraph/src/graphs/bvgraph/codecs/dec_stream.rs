@@ -0,0 +1,211 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::super::*;
+use anyhow::{bail, ensure, Context};
+use dsi_bitstream::prelude::*;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// The magic number at the start of a Zstandard frame, in little-endian order.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// A type alias for the buffered reader used by a [`StreamDecoderFactory`].
+pub type StreamBufReader<E> = BufBitReader<E, WordAdapter<u32, BufReader<ZeroExtended>>>;
+
+/// A reader returning an infinite sequence of zeroes once the underlying
+/// reader is exhausted.
+///
+/// Bitstreams are read one `u32` at a time, but there is no guarantee that a
+/// stream contains a multiple of four bytes (e.g., graphs compressed by the
+/// Java version are byte aligned): this reader provides the same
+/// zero-extension semantics of in-memory bitstreams.
+pub struct ZeroExtended {
+    reader: Box<dyn Read + Send>,
+    exhausted: bool,
+}
+
+impl core::fmt::Debug for ZeroExtended {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ZeroExtended")
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for ZeroExtended {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.exhausted {
+            let n = self.reader.read(buf)?;
+            if n != 0 || buf.is_empty() {
+                return Ok(n);
+            }
+            self.exhausted = true;
+        }
+        buf.fill(0);
+        Ok(buf.len())
+    }
+}
+
+/// The source of the data of a [`StreamDecoderFactory`].
+enum Source {
+    /// A file that is reopened for each decoder.
+    Path(Box<Path>),
+    /// A stream that can be read just once.
+    Reader(Mutex<Option<Box<dyn Read + Send>>>),
+}
+
+impl core::fmt::Debug for Source {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Source::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Source::Reader(_) => f.debug_tuple("Reader").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// A [`SequentialDecoderFactory`] reading a graph from a [`Read`].
+///
+/// If the `zstd` feature is enabled, data compressed with
+/// [Zstandard](https://facebook.github.io/zstd/) is detected by its magic
+/// number and decompressed transparently, so this factory can be used to
+/// read, for example, a `.graph.zst` file or a graph coming from a pipe
+/// without temporary files. Otherwise, decoders on such data cannot be
+/// created.
+///
+/// A factory built with [`from_path`](StreamDecoderFactory::from_path)
+/// reopens the file for each decoder, whereas a factory built with
+/// [`new`](StreamDecoderFactory::new) can create a single decoder, and
+/// further requests will return an error: thus, the associated
+/// [`BvGraphSeq`] can be iterated upon just once.
+///
+/// If the graph is [Huffman-coded](CompFlags::huffman_coding), its models
+/// must be provided at construction time.
+///
+/// Usually the factory is not built directly, but rather using
+/// [`LoadConfig::load_from_path`] or [`LoadConfig::load_from_reader`].
+#[derive(Debug)]
+pub struct StreamDecoderFactory<E: Endianness> {
+    /// Where to read the bitstream from.
+    source: Source,
+    /// The compression flags.
+    compression_flags: CompFlags,
+    /// The models of a Huffman-coded graph.
+    models: Option<HuffmanModels>,
+    _marker: core::marker::PhantomData<E>,
+}
+
+impl<E: Endianness> StreamDecoderFactory<E> {
+    /// Creates a new factory that can be used just once from a reader, the
+    /// compression flags and, if the graph is Huffman-coded, its models.
+    pub fn new(
+        reader: impl Read + Send + 'static,
+        cf: CompFlags,
+        models: Option<HuffmanModels>,
+    ) -> anyhow::Result<Self> {
+        Self::with_source(
+            Source::Reader(Mutex::new(Some(Box::new(reader)))),
+            cf,
+            models,
+        )
+    }
+
+    /// Creates a new factory reading the given file, the compression flags
+    /// and, if the graph is Huffman-coded, its models.
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        cf: CompFlags,
+        models: Option<HuffmanModels>,
+    ) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Could not stat {}", path.display()))?;
+        ensure!(metadata.is_file(), "File {} is not a file", path.display());
+        Self::with_source(Source::Path(path.into()), cf, models)
+    }
+
+    fn with_source(
+        source: Source,
+        cf: CompFlags,
+        models: Option<HuffmanModels>,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            cf.huffman_coding == models.is_some(),
+            "Huffman models must be provided if and only if the graph is Huffman-coded"
+        );
+        Ok(Self {
+            source,
+            compression_flags: cf,
+            models,
+            _marker: core::marker::PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    /// Returns a clone of the compression flags.
+    pub fn get_compression_flags(&self) -> CompFlags {
+        self.compression_flags
+    }
+
+    /// Returns a new reader on the data, decompressing it if necessary.
+    fn new_reader(&self) -> anyhow::Result<Box<dyn Read + Send>> {
+        let reader: Box<dyn Read + Send> = match &self.source {
+            Source::Path(path) => Box::new(
+                std::fs::File::open(path)
+                    .with_context(|| format!("Could not open {}", path.display()))?,
+            ),
+            Source::Reader(reader) => match reader.lock().unwrap().take() {
+                Some(reader) => reader,
+                None => bail!("The stream has already been consumed"),
+            },
+        };
+        decompress(reader)
+    }
+}
+
+/// Wraps a reader into a Zstandard decoder if it starts with the Zstandard
+/// magic number, failing if the `zstd` feature is not enabled.
+fn decompress(mut reader: Box<dyn Read + Send>) -> anyhow::Result<Box<dyn Read + Send>> {
+    let mut magic = Vec::with_capacity(ZSTD_MAGIC.len());
+    (&mut reader)
+        .take(ZSTD_MAGIC.len() as u64)
+        .read_to_end(&mut magic)
+        .context("Could not read the start of the stream")?;
+    // We must put back the bytes we read
+    let reader = std::io::Cursor::new(magic).chain(reader);
+    if reader.get_ref().0.get_ref()[..] != ZSTD_MAGIC {
+        return Ok(Box::new(reader));
+    }
+    #[cfg(feature = "zstd")]
+    return Ok(Box::new(
+        zstd::Decoder::new(reader).context("Could not create Zstandard decoder")?,
+    ));
+    #[cfg(not(feature = "zstd"))]
+    bail!("The stream is compressed with Zstandard, but the zstd feature is not enabled");
+}
+
+impl<E: Endianness> SequentialDecoderFactory for StreamDecoderFactory<E>
+where
+    StreamBufReader<E>: CodesRead<E>,
+{
+    type Decoder<'a>
+        = DynDecoder<'a, E, StreamBufReader<E>>
+    where
+        Self: 'a;
+
+    fn new_decoder(&self) -> anyhow::Result<Self::Decoder<'_>> {
+        let code_reader = BufBitReader::new(WordAdapter::new(BufReader::new(ZeroExtended {
+            reader: self.new_reader()?,
+            exhausted: false,
+        })));
+        Ok(match &self.models {
+            Some(models) => DynDecoder::Huffman(HuffmanDecoder::new(code_reader, models)),
+            None => DynDecoder::Codes(DynCodesDecoder::new(code_reader, &self.compression_flags)?),
+        })
+    }
+}
@@ -13,6 +13,9 @@ use super::{Encode, EncodeAndEstimate};
 
 #[repr(transparent)]
 /// An implementation of [`EncodeAndEstimate`] with compile time defined codes
+///
+/// The meaning of the parameters is the same as in
+/// [`Static`](crate::graphs::bvgraph::Static).
 #[derive(Debug, Clone)]
 pub struct ConstCodesEncoder<
     E: Endianness,
@@ -22,6 +25,10 @@ pub struct ConstCodesEncoder<
     const BLOCKS: usize = { code_consts::GAMMA },
     const INTERVALS: usize = { code_consts::GAMMA },
     const RESIDUALS: usize = { code_consts::ZETA3 },
+    const BLOCK_COUNTS: usize = BLOCKS,
+    const INTERVAL_COUNTS: usize = INTERVALS,
+    const INTERVAL_STARTS: usize = INTERVALS,
+    const FIRST_RESIDUALS: usize = RESIDUALS,
 > {
     code_writer: CW,
     _marker: core::marker::PhantomData<E>,
@@ -35,7 +42,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > BitSeek for ConstCodesEncoder<E, CW, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > BitSeek
+    for ConstCodesEncoder<
+        E,
+        CW,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Error = <CW as BitSeek>::Error;
 
@@ -56,7 +80,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > ConstCodesEncoder<E, CW, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    ConstCodesEncoder<
+        E,
+        CW,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     /// Creates a new [`ConstCodesEncoder`] with the given [`CodesWrite`] implementation.
     pub fn new(code_writer: CW) -> Self {
@@ -75,7 +116,24 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > Encode for ConstCodesEncoder<E, CW, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > Encode
+    for ConstCodesEncoder<
+        E,
+        CW,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Error = <CW as BitWrite<E>>::Error;
 
@@ -101,7 +159,7 @@ impl<
 
     #[inline(always)]
     fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
-        ConstCode::<BLOCKS>.write(&mut self.code_writer, value)
+        ConstCode::<BLOCK_COUNTS>.write(&mut self.code_writer, value)
     }
     #[inline(always)]
     fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
@@ -110,11 +168,11 @@ impl<
 
     #[inline(always)]
     fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
-        ConstCode::<INTERVALS>.write(&mut self.code_writer, value)
+        ConstCode::<INTERVAL_COUNTS>.write(&mut self.code_writer, value)
     }
     #[inline(always)]
     fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
-        ConstCode::<INTERVALS>.write(&mut self.code_writer, value)
+        ConstCode::<INTERVAL_STARTS>.write(&mut self.code_writer, value)
     }
     #[inline(always)]
     fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
@@ -123,7 +181,7 @@ impl<
 
     #[inline(always)]
     fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
-        ConstCode::<RESIDUALS>.write(&mut self.code_writer, value)
+        ConstCode::<FIRST_RESIDUALS>.write(&mut self.code_writer, value)
     }
     #[inline(always)]
     fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
@@ -131,7 +189,9 @@ impl<
     }
 
     fn flush(&mut self) -> Result<usize, Self::Error> {
-        self.code_writer.flush()
+        // the bits still in the buffer have been counted when written
+        self.code_writer.flush()?;
+        Ok(0)
     }
 }
 
@@ -143,11 +203,37 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
     > EncodeAndEstimate
-    for ConstCodesEncoder<E, CW, OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+    for ConstCodesEncoder<
+        E,
+        CW,
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Estimator<'a>
-        = ConstCodesEstimator<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        = ConstCodesEstimator<
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
     where
         Self: 'a;
     fn estimator(&mut self) -> Self::Estimator<'_> {
@@ -163,6 +249,10 @@ pub struct ConstCodesEstimator<
     const BLOCKS: usize = { code_consts::GAMMA },
     const INTERVALS: usize = { code_consts::GAMMA },
     const RESIDUALS: usize = { code_consts::ZETA3 },
+    const BLOCK_COUNTS: usize = BLOCKS,
+    const INTERVAL_COUNTS: usize = INTERVALS,
+    const INTERVAL_STARTS: usize = INTERVALS,
+    const FIRST_RESIDUALS: usize = RESIDUALS,
 >;
 
 impl<
@@ -171,7 +261,22 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > ConstCodesEstimator<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    ConstCodesEstimator<
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     pub fn new() -> Self {
         Self
@@ -184,7 +289,22 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > Encode for ConstCodesEstimator<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > Encode
+    for ConstCodesEstimator<
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
     type Error = Infallible;
 
@@ -210,7 +330,7 @@ impl<
 
     #[inline(always)]
     fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
-        Ok(ConstCode::<BLOCKS>.len(value))
+        Ok(ConstCode::<BLOCK_COUNTS>.len(value))
     }
     #[inline(always)]
     fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
@@ -219,11 +339,11 @@ impl<
 
     #[inline(always)]
     fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
-        Ok(ConstCode::<INTERVALS>.len(value))
+        Ok(ConstCode::<INTERVAL_COUNTS>.len(value))
     }
     #[inline(always)]
     fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
-        Ok(ConstCode::<INTERVALS>.len(value))
+        Ok(ConstCode::<INTERVAL_STARTS>.len(value))
     }
     #[inline(always)]
     fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
@@ -232,7 +352,7 @@ impl<
 
     #[inline(always)]
     fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
-        Ok(ConstCode::<RESIDUALS>.len(value))
+        Ok(ConstCode::<FIRST_RESIDUALS>.len(value))
     }
     #[inline(always)]
     fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
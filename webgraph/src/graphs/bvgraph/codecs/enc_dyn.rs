@@ -39,12 +39,12 @@ impl<E: Endianness, CW: CodesWrite<E>> DynCodesEncoder<E, CW> {
             code_writer,
             write_outdegree: FuncCodeWriter::new(cf.outdegrees)?,
             write_reference_offset: FuncCodeWriter::new(cf.references)?,
-            write_block_count: FuncCodeWriter::new(cf.blocks)?,
+            write_block_count: FuncCodeWriter::new(cf.block_counts)?,
             write_block: FuncCodeWriter::new(cf.blocks)?,
-            write_interval_count: FuncCodeWriter::new(cf.intervals)?,
-            write_interval_start: FuncCodeWriter::new(cf.intervals)?,
-            write_interval_len: FuncCodeWriter::new(cf.intervals)?,
-            write_first_residual: FuncCodeWriter::new(cf.residuals)?,
+            write_interval_count: FuncCodeWriter::new(cf.interval_counts)?,
+            write_interval_start: FuncCodeWriter::new(cf.interval_starts)?,
+            write_interval_len: FuncCodeWriter::new(cf.interval_lens)?,
+            write_first_residual: FuncCodeWriter::new(cf.first_residuals)?,
             write_residual: FuncCodeWriter::new(cf.residuals)?,
             estimator: DynCodesEstimator::new(cf)?,
             _marker: core::marker::PhantomData,
@@ -126,7 +126,9 @@ where
     }
 
     fn flush(&mut self) -> Result<usize, Self::Error> {
-        self.code_writer.flush()
+        // the bits still in the buffer have been counted when written
+        self.code_writer.flush()?;
+        Ok(0)
     }
 }
 
@@ -162,12 +164,12 @@ impl DynCodesEstimator {
         Ok(Self {
             len_outdegree: FuncCodeLen::new(cf.outdegrees)?,
             len_reference_offset: FuncCodeLen::new(cf.references)?,
-            len_block_count: FuncCodeLen::new(cf.blocks)?,
+            len_block_count: FuncCodeLen::new(cf.block_counts)?,
             len_block: FuncCodeLen::new(cf.blocks)?,
-            len_interval_count: FuncCodeLen::new(cf.intervals)?,
-            len_interval_start: FuncCodeLen::new(cf.intervals)?,
-            len_interval_len: FuncCodeLen::new(cf.intervals)?,
-            len_first_residual: FuncCodeLen::new(cf.residuals)?,
+            len_interval_count: FuncCodeLen::new(cf.interval_counts)?,
+            len_interval_start: FuncCodeLen::new(cf.interval_starts)?,
+            len_interval_len: FuncCodeLen::new(cf.interval_lens)?,
+            len_first_residual: FuncCodeLen::new(cf.first_residuals)?,
             len_residual: FuncCodeLen::new(cf.residuals)?,
         })
     }
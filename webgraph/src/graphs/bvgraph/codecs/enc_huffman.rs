@@ -0,0 +1,207 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::huffman::{bucket, Component};
+use super::{Encode, EncodeAndEstimate, HuffmanModels};
+use dsi_bitstream::prelude::*;
+use std::convert::Infallible;
+
+/// An encoder writing each component using the [Huffman models](HuffmanModels)
+/// of its context.
+#[derive(Debug, Clone)]
+pub struct HuffmanEncoder<'a, E: Endianness, CW: BitWrite<E>> {
+    /// The bit writer used to output the compressed data.
+    code_writer: CW,
+    /// The models used to write the components.
+    models: &'a HuffmanModels,
+    /// The bucket of the outdegree of the current node.
+    bucket: usize,
+    _marker: core::marker::PhantomData<E>,
+}
+
+impl<'a, E: Endianness, CW: BitWrite<E>> HuffmanEncoder<'a, E, CW> {
+    pub fn new(code_writer: CW, models: &'a HuffmanModels) -> Self {
+        Self {
+            code_writer,
+            models,
+            bucket: 0,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, component: Component, value: u64) -> Result<usize, CW::Error> {
+        self.models
+            .write(&mut self.code_writer, component, self.bucket, value)
+    }
+}
+
+impl<E: Endianness, CW: BitWrite<E>> Encode for HuffmanEncoder<'_, E, CW>
+where
+    <CW as BitWrite<E>>::Error: Send + Sync,
+{
+    type Error = <CW as BitWrite<E>>::Error;
+
+    #[inline(always)]
+    fn start_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn end_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn write_outdegree(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let bits = self.write(Component::Outdegree, value)?;
+        self.bucket = bucket(value);
+        Ok(bits)
+    }
+
+    #[inline(always)]
+    fn write_reference_offset(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::ReferenceOffset, value)
+    }
+
+    #[inline(always)]
+    fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::BlockCount, value)
+    }
+
+    #[inline(always)]
+    fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::Block, value)
+    }
+
+    #[inline(always)]
+    fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::IntervalCount, value)
+    }
+
+    #[inline(always)]
+    fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::IntervalStart, value)
+    }
+
+    #[inline(always)]
+    fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::IntervalLen, value)
+    }
+
+    #[inline(always)]
+    fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::FirstResidual, value)
+    }
+
+    #[inline(always)]
+    fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.write(Component::Residual, value)
+    }
+
+    fn flush(&mut self) -> Result<usize, Self::Error> {
+        self.code_writer.flush()?;
+        Ok(0)
+    }
+}
+
+impl<E: Endianness, CW: BitWrite<E>> EncodeAndEstimate for HuffmanEncoder<'_, E, CW>
+where
+    <CW as BitWrite<E>>::Error: Send + Sync,
+{
+    type Estimator<'b>
+        = HuffmanEstimator<'b>
+    where
+        Self: 'b;
+
+    fn estimator(&mut self) -> Self::Estimator<'_> {
+        HuffmanEstimator {
+            models: self.models,
+            bucket: 0,
+        }
+    }
+}
+
+/// An estimator computing the lengths of the values written by an
+/// [`HuffmanEncoder`].
+#[derive(Debug, Clone)]
+pub struct HuffmanEstimator<'a> {
+    models: &'a HuffmanModels,
+    bucket: usize,
+}
+
+impl HuffmanEstimator<'_> {
+    #[inline(always)]
+    fn len(&self, component: Component, value: u64) -> usize {
+        self.models.len(component, self.bucket, value)
+    }
+}
+
+impl Encode for HuffmanEstimator<'_> {
+    type Error = Infallible;
+
+    #[inline(always)]
+    fn start_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn end_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn write_outdegree(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let bits = self.len(Component::Outdegree, value);
+        self.bucket = bucket(value);
+        Ok(bits)
+    }
+
+    #[inline(always)]
+    fn write_reference_offset(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::ReferenceOffset, value))
+    }
+
+    #[inline(always)]
+    fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::BlockCount, value))
+    }
+
+    #[inline(always)]
+    fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::Block, value))
+    }
+
+    #[inline(always)]
+    fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::IntervalCount, value))
+    }
+
+    #[inline(always)]
+    fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::IntervalStart, value))
+    }
+
+    #[inline(always)]
+    fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::IntervalLen, value))
+    }
+
+    #[inline(always)]
+    fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::FirstResidual, value))
+    }
+
+    #[inline(always)]
+    fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        Ok(self.len(Component::Residual, value))
+    }
+
+    fn flush(&mut self) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+}
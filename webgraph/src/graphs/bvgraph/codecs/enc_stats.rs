@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::{DecoderStats, DynCodesEstimator, Encode, EncodeAndEstimate};
+use crate::prelude::CompFlags;
+use anyhow::Result;
+use std::convert::Infallible;
+
+/// An encoder that does not write anything, but keeps track of how many bits
+/// each value written would take using different codes.
+///
+/// The statistics are gathered in a [`DecoderStats`], as they are exactly
+/// those that a [`StatsDecoder`](super::StatsDecoder) would gather decoding
+/// the resulting graph. The estimator uses the codes of the [`CompFlags`]
+/// passed at construction time, so compressing with this encoder makes the
+/// same reference choices as compressing with a
+/// [`DynCodesEncoder`](super::DynCodesEncoder) with the same flags.
+///
+/// Since compressors take ownership of their encoder, you will usually pass a
+/// mutable reference to an instance of this struct, and examine
+/// [`stats`](Self::stats) after flushing the compressor.
+#[derive(Debug, Clone)]
+pub struct StatsEncoder {
+    /// The estimator used to compute the length of the values written.
+    estimator: DynCodesEstimator,
+    /// The statistics of the values written so far.
+    pub stats: DecoderStats,
+}
+
+impl StatsEncoder {
+    /// Creates a new statistics encoder estimating lengths with the codes of
+    /// the given compression flags.
+    pub fn new(cf: &CompFlags) -> Result<Self> {
+        Ok(Self {
+            estimator: DynCodesEstimator::new(cf)?,
+            stats: DecoderStats::default(),
+        })
+    }
+}
+
+impl Encode for StatsEncoder {
+    type Error = Infallible;
+
+    #[inline(always)]
+    fn start_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn end_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn write_outdegree(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_outdegree(self.stats.outdegrees.update(value))
+    }
+
+    #[inline(always)]
+    fn write_reference_offset(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_reference_offset(self.stats.reference_offsets.update(value))
+    }
+
+    #[inline(always)]
+    fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_block_count(self.stats.block_counts.update(value))
+    }
+
+    #[inline(always)]
+    fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator.write_block(self.stats.blocks.update(value))
+    }
+
+    #[inline(always)]
+    fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_interval_count(self.stats.interval_counts.update(value))
+    }
+
+    #[inline(always)]
+    fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_interval_start(self.stats.interval_starts.update(value))
+    }
+
+    #[inline(always)]
+    fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_interval_len(self.stats.interval_lens.update(value))
+    }
+
+    #[inline(always)]
+    fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_first_residual(self.stats.first_residuals.update(value))
+    }
+
+    #[inline(always)]
+    fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.estimator
+            .write_residual(self.stats.residuals.update(value))
+    }
+
+    fn flush(&mut self) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+}
+
+impl EncodeAndEstimate for StatsEncoder {
+    type Estimator<'a>
+        = &'a mut DynCodesEstimator
+    where
+        Self: 'a;
+
+    fn estimator(&mut self) -> Self::Estimator<'_> {
+        &mut self.estimator
+    }
+}
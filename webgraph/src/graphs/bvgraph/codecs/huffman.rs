@@ -0,0 +1,495 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::{DynCodesEstimator, Encode, EncodeAndEstimate};
+use crate::prelude::CompFlags;
+use anyhow::{ensure, Context, Result};
+use dsi_bitstream::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::convert::Infallible;
+use std::path::Path;
+
+/// The number of values represented directly by a token.
+const DIRECT_TOKENS: usize = 16;
+/// The number of tokens: values smaller than [`DIRECT_TOKENS`] have their
+/// own token, and larger values are grouped by their most significant bit
+/// and by the bit following it.
+const NUM_TOKENS: usize = DIRECT_TOKENS + 2 * (64 - DIRECT_TOKENS.ilog2() as usize);
+/// The number of outdegree buckets.
+const NUM_BUCKETS: usize = 8;
+/// The number of components of the format.
+const NUM_COMPONENTS: usize = 9;
+/// The number of contexts.
+const NUM_CONTEXTS: usize = NUM_COMPONENTS * NUM_BUCKETS;
+/// The maximum length of a codeword.
+const MAX_CODEWORD_LEN: usize = 32;
+
+/// The components of the format, used to select a context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Component {
+    Outdegree,
+    ReferenceOffset,
+    BlockCount,
+    Block,
+    IntervalCount,
+    IntervalStart,
+    IntervalLen,
+    FirstResidual,
+    Residual,
+}
+
+/// Returns the bucket of an outdegree, that is, the length of its binary
+/// representation, up to `NUM_BUCKETS - 1`.
+#[inline(always)]
+pub(super) fn bucket(outdegree: u64) -> usize {
+    ((u64::BITS - outdegree.leading_zeros()) as usize).min(NUM_BUCKETS - 1)
+}
+
+/// Returns the context of a component given the bucket of the outdegree of
+/// the current node.
+///
+/// Outdegrees are the first component of a node, so they have a single
+/// context.
+#[inline(always)]
+fn context(component: Component, bucket: usize) -> usize {
+    match component {
+        Component::Outdegree => 0,
+        _ => component as usize * NUM_BUCKETS + bucket,
+    }
+}
+
+/// Splits a value into a token, a number of raw bits, and the raw bits.
+#[inline(always)]
+fn tokenize(value: u64) -> (usize, usize, u64) {
+    if value < DIRECT_TOKENS as u64 {
+        return (value as usize, 0, 0);
+    }
+    let msb = value.ilog2() as usize;
+    let raw_len = msb - 1;
+    let token = DIRECT_TOKENS
+        + (msb - DIRECT_TOKENS.ilog2() as usize) * 2
+        + ((value >> raw_len) & 1) as usize;
+    (token, raw_len, value & ((1 << raw_len) - 1))
+}
+
+/// Returns the number of raw bits of a token and the value of the token
+/// with all raw bits set to zero.
+#[inline(always)]
+fn detokenize(token: usize) -> (usize, u64) {
+    if token < DIRECT_TOKENS {
+        return (0, token as u64);
+    }
+    let msb = (token - DIRECT_TOKENS) / 2 + DIRECT_TOKENS.ilog2() as usize;
+    let raw_len = msb - 1;
+    let second = ((token - DIRECT_TOKENS) & 1) as u64;
+    (raw_len, (1 << msb) | (second << raw_len))
+}
+
+/// The number of occurrences of each token in each context.
+///
+/// Statistics are gathered by a [`HuffmanStatsEncoder`], and turned into
+/// models by [`models`](Self::models).
+#[derive(Debug, Clone)]
+pub struct HuffmanStats {
+    counts: Box<[u64]>,
+}
+
+impl core::default::Default for HuffmanStats {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; NUM_CONTEXTS * NUM_TOKENS].into_boxed_slice(),
+        }
+    }
+}
+
+impl HuffmanStats {
+    /// Records a value in the context of a component.
+    #[inline(always)]
+    fn update(&mut self, component: Component, bucket: usize, value: u64) -> u64 {
+        self.counts[context(component, bucket) * NUM_TOKENS + tokenize(value).0] += 1;
+        value
+    }
+
+    /// Returns Huffman models built on these statistics.
+    ///
+    /// Every token gets a codeword, even if it never occurred, so the models
+    /// can encode any value.
+    pub fn models(&self) -> HuffmanModels {
+        let lens = self
+            .counts
+            .chunks_exact(NUM_TOKENS)
+            .flat_map(huffman_lens)
+            .collect::<Vec<_>>();
+        HuffmanModels::from_lens(lens.into_boxed_slice()).expect("Huffman codes are complete")
+    }
+}
+
+impl std::ops::AddAssign<&Self> for HuffmanStats {
+    fn add_assign(&mut self, rhs: &Self) {
+        for (count, rhs_count) in self.counts.iter_mut().zip(rhs.counts.iter()) {
+            *count += rhs_count;
+        }
+    }
+}
+
+impl core::iter::Sum for HuffmanStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut acc, x| {
+            acc += &x;
+            acc
+        })
+    }
+}
+
+/// Returns the lengths of the codewords of a Huffman code for the given
+/// counts, limited to [`MAX_CODEWORD_LEN`].
+///
+/// Counts are incremented by one, so that all tokens get a codeword; if the
+/// resulting code is too long, counts are halved until it fits.
+fn huffman_lens(counts: &[u64]) -> Vec<u8> {
+    let mut counts = counts
+        .iter()
+        .map(|&count| count.saturating_add(1))
+        .collect::<Vec<_>>();
+    loop {
+        let n = counts.len();
+        let mut heap = counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| Reverse((count, i)))
+            .collect::<BinaryHeap<_>>();
+        // Internal nodes have larger indices than their children
+        let mut parent = vec![0; 2 * n - 1];
+        let mut next = n;
+        while let (Some(Reverse((c0, i0))), Some(Reverse((c1, i1)))) = (heap.pop(), heap.pop()) {
+            parent[i0] = next;
+            parent[i1] = next;
+            heap.push(Reverse((c0.saturating_add(c1), next)));
+            next += 1;
+        }
+        let mut depth = vec![0_usize; 2 * n - 1];
+        for i in (0..2 * n - 2).rev() {
+            depth[i] = depth[parent[i]] + 1;
+        }
+        if depth[..n].iter().all(|&d| d <= MAX_CODEWORD_LEN) {
+            return depth[..n].iter().map(|&d| d as u8).collect();
+        }
+        for count in &mut counts {
+            *count = count.div_ceil(2);
+        }
+    }
+}
+
+/// Per-context canonical Huffman models for the components of a
+/// [`BvGraph`](crate::graphs::bvgraph::BvGraph).
+///
+/// Each value is split into a token and some raw bits: the token is
+/// Huffman-coded using the model of the context of the value, and the raw
+/// bits are written verbatim. The context depends on the component and, for
+/// all components except outdegrees, on the number of bits of the outdegree
+/// of the current node, so that nodes can be decoded independently, and
+/// thus randomly accessed.
+///
+/// The models are static: they are built in a first pass by gathering
+/// statistics with a [`HuffmanStatsEncoder`] (see
+/// [`BvComp::parallel_huffman_models`](crate::graphs::bvgraph::BvComp::parallel_huffman_models)),
+/// are stored in a `.models` file containing the lengths of the codewords,
+/// and do not change during compression or decompression. Since every token
+/// is written using at least one bit, components that are almost always zero
+/// (e.g., block and interval counts) cannot use less than one bit per value,
+/// as they would with an arithmetic or ANS coder.
+#[derive(Debug, Clone)]
+pub struct HuffmanModels {
+    /// The length of the codeword of each token in each context.
+    lens: Box<[u8]>,
+    /// The canonical codeword of each token in each context.
+    codewords: Box<[u32]>,
+    /// The number of codewords of each length in each context.
+    len_counts: Box<[u32]>,
+    /// The tokens of each context in canonical order.
+    sorted_tokens: Box<[u8]>,
+}
+
+impl HuffmanModels {
+    /// Creates models from the lengths of the codewords of each token in
+    /// each context.
+    ///
+    /// The lengths must define a complete prefix code in each context.
+    pub fn from_lens(lens: Box<[u8]>) -> Result<Self> {
+        ensure!(
+            lens.len() == NUM_CONTEXTS * NUM_TOKENS,
+            "Expected {} codeword lengths, got {}",
+            NUM_CONTEXTS * NUM_TOKENS,
+            lens.len()
+        );
+        let mut codewords = vec![0; lens.len()];
+        let mut len_counts = vec![0; NUM_CONTEXTS * (MAX_CODEWORD_LEN + 1)];
+        let mut sorted_tokens = vec![0; lens.len()];
+        for ctx in 0..NUM_CONTEXTS {
+            let ctx_lens = &lens[ctx * NUM_TOKENS..][..NUM_TOKENS];
+            let mut kraft = 0_u64;
+            for &len in ctx_lens {
+                ensure!(
+                    (1..=MAX_CODEWORD_LEN).contains(&(len as usize)),
+                    "Invalid codeword length {} in context {}",
+                    len,
+                    ctx
+                );
+                kraft += 1 << (MAX_CODEWORD_LEN - len as usize);
+                len_counts[ctx * (MAX_CODEWORD_LEN + 1) + len as usize] += 1;
+            }
+            ensure!(
+                kraft == 1 << MAX_CODEWORD_LEN,
+                "The codeword lengths of context {} do not define a complete prefix code",
+                ctx
+            );
+
+            let mut tokens = (0..NUM_TOKENS).collect::<Vec<_>>();
+            tokens.sort_by_key(|&token| (ctx_lens[token], token));
+            let mut codeword = 0_u64;
+            let mut prev_len = 0;
+            for (i, &token) in tokens.iter().enumerate() {
+                let len = ctx_lens[token] as usize;
+                codeword <<= len - prev_len;
+                codewords[ctx * NUM_TOKENS + token] = codeword as u32;
+                sorted_tokens[ctx * NUM_TOKENS + i] = token as u8;
+                codeword += 1;
+                prev_len = len;
+            }
+        }
+        Ok(Self {
+            lens,
+            codewords: codewords.into_boxed_slice(),
+            len_counts: len_counts.into_boxed_slice(),
+            sorted_tokens: sorted_tokens.into_boxed_slice(),
+        })
+    }
+
+    /// Loads models from a `.models` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let lens =
+            std::fs::read(path).with_context(|| format!("Could not read {}", path.display()))?;
+        Self::from_lens(lens.into_boxed_slice())
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    /// Stores the models in a `.models` file.
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, &self.lens)
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Returns the number of bits used to write a value.
+    #[inline(always)]
+    pub(super) fn len(&self, component: Component, bucket: usize, value: u64) -> usize {
+        let (token, raw_len, _) = tokenize(value);
+        self.lens[context(component, bucket) * NUM_TOKENS + token] as usize + raw_len
+    }
+
+    /// Writes a value and returns the number of bits written.
+    ///
+    /// Codewords are written starting from their most significant bit
+    /// independently of the endianness, so that they can be decoded one bit
+    /// at a time.
+    #[inline(always)]
+    pub(super) fn write<E: Endianness, CW: BitWrite<E>>(
+        &self,
+        writer: &mut CW,
+        component: Component,
+        bucket: usize,
+        value: u64,
+    ) -> Result<usize, CW::Error> {
+        let (token, raw_len, raw) = tokenize(value);
+        let index = context(component, bucket) * NUM_TOKENS + token;
+        let len = self.lens[index] as usize;
+        let codeword = self.codewords[index] as u64;
+        if E::IS_BIG {
+            writer.write_bits(codeword, len)?;
+        } else {
+            writer.write_bits(codeword.reverse_bits() >> (64 - len), len)?;
+        }
+        if raw_len != 0 {
+            writer.write_bits(raw, raw_len)?;
+        }
+        Ok(len + raw_len)
+    }
+
+    /// Reads a value.
+    #[inline(always)]
+    pub(super) fn read<E: Endianness, CR: BitRead<E>>(
+        &self,
+        reader: &mut CR,
+        component: Component,
+        bucket: usize,
+    ) -> Result<u64, CR::Error> {
+        let ctx = context(component, bucket);
+        let len_counts = &self.len_counts[ctx * (MAX_CODEWORD_LEN + 1)..][..MAX_CODEWORD_LEN + 1];
+        // Canonical decoding: `first` is the first codeword of the current
+        // length, and `index` the index of the corresponding token
+        let mut codeword = 0_u64;
+        let mut first = 0_u64;
+        let mut index = 0;
+        for &count in &len_counts[1..] {
+            codeword |= reader.read_bits(1)?;
+            let count = count as u64;
+            if codeword < first + count {
+                let token =
+                    self.sorted_tokens[ctx * NUM_TOKENS + index + (codeword - first) as usize];
+                let (raw_len, value) = detokenize(token as usize);
+                return Ok(if raw_len == 0 {
+                    value
+                } else {
+                    value | reader.read_bits(raw_len)?
+                });
+            }
+            index += count as usize;
+            first = (first + count) << 1;
+            codeword <<= 1;
+        }
+        unreachable!("The code of context {} is not complete", ctx)
+    }
+}
+
+/// An encoder that does not write anything, but gathers the [statistics
+/// needed to build Huffman models](HuffmanStats).
+///
+/// As in the case of [`StatsEncoder`](super::StatsEncoder), the estimator
+/// uses the codes of the [`CompFlags`] passed at construction time.
+#[derive(Debug, Clone)]
+pub struct HuffmanStatsEncoder {
+    /// The estimator used to compute the length of the values written.
+    estimator: DynCodesEstimator,
+    /// The bucket of the outdegree of the current node.
+    bucket: usize,
+    /// The statistics of the values written so far.
+    pub stats: HuffmanStats,
+}
+
+impl HuffmanStatsEncoder {
+    /// Creates a new statistics encoder estimating lengths with the codes of
+    /// the given compression flags.
+    pub fn new(cf: &CompFlags) -> Result<Self> {
+        Ok(Self {
+            estimator: DynCodesEstimator::new(cf)?,
+            bucket: 0,
+            stats: HuffmanStats::default(),
+        })
+    }
+
+    #[inline(always)]
+    fn update(&mut self, component: Component, value: u64) -> u64 {
+        self.stats.update(component, self.bucket, value)
+    }
+}
+
+impl Encode for HuffmanStatsEncoder {
+    type Error = Infallible;
+
+    #[inline(always)]
+    fn start_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn end_node(&mut self, _node: usize) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn write_outdegree(&mut self, value: u64) -> Result<usize, Self::Error> {
+        self.update(Component::Outdegree, value);
+        self.bucket = bucket(value);
+        self.estimator.write_outdegree(value)
+    }
+
+    #[inline(always)]
+    fn write_reference_offset(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::ReferenceOffset, value);
+        self.estimator.write_reference_offset(value)
+    }
+
+    #[inline(always)]
+    fn write_block_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::BlockCount, value);
+        self.estimator.write_block_count(value)
+    }
+
+    #[inline(always)]
+    fn write_block(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::Block, value);
+        self.estimator.write_block(value)
+    }
+
+    #[inline(always)]
+    fn write_interval_count(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::IntervalCount, value);
+        self.estimator.write_interval_count(value)
+    }
+
+    #[inline(always)]
+    fn write_interval_start(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::IntervalStart, value);
+        self.estimator.write_interval_start(value)
+    }
+
+    #[inline(always)]
+    fn write_interval_len(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::IntervalLen, value);
+        self.estimator.write_interval_len(value)
+    }
+
+    #[inline(always)]
+    fn write_first_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::FirstResidual, value);
+        self.estimator.write_first_residual(value)
+    }
+
+    #[inline(always)]
+    fn write_residual(&mut self, value: u64) -> Result<usize, Self::Error> {
+        let value = self.update(Component::Residual, value);
+        self.estimator.write_residual(value)
+    }
+
+    fn flush(&mut self) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+}
+
+impl EncodeAndEstimate for HuffmanStatsEncoder {
+    type Estimator<'a>
+        = &'a mut DynCodesEstimator
+    where
+        Self: 'a;
+
+    fn estimator(&mut self) -> Self::Estimator<'_> {
+        &mut self.estimator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let mut values = (0..1000).collect::<Vec<u64>>();
+        for msb in 10..64 {
+            values.extend([1 << msb, (1 << msb) + 1, u64::MAX >> (63 - msb)]);
+        }
+        values.push(u64::MAX);
+        for value in values {
+            let (token, raw_len, raw) = tokenize(value);
+            assert!(token < NUM_TOKENS);
+            let (detok_raw_len, base) = detokenize(token);
+            assert_eq!(raw_len, detok_raw_len);
+            assert_eq!(base | raw, value);
+        }
+    }
+}
@@ -18,15 +18,30 @@ pub use dec_dbg::*;
 mod dec_dyn;
 pub use dec_dyn::*;
 
+mod dec_huffman;
+pub use dec_huffman::*;
+
 mod dec_stats;
 pub use dec_stats::*;
 
+mod dec_stream;
+pub use dec_stream::*;
+
 mod enc_const;
 pub use enc_const::*;
 
 mod enc_dyn;
 pub use enc_dyn::*;
 
+mod enc_huffman;
+pub use enc_huffman::*;
+
+mod huffman;
+pub use huffman::*;
+
+mod enc_stats;
+pub use enc_stats::*;
+
 use std::error::Error;
 
 /// Methods to decode the component of a [`super::BvGraph`] or [`super::BvGraphSeq`].
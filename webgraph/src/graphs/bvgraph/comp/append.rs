@@ -0,0 +1,274 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::labeled::store_ef;
+use crate::prelude::*;
+use anyhow::{ensure, Context, Result};
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::prelude::*;
+use dsi_progress_logger::prelude::*;
+use epserde::deser::MemCase;
+use lender::prelude::*;
+use mmap_rs::MmapFlags;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
+use std::path::Path;
+use sux::dict::EliasFanoBuilder;
+use sux::rank_sel::SelectAdaptConst;
+
+impl BvComp<()> {
+    /// Appends the nodes returned by a [`NodeLabelsLender`] to an existing
+    /// BvGraph with basename `basename` and returns the number of bits
+    /// appended to the graph bitstream.
+    ///
+    /// The lender must return contiguous nodes starting from the number of
+    /// nodes of the graph; successors can be arbitrary nodes, including
+    /// nodes of the original graph. The new nodes are compressed using the
+    /// flags stored in the `.properties` file, and the reference window is
+    /// rebuilt from the last nodes of the graph, so the resulting bitstream
+    /// is identical to the one obtained compressing the whole graph at once
+    /// with [`single_thread`](Self::single_thread).
+    ///
+    /// The graph must have an `.offsets` file, which can be built using
+    /// `webgraph build offsets`. The graph, the `.offsets` file and the
+    /// `.properties` file are updated in place, and the Elias–Fano
+    /// representation of the offsets is rebuilt and stored in an `.ef` file.
+    pub fn append<E, L>(basename: impl AsRef<Path>, iter: L) -> Result<u64>
+    where
+        E: Endianness,
+        L: IntoLender,
+        L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
+        MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+        for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek,
+        for<'a> MemBufReader<'a, E>: BitRead<E>,
+        BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: CodesWrite<E>,
+    {
+        let basename = basename.as_ref();
+        let properties_path = basename.with_extension(PROPERTIES_EXTENSION);
+        let (num_nodes, num_arcs, compression_flags) = parse_properties::<E>(&properties_path)
+            .with_context(|| format!("Could not load {}", properties_path.display()))?;
+        // Checksums are updated only if the graph has them already
+        let mut checksums = Checksums::load(&properties_path)?;
+
+        let graph_path = basename.with_extension(GRAPH_EXTENSION);
+        let offsets_path = basename.with_extension(OFFSETS_EXTENSION);
+        ensure!(
+            offsets_path.exists(),
+            "Could not find {}; you can build it with \"webgraph build offsets\"",
+            offsets_path.display()
+        );
+        let graph_file_len = graph_path
+            .metadata()
+            .with_context(|| format!("Could not stat {}", graph_path.display()))?
+            .len();
+
+        // Read the offsets, keeping track of their length in bits
+        let mut reader = <BufBitReader<BE, _>>::new(<WordAdapter<u32, _>>::new(BufReader::new(
+            File::open(&offsets_path)
+                .with_context(|| format!("Could not open {}", offsets_path.display()))?,
+        )));
+        let mut efb = EliasFanoBuilder::new(num_nodes + 1, graph_file_len as usize * 8);
+        let mut bitstream_len = 0;
+        let mut offsets_len = 0;
+        for _ in 0..num_nodes + 1 {
+            let delta = reader.read_gamma().context("Could not read offset")?;
+            offsets_len += len_gamma(delta) as u64;
+            bitstream_len += delta;
+            ensure!(
+                bitstream_len <= graph_file_len * 8,
+                "The offsets in {} exceed the length of {}",
+                offsets_path.display(),
+                graph_path.display()
+            );
+            efb.push(bitstream_len as usize);
+        }
+        drop(reader);
+        let ef: EF = unsafe {
+            efb.build()
+                .map_high_bits(SelectAdaptConst::<_, _, 12, 4>::new)
+        };
+
+        // Rebuild the reference window from the last nodes of the graph
+        let window_start = num_nodes - compression_flags.compression_window.min(num_nodes);
+        let window = {
+            let factory = DynCodesDecoderFactory::new(
+                MmapHelper::<u32>::mmap(&graph_path, MmapFlags::empty())
+                    .with_context(|| format!("Could not mmap {}", graph_path.display()))?,
+                MemCase::encase(ef),
+                compression_flags,
+            )?;
+            let ref_counts = (window_start..num_nodes)
+                .map(|node| ref_count(&factory, node, compression_flags.max_ref_count))
+                .collect::<Result<Vec<_>>>()?;
+            let graph = BvGraph::new(
+                factory,
+                num_nodes,
+                num_arcs,
+                compression_flags.compression_window,
+                compression_flags.min_interval_length,
+            );
+            (window_start..num_nodes)
+                .map(|node| graph.successors(node).collect::<Vec<_>>())
+                .zip(ref_counts)
+                .collect::<Vec<_>>()
+        };
+
+        let codes_writer = DynCodesEncoder::new(
+            open_for_append::<E>(&graph_path, bitstream_len)?,
+            &compression_flags,
+        )?;
+        let mut offsets_writer = open_for_append::<BE>(&offsets_path, offsets_len)?;
+
+        let mut bvcomp = BvComp::new(
+            codes_writer,
+            compression_flags.compression_window,
+            compression_flags.max_ref_count,
+            compression_flags.min_interval_length,
+            window_start,
+        );
+        for (successors, ref_count) in window {
+            bvcomp.preload(successors, ref_count);
+        }
+
+        let mut pl = ProgressLogger::default();
+        pl.display_memory(true).item_name("node");
+        pl.start("Appending successors...");
+        let mut written_bits = 0;
+        let mut next_node = num_nodes;
+        for_! ( (node_id, successors) in iter {
+            ensure!(
+                node_id == next_node,
+                "Expected node {} but got node {}",
+                next_node,
+                node_id
+            );
+            let mut hasher = NodeHasher::new(node_id);
+            let node_bits = bvcomp
+                .push(successors.into_iter().inspect(|&s| hasher.push(s)))
+                .context("Could not push successors")?;
+            if let Some(checksums) = &mut checksums {
+                checksums.add(node_id, hasher.finish());
+            }
+            offsets_writer
+                .write_gamma(node_bits)
+                .context("Could not write offset")?;
+            written_bits += node_bits;
+            next_node += 1;
+            pl.update();
+        });
+        pl.done();
+
+        let new_num_arcs = num_arcs + bvcomp.arcs;
+        written_bits += bvcomp.flush().context("Could not flush bvcomp")? as u64;
+        offsets_writer.flush().context("Could not flush offsets")?;
+        drop(offsets_writer);
+
+        log::info!("Writing the .properties file");
+        let mut properties = compression_flags
+            .to_properties::<E>(next_node, new_num_arcs, bitstream_len + written_bits)
+            .context("Could not serialize properties")?;
+        if let Some(checksums) = &checksums {
+            properties.push_str(&checksums.to_properties());
+        }
+        std::fs::write(&properties_path, properties)
+            .with_context(|| format!("Could not write {}", properties_path.display()))?;
+
+        log::info!("Building the Elias–Fano representation of the offsets");
+        store_ef(
+            &offsets_path,
+            next_node,
+            bitstream_len + written_bits,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+
+        Ok(written_bits)
+    }
+}
+
+/// Returns the length of the reference chain of a node, that is, the number
+/// of references that must be followed to decode its successors, up to
+/// `max_ref_count`.
+fn ref_count<F: RandomAccessDecoderFactory>(
+    factory: &F,
+    mut node: usize,
+    max_ref_count: usize,
+) -> Result<usize> {
+    // reference chains are not limited, so their length is irrelevant
+    if max_ref_count == usize::MAX {
+        return Ok(0);
+    }
+    let mut count = 0;
+    while count < max_ref_count {
+        let mut decoder = factory.new_decoder(node)?;
+        if decoder.read_outdegree() == 0 {
+            break;
+        }
+        let reference_offset = decoder.read_reference_offset() as usize;
+        if reference_offset == 0 {
+            break;
+        }
+        node -= reference_offset;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Opens a bitstream written by [`BufBitWriter`] so that new bits can be
+/// appended after the first `bit_len` bits.
+///
+/// The file is truncated at the word containing bit `bit_len`, and the
+/// bits of the word preceding `bit_len` are written again by the returned
+/// writer.
+fn open_for_append<E: Endianness>(
+    path: &Path,
+    bit_len: u64,
+) -> Result<BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>>
+where
+    for<'a> MemBufReader<'a, E>: BitRead<E>,
+    BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: BitWrite<E>,
+{
+    let word_bits = usize::BITS as u64;
+    let word_start = bit_len / word_bits * (word_bits / 8);
+    let rem = (bit_len % word_bits) as usize;
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+    file.seek(SeekFrom::Start(word_start))
+        .with_context(|| format!("Could not seek in {}", path.display()))?;
+    let mut tail = Vec::new();
+    (&mut file)
+        .take(word_bits / 8)
+        .read_to_end(&mut tail)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    ensure!(
+        tail.len() * 8 >= rem,
+        "{} is shorter than {} bits",
+        path.display(),
+        bit_len
+    );
+    // Read the remaining bits with the same endianness they were written with
+    tail.resize(word_bits as usize / 8, 0);
+    let words = tail
+        .chunks_exact(4)
+        .map(|chunk| u32::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    let mut reader = <BufBitReader<E, _>>::new(MemWordReader::new(words.as_slice()));
+    let value = if rem == 0 { 0 } else { reader.read_bits(rem)? };
+
+    file.set_len(word_start)
+        .with_context(|| format!("Could not truncate {}", path.display()))?;
+    file.seek(SeekFrom::Start(word_start))
+        .with_context(|| format!("Could not seek in {}", path.display()))?;
+    let mut writer = <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(BufWriter::new(file)));
+    if rem != 0 {
+        writer.write_bits(value, rem)?;
+    }
+    Ok(writer)
+}
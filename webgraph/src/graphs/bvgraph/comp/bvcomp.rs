@@ -47,7 +47,7 @@ pub struct BvComp<E> {
 /// Compute how to encode the successors of a node, given a reference node.
 /// This could be a function, but we made it a struct so we can reuse the
 /// allocations for performance reasons
-struct Compressor {
+pub(super) struct Compressor {
     /// The outdegree of the node we are compressing
     outdegree: usize,
     /// The blocks of nodes we are copying from the reference node
@@ -66,10 +66,10 @@ impl Compressor {
     /// Constant used only to make the code more readable.
     /// When min_interval_length is 0, we don't use intervals, which might be
     /// counter-intuitive
-    const NO_INTERVALS: usize = 0;
+    pub(super) const NO_INTERVALS: usize = 0;
 
     /// Creates a new empty compressor
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Compressor {
             outdegree: 0,
             blocks: Vec::with_capacity(1024),
@@ -85,7 +85,7 @@ impl Compressor {
     /// called only after `compress`.
     ///
     /// This returns the number of bits written.
-    fn write<E: Encode>(
+    pub(super) fn write<E: Encode>(
         &self,
         writer: &mut E,
         curr_node: usize,
@@ -163,7 +163,7 @@ impl Compressor {
     }
 
     /// setup the internal buffers for the compression of the given values
-    fn compress(
+    pub(super) fn compress(
         &mut self,
         curr_list: &[usize],
         ref_list: Option<&[usize]>,
@@ -332,6 +332,28 @@ impl<E: EncodeAndEstimate> BvComp<E> {
         }
     }
 
+    /// Loads in the reference window a node that has been already compressed,
+    /// without writing anything.
+    ///
+    /// `ref_count` must be the length of the reference chain of the node,
+    /// that is, the number of references that must be followed to decode its
+    /// successors. This method is used to rebuild the state of the compressor
+    /// when appending nodes to an existing graph: the compressor must be
+    /// created with `start_node` equal to the first node to preload, and
+    /// after preloading the last
+    /// [`compression_window`](CompFlags::compression_window) nodes of the
+    /// graph in order, [`push`](Self::push) will compress the following nodes
+    /// as if the whole graph had been compressed at once. Preloaded nodes do
+    /// not contribute to the number of [arcs](Self::arcs).
+    pub fn preload<I: IntoIterator<Item = usize>>(&mut self, succ_iter: I, ref_count: usize) {
+        let mut succ_vec = self.backrefs.take(self.curr_node);
+        succ_vec.clear();
+        succ_vec.extend(succ_iter);
+        self.backrefs.replace(self.curr_node, succ_vec);
+        self.ref_counts[self.curr_node] = ref_count;
+        self.curr_node += 1;
+    }
+
     /// Push a new node to the compressor.
     /// The iterator must yield the successors of the node and the nodes HAVE
     /// TO BE CONTIGUOUS (i.e. if a node has no neighbors you have to pass an
@@ -0,0 +1,451 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::bvcomp::Compressor;
+use crate::prelude::*;
+use lender::prelude::*;
+
+/// A BvGraph compressor that selects references optimally on chunks of nodes.
+///
+/// [`BvComp`] chooses greedily, for each node, the reference that minimizes
+/// the size of its encoding among the previous nodes whose reference chain
+/// is shorter than `max_ref_count`. This compressor instead buffers
+/// `chunk_size` nodes and selects the references of the whole chunk at once,
+/// following the approach of [Zuckerli](https://arxiv.org/abs/2009.01353):
+/// first, each node chooses its best reference without considering reference
+/// chains, which yields a forest; then, a dynamic-programming visit of the
+/// forest decides which references to drop so that no reference chain is
+/// longer than `max_ref_count` and the overall size is minimized. Should the
+/// result be worse than the greedy selection on the same chunk, the latter is
+/// used instead.
+///
+/// The resulting bitstream has the same format as the one produced by
+/// [`BvComp`], so it can be read by [`BvGraph`] and [`BvGraphSeq`] with the
+/// same compression flags. Since nodes are written a chunk at a time, the
+/// length in bits of each node is available through
+/// [`written_lens`](Self::written_lens) after the call that wrote it.
+#[derive(Debug, Clone)]
+pub struct BvCompZ<E> {
+    /// The successor lists of the last (at most) `compression_window` nodes
+    /// already written, followed by those of the nodes of the current chunk.
+    lists: Vec<Vec<usize>>,
+    /// The number of recursion steps needed to decode each node in `lists`;
+    /// only the values of nodes already written are meaningful.
+    ref_counts: Vec<usize>,
+    /// The number of nodes at the start of `lists` that have already been
+    /// written.
+    num_written: usize,
+    /// The estimated length in bits of each node of the current chunk for
+    /// each reference delta in `0..=compression_window`, with `u64::MAX`
+    /// marking references that cannot be used.
+    costs: Vec<u64>,
+    /// The lengths in bits of the nodes written by the last call to
+    /// [`push`](Self::push) or [`write_pending`](Self::write_pending).
+    lens: Vec<u64>,
+    /// The compressor used both for estimating and for writing nodes.
+    compressor: Compressor,
+    /// The bitstream writer, also used to estimate the cost of references.
+    encoder: E,
+    /// The number of previous nodes that will be considered during the compression
+    compression_window: usize,
+    /// The number of nodes whose references are selected together
+    chunk_size: usize,
+    /// The maximum recursion depth that will be used to decompress a node
+    max_ref_count: usize,
+    /// The minimum length of sequences that will be compressed as a (start, len)
+    min_interval_length: usize,
+    /// The next node to be pushed
+    curr_node: usize,
+    /// The number of arcs compressed so far
+    pub arcs: u64,
+}
+
+impl<E: EncodeAndEstimate> BvCompZ<E> {
+    /// This value for `min_interval_length` implies that no intervalization will be performed.
+    pub const NO_INTERVALS: usize = Compressor::NO_INTERVALS;
+
+    /// Creates a new BvGraph compressor selecting references on chunks of
+    /// `chunk_size` nodes.
+    pub fn new(
+        encoder: E,
+        compression_window: usize,
+        chunk_size: usize,
+        max_ref_count: usize,
+        min_interval_length: usize,
+        start_node: usize,
+    ) -> Self {
+        let chunk_size = chunk_size.max(1);
+        BvCompZ {
+            lists: Vec::with_capacity(compression_window + chunk_size),
+            ref_counts: Vec::with_capacity(compression_window + chunk_size),
+            num_written: 0,
+            costs: Vec::with_capacity((compression_window + 1) * chunk_size),
+            lens: Vec::with_capacity(chunk_size),
+            compressor: Compressor::new(),
+            encoder,
+            compression_window,
+            chunk_size,
+            max_ref_count,
+            min_interval_length,
+            curr_node: start_node,
+            arcs: 0,
+        }
+    }
+
+    /// Push a new node to the compressor and returns the number of bits
+    /// written, which is zero unless the node completes a chunk.
+    ///
+    /// The iterator must yield the successors of the node and the nodes HAVE
+    /// TO BE CONTIGUOUS (i.e. if a node has no neighbors you have to pass an
+    /// empty iterator)
+    pub fn push<I: IntoIterator<Item = usize>>(&mut self, succ_iter: I) -> anyhow::Result<u64> {
+        self.lens.clear();
+        let curr_list = succ_iter.into_iter().collect::<Vec<_>>();
+        self.arcs += curr_list.len() as u64;
+
+        let idx = self.lists.len();
+        self.compressor
+            .compress(&curr_list, None, self.min_interval_length)?;
+        let ref_offset = self.ref_offset(0);
+        let bits = self.compressor.write(
+            &mut self.encoder.estimator(),
+            self.curr_node,
+            ref_offset,
+            self.min_interval_length,
+        )?;
+        self.costs.push(bits);
+
+        for delta in 1..=self.compression_window {
+            let usable = delta <= idx
+                && self.max_ref_count > 0
+                && !self.lists[idx - delta].is_empty()
+                && (idx - delta >= self.num_written
+                    || self.ref_counts[idx - delta] < self.max_ref_count);
+            if !usable {
+                self.costs.push(u64::MAX);
+                continue;
+            }
+            self.compressor.compress(
+                &curr_list,
+                Some(&self.lists[idx - delta]),
+                self.min_interval_length,
+            )?;
+            let bits = self.compressor.write(
+                &mut self.encoder.estimator(),
+                self.curr_node,
+                Some(delta),
+                self.min_interval_length,
+            )?;
+            self.costs.push(bits);
+        }
+
+        self.lists.push(curr_list);
+        self.ref_counts.push(0);
+        self.curr_node += 1;
+
+        if self.lists.len() - self.num_written == self.chunk_size {
+            self.write_pending()
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Given an iterator over the nodes successors iterators, push them all.
+    /// The iterator must yield the successors of the node and the nodes HAVE
+    /// TO BE CONTIGUOUS (i.e. if a node has no neighbors you have to pass an
+    /// empty iterator).
+    ///
+    /// This most commonly is called with a reference to a graph.
+    pub fn extend<L>(&mut self, iter_nodes: L) -> anyhow::Result<u64>
+    where
+        L: IntoLender,
+        L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
+    {
+        let mut count = 0;
+        for_! ( (_, succ) in iter_nodes {
+            count += self.push(succ.into_iter())?;
+        });
+        Ok(count)
+    }
+
+    /// Selects the references of the nodes pushed but not yet written, writes
+    /// them, and returns the number of bits written.
+    ///
+    /// This method is called automatically when a chunk is complete, and by
+    /// [`flush`](Self::flush); calling it explicitly just ends the current
+    /// chunk early.
+    pub fn write_pending(&mut self) -> anyhow::Result<u64> {
+        self.lens.clear();
+        let num_pending = self.lists.len() - self.num_written;
+        if num_pending == 0 {
+            return Ok(0);
+        }
+        let refs = self.select_refs();
+        let first_node = self.curr_node - num_pending;
+        let mut written_bits = 0;
+
+        for (i, &delta) in refs.iter().enumerate() {
+            let idx = self.num_written + i;
+            let (prev, rest) = self.lists.split_at(idx);
+            let ref_list = if delta == 0 {
+                None
+            } else {
+                Some(prev[idx - delta].as_slice())
+            };
+            self.compressor
+                .compress(&rest[0], ref_list, self.min_interval_length)?;
+            let ref_offset = self.ref_offset(delta);
+            let bits = self.compressor.write(
+                &mut self.encoder,
+                first_node + i,
+                ref_offset,
+                self.min_interval_length,
+            )?;
+            // consistency check
+            debug_assert_eq!(bits, self.cost(i, delta));
+            self.ref_counts[idx] = if delta == 0 {
+                0
+            } else {
+                self.ref_counts[idx - delta] + 1
+            };
+            debug_assert!(self.ref_counts[idx] <= self.max_ref_count);
+            self.lens.push(bits);
+            written_bits += bits;
+        }
+
+        // keep only the lists that can still be referenced
+        let to_remove = self.lists.len() - self.compression_window.min(self.lists.len());
+        self.lists.drain(..to_remove);
+        self.ref_counts.drain(..to_remove);
+        self.num_written = self.lists.len();
+        self.costs.clear();
+        Ok(written_bits)
+    }
+
+    /// Returns the lengths in bits of the nodes written by the last call to
+    /// [`push`](Self::push) or [`write_pending`](Self::write_pending), in
+    /// node order.
+    pub fn written_lens(&self) -> &[u64] {
+        &self.lens
+    }
+
+    /// Consume the compressor, writing the pending nodes, and return the
+    /// number of bits written by writing them and flushing the encoder.
+    pub fn flush(mut self) -> anyhow::Result<u64> {
+        let written_bits = self.write_pending()?;
+        Ok(written_bits + self.encoder.flush()? as u64)
+    }
+
+    /// Returns the reference offset to write for a given delta.
+    ///
+    /// When the compression window is zero, no reference offset is written.
+    fn ref_offset(&self, delta: usize) -> Option<usize> {
+        if self.compression_window == 0 {
+            None
+        } else {
+            Some(delta)
+        }
+    }
+
+    /// Returns the estimated cost of the `i`-th pending node with reference
+    /// delta `delta`.
+    #[inline(always)]
+    fn cost(&self, i: usize, delta: usize) -> u64 {
+        self.costs[i * (self.compression_window + 1) + delta]
+    }
+
+    /// Returns the reference count of the node at distance `delta` from the
+    /// `i`-th pending node, provided that it has already been written.
+    #[inline(always)]
+    fn written_ref_count(&self, i: usize, delta: usize) -> usize {
+        self.ref_counts[self.num_written + i - delta]
+    }
+
+    /// Returns the total estimated cost of the given references for the
+    /// pending nodes.
+    fn total_cost(&self, refs: &[usize]) -> u64 {
+        refs.iter()
+            .enumerate()
+            .map(|(i, &delta)| self.cost(i, delta))
+            .sum()
+    }
+
+    /// Selects the reference delta of each pending node.
+    fn select_refs(&self) -> Vec<usize> {
+        let num_pending = self.lists.len() - self.num_written;
+
+        // Best references without considering reference chains
+        let best = (0..num_pending)
+            .map(|i| {
+                let mut best_delta = 0;
+                for delta in 1..=self.compression_window {
+                    // strictly less, so we keep the nearest one among equals
+                    if self.cost(i, delta) < self.cost(i, best_delta) {
+                        best_delta = delta;
+                    }
+                }
+                best_delta
+            })
+            .collect::<Vec<_>>();
+
+        let mut depths = vec![0; num_pending];
+        for (i, &delta) in best.iter().enumerate() {
+            depths[i] = match delta {
+                0 => 0,
+                delta if delta <= i => depths[i - delta] + 1,
+                delta => self.written_ref_count(i, delta) + 1,
+            };
+        }
+        if depths.iter().all(|&depth| depth <= self.max_ref_count) {
+            return best;
+        }
+
+        let optimal = self.tree_dp(&best);
+        let greedy = self.greedy();
+        if self.total_cost(&optimal) <= self.total_cost(&greedy) {
+            optimal
+        } else {
+            greedy
+        }
+    }
+
+    /// Selects references on the forest induced by `best` using dynamic
+    /// programming: each node either keeps its best reference, if this does
+    /// not make its reference chain too long, or uses no reference at all.
+    ///
+    /// Must be called only if `max_ref_count` is smaller than the length of
+    /// some reference chain, so the table has a reasonable size.
+    fn tree_dp(&self, best: &[usize]) -> Vec<usize> {
+        let num_pending = best.len();
+        let max_ref_count = self.max_ref_count;
+        let num_depths = max_ref_count + 1;
+        // subtree[i * num_depths + d] is the minimum cost of the descendants
+        // of the i-th pending node when its reference chain has length d
+        let mut subtree = vec![0_u64; num_pending * num_depths];
+
+        // children follow their parents, so we visit the nodes backwards
+        for i in (0..num_pending).rev() {
+            let delta = best[i];
+            if delta == 0 || delta > i {
+                continue;
+            }
+            let parent = i - delta;
+            let drop = self.cost(i, 0) + subtree[i * num_depths];
+            for depth in 0..num_depths {
+                let cost = if depth < max_ref_count {
+                    drop.min(self.cost(i, delta) + subtree[i * num_depths + depth + 1])
+                } else {
+                    drop
+                };
+                subtree[parent * num_depths + depth] += cost;
+            }
+        }
+
+        let mut refs = vec![0; num_pending];
+        let mut depths = vec![0; num_pending];
+        for i in 0..num_pending {
+            let delta = best[i];
+            if delta == 0 {
+                continue;
+            }
+            let parent_depth = if delta <= i {
+                depths[i - delta]
+            } else {
+                self.written_ref_count(i, delta)
+            };
+            let drop = self.cost(i, 0) + subtree[i * num_depths];
+            if parent_depth < max_ref_count
+                && self.cost(i, delta) + subtree[i * num_depths + parent_depth + 1] < drop
+            {
+                refs[i] = delta;
+                depths[i] = parent_depth + 1;
+            }
+        }
+        refs
+    }
+
+    /// Selects references greedily, as [`BvComp`] does.
+    fn greedy(&self) -> Vec<usize> {
+        let num_pending = self.lists.len() - self.num_written;
+        let mut refs = vec![0; num_pending];
+        let mut counts = vec![0; num_pending];
+        for i in 0..num_pending {
+            for delta in 1..=self.compression_window {
+                if self.cost(i, delta) >= self.cost(i, refs[i]) {
+                    continue;
+                }
+                let count = if delta <= i {
+                    counts[i - delta]
+                } else {
+                    self.written_ref_count(i, delta)
+                };
+                if count < self.max_ref_count {
+                    refs[i] = delta;
+                    counts[i] = count + 1;
+                }
+            }
+        }
+        refs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dsi_bitstream::prelude::*;
+    use itertools::Itertools;
+
+    fn compress(
+        graph: &impl SequentialGraph,
+        chunk_size: Option<usize>,
+        max_ref_count: usize,
+    ) -> anyhow::Result<Vec<u64>> {
+        let mut buffer: Vec<u64> = Vec::new();
+        let bit_write = <BufBitWriter<LE, _>>::new(MemWordWriterVec::new(&mut buffer));
+        let codes_writer = <ConstCodesEncoder<LE, _>>::new(bit_write);
+        match chunk_size {
+            Some(chunk_size) => {
+                let mut bvcomp = BvCompZ::new(codes_writer, 7, chunk_size, max_ref_count, 4, 0);
+                bvcomp.extend(graph.iter())?;
+                bvcomp.flush()?;
+            }
+            None => {
+                let mut bvcomp = BvComp::new(codes_writer, 7, max_ref_count, 4, 0);
+                bvcomp.extend(graph.iter())?;
+                bvcomp.flush()?;
+            }
+        }
+        Ok(buffer)
+    }
+
+    #[test]
+    fn test_bvcompz_cnr() -> anyhow::Result<()> {
+        let seq_graph = BvGraphSeq::with_basename("../data/cnr-2000")
+            .endianness::<BE>()
+            .load()?;
+        let comp_flags = CompFlags::default();
+
+        for max_ref_count in [0, 1, 3] {
+            let greedy = compress(&seq_graph, None, max_ref_count)?;
+            let buffer = compress(&seq_graph, Some(10_000), max_ref_count)?;
+            assert!(buffer.len() <= greedy.len());
+
+            let buffer_32: &[u32] = unsafe { buffer.align_to().1 };
+            let bit_read = <BufBitReader<LE, _>>::new(MemWordReader::new(buffer_32));
+            let codes_reader = <ConstCodesDecoder<LE, _>>::new(bit_read, &comp_flags)?;
+            let mut seq_iter = sequential::Iter::new(codes_reader, seq_graph.num_nodes(), 7, 4);
+
+            let mut iter = seq_graph.iter();
+            while let Some((node, succ)) = iter.next() {
+                let (seq_node, seq_succ) = seq_iter.next().unwrap();
+                assert_eq!(node, seq_node);
+                assert_eq!(succ.collect_vec(), seq_succ.collect_vec());
+            }
+        }
+        Ok(())
+    }
+}
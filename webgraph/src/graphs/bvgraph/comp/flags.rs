@@ -5,30 +5,57 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
-use anyhow::{bail, ensure, Result};
-use dsi_bitstream::dispatch::Codes;
+use anyhow::{anyhow, bail, ensure, Result};
+use dsi_bitstream::dispatch::{Codes, FuncCodeReader};
+use dsi_bitstream::impls::{BufBitReader, MemWordReader};
 use dsi_bitstream::traits::{BigEndian, Endianness, LittleEndian};
 use std::collections::HashMap;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+/// How the compressor selects the reference of each node.
+///
+/// The selection strategy does not affect the format of the bitstream, so it
+/// is not recorded in the `.properties` file.
+pub enum RefSelection {
+    /// Each node uses the best reference available, as in the Java version
+    /// of the library (see [`BvComp`](super::BvComp)).
+    #[default]
+    Greedy,
+    /// References are selected optimally on chunks of `chunk_size` nodes
+    /// (see [`BvCompZ`](super::BvCompZ)).
+    Optimal { chunk_size: usize },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 /// The compression flags for reading or compressing a graph.
 ///
-/// As documented, one code might sets multiple values. This is done for
-/// compatibility with the previous Java version of the library.
-/// But the codes optimizers will return the optimal codes for each of them,
-/// so if it identify some big save from using different codes, we can consider
-/// splitting them.
+/// Each of the nine components of the format has its own code. The Java
+/// version of the library, however, uses a single code for the block count
+/// and the blocks, one for the interval count, the interval starts, and the
+/// interval lengths, and one for the first residual and the residuals: graphs
+/// using different codes for components of the same group can be read only by
+/// the Rust version (see [`is_java_compatible`](Self::is_java_compatible)).
+/// Such graphs are marked by the `rustonly=true` property.
 pub struct CompFlags {
     /// The instantaneous code to use to encode the `outdegrees`
     pub outdegrees: Codes,
     /// The instantaneous code to use to encode the `reference_offset`
     pub references: Codes,
-    /// The instantaneous code to use to encode the `block_count` and `blocks`
+    /// The instantaneous code to use to encode the `block_count`
+    pub block_counts: Codes,
+    /// The instantaneous code to use to encode the `blocks`
     pub blocks: Codes,
-    /// The instantaneous code to use to encode the `interval_count`, `interval_start`, and `interval_len`.
-    pub intervals: Codes,
-    /// The instantaneous code to use to encode the `first_residual` and `residual`
+    /// The instantaneous code to use to encode the `interval_count`
+    pub interval_counts: Codes,
+    /// The instantaneous code to use to encode the `interval_start`
+    pub interval_starts: Codes,
+    /// The instantaneous code to use to encode the `interval_len`
+    pub interval_lens: Codes,
+    /// The instantaneous code to use to encode the `first_residual`
+    pub first_residuals: Codes,
+    /// The instantaneous code to use to encode the `residual`
     pub residuals: Codes,
     /// The minimum length of an interval to be compressed as (start, len)
     pub min_interval_length: usize,
@@ -37,6 +64,14 @@ pub struct CompFlags {
     /// The maximum recursion depth during decoding, this modulates the tradeoff
     /// between compression ratio and decoding speed
     pub max_ref_count: usize,
+    /// How references are selected during compression
+    pub ref_selection: RefSelection,
+    /// Whether the components are Huffman-coded using the
+    /// [models](crate::graphs::bvgraph::HuffmanModels) stored in the
+    /// `.models` file instead of the instantaneous codes above, which are
+    /// then used only to select references while gathering statistics (see
+    /// [`BvComp::parallel_huffman`](super::BvComp::parallel_huffman))
+    pub huffman_coding: bool,
 }
 
 impl core::default::Default for CompFlags {
@@ -44,41 +79,163 @@ impl core::default::Default for CompFlags {
         CompFlags {
             outdegrees: Codes::Gamma,
             references: Codes::Unary,
+            block_counts: Codes::Gamma,
             blocks: Codes::Gamma,
-            intervals: Codes::Gamma,
+            interval_counts: Codes::Gamma,
+            interval_starts: Codes::Gamma,
+            interval_lens: Codes::Gamma,
+            first_residuals: Codes::Zeta { k: 3 },
             residuals: Codes::Zeta { k: 3 },
             min_interval_length: 4,
             compression_window: 7,
             max_ref_count: 3,
+            ref_selection: RefSelection::Greedy,
+            huffman_coding: false,
         }
     }
 }
 
 impl CompFlags {
+    /// Sets the code for the block count and the blocks, as the `BLOCKS`
+    /// flag of the Java version does.
+    pub fn set_blocks(&mut self, code: Codes) {
+        self.block_counts = code;
+        self.blocks = code;
+    }
+
+    /// Sets the code for the interval count, the interval starts and the
+    /// interval lengths, as the `INTERVALS` flag of the Java version does.
+    pub fn set_intervals(&mut self, code: Codes) {
+        self.interval_counts = code;
+        self.interval_starts = code;
+        self.interval_lens = code;
+    }
+
+    /// Sets the code for the first residual and the residuals, as the
+    /// `RESIDUALS` flag of the Java version does.
+    pub fn set_residuals(&mut self, code: Codes) {
+        self.first_residuals = code;
+        self.residuals = code;
+    }
+
+    /// Returns the codes of the nine components, with the residuals first.
+    fn codes(&self) -> [Codes; 9] {
+        [
+            self.residuals,
+            self.first_residuals,
+            self.outdegrees,
+            self.references,
+            self.block_counts,
+            self.blocks,
+            self.interval_counts,
+            self.interval_starts,
+            self.interval_lens,
+        ]
+    }
+
     /// Convert a string from the `compflags` field from the `.properties` file
     /// into which code to use.
     ///
-    /// Returns `None` if the string is not recognized.
+    /// Parameterized codes are written as the name of the code followed by
+    /// the parameter (e.g., `PI2`, `GOLOMB3`, `EXPGOLOMB1`, `RICE2`, or `ZETA5`).
+    /// `ZETA` without a parameter uses the ζ parameter `k` specified by the
+    /// `zetak` property, as in the Java version.
+    ///
+    /// Returns `None` if the string is not recognized, or if the code is not
+    /// [supported by dynamic dispatch](Self::is_supported).
     pub fn code_from_str(s: &str, k: usize) -> Option<Codes> {
-        match s.to_uppercase().as_str() {
-            "UNARY" => Some(Codes::Unary),
-            "GAMMA" => Some(Codes::Gamma),
-            "DELTA" => Some(Codes::Delta),
-            "ZETA" => Some(Codes::Zeta { k }),
-            _ => None,
-        }
+        let s = s.to_uppercase();
+        let code = match s.as_str() {
+            "UNARY" => Codes::Unary,
+            "GAMMA" => Codes::Gamma,
+            "DELTA" => Codes::Delta,
+            "ZETA" => Codes::Zeta { k },
+            "OMEGA" => Codes::Omega,
+            "VBYTEBE" => Codes::VByteBe,
+            "VBYTELE" => Codes::VByteLe,
+            _ => {
+                let (name, param) = s.split_at(s.find(|c: char| c.is_ascii_digit())?);
+                let param = param.parse::<usize>().ok()?;
+                match name {
+                    "ZETA" => Codes::Zeta { k: param },
+                    "PI" => Codes::Pi { k: param },
+                    "GOLOMB" => Codes::Golomb { b: param as u64 },
+                    "EXPGOLOMB" => Codes::ExpGolomb { k: param },
+                    "RICE" => Codes::Rice { log2_b: param },
+                    _ => return None,
+                }
+            }
+        };
+        Self::is_supported(code).then_some(code)
+    }
+
+    /// Returns whether a code is supported by the dynamic dispatch of
+    /// [`dsi_bitstream`], which decoders use to read graphs.
+    ///
+    /// Parameterized codes are supported only for small values of their
+    /// parameter (e.g., ζ codes with parameter from 1 to 10).
+    pub fn is_supported(code: Codes) -> bool {
+        FuncCodeReader::<BigEndian, BufBitReader<BigEndian, MemWordReader<u32, &[u32]>>>::new(code)
+            .is_ok()
     }
 
-    pub fn code_to_str(c: Codes) -> Option<&'static str> {
+    /// Convert a code into the string used in the `compflags` field of the
+    /// `.properties` file, given the ζ parameter `k` specified by the `zetak`
+    /// property.
+    ///
+    /// This is the inverse of [`code_from_str`](Self::code_from_str). ζ codes
+    /// with parameter `k` are written as `ZETA`, as in the Java version.
+    ///
+    /// Returns `None` if the code is not supported.
+    pub fn code_to_str(c: Codes, k: usize) -> Option<String> {
         match c {
-            Codes::Unary => Some("UNARY"),
-            Codes::Gamma => Some("GAMMA"),
-            Codes::Delta => Some("DELTA"),
-            Codes::Zeta { k: _ } => Some("ZETA"),
-            _ => unimplemented!("Code {:?} not supported", c),
+            Codes::Unary => Some("UNARY".to_string()),
+            Codes::Gamma => Some("GAMMA".to_string()),
+            Codes::Delta => Some("DELTA".to_string()),
+            Codes::Zeta { k: code_k } if code_k == k => Some("ZETA".to_string()),
+            Codes::Zeta { k } => Some(format!("ZETA{}", k)),
+            Codes::Omega => Some("OMEGA".to_string()),
+            Codes::VByteBe => Some("VBYTEBE".to_string()),
+            Codes::VByteLe => Some("VBYTELE".to_string()),
+            Codes::Pi { k } => Some(format!("PI{}", k)),
+            Codes::Golomb { b } => Some(format!("GOLOMB{}", b)),
+            Codes::ExpGolomb { k } => Some(format!("EXPGOLOMB{}", k)),
+            Codes::Rice { log2_b } => Some(format!("RICE{}", log2_b)),
+            _ => None,
         }
     }
 
+    /// Returns the ζ parameter to write in the `zetak` property: the
+    /// parameter of the residuals code, if it is a ζ code, or the parameter of
+    /// the first ζ code used otherwise, or 3 if no ζ code is used.
+    fn zeta_k(&self) -> usize {
+        self.codes()
+            .into_iter()
+            .find_map(|code| match code {
+                Codes::Zeta { k } => Some(k),
+                _ => None,
+            })
+            .unwrap_or(3)
+    }
+
+    /// Returns whether the codes can be read by the Java version of the
+    /// library, that is, whether the graph is not Huffman-coded, the codes
+    /// are unary, γ, δ, or ζ with the same parameter, and components sharing
+    /// a code in the Java version have the same code.
+    pub fn is_java_compatible(&self) -> bool {
+        let k = self.zeta_k();
+        !self.huffman_coding
+            && self.block_counts == self.blocks
+            && self.interval_counts == self.interval_lens
+            && self.interval_starts == self.interval_lens
+            && self.first_residuals == self.residuals
+            && self.codes().into_iter().all(|code| match code {
+                Codes::Unary | Codes::Gamma | Codes::Delta => true,
+                Codes::Zeta { k: code_k } => code_k == k,
+                _ => false,
+            })
+    }
+
     pub fn to_properties<E: Endianness>(
         &self,
         num_nodes: usize,
@@ -89,7 +246,13 @@ impl CompFlags {
         s.push_str("#BVGraph properties\n");
         s.push_str("graphclass=it.unimi.dsi.webgraph.BVGraph\n");
 
-        if core::any::TypeId::of::<E>() == core::any::TypeId::of::<BigEndian>() {
+        let java_compatible = self.is_java_compatible();
+        if !java_compatible {
+            // the graph uses codes not supported by the Java version
+            s.push_str("rustonly=true\n");
+        }
+        // version 1 makes the Java version refuse to load the graph
+        if core::any::TypeId::of::<E>() == core::any::TypeId::of::<BigEndian>() && java_compatible {
             s.push_str("version=0\n");
         } else {
             s.push_str("version=1\n");
@@ -125,69 +288,70 @@ impl CompFlags {
             bitstream_len as f64 / theoretical_bound
         ));
 
-        s.push_str("compressionflags=");
-        let mut cflags = false;
-        if self.outdegrees != Codes::Gamma {
-            s.push_str(&format!(
-                "OUTDEGREES_{}|",
-                Self::code_to_str(self.outdegrees).unwrap()
-            ));
-            cflags = true;
-        }
-        if self.references != Codes::Unary {
-            s.push_str(&format!(
-                "REFERENCES_{}|",
-                Self::code_to_str(self.references).unwrap()
-            ));
-            cflags = true;
-        }
-        if self.blocks != Codes::Gamma {
-            s.push_str(&format!(
-                "BLOCKS_{}|",
-                Self::code_to_str(self.blocks).unwrap()
-            ));
-            cflags = true;
-        }
-        if self.intervals != Codes::Gamma {
-            s.push_str(&format!(
-                "INTERVALS_{}|",
-                Self::code_to_str(self.intervals).unwrap()
-            ));
-            cflags = true;
-        }
-        if !matches!(self.residuals, Codes::Zeta { k: _ }) {
-            s.push_str(&format!(
-                "RESIDUALS_{}|",
-                Self::code_to_str(self.residuals).unwrap()
-            ));
-            cflags = true;
+        let k = self.zeta_k();
+        // groups of components sharing a flag in the Java version; if the
+        // components of a group have the same code we write the group flag,
+        // otherwise we write a flag for each component, as a group flag
+        // would set all of them
+        let groups = [
+            (
+                "OUTDEGREES",
+                Codes::Gamma,
+                vec![("OUTDEGREES", self.outdegrees)],
+            ),
+            (
+                "REFERENCES",
+                Codes::Unary,
+                vec![("REFERENCES", self.references)],
+            ),
+            (
+                "BLOCKS",
+                Codes::Gamma,
+                vec![("BLOCK_COUNTS", self.block_counts), ("BLOCKS", self.blocks)],
+            ),
+            (
+                "INTERVALS",
+                Codes::Gamma,
+                vec![
+                    ("INTERVAL_COUNTS", self.interval_counts),
+                    ("INTERVAL_STARTS", self.interval_starts),
+                    ("INTERVAL_LENS", self.interval_lens),
+                ],
+            ),
+            (
+                "RESIDUALS",
+                Codes::Zeta { k },
+                vec![
+                    ("FIRST_RESIDUALS", self.first_residuals),
+                    ("RESIDUALS", self.residuals),
+                ],
+            ),
+        ];
+        let mut flags = vec![];
+        for (group, default, components) in groups {
+            let code = components[0].1;
+            if components.iter().all(|&(_, c)| c == code) {
+                if code != default {
+                    flags.push((group, code));
+                }
+            } else {
+                flags.extend(components);
+            }
         }
-        if cflags {
-            s.pop();
+        s.push_str("compressionflags=");
+        for (i, (name, code)) in flags.into_iter().enumerate() {
+            let code_str = Self::code_to_str(code, k)
+                .ok_or_else(|| anyhow!("Code {:?} is not supported", code))?;
+            if i != 0 {
+                s.push('|');
+            }
+            s.push_str(&format!("{}_{}", name, code_str));
         }
         s.push('\n');
-        // check that if a k is specified, it is the same for all codes
-        let mut k = None;
-        macro_rules! check_and_set_k {
-            ($code:expr) => {
-                match $code {
-                    Codes::Zeta { k: new_k } => {
-                        if let Some(old_k) = k {
-                            ensure!(old_k == new_k, "Only one value of k is supported")
-                        }
-                        k = Some(new_k)
-                    }
-                    _ => {}
-                }
-            };
+        s.push_str(&format!("zetak={}\n", k));
+        if self.huffman_coding {
+            s.push_str("huffmancoding=true\n");
         }
-        check_and_set_k!(self.outdegrees);
-        check_and_set_k!(self.references);
-        check_and_set_k!(self.blocks);
-        check_and_set_k!(self.intervals);
-        check_and_set_k!(self.residuals);
-        // if no k was specified, use the default one (3)
-        s.push_str(&format!("zetak={}\n", k.unwrap_or(3)));
         Ok(s)
     }
 
@@ -217,26 +381,41 @@ impl CompFlags {
 
         let mut cf = CompFlags::default();
         let mut k = 3;
-        if let Some(spec_k) = map.get("zeta_k") {
-            let spec_k = spec_k.parse::<usize>()?;
-            if !(1..=7).contains(&spec_k) {
-                bail!("Only ζ₁-ζ₇ are supported");
-            }
-            k = spec_k;
+        if let Some(spec_k) = map.get("zetak") {
+            k = spec_k.parse::<usize>()?;
+            ensure!(k > 0, "The ζ parameter must be positive");
         }
+        // residuals are ζ-coded unless otherwise specified
+        cf.set_residuals(Codes::Zeta { k });
         if let Some(comp_flags) = map.get("compressionflags") {
             if !comp_flags.is_empty() {
+                let mut flags = vec![];
                 for flag in comp_flags.split('|') {
-                    let s: Vec<_> = flag.split('_').collect();
-                    // FIXME: this is a hack to avoid having to implement
-                    // FromStr for Code
-                    let code = CompFlags::code_from_str(s[1], k).unwrap();
-                    match s[0] {
+                    let (component, code) = flag
+                        .rsplit_once('_')
+                        .ok_or_else(|| anyhow!("Malformed compression flag {}", flag))?;
+                    let code = CompFlags::code_from_str(code, k).ok_or_else(|| {
+                        anyhow!("Unknown or unsupported code in compression flag {}", flag)
+                    })?;
+                    flags.push((component, code, flag));
+                }
+                // group flags are applied first, so that flags for single
+                // components override them
+                flags.sort_by_key(|&(component, _, _)| {
+                    !matches!(component, "BLOCKS" | "INTERVALS" | "RESIDUALS")
+                });
+                for (component, code, flag) in flags {
+                    match component {
                         "OUTDEGREES" => cf.outdegrees = code,
                         "REFERENCES" => cf.references = code,
-                        "BLOCKS" => cf.blocks = code,
-                        "INTERVALS" => cf.intervals = code,
-                        "RESIDUALS" => cf.residuals = code,
+                        "BLOCKS" => cf.set_blocks(code),
+                        "INTERVALS" => cf.set_intervals(code),
+                        "RESIDUALS" => cf.set_residuals(code),
+                        "BLOCK_COUNTS" => cf.block_counts = code,
+                        "INTERVAL_COUNTS" => cf.interval_counts = code,
+                        "INTERVAL_STARTS" => cf.interval_starts = code,
+                        "INTERVAL_LENS" => cf.interval_lens = code,
+                        "FIRST_RESIDUALS" => cf.first_residuals = code,
                         "OFFSETS" => {
                             ensure!(code == Codes::Gamma, "Only γ code is supported for offsets")
                         }
@@ -251,6 +430,32 @@ impl CompFlags {
         if let Some(min_interval_length) = map.get("minintervallength") {
             cf.min_interval_length = min_interval_length.parse()?;
         }
+        if let Some(max_ref_count) = map.get("maxrefcount") {
+            // -1 means an unbounded reference chain, as in the CLI
+            cf.max_ref_count = match max_ref_count.as_str() {
+                "-1" => usize::MAX,
+                _ => max_ref_count.parse()?,
+            };
+        }
+        if let Some(huffman_coding) = map.get("huffmancoding") {
+            cf.huffman_coding = huffman_coding.parse()?;
+        }
+        // residuals might use an unsupported ζ code given by zetak
+        for code in cf.codes() {
+            ensure!(
+                Self::is_supported(code),
+                "Code {:?} is not supported by dynamic dispatch",
+                code
+            );
+        }
+        let rust_only = match map.get("rustonly") {
+            Some(rust_only) => rust_only.parse()?,
+            None => false,
+        };
+        ensure!(
+            rust_only || cf.is_java_compatible(),
+            "The graph uses codes not supported by the Java version, but the rustonly property is not set"
+        );
         Ok(cf)
     }
 }
@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::impls::AnyBvComp;
+use super::labeled::store_ef;
+use crate::prelude::*;
+use anyhow::{Context, Result};
+use dsi_bitstream::prelude::*;
+use dsi_progress_logger::prelude::*;
+use lender::prelude::*;
+use rayon::ThreadPool;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+impl BvComp<()> {
+    /// Performs a statistics pass on a [`NodeLabelsLender`] and returns
+    /// Huffman models for the values that would be written.
+    ///
+    /// The lender is compressed using `compression_flags` without writing
+    /// anything, gathering statistics on the values written with an
+    /// [`HuffmanStatsEncoder`]; references are selected using the codes of
+    /// `compression_flags`.
+    pub fn huffman_models<L>(iter: L, compression_flags: CompFlags) -> Result<HuffmanModels>
+    where
+        L: IntoLender,
+        L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
+    {
+        let mut pl = ProgressLogger::default();
+        pl.display_memory(true).item_name("node");
+        pl.start("Gathering statistics...");
+
+        let mut encoder = HuffmanStatsEncoder::new(&compression_flags)?;
+        let mut bvcomp = AnyBvComp::new(&mut encoder, &compression_flags, 0);
+        let mut lens = Vec::new();
+        for_! ( (_node_id, successors) in iter {
+            bvcomp.push(successors, &mut lens).context("Could not push successors")?;
+            lens.clear();
+            pl.light_update();
+        });
+        bvcomp.flush(&mut lens).context("Could not flush bvcomp")?;
+        pl.done();
+
+        Ok(encoder.stats.models())
+    }
+
+    /// Performs in parallel a statistics pass on a graph and returns Huffman
+    /// models for the values that would be written.
+    ///
+    /// This method is the parallel version of
+    /// [`huffman_models`](Self::huffman_models): the graph is split in as
+    /// many lenders as there are threads in `threads`, and the statistics
+    /// gathered on each lender are merged.
+    pub fn parallel_huffman_models(
+        graph: &(impl SequentialGraph + SplitLabeling),
+        compression_flags: CompFlags,
+        threads: &ThreadPool,
+    ) -> Result<HuffmanModels> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let stats = threads.in_place_scope(|s| {
+            let cp_flags = &compression_flags;
+
+            for mut thread_lender in graph.split_iter(threads.current_num_threads()) {
+                let tx = tx.clone();
+                s.spawn(move |_| {
+                    let gather = || -> Result<HuffmanStats> {
+                        let mut encoder = HuffmanStatsEncoder::new(cp_flags)?;
+                        let Some((node_id, successors)) = thread_lender.next() else {
+                            return Ok(encoder.stats);
+                        };
+                        let mut bvcomp = AnyBvComp::new(&mut encoder, cp_flags, node_id);
+                        let mut lens = Vec::new();
+                        bvcomp.push(successors, &mut lens)?;
+                        for_! ( (_, succ) in thread_lender {
+                            bvcomp.push(succ.into_iter(), &mut lens)?;
+                            lens.clear();
+                        });
+                        bvcomp.flush(&mut lens)?;
+                        Ok(encoder.stats)
+                    };
+                    tx.send(gather()).unwrap()
+                });
+            }
+
+            drop(tx);
+            rx.iter().sum::<Result<HuffmanStats>>()
+        })?;
+
+        Ok(stats.models())
+    }
+
+    /// Compresses a graph in parallel Huffman coding its components with the
+    /// given models, and returns the length in bits of the graph bitstream.
+    ///
+    /// The models, which are usually computed by
+    /// [`parallel_huffman_models`](Self::parallel_huffman_models) on the same
+    /// graph, are stored in a `.models` file, and the `.properties` file
+    /// records that the graph is [Huffman-coded](CompFlags::huffman_coding),
+    /// so the result can be loaded using the [`Huffman`] dispatch. References
+    /// are selected using the lengths of the values under the models.
+    ///
+    /// Besides the `.offsets` file, this method writes its Elias–Fano
+    /// representation in the `.ef` file, as the offsets of a Huffman-coded
+    /// graph cannot be rebuilt by decoding it with instantaneous codes.
+    ///
+    /// Huffman-coded graphs are usually smaller than graphs compressed with
+    /// instantaneous codes, but slower to decode, and they cannot be read by
+    /// the Java version.
+    pub fn parallel_huffman<E: Endianness>(
+        basename: impl AsRef<Path> + Send + Sync,
+        graph: &(impl SequentialGraph + SplitLabeling),
+        compression_flags: CompFlags,
+        models: &HuffmanModels,
+        threads: &ThreadPool,
+        tmp_dir: impl AsRef<Path>,
+    ) -> Result<u64>
+    where
+        BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: BitWrite<E>,
+        <BufBitWriter<E, WordAdapter<usize, BufWriter<File>>> as BitWrite<E>>::Error: Send + Sync,
+        BufBitReader<E, WordAdapter<u32, BufReader<File>>>: BitRead<E>,
+    {
+        let basename = basename.as_ref();
+        models.store(basename.with_extension(MODELS_EXTENSION))?;
+        let bitstream_len = Self::parallel_iter_with::<E, _, _>(
+            basename,
+            graph.split_iter(threads.current_num_threads()).into_iter(),
+            graph.num_nodes(),
+            CompFlags {
+                huffman_coding: true,
+                ..compression_flags
+            },
+            threads,
+            tmp_dir,
+            |writer| Ok(<HuffmanEncoder<E, _>>::new(writer, models)),
+        )?;
+
+        log::info!("Building the Elias–Fano representation of the offsets");
+        store_ef(
+            &basename.with_extension(OFFSETS_EXTENSION),
+            graph.num_nodes(),
+            bitstream_len,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+        Ok(bitstream_len)
+    }
+
+    /// Compresses a graph in parallel Huffman coding its components, as in
+    /// [`parallel_huffman`](Self::parallel_huffman), using the endianness
+    /// specified by the string `endianness`.
+    pub fn parallel_huffman_endianness(
+        basename: impl AsRef<Path> + Send + Sync,
+        graph: &(impl SequentialGraph + SplitLabeling),
+        compression_flags: CompFlags,
+        models: &HuffmanModels,
+        threads: &ThreadPool,
+        tmp_dir: impl AsRef<Path>,
+        endianness: &str,
+    ) -> Result<u64> {
+        match endianness {
+            #[cfg(any(
+                feature = "be_bins",
+                not(any(feature = "be_bins", feature = "le_bins"))
+            ))]
+            BE::NAME => Self::parallel_huffman::<BigEndian>(
+                basename,
+                graph,
+                compression_flags,
+                models,
+                threads,
+                tmp_dir,
+            ),
+            #[cfg(any(
+                feature = "le_bins",
+                not(any(feature = "be_bins", feature = "le_bins"))
+            ))]
+            LE::NAME => Self::parallel_huffman::<LittleEndian>(
+                basename,
+                graph,
+                compression_flags,
+                models,
+                threads,
+                tmp_dir,
+            ),
+            x => anyhow::bail!("Unknown endianness {}", x),
+        }
+    }
+}
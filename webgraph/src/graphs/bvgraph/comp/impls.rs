@@ -80,6 +80,7 @@ struct Job {
     chunk_offsets_path: PathBuf,
     offsets_written_bits: u64,
     num_arcs: u64,
+    checksums: Checksums,
 }
 
 impl JobId for Job {
@@ -88,6 +89,77 @@ impl JobId for Job {
     }
 }
 
+/// A compressor selecting references as prescribed by
+/// [`CompFlags::ref_selection`].
+///
+/// Since [`BvCompZ`] writes nodes a chunk at a time, the lengths in bits of
+/// the nodes written are appended to a vector passed by the caller.
+pub(super) enum AnyBvComp<E> {
+    Greedy(BvComp<E>),
+    Optimal(BvCompZ<E>),
+}
+
+impl<E: EncodeAndEstimate> AnyBvComp<E> {
+    /// Creates a new compressor using the given compression flags.
+    pub(super) fn new(encoder: E, compression_flags: &CompFlags, start_node: usize) -> Self {
+        match compression_flags.ref_selection {
+            RefSelection::Greedy => Self::Greedy(BvComp::new(
+                encoder,
+                compression_flags.compression_window,
+                compression_flags.max_ref_count,
+                compression_flags.min_interval_length,
+                start_node,
+            )),
+            RefSelection::Optimal { chunk_size } => Self::Optimal(BvCompZ::new(
+                encoder,
+                compression_flags.compression_window,
+                chunk_size,
+                compression_flags.max_ref_count,
+                compression_flags.min_interval_length,
+                start_node,
+            )),
+        }
+    }
+
+    /// Pushes a new node, appending to `lens` the lengths in bits of the
+    /// nodes written.
+    pub(super) fn push<I: IntoIterator<Item = usize>>(
+        &mut self,
+        succ_iter: I,
+        lens: &mut Vec<u64>,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::Greedy(bvcomp) => lens.push(bvcomp.push(succ_iter)?),
+            Self::Optimal(bvcompz) => {
+                bvcompz.push(succ_iter)?;
+                lens.extend_from_slice(bvcompz.written_lens());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of arcs compressed so far.
+    pub(super) fn arcs(&self) -> u64 {
+        match self {
+            Self::Greedy(bvcomp) => bvcomp.arcs,
+            Self::Optimal(bvcompz) => bvcompz.arcs,
+        }
+    }
+
+    /// Writes the pending nodes, appending their lengths in bits to `lens`,
+    /// and returns the number of bits written by flushing the encoder.
+    pub(super) fn flush(self, lens: &mut Vec<u64>) -> anyhow::Result<u64> {
+        match self {
+            Self::Greedy(bvcomp) => Ok(bvcomp.flush()? as u64),
+            Self::Optimal(mut bvcompz) => {
+                bvcompz.write_pending()?;
+                lens.extend_from_slice(bvcompz.written_lens());
+                bvcompz.flush()
+            }
+        }
+    }
+}
+
 impl BvComp<()> {
     /// Compresses s [`NodeLabelsLender`] and returns the length in bits of the
     /// graph bitstream.
@@ -104,28 +176,54 @@ impl BvComp<()> {
         L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
         BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: CodesWrite<E>,
     {
+        ensure!(
+            !compression_flags.huffman_coding,
+            "Huffman-coded graphs must be compressed using BvComp::parallel_huffman"
+        );
         let basename = basename.as_ref();
-        let graph_path = basename.with_extension(GRAPH_EXTENSION);
-
-        // Compress the graph
-        let bit_write = <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(BufWriter::new(
-            File::create(&graph_path)
-                .with_context(|| format!("Could not create {}", graph_path.display()))?,
-        )));
-
-        let comp_flags = CompFlags {
-            ..Default::default()
-        };
+        let codes_writer =
+            DynCodesEncoder::new(Self::create_bitstream::<E>(basename)?, &compression_flags)?;
+        Self::compress::<E, _, _>(
+            basename,
+            iter,
+            codes_writer,
+            compression_flags,
+            build_offsets,
+            num_nodes,
+        )
+    }
 
-        let codes_writer = DynCodesEncoder::new(bit_write, &comp_flags)?;
+    /// Creates the graph bitstream of the given basename.
+    pub(super) fn create_bitstream<E: Endianness>(
+        basename: &Path,
+    ) -> Result<BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>> {
+        let graph_path = basename.with_extension(GRAPH_EXTENSION);
+        Ok(<BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(
+            BufWriter::new(
+                File::create(&graph_path)
+                    .with_context(|| format!("Could not create {}", graph_path.display()))?,
+            ),
+        )))
+    }
 
-        let mut bvcomp = BvComp::new(
-            codes_writer,
-            compression_flags.compression_window,
-            compression_flags.max_ref_count,
-            compression_flags.min_interval_length,
-            0,
-        );
+    /// Compresses a [`NodeLabelsLender`] using the given encoder, writing
+    /// the offsets (if `build_offsets` is true) and the `.properties` file,
+    /// and returns the length in bits of the graph bitstream.
+    pub(super) fn compress<E, L, C>(
+        basename: &Path,
+        iter: L,
+        encoder: C,
+        compression_flags: CompFlags,
+        build_offsets: bool,
+        num_nodes: Option<usize>,
+    ) -> Result<u64>
+    where
+        E: Endianness,
+        L: IntoLender,
+        L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
+        C: EncodeAndEstimate,
+    {
+        let mut bvcomp = AnyBvComp::new(encoder, &compression_flags, 0);
 
         let mut pl = ProgressLogger::default();
         pl.display_memory(true)
@@ -134,33 +232,46 @@ impl BvComp<()> {
         pl.start("Compressing successors...");
         let mut bitstream_len = 0;
 
-        let mut real_num_nodes = 0;
-        if build_offsets {
+        let mut offsets_writer = if build_offsets {
             let offsets_path = basename.with_extension(OFFSETS_EXTENSION);
             let file = std::fs::File::create(&offsets_path)
                 .with_context(|| format!("Could not create {}", offsets_path.display()))?;
             // create a bit writer on the file
-            let mut writer = <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(
+            let mut writer = <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(
                 BufWriter::with_capacity(1 << 20, file),
             ));
 
             writer
                 .write_gamma(0)
                 .context("Could not write initial delta")?;
-            for_! ( (_node_id, successors) in iter {
-                let delta = bvcomp.push(successors).context("Could not push successors")?;
-                bitstream_len += delta;
-                writer.write_gamma(delta).context("Could not write delta")?;
-                pl.update();
-                real_num_nodes += 1;
-            });
+            Some(writer)
         } else {
-            for_! ( (_node_id, successors) in iter {
-                bitstream_len += bvcomp.push(successors).context("Could not push successors")?;
-                pl.update();
-                real_num_nodes += 1;
-            });
-        }
+            None
+        };
+
+        let mut real_num_nodes = 0;
+        let mut checksums = Checksums::default();
+        let mut lens = Vec::new();
+        let mut write_lens = |lens: &mut Vec<u64>| -> Result<u64> {
+            let mut written_bits = 0;
+            for delta in lens.drain(..) {
+                written_bits += delta;
+                if let Some(writer) = &mut offsets_writer {
+                    writer.write_gamma(delta).context("Could not write delta")?;
+                }
+            }
+            Ok(written_bits)
+        };
+        for_! ( (node_id, successors) in iter {
+            let mut hasher = NodeHasher::new(node_id);
+            bvcomp
+                .push(successors.into_iter().inspect(|&s| hasher.push(s)), &mut lens)
+                .context("Could not push successors")?;
+            checksums.add(node_id, hasher.finish());
+            bitstream_len += write_lens(&mut lens)?;
+            pl.update();
+            real_num_nodes += 1;
+        });
         pl.done();
 
         if let Some(num_nodes) = num_nodes {
@@ -172,13 +283,18 @@ impl BvComp<()> {
                 );
             }
         }
-        let num_arcs = bvcomp.arcs;
-        bitstream_len += bvcomp.flush().context("Could not flush bvcomp")? as u64;
+        let num_arcs = bvcomp.arcs();
+        bitstream_len += bvcomp.flush(&mut lens).context("Could not flush bvcomp")?;
+        bitstream_len += write_lens(&mut lens)?;
+        if let Some(mut writer) = offsets_writer {
+            writer.flush().context("Could not flush offsets")?;
+        }
 
         log::info!("Writing the .properties file");
-        let properties = compression_flags
-            .to_properties::<BE>(real_num_nodes, num_arcs, bitstream_len)
+        let mut properties = compression_flags
+            .to_properties::<E>(real_num_nodes, num_arcs, bitstream_len)
             .context("Could not serialize properties")?;
+        properties.push_str(&checksums.to_properties());
         let properties_path = basename.with_extension(PROPERTIES_EXTENSION);
         std::fs::write(&properties_path, properties)
             .with_context(|| format!("Could not write {}", properties_path.display()))?;
@@ -186,6 +302,110 @@ impl BvComp<()> {
         Ok(bitstream_len)
     }
 
+    /// Performs a statistics pass on a [`NodeLabelsLender`] and returns a
+    /// copy of the given compression flags with the codes that minimize the
+    /// size of the resulting bitstream.
+    ///
+    /// The lender is compressed using `compression_flags` without writing
+    /// anything, gathering statistics on the values written with a
+    /// [`StatsEncoder`]; the codes are then chosen by
+    /// [`DecoderStats::best_codes`]. The values of the remaining flags are
+    /// unchanged.
+    ///
+    /// Compressing again with the returned flags (e.g., using
+    /// [`single_thread`](Self::single_thread)) records the chosen codes in
+    /// the `.properties` file, so the result can be loaded transparently
+    /// using [dynamic dispatch](crate::graphs::bvgraph::Dynamic).
+    pub fn best_codes<L>(iter: L, compression_flags: CompFlags) -> Result<CompFlags>
+    where
+        L: IntoLender,
+        L::Lender: for<'next> NodeLabelsLender<'next, Label = usize>,
+    {
+        let mut pl = ProgressLogger::default();
+        pl.display_memory(true).item_name("node");
+        pl.start("Gathering statistics...");
+
+        let mut encoder = StatsEncoder::new(&compression_flags)?;
+        let mut bvcomp = AnyBvComp::new(&mut encoder, &compression_flags, 0);
+        let mut lens = Vec::new();
+        for_! ( (_node_id, successors) in iter {
+            bvcomp.push(successors, &mut lens).context("Could not push successors")?;
+            lens.clear();
+            pl.light_update();
+        });
+        bvcomp.flush(&mut lens).context("Could not flush bvcomp")?;
+        pl.done();
+
+        Ok(Self::log_best_codes(&encoder.stats, compression_flags))
+    }
+
+    /// Performs in parallel a statistics pass on a graph and returns a copy
+    /// of the given compression flags with the codes that minimize the size
+    /// of the resulting bitstream.
+    ///
+    /// This method is the parallel version of
+    /// [`best_codes`](Self::best_codes). Note that, as in
+    /// [`parallel_graph`](Self::parallel_graph), the graph is split in as
+    /// many lenders as there are threads in the pool, so the statistics are
+    /// the same that would be gathered by compressing with the same pool.
+    pub fn parallel_best_codes(
+        graph: &(impl SequentialGraph + SplitLabeling),
+        compression_flags: CompFlags,
+        threads: &ThreadPool,
+    ) -> Result<CompFlags> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let stats = threads.in_place_scope(|s| {
+            let cp_flags = &compression_flags;
+
+            for mut thread_lender in graph.split_iter(threads.current_num_threads()) {
+                let tx = tx.clone();
+                s.spawn(move |_| {
+                    let gather = || -> Result<DecoderStats> {
+                        let mut encoder = StatsEncoder::new(cp_flags)?;
+                        let Some((node_id, successors)) = thread_lender.next() else {
+                            return Ok(encoder.stats);
+                        };
+                        let mut bvcomp = AnyBvComp::new(&mut encoder, cp_flags, node_id);
+                        let mut lens = Vec::new();
+                        bvcomp.push(successors, &mut lens)?;
+                        for_! ( (_, succ) in thread_lender {
+                            bvcomp.push(succ.into_iter(), &mut lens)?;
+                            lens.clear();
+                        });
+                        bvcomp.flush(&mut lens)?;
+                        Ok(encoder.stats)
+                    };
+                    tx.send(gather()).unwrap()
+                });
+            }
+
+            drop(tx);
+            rx.iter().sum::<Result<DecoderStats>>()
+        })?;
+
+        Ok(Self::log_best_codes(&stats, compression_flags))
+    }
+
+    /// Chooses the best codes given the statistics and logs the result.
+    fn log_best_codes(stats: &DecoderStats, compression_flags: CompFlags) -> CompFlags {
+        let (best, best_len) = stats.best_codes(&compression_flags);
+        log::info!(
+            "Best codes: outdegrees {}, references {}, block counts {}, blocks {}, interval counts {}, interval starts {}, interval lengths {}, first residuals {}, residuals {} ({} bits)",
+            best.outdegrees,
+            best.references,
+            best.block_counts,
+            best.blocks,
+            best.interval_counts,
+            best.interval_starts,
+            best.interval_lens,
+            best.first_residuals,
+            best.residuals,
+            best_len
+        );
+        best
+    }
+
     /// A wrapper over [`parallel_graph`](Self::parallel_graph) that takes the
     /// endianness as a string.
     ///
@@ -279,6 +499,42 @@ impl BvComp<()> {
     where
         BufBitWriter<E, WordAdapter<usize, BufWriter<std::fs::File>>>: CodesWrite<E>,
         BufBitReader<E, WordAdapter<u32, BufReader<std::fs::File>>>: BitRead<E>,
+    {
+        ensure!(
+            !compression_flags.huffman_coding,
+            "Huffman-coded graphs must be compressed using BvComp::parallel_huffman"
+        );
+        Self::parallel_iter_with(
+            basename,
+            iter,
+            num_nodes,
+            compression_flags,
+            threads,
+            tmp_dir,
+            |writer| <DynCodesEncoder<E, _>>::new(writer, &compression_flags),
+        )
+    }
+
+    /// Compresses multiple [`NodeLabelsLender`] in parallel using the
+    /// encoders returned by `encoder`, which is called once per lender on
+    /// the bit writer of its temporary bitstream, and returns the length in
+    /// bits of the graph bitstream.
+    pub(super) fn parallel_iter_with<
+        E: Endianness,
+        L: Lender + for<'next> NodeLabelsLender<'next, Label = usize> + Send,
+        C: EncodeAndEstimate,
+    >(
+        basename: impl AsRef<Path> + Send + Sync,
+        iter: impl Iterator<Item = L>,
+        num_nodes: usize,
+        compression_flags: CompFlags,
+        threads: &ThreadPool,
+        tmp_dir: impl AsRef<Path>,
+        encoder: impl Fn(BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>) -> Result<C> + Sync,
+    ) -> Result<u64>
+    where
+        BufBitWriter<E, WordAdapter<usize, BufWriter<std::fs::File>>>: BitWrite<E>,
+        BufBitReader<E, WordAdapter<u32, BufReader<std::fs::File>>>: BitRead<E>,
     {
         let tmp_dir = tmp_dir.as_ref();
         let basename = basename.as_ref();
@@ -292,6 +548,7 @@ impl BvComp<()> {
 
         threads.in_place_scope(|s| {
             let cp_flags = &compression_flags;
+            let encoder = &encoder;
 
             for (thread_id, mut thread_lender) in iter.enumerate() {
                 let tmp_path = thread_path(thread_id);
@@ -304,8 +561,10 @@ impl BvComp<()> {
                     let first_node;
                     let mut bvcomp;
                     let mut offsets_writer;
-                    let mut written_bits;
-                    let mut offsets_written_bits;
+                    let mut written_bits = 0;
+                    let mut offsets_written_bits = 0;
+                    let mut checksums = Checksums::default();
+                    let mut lens = Vec::new();
 
                     match thread_lender.next() {
                         None => return,
@@ -319,30 +578,31 @@ impl BvComp<()> {
                             let writer = <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(
                                 BufWriter::new(File::create(&chunk_graph_path).unwrap()),
                             ));
-                            let codes_encoder = <DynCodesEncoder<E, _>>::new(writer, cp_flags).unwrap();
-
-                            bvcomp = BvComp::new(
-                                codes_encoder,
-                                cp_flags.compression_window,
-                                cp_flags.max_ref_count,
-                                cp_flags.min_interval_length,
-                                node_id,
-                            );
-                            written_bits = bvcomp.push(successors).unwrap();
-                            offsets_written_bits = offsets_writer.write_gamma(written_bits).unwrap() as u64;
+                            bvcomp = AnyBvComp::new(encoder(writer).unwrap(), cp_flags, node_id);
+                            let mut hasher = NodeHasher::new(node_id);
+                            bvcomp.push(successors.into_iter().inspect(|&s| hasher.push(s)), &mut lens).unwrap();
+                            checksums.add(node_id, hasher.finish());
                         }
                     };
 
                     let mut last_node = first_node;
                     let iter_nodes = thread_lender.inspect(|(x, _)| last_node = *x);
-                    for_! ( (_, succ) in iter_nodes {
-                        let node_bits = bvcomp.push(succ.into_iter()).unwrap();
-                        written_bits += node_bits;
-                        offsets_written_bits += offsets_writer.write_gamma(node_bits).unwrap() as u64;
+                    for_! ( (node_id, succ) in iter_nodes {
+                        for node_bits in lens.drain(..) {
+                            written_bits += node_bits;
+                            offsets_written_bits += offsets_writer.write_gamma(node_bits).unwrap() as u64;
+                        }
+                        let mut hasher = NodeHasher::new(node_id);
+                        bvcomp.push(succ.into_iter().inspect(|&s| hasher.push(s)), &mut lens).unwrap();
+                        checksums.add(node_id, hasher.finish());
                     });
 
-                    let num_arcs = bvcomp.arcs;
-                    bvcomp.flush().unwrap();
+                    let num_arcs = bvcomp.arcs();
+                    bvcomp.flush(&mut lens).unwrap();
+                    for node_bits in lens.drain(..) {
+                        written_bits += node_bits;
+                        offsets_written_bits += offsets_writer.write_gamma(node_bits).unwrap() as u64;
+                    }
                     offsets_writer.flush().unwrap();
 
                     log::info!(
@@ -360,6 +620,7 @@ impl BvComp<()> {
                         chunk_offsets_path,
                         offsets_written_bits,
                         num_arcs,
+                        checksums,
                     })
                     .unwrap()
                 });
@@ -381,6 +642,7 @@ impl BvComp<()> {
             let mut total_written_bits: u64 = 0;
             let mut total_offsets_written_bits: u64 = 0;
             let mut total_arcs: u64 = 0;
+            let mut total_checksums = Checksums::default();
 
             let mut next_node = 0;
             // glue together the bitstreams as they finish, this allows us to do
@@ -394,6 +656,7 @@ impl BvComp<()> {
                 chunk_offsets_path,
                 offsets_written_bits,
                 num_arcs,
+                checksums,
             } in TaskQueue::new(rx.iter())
             {
                 ensure!(
@@ -406,6 +669,7 @@ impl BvComp<()> {
 
                 next_node = last_node + 1;
                 total_arcs += num_arcs;
+                total_checksums.merge(&checksums);
                 log::info!(
                     "Copying {} [{}..{}) bits from {} to {}",
                     written_bits,
@@ -462,9 +726,10 @@ impl BvComp<()> {
             offsets_writer.flush()?;
 
             log::info!("Writing the .properties file");
-            let properties = compression_flags
-                .to_properties::<BE>(num_nodes, total_arcs, total_written_bits)
+            let mut properties = compression_flags
+                .to_properties::<E>(num_nodes, total_arcs, total_written_bits)
                 .context("Could not serialize properties")?;
+            properties.push_str(&total_checksums.to_properties());
             let properties_path = basename.with_extension(PROPERTIES_EXTENSION);
             std::fs::write(&properties_path, properties).with_context(|| {
                 format!(
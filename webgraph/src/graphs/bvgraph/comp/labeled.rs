@@ -0,0 +1,428 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::impls::AnyBvComp;
+use crate::prelude::*;
+use anyhow::{Context, Result};
+use dsi_bitstream::prelude::*;
+use dsi_progress_logger::prelude::*;
+use epserde::ser::Serialize;
+use lender::prelude::*;
+use rayon::ThreadPool;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Mutex;
+use sux::dict::EliasFanoBuilder;
+use sux::rank_sel::SelectAdaptConst;
+
+/// The Java class of labeled graphs stored as a bitstream of labels.
+pub const BITSTREAM_LABELED_GRAPH_CLASS: &str =
+    "it.unimi.dsi.webgraph.labelling.BitStreamArcLabelledImmutableGraph";
+
+/// A type alias for the bit writer passed to the label serializer of
+/// [`BvComp::single_thread_labeled`].
+pub type LabelsBitWriter<E> = BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>;
+
+impl BvComp<()> {
+    /// Compresses a [labeled sequential graph](LabeledSequentialGraph) and
+    /// returns the lengths in bits of the graph and label bitstreams.
+    ///
+    /// The underlying graph is stored as a standard BvGraph with basename
+    /// `basename`, together with its offsets and their Elias–Fano
+    /// representation. Labels are serialized in arc order using `serializer`
+    /// to a `.labels` file with basename `labels_basename`, and their
+    /// γ-coded bit offsets are written to a `.labeloffsets` file. A
+    /// `.properties` file for the labeling is written, too, and an
+    /// Elias–Fano representation of the label offsets is stored in a `.ef`
+    /// file, so the result can be loaded using [`load_labeled`].
+    ///
+    /// The layout (and, if `E` is [`BigEndian`], the bitstreams) is compatible
+    /// with the Java class `BitStreamArcLabelledImmutableGraph`, provided that
+    /// `label_spec` is a valid Java label specification for the serialized
+    /// labels. The two basenames must be distinct, as both sets of files have
+    /// a `.properties` and a `.ef` file.
+    ///
+    /// Note that the bit counts returned by the serializer are used to compute
+    /// label offsets, so they must be exact.
+    pub fn single_thread_labeled<E, G, S>(
+        basename: impl AsRef<Path>,
+        labels_basename: impl AsRef<Path>,
+        graph: &G,
+        serializer: S,
+        label_spec: &str,
+        compression_flags: CompFlags,
+    ) -> Result<(u64, u64)>
+    where
+        E: Endianness,
+        G: LabeledSequentialGraph<S::SerType>,
+        S: BitSerializer<E, LabelsBitWriter<E>>,
+        LabelsBitWriter<E>: CodesWrite<E>,
+    {
+        let basename = basename.as_ref();
+        let labels_basename = labels_basename.as_ref();
+        anyhow::ensure!(
+            basename != labels_basename,
+            "The graph and the labels must have different basenames"
+        );
+
+        let graph_path = basename.with_extension(GRAPH_EXTENSION);
+        let offsets_path = basename.with_extension(OFFSETS_EXTENSION);
+        let labels_path = labels_basename.with_extension(LABELS_EXTENSION);
+        let label_offsets_path = labels_basename.with_extension(LABELOFFSETS_EXTENSION);
+
+        let create = |path: &Path| -> Result<BufWriter<File>> {
+            Ok(BufWriter::new(File::create(path).with_context(|| {
+                format!("Could not create {}", path.display())
+            })?))
+        };
+
+        let codes_writer = DynCodesEncoder::new(
+            <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(create(&graph_path)?)),
+            &compression_flags,
+        )?;
+        let mut bvcomp = AnyBvComp::new(codes_writer, &compression_flags, 0);
+        let mut offsets_writer =
+            <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(create(&offsets_path)?));
+        let mut labels_writer: LabelsBitWriter<E> =
+            <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(create(&labels_path)?));
+        let mut label_offsets_writer =
+            <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(create(&label_offsets_path)?));
+
+        offsets_writer
+            .write_gamma(0)
+            .context("Could not write initial offset")?;
+        label_offsets_writer
+            .write_gamma(0)
+            .context("Could not write initial label offset")?;
+
+        let mut pl = ProgressLogger::default();
+        pl.display_memory(true)
+            .item_name("node")
+            .expected_updates(Some(graph.num_nodes()));
+        pl.start("Compressing successors and labels...");
+
+        let mut graph_len = 0;
+        let mut labels_len = 0;
+        let mut num_nodes = 0;
+        let mut succs = Vec::new();
+        let mut labels = Vec::new();
+        let mut lens = Vec::new();
+        let mut checksums = Checksums::default();
+        for_! ( (node_id, successors) in graph.iter() {
+            succs.clear();
+            labels.clear();
+            for (succ, label) in successors {
+                succs.push(succ);
+                labels.push(label);
+            }
+
+            bvcomp
+                .push(succs.iter().copied(), &mut lens)
+                .context("Could not push successors")?;
+            checksums.add_successors(node_id, succs.iter().copied());
+            for delta in lens.drain(..) {
+                graph_len += delta;
+                offsets_writer.write_gamma(delta).context("Could not write offset")?;
+            }
+
+            let mut label_delta = 0;
+            for label in &labels {
+                label_delta += serializer
+                    .serialize(label, &mut labels_writer)
+                    .context("Could not serialize label")?
+                    as u64;
+            }
+            labels_len += label_delta;
+            label_offsets_writer
+                .write_gamma(label_delta)
+                .context("Could not write label offset")?;
+
+            num_nodes += 1;
+            pl.light_update();
+        });
+        pl.done();
+
+        let num_arcs = bvcomp.arcs();
+        graph_len += bvcomp.flush(&mut lens).context("Could not flush bvcomp")?;
+        for delta in lens.drain(..) {
+            graph_len += delta;
+            offsets_writer
+                .write_gamma(delta)
+                .context("Could not write offset")?;
+        }
+        offsets_writer.flush().context("Could not flush offsets")?;
+        labels_writer.flush().context("Could not flush labels")?;
+        label_offsets_writer
+            .flush()
+            .context("Could not flush label offsets")?;
+
+        log::info!("Writing the .properties files");
+        let mut properties = compression_flags
+            .to_properties::<E>(num_nodes, num_arcs, graph_len)
+            .context("Could not serialize properties")?;
+        properties.push_str(&checksums.to_properties());
+        let properties_path = basename.with_extension(PROPERTIES_EXTENSION);
+        std::fs::write(&properties_path, properties)
+            .with_context(|| format!("Could not write {}", properties_path.display()))?;
+
+        let properties = labels_properties::<E>(basename, labels_basename, label_spec)?;
+        let properties_path = labels_basename.with_extension(PROPERTIES_EXTENSION);
+        std::fs::write(&properties_path, properties)
+            .with_context(|| format!("Could not write {}", properties_path.display()))?;
+
+        log::info!("Building the Elias–Fano representations of the offsets");
+        store_ef(
+            &offsets_path,
+            num_nodes,
+            graph_len,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+        store_ef(
+            &label_offsets_path,
+            num_nodes,
+            labels_len,
+            labels_basename.with_extension(EF_EXTENSION),
+        )?;
+
+        log::info!(
+            "Compressed {} arcs into {} bits for {:.4} bits/arc, labels take {} bits for {:.4} bits/arc",
+            num_arcs,
+            graph_len,
+            graph_len as f64 / num_arcs as f64,
+            labels_len,
+            labels_len as f64 / num_arcs as f64
+        );
+
+        Ok((graph_len, labels_len))
+    }
+
+    /// Compresses in parallel a [labeled sequential
+    /// graph](LabeledSequentialGraph) that can be [split](SplitLabeling),
+    /// writing the same files as
+    /// [`single_thread_labeled`](Self::single_thread_labeled), and returns
+    /// the lengths in bits of the graph and label bitstreams.
+    ///
+    /// The graph is iterated twice: a first pass serializes the labels, and a
+    /// second pass compresses the successors using
+    /// [`parallel_iter`](Self::parallel_iter). Temporary files are written
+    /// to `tmp_dir`, which is removed at the end.
+    #[allow(clippy::too_many_arguments)]
+    pub fn parallel_labeled<E, G, S>(
+        basename: impl AsRef<Path> + Send + Sync,
+        labels_basename: impl AsRef<Path>,
+        graph: &G,
+        serializer: S,
+        label_spec: &str,
+        compression_flags: CompFlags,
+        threads: &ThreadPool,
+        tmp_dir: impl AsRef<Path>,
+    ) -> Result<(u64, u64)>
+    where
+        E: Endianness,
+        G: LabeledSequentialGraph<S::SerType> + SplitLabeling,
+        S: BitSerializer<E, LabelsBitWriter<E>> + Sync,
+        LabelsBitWriter<E>: CodesWrite<E>,
+        BufBitReader<E, WordAdapter<u32, BufReader<File>>>: BitRead<E>,
+    {
+        let basename = basename.as_ref();
+        let labels_basename = labels_basename.as_ref();
+        let tmp_dir = tmp_dir.as_ref();
+        anyhow::ensure!(
+            basename != labels_basename,
+            "The graph and the labels must have different basenames"
+        );
+        let num_nodes = graph.num_nodes();
+        let labels_path = labels_basename.with_extension(LABELS_EXTENSION);
+        let label_offsets_path = labels_basename.with_extension(LABELOFFSETS_EXTENSION);
+        let chunk_path = |thread_id: usize, extension: &str| {
+            tmp_dir
+                .join(format!("{:016x}", thread_id))
+                .with_extension(extension)
+        };
+        let create = |path: &Path| -> Result<BufWriter<File>> {
+            Ok(BufWriter::new(File::create(path).with_context(|| {
+                format!("Could not create {}", path.display())
+            })?))
+        };
+        let open = |path: &Path| -> Result<BufReader<File>> {
+            Ok(BufReader::new(File::open(path).with_context(|| {
+                format!("Could not open {}", path.display())
+            })?))
+        };
+
+        log::info!("Serializing labels");
+        let chunks = Mutex::new(Vec::new());
+        threads.in_place_scope(|s| {
+            for (thread_id, lender) in graph
+                .split_iter(threads.current_num_threads())
+                .into_iter()
+                .enumerate()
+            {
+                let (chunks, serializer, create) = (&chunks, &serializer, &create);
+                s.spawn(move |_| {
+                    let result = (|| -> Result<(u64, u64)> {
+                        let mut labels_writer: LabelsBitWriter<E> =
+                            <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(create(
+                                &chunk_path(thread_id, LABELS_EXTENSION),
+                            )?));
+                        let mut label_offsets_writer =
+                            <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(create(
+                                &chunk_path(thread_id, LABELOFFSETS_EXTENSION),
+                            )?));
+                        let mut labels_len = 0;
+                        let mut label_offsets_len = 0;
+                        for_! ( (_node_id, successors) in lender {
+                            let mut label_delta = 0;
+                            for (_, label) in successors {
+                                label_delta += serializer
+                                    .serialize(&label, &mut labels_writer)
+                                    .context("Could not serialize label")?
+                                    as u64;
+                            }
+                            labels_len += label_delta;
+                            label_offsets_len += label_offsets_writer
+                                .write_gamma(label_delta)
+                                .context("Could not write label offset")?
+                                as u64;
+                        });
+                        labels_writer.flush().context("Could not flush labels")?;
+                        label_offsets_writer
+                            .flush()
+                            .context("Could not flush label offsets")?;
+                        Ok((labels_len, label_offsets_len))
+                    })();
+                    chunks.lock().unwrap().push((thread_id, result));
+                });
+            }
+        });
+        let mut chunks = chunks.into_inner().unwrap();
+        chunks.sort_by_key(|&(thread_id, _)| thread_id);
+
+        let mut labels_writer: LabelsBitWriter<E> =
+            <BufBitWriter<E, _>>::new(<WordAdapter<usize, _>>::new(create(&labels_path)?));
+        let mut label_offsets_writer =
+            <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(create(&label_offsets_path)?));
+        label_offsets_writer
+            .write_gamma(0)
+            .context("Could not write initial label offset")?;
+        let mut labels_len = 0;
+        for (thread_id, result) in chunks {
+            let (chunk_labels_len, chunk_label_offsets_len) = result?;
+            labels_len += chunk_labels_len;
+            let mut reader = <BufBitReader<E, _>>::new(<WordAdapter<u32, _>>::new(open(
+                &chunk_path(thread_id, LABELS_EXTENSION),
+            )?));
+            labels_writer
+                .copy_from(&mut reader, chunk_labels_len)
+                .context("Could not copy labels")?;
+            let mut reader = <BufBitReader<BE, _>>::new(<WordAdapter<u32, _>>::new(open(
+                &chunk_path(thread_id, LABELOFFSETS_EXTENSION),
+            )?));
+            label_offsets_writer
+                .copy_from(&mut reader, chunk_label_offsets_len)
+                .context("Could not copy label offsets")?;
+        }
+        labels_writer.flush().context("Could not flush labels")?;
+        label_offsets_writer
+            .flush()
+            .context("Could not flush label offsets")?;
+
+        log::info!("Compressing successors");
+        let graph_len = Self::parallel_iter::<E, _>(
+            basename,
+            graph
+                .split_iter(threads.current_num_threads())
+                .into_iter()
+                .map(LeftIterator),
+            num_nodes,
+            compression_flags,
+            threads,
+            tmp_dir,
+        )?;
+
+        let properties = labels_properties::<E>(basename, labels_basename, label_spec)?;
+        let properties_path = labels_basename.with_extension(PROPERTIES_EXTENSION);
+        std::fs::write(&properties_path, properties)
+            .with_context(|| format!("Could not write {}", properties_path.display()))?;
+
+        log::info!("Building the Elias–Fano representations of the offsets");
+        store_ef(
+            &basename.with_extension(OFFSETS_EXTENSION),
+            num_nodes,
+            graph_len,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+        store_ef(
+            &label_offsets_path,
+            num_nodes,
+            labels_len,
+            labels_basename.with_extension(EF_EXTENSION),
+        )?;
+
+        Ok((graph_len, labels_len))
+    }
+}
+
+/// Returns the content of the `.properties` file of a labeling.
+///
+/// The underlying graph is recorded relatively to the directory of the labels
+/// whenever the two basenames are in the same directory, as Java does.
+fn labels_properties<E: Endianness>(
+    basename: &Path,
+    labels_basename: &Path,
+    label_spec: &str,
+) -> Result<String> {
+    let underlying = if basename.parent() == labels_basename.parent() {
+        basename
+            .file_name()
+            .with_context(|| format!("Basename {} has no file name", basename.display()))?
+            .into()
+    } else {
+        std::path::absolute(basename)
+            .with_context(|| format!("Could not make {} absolute", basename.display()))?
+    };
+
+    let mut s = String::new();
+    s.push_str("#BitStreamArcLabelledImmutableGraph properties\n");
+    s.push_str(&format!("graphclass={}\n", BITSTREAM_LABELED_GRAPH_CLASS));
+    s.push_str(&format!("underlyinggraph={}\n", underlying.display()));
+    s.push_str(&format!("labelspec={}\n", label_spec));
+    s.push_str(&format!("endianness={}\n", E::NAME));
+    Ok(s)
+}
+
+/// Reads `num_nodes + 1` γ-coded offset gaps from `offsets_path` and stores
+/// their Elias–Fano representation in `ef_path`.
+pub(crate) fn store_ef(
+    offsets_path: &Path,
+    num_nodes: usize,
+    bitstream_len: u64,
+    ef_path: impl AsRef<Path>,
+) -> Result<()> {
+    let ef_path = ef_path.as_ref();
+    let mut reader = <BufBitReader<BE, _>>::new(<WordAdapter<u32, _>>::new(BufReader::new(
+        File::open(offsets_path)
+            .with_context(|| format!("Could not open {}", offsets_path.display()))?,
+    )));
+    let mut efb = EliasFanoBuilder::new(num_nodes + 1, bitstream_len as usize);
+    let mut offset = 0;
+    for _ in 0..num_nodes + 1 {
+        offset += reader.read_gamma().context("Could not read offset")?;
+        efb.push(offset as usize);
+    }
+    let ef: EF = unsafe {
+        efb.build()
+            .map_high_bits(SelectAdaptConst::<_, _, 12, 4>::new)
+    };
+    let mut ef_file = BufWriter::new(
+        File::create(ef_path).with_context(|| format!("Could not create {}", ef_path.display()))?,
+    );
+    ef.serialize(&mut ef_file)
+        .with_context(|| format!("Could not serialize Elias–Fano to {}", ef_path.display()))?;
+    Ok(())
+}
@@ -8,7 +8,17 @@
 mod bvcomp;
 pub use bvcomp::*;
 
+mod bvcompz;
+pub use bvcompz::*;
+
+mod append;
+
+mod huffman;
+
 mod impls;
 
+mod labeled;
+pub use labeled::*;
+
 mod flags;
 pub use flags::*;
@@ -6,6 +6,7 @@
  */
 
 use super::*;
+use crate::labels::bitstream::MmapReaderSupplier;
 use crate::prelude::*;
 use anyhow::{Context, Result};
 use dsi_bitstream::prelude::*;
@@ -16,6 +17,8 @@ use std::{
     io::BufReader,
     path::{Path, PathBuf},
 };
+use sux::dict::EliasFanoBuilder;
+use sux::rank_sel::SelectAdaptConst;
 use sux::traits::IndexedSeq;
 
 /// Sequential or random access.
@@ -41,6 +44,12 @@ pub trait Dispatch: 'static {}
 ///
 /// You have to specify all codes used of the graph. The defaults
 /// are the same as the default parameters of the Java version.
+///
+/// `BLOCKS`, `INTERVALS`, and `RESIDUALS` are the codes for blocks, interval
+/// lengths, and residuals, respectively. The last four parameters are the
+/// codes for block counts, interval counts, interval starts, and first
+/// residuals, and default to the code of the corresponding group, as in the
+/// Java version, in which they cannot be specified separately.
 #[derive(Debug, Clone)]
 pub struct Static<
     const OUTDEGREES: usize = { code_consts::GAMMA },
@@ -48,6 +57,10 @@ pub struct Static<
     const BLOCKS: usize = { code_consts::GAMMA },
     const INTERVALS: usize = { code_consts::GAMMA },
     const RESIDUALS: usize = { code_consts::ZETA3 },
+    const BLOCK_COUNTS: usize = BLOCKS,
+    const INTERVAL_COUNTS: usize = INTERVALS,
+    const INTERVAL_STARTS: usize = INTERVALS,
+    const FIRST_RESIDUALS: usize = RESIDUALS,
 > {}
 
 #[sealed]
@@ -57,19 +70,48 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
-    > Dispatch for Static<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    > Dispatch
+    for Static<
+        OUTDEGREES,
+        REFERENCES,
+        BLOCKS,
+        INTERVALS,
+        RESIDUALS,
+        BLOCK_COUNTS,
+        INTERVAL_COUNTS,
+        INTERVAL_STARTS,
+        FIRST_RESIDUALS,
+    >
 {
 }
 
 /// Dynamic dispatch.
 ///
-/// Parameters are retrieved from the graph properties.
+/// Parameters are retrieved from the graph properties. If the graph is
+/// [Huffman-coded](CompFlags::huffman_coding), the models are loaded from the
+/// `.models` file.
 #[derive(Debug, Clone)]
 pub struct Dynamic {}
 
 #[sealed]
 impl Dispatch for Dynamic {}
 
+/// Dispatch for [Huffman-coded](CompFlags::huffman_coding) graphs.
+///
+/// Parameters are retrieved from the graph properties, and the
+/// [models](HuffmanModels) from the `.models` file. [`Dynamic`] dispatch can
+/// load Huffman-coded graphs, too, but this dispatch avoids choosing the
+/// decoder at each read.
+#[derive(Debug, Clone)]
+pub struct Huffman {}
+
+#[sealed]
+impl Dispatch for Huffman {}
+
 /// Load mode.
 ///
 /// The load mode is the way the graph data is accessed. Each load mode has
@@ -232,6 +274,158 @@ impl LoadMode for LoadMmap {
     }
 }
 
+/// The graph is memory mapped; offsets are built in memory by a sequential
+/// scan of the graph.
+///
+/// This mode is useful as an [offsets mode](LoadConfig::offsets_mode) when the
+/// `.ef` file is not available and cannot be written (e.g., because the graph
+/// is in a read-only directory): the `.ef` file is neither read nor written.
+/// As a graph mode, it is equivalent to [`Mmap`].
+#[derive(Debug, Clone)]
+pub struct BuildMem {}
+#[sealed]
+impl LoadMode for BuildMem {
+    type Factory<E: Endianness> = MmapHelper<u32>;
+    type Offsets = EF;
+
+    fn new_factory<E: Endianness, P: AsRef<Path>>(
+        graph: P,
+        flags: MemoryFlags,
+    ) -> Result<Self::Factory<E>> {
+        MmapHelper::mmap(graph, flags.into())
+    }
+
+    fn load_offsets<P: AsRef<Path>>(
+        offsets: P,
+        _flags: MemoryFlags,
+    ) -> Result<MemCase<Self::Offsets>> {
+        let path = offsets.as_ref();
+        Ok(build_ef(path, |_| Ok(()))
+            .with_context(|| {
+                format!(
+                    "Cannot build Elias-Fano pointer list for {}",
+                    path.display()
+                )
+            })?
+            .into())
+    }
+}
+
+/// Computes by a sequential scan the Elias–Fano representation of the offsets
+/// of the graph with given basename, passing each offset gap to `gap`.
+fn build_ef(basename: &Path, gap: impl FnMut(u64) -> Result<()>) -> Result<EF> {
+    match get_endianness(basename)?.as_str() {
+        BE::NAME => build_ef_endianness::<BE>(basename, gap),
+        LE::NAME => build_ef_endianness::<LE>(basename, gap),
+        e => anyhow::bail!("Unknown endianness: {}", e),
+    }
+}
+
+fn build_ef_endianness<E: Endianness>(
+    basename: &Path,
+    gap: impl FnMut(u64) -> Result<()>,
+) -> Result<EF>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek,
+{
+    let graph_path = basename.with_extension(GRAPH_EXTENSION);
+    let bit_len = 8 * graph_path
+        .metadata()
+        .with_context(|| format!("Could not stat {}", graph_path.display()))?
+        .len() as usize;
+    let graph = BvGraphSeq::with_basename(basename)
+        .endianness::<E>()
+        .graph_flags(MemoryFlags::SEQUENTIAL)
+        .load()?;
+    scan_offsets(&graph, bit_len, gap)
+}
+
+/// Scans a sequential graph, building the Elias–Fano representation of its
+/// offsets and passing each offset gap to `gap`.
+fn scan_offsets<F: SequentialDecoderFactory>(
+    graph: &BvGraphSeq<F>,
+    bit_len: usize,
+    mut gap: impl FnMut(u64) -> Result<()>,
+) -> Result<EF>
+where
+    for<'a> F::Decoder<'a>: BitSeek,
+{
+    let mut efb = EliasFanoBuilder::new(graph.num_nodes() + 1, bit_len);
+    let mut last = 0;
+    let mut iter = graph.offset_deg_iter();
+    for (offset, _) in &mut iter {
+        efb.push(offset as usize);
+        gap(offset - last)?;
+        last = offset;
+    }
+    // The final offset is the end of the bitstream
+    let offset = iter.get_pos();
+    efb.push(offset as usize);
+    gap(offset - last)?;
+    Ok(unsafe {
+        efb.build()
+            .map_high_bits(SelectAdaptConst::<_, _, 12, 4>::new)
+    })
+}
+
+/// Rebuilds the `.offsets` and `.ef` files of the graph with given basename if
+/// any of them is missing or older than the `.graph` file.
+///
+/// The files are first written to temporary files in the same directory, and
+/// then atomically renamed, so concurrent loaders will never see partially
+/// written files.
+fn ensure_offsets(basename: &Path) -> Result<()> {
+    let graph_path = basename.with_extension(GRAPH_EXTENSION);
+    let graph_modified = graph_path
+        .metadata()
+        .and_then(|m| m.modified())
+        .with_context(|| format!("Could not stat {}", graph_path.display()))?;
+    let offsets_path = basename.with_extension(OFFSETS_EXTENSION);
+    let ef_path = basename.with_extension(EF_EXTENSION);
+    let is_stale = |path: &Path| {
+        path.metadata()
+            .and_then(|m| m.modified())
+            .map_or(true, |modified| modified < graph_modified)
+    };
+    if !is_stale(&offsets_path) && !is_stale(&ef_path) {
+        return Ok(());
+    }
+
+    log::info!("Rebuilding offsets of {}", basename.display());
+    let dir = match basename.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let offsets_file = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Could not create temporary file in {}", dir.display()))?;
+    let mut writer = <BufBitWriter<BE, _>>::new(<WordAdapter<u64, _>>::new(
+        std::io::BufWriter::new(offsets_file.as_file()),
+    ));
+    let ef = build_ef(basename, |gap| {
+        writer.write_gamma(gap).context("Could not write offset")?;
+        Ok(())
+    })?;
+    BitWrite::flush(&mut writer).context("Could not flush offsets")?;
+    drop(writer);
+
+    let ef_file = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Could not create temporary file in {}", dir.display()))?;
+    let mut ef_writer = std::io::BufWriter::new(ef_file.as_file());
+    ef.serialize(&mut ef_writer)
+        .context("Could not serialize Elias–Fano")?;
+    std::io::Write::flush(&mut ef_writer).context("Could not flush Elias–Fano")?;
+    drop(ef_writer);
+
+    offsets_file
+        .persist(&offsets_path)
+        .with_context(|| format!("Could not write {}", offsets_path.display()))?;
+    ef_file
+        .persist(&ef_path)
+        .with_context(|| format!("Could not write {}", ef_path.display()))?;
+    Ok(())
+}
+
 /// A load configuration for a [`BvGraph`]/[`BvGraphSeq`].
 ///
 /// A basic configuration is returned by
@@ -242,6 +436,7 @@ pub struct LoadConfig<E: Endianness, A: Access, D: Dispatch, GLM: LoadMode, OLM:
     pub(crate) basename: PathBuf,
     pub(crate) graph_load_flags: MemoryFlags,
     pub(crate) offsets_load_flags: MemoryFlags,
+    pub(crate) build_offsets: bool,
     pub(crate) _marker: std::marker::PhantomData<(E, A, D, GLM, OLM)>,
 }
 
@@ -258,6 +453,7 @@ impl<E: Endianness, A: Access, D: Dispatch, GLM: LoadMode, OLM: LoadMode>
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -272,6 +468,7 @@ impl<E: Endianness, A: Access, D: Dispatch, GLM: LoadMode, OLM: LoadMode>
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -286,6 +483,7 @@ impl<E: Endianness, A: Access, D: Dispatch, GLM: LoadMode, OLM: LoadMode>
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -298,6 +496,7 @@ impl<E: Endianness, A: Access, D: Dispatch> LoadConfig<E, A, D, Mmap, Mmap> {
             basename: self.basename,
             graph_load_flags: flags,
             offsets_load_flags: flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -310,6 +509,7 @@ impl<E: Endianness, A: Access, D: Dispatch> LoadConfig<E, A, D, LoadMmap, LoadMm
             basename: self.basename,
             graph_load_flags: flags,
             offsets_load_flags: flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -324,6 +524,7 @@ impl<E: Endianness, A: Access, D: Dispatch, GLM: LoadMode, OLM: LoadMode>
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -336,6 +537,7 @@ impl<E: Endianness, A: Access, D: Dispatch, OLM: LoadMode> LoadConfig<E, A, D, M
             basename: self.basename,
             graph_load_flags: flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -348,18 +550,33 @@ impl<E: Endianness, A: Access, D: Dispatch, OLM: LoadMode> LoadConfig<E, A, D, L
             basename: self.basename,
             graph_load_flags: flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
 }
 
 impl<E: Endianness, D: Dispatch, GLM: LoadMode, OLM: LoadMode> LoadConfig<E, Random, D, GLM, OLM> {
-    /// Choose the [`LoadMode`] for the graph only.
+    /// Rebuild the `.offsets` and `.ef` files if any of them is missing or
+    /// older than the `.graph` file.
+    ///
+    /// The offsets are computed by a sequential scan of the graph, and the
+    /// files are replaced atomically, so concurrent loaders do not race. If
+    /// the files cannot be written, use the [`BuildMem`] offsets mode instead.
+    pub fn build_offsets(self) -> Self {
+        LoadConfig {
+            build_offsets: true,
+            ..self
+        }
+    }
+
+    /// Choose the [`LoadMode`] for the offsets only.
     pub fn offsets_mode<NOLM: LoadMode>(self) -> LoadConfig<E, Random, D, GLM, NOLM> {
         LoadConfig {
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: self.offsets_load_flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -372,6 +589,7 @@ impl<E: Endianness, D: Dispatch, GLM: LoadMode> LoadConfig<E, Random, D, GLM, Mm
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -384,6 +602,7 @@ impl<E: Endianness, D: Dispatch, GLM: LoadMode> LoadConfig<E, Random, D, GLM, Lo
             basename: self.basename,
             graph_load_flags: self.graph_load_flags,
             offsets_load_flags: flags,
+            build_offsets: self.build_offsets,
             _marker: std::marker::PhantomData,
         }
     }
@@ -407,12 +626,16 @@ impl<E: Endianness, GLM: LoadMode, OLM: LoadMode> LoadConfig<E, Random, Dynamic,
         self.basename.set_extension(GRAPH_EXTENSION);
         let factory = GLM::new_factory(&self.basename, self.graph_load_flags)
             .with_context(|| format!("Could not graph file {}", self.basename.display()))?;
+        if self.build_offsets {
+            ensure_offsets(&self.basename)?;
+        }
         self.basename.set_extension(EF_EXTENSION);
         let offsets = OLM::load_offsets(&self.basename, self.offsets_load_flags)
             .with_context(|| format!("Could not offsets file {}", self.basename.display()))?;
+        let models = load_models(&self.basename, &comp_flags)?;
 
         Ok(BvGraph::new(
-            DynCodesDecoderFactory::new(factory, offsets, comp_flags)?,
+            DynCodesDecoderFactory::with_models(factory, offsets, comp_flags, models)?,
             num_nodes,
             num_arcs,
             comp_flags.compression_window,
@@ -437,15 +660,159 @@ impl<E: Endianness, GLM: LoadMode, OLM: LoadMode> LoadConfig<E, Sequential, Dyna
         let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(&self.basename)?;
         self.basename.set_extension(GRAPH_EXTENSION);
         let factory = GLM::new_factory(&self.basename, self.graph_load_flags)?;
+        let models = load_models(&self.basename, &comp_flags)?;
+
+        Ok(BvGraphSeq::new(
+            DynCodesDecoderFactory::with_models(
+                factory,
+                MemCase::from(EmptyDict::default()),
+                comp_flags,
+                models,
+            )?,
+            num_nodes,
+            Some(num_arcs),
+            comp_flags.compression_window,
+            comp_flags.min_interval_length,
+        ))
+    }
+
+    /// Load a sequential graph with dynamic dispatch reading the bitstream
+    /// from the given file rather than from the `.graph` file.
+    ///
+    /// The properties are still read from the `.properties` file associated
+    /// with the basename. The file is reopened at each iteration, and
+    /// decompressed transparently if it is compressed with Zstandard (e.g., a
+    /// `.graph.zst` file).
+    pub fn load_from_path(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> anyhow::Result<BvGraphSeq<StreamDecoderFactory<E>>>
+    where
+        StreamBufReader<E>: CodesRead<E>,
+    {
+        self.basename.set_extension(PROPERTIES_EXTENSION);
+        let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(&self.basename)?;
+        let models = load_models(&self.basename, &comp_flags)?;
 
         Ok(BvGraphSeq::new(
-            DynCodesDecoderFactory::new(factory, MemCase::from(EmptyDict::default()), comp_flags)?,
+            StreamDecoderFactory::from_path(path, comp_flags, models)?,
             num_nodes,
             Some(num_arcs),
             comp_flags.compression_window,
             comp_flags.min_interval_length,
         ))
     }
+
+    /// Load a sequential graph with dynamic dispatch reading the bitstream
+    /// from the given reader rather than from the `.graph` file.
+    ///
+    /// The properties are still read from the `.properties` file associated
+    /// with the basename. The data is decompressed transparently if it is
+    /// compressed with Zstandard. Since the reader can be consumed just once,
+    /// the resulting graph can be iterated upon just once.
+    pub fn load_from_reader(
+        mut self,
+        reader: impl std::io::Read + Send + 'static,
+    ) -> anyhow::Result<BvGraphSeq<StreamDecoderFactory<E>>>
+    where
+        StreamBufReader<E>: CodesRead<E>,
+    {
+        self.basename.set_extension(PROPERTIES_EXTENSION);
+        let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(&self.basename)?;
+        let models = load_models(&self.basename, &comp_flags)?;
+
+        Ok(BvGraphSeq::new(
+            StreamDecoderFactory::new(reader, comp_flags, models)?,
+            num_nodes,
+            Some(num_arcs),
+            comp_flags.compression_window,
+            comp_flags.min_interval_length,
+        ))
+    }
+}
+
+impl<E: Endianness, GLM: LoadMode, OLM: LoadMode> LoadConfig<E, Random, Huffman, GLM, OLM> {
+    /// Load a random-access Huffman-coded graph.
+    #[allow(clippy::type_complexity)]
+    pub fn load(
+        mut self,
+    ) -> anyhow::Result<BvGraph<HuffmanDecoderFactory<E, GLM::Factory<E>, OLM::Offsets>>>
+    where
+        <GLM as LoadMode>::Factory<E>: CodesReaderFactoryHelper<E>,
+        for<'a> LoadModeCodesReader<'a, E, GLM>: CodesRead<E> + BitSeek,
+    {
+        self.basename.set_extension(PROPERTIES_EXTENSION);
+        let (num_nodes, num_arcs, comp_flags) = parse_huffman_properties::<E>(&self.basename)?;
+        self.basename.set_extension(MODELS_EXTENSION);
+        let models = HuffmanModels::load(&self.basename)?;
+        self.basename.set_extension(GRAPH_EXTENSION);
+        let factory = GLM::new_factory(&self.basename, self.graph_load_flags)
+            .with_context(|| format!("Could not graph file {}", self.basename.display()))?;
+        if self.build_offsets {
+            ensure_offsets(&self.basename)?;
+        }
+        self.basename.set_extension(EF_EXTENSION);
+        let offsets = OLM::load_offsets(&self.basename, self.offsets_load_flags)
+            .with_context(|| format!("Could not offsets file {}", self.basename.display()))?;
+
+        Ok(BvGraph::new(
+            HuffmanDecoderFactory::new(factory, offsets, models),
+            num_nodes,
+            num_arcs,
+            comp_flags.compression_window,
+            comp_flags.min_interval_length,
+        ))
+    }
+}
+
+impl<E: Endianness, GLM: LoadMode, OLM: LoadMode> LoadConfig<E, Sequential, Huffman, GLM, OLM> {
+    /// Load a sequential Huffman-coded graph.
+    #[allow(clippy::type_complexity)]
+    pub fn load(
+        mut self,
+    ) -> anyhow::Result<
+        BvGraphSeq<HuffmanDecoderFactory<E, GLM::Factory<E>, EmptyDict<usize, usize>>>,
+    >
+    where
+        <GLM as LoadMode>::Factory<E>: CodesReaderFactoryHelper<E>,
+        for<'a> LoadModeCodesReader<'a, E, GLM>: CodesRead<E>,
+    {
+        self.basename.set_extension(PROPERTIES_EXTENSION);
+        let (num_nodes, num_arcs, comp_flags) = parse_huffman_properties::<E>(&self.basename)?;
+        self.basename.set_extension(MODELS_EXTENSION);
+        let models = HuffmanModels::load(&self.basename)?;
+        self.basename.set_extension(GRAPH_EXTENSION);
+        let factory = GLM::new_factory(&self.basename, self.graph_load_flags)?;
+
+        Ok(BvGraphSeq::new(
+            HuffmanDecoderFactory::new(factory, MemCase::from(EmptyDict::default()), models),
+            num_nodes,
+            Some(num_arcs),
+            comp_flags.compression_window,
+            comp_flags.min_interval_length,
+        ))
+    }
+}
+
+/// Loads the models of the graph with given basename, if it is
+/// [Huffman-coded](CompFlags::huffman_coding).
+fn load_models(basename: &Path, comp_flags: &CompFlags) -> Result<Option<HuffmanModels>> {
+    comp_flags
+        .huffman_coding
+        .then(|| HuffmanModels::load(basename.with_extension(MODELS_EXTENSION)))
+        .transpose()
+}
+
+/// Parses the `.properties` file of a Huffman-coded graph.
+fn parse_huffman_properties<E: Endianness>(path: &Path) -> Result<(usize, u64, CompFlags)> {
+    let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(path)
+        .with_context(|| format!("Could not load properties file {}", path.display()))?;
+    anyhow::ensure!(
+        comp_flags.huffman_coding,
+        "The graph described by {} is not Huffman-coded",
+        path.display()
+    );
+    Ok((num_nodes, num_arcs, comp_flags))
 }
 
 impl<
@@ -457,8 +824,28 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
+    >
+    LoadConfig<
+        E,
+        Random,
+        Static<
+            OUTDEGREES,
+            REFERENCES,
+            BLOCKS,
+            INTERVALS,
+            RESIDUALS,
+            BLOCK_COUNTS,
+            INTERVAL_COUNTS,
+            INTERVAL_STARTS,
+            FIRST_RESIDUALS,
+        >,
+        GLM,
+        OLM,
     >
-    LoadConfig<E, Random, Static<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>, GLM, OLM>
 {
     /// Load a random-access graph with static dispatch.
     #[allow(clippy::type_complexity)]
@@ -475,6 +862,10 @@ impl<
                 BLOCKS,
                 INTERVALS,
                 RESIDUALS,
+                BLOCK_COUNTS,
+                INTERVAL_COUNTS,
+                INTERVAL_STARTS,
+                FIRST_RESIDUALS,
             >,
         >,
     >
@@ -486,6 +877,9 @@ impl<
         let (num_nodes, num_arcs, comp_flags) = parse_properties::<E>(&self.basename)?;
         self.basename.set_extension(GRAPH_EXTENSION);
         let factory = GLM::new_factory(&self.basename, self.graph_load_flags)?;
+        if self.build_offsets {
+            ensure_offsets(&self.basename)?;
+        }
         self.basename.set_extension(EF_EXTENSION);
         let offsets = OLM::load_offsets(&self.basename, self.offsets_load_flags)?;
 
@@ -508,11 +902,25 @@ impl<
         const BLOCKS: usize,
         const INTERVALS: usize,
         const RESIDUALS: usize,
+        const BLOCK_COUNTS: usize,
+        const INTERVAL_COUNTS: usize,
+        const INTERVAL_STARTS: usize,
+        const FIRST_RESIDUALS: usize,
     >
     LoadConfig<
         E,
         Sequential,
-        Static<OUTDEGREES, REFERENCES, BLOCKS, INTERVALS, RESIDUALS>,
+        Static<
+            OUTDEGREES,
+            REFERENCES,
+            BLOCKS,
+            INTERVALS,
+            RESIDUALS,
+            BLOCK_COUNTS,
+            INTERVAL_COUNTS,
+            INTERVAL_STARTS,
+            FIRST_RESIDUALS,
+        >,
         GLM,
         OLM,
     >
@@ -532,6 +940,10 @@ impl<
                 BLOCKS,
                 INTERVALS,
                 RESIDUALS,
+                BLOCK_COUNTS,
+                INTERVAL_COUNTS,
+                INTERVAL_STARTS,
+                FIRST_RESIDUALS,
             >,
         >,
     >
@@ -598,3 +1010,84 @@ pub fn parse_properties<E: Endianness>(path: impl AsRef<Path>) -> Result<(usize,
         .with_context(|| format!("Cannot parse compression flags from {}", name))?;
     Ok((num_nodes, num_arcs, comp_flags))
 }
+
+/// Loads a labeled graph stored by [`BvComp::single_thread_labeled`].
+///
+/// The `.properties` file of the labels is parsed to find the underlying
+/// graph, which is memory-mapped using [dynamic dispatch](Dynamic); the
+/// labels and their Elias–Fano offsets are memory-mapped, too. Relative
+/// underlying basenames are resolved with respect to the directory of the
+/// labels, as in Java, so labeled graphs stored by Java's
+/// `BitStreamArcLabelledImmutableGraph` can be loaded once the `.ef` files of
+/// the graph and of the labels have been built.
+#[allow(clippy::type_complexity)]
+pub fn load_labeled<E: Endianness, D, L>(
+    labels_basename: impl AsRef<Path>,
+    deserializer: D,
+) -> Result<
+    Zip<
+        BvGraph<DynCodesDecoderFactory<E, MmapHelper<u32>, DeserType<'static, EF>>>,
+        BitStreamLabeling<E, MmapReaderSupplier<E>, D, MemCase<DeserType<'static, EF>>>,
+    >,
+>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek,
+    for<'a> D: BitDeserializer<E, LoadModeCodesReader<'a, E, Mmap>, DeserType = L>,
+{
+    let labels_basename = labels_basename.as_ref();
+    let path = labels_basename.with_extension(PROPERTIES_EXTENSION);
+    let name = path.display();
+    let f = std::fs::File::open(&path)
+        .with_context(|| format!("Cannot open property file {}", name))?;
+    let map = java_properties::read(BufReader::new(f))
+        .with_context(|| format!("cannot parse {} as a java properties file", name))?;
+
+    let graph_class = map
+        .get("graphclass")
+        .with_context(|| format!("Missing 'graphclass' property in {}", name))?;
+    anyhow::ensure!(
+        graph_class == BITSTREAM_LABELED_GRAPH_CLASS,
+        "Unsupported graph class {} in {}",
+        graph_class,
+        name
+    );
+    let endianness = map
+        .get("endianness")
+        .map(|x| x.to_string())
+        .unwrap_or_else(|| BigEndian::NAME.to_string());
+    anyhow::ensure!(
+        endianness == E::NAME,
+        "Wrong endianness in {}, got {} while expected {}",
+        name,
+        endianness,
+        E::NAME
+    );
+    let underlying = PathBuf::from(
+        map.get("underlyinggraph")
+            .with_context(|| format!("Missing 'underlyinggraph' property in {}", name))?,
+    );
+    let underlying = match labels_basename.parent() {
+        Some(parent) if underlying.is_relative() => parent.join(underlying),
+        _ => underlying,
+    };
+
+    let graph = BvGraph::with_basename(&underlying)
+        .endianness::<E>()
+        .load()
+        .with_context(|| format!("Could not load underlying graph {}", underlying.display()))?;
+
+    let labels_path = labels_basename.with_extension(LABELS_EXTENSION);
+    let ef_path = labels_basename.with_extension(EF_EXTENSION);
+    let offsets = EF::mmap(&ef_path, Flags::empty())
+        .with_context(|| format!("Cannot map Elias-Fano pointer list {}", ef_path.display()))?;
+
+    Ok(Zip(
+        graph,
+        BitStreamLabeling::new(
+            MmapReaderSupplier::mmap(&labels_path, mmap_rs::MmapFlags::empty())?,
+            deserializer,
+            offsets,
+        ),
+    ))
+}
@@ -5,6 +5,9 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+use super::random_access::Succ;
+use super::Decode;
+
 /// An iterator that filters out blocks of values.
 #[derive(Debug, Clone)]
 pub struct MaskedIterator<I> {
@@ -31,7 +34,18 @@ impl<I: Iterator<Item = usize> + ExactSizeIterator> MaskedIterator<I> {
     /// The blocks of even index are copy blocks, the blocks of odd index are
     /// skip blocks. If the number of blocks is odd, a last copy block to the
     /// end is added.
-    pub fn new(parent: I, mut blocks: Vec<usize>) -> Self {
+    pub fn new(parent: I, blocks: Vec<usize>) -> Self {
+        Self::new_skipped(parent, blocks, 0).0
+    }
+
+    /// Creates a new iterator that filters out blocks of values from a
+    /// parent iterator whose first `skipped` values have already been
+    /// consumed.
+    ///
+    /// The blocks are interpreted as in [`new`](MaskedIterator::new), and
+    /// are then advanced past the skipped values; the number of copied
+    /// values among them is returned along with the iterator.
+    pub(super) fn new_skipped(parent: I, mut blocks: Vec<usize>, skipped: usize) -> (Self, usize) {
         // the number of copied nodes
         let mut size: usize = 0;
         // the cumulative sum of the blocks
@@ -44,21 +58,57 @@ impl<I: Iterator<Item = usize> + ExactSizeIterator> MaskedIterator<I> {
         }
 
         // an empty blocks means that we should take all the neighbours
-        let remainder = parent.len() - cumsum_blocks;
+        let remainder = parent.len() + skipped - cumsum_blocks;
 
         // check if the last block is a copy or skip block
         // avoid pushing it so we end faster
-        if remainder != 0 && blocks.len() % 2 == 0 {
+        if remainder != 0 && blocks.len().is_multiple_of(2) {
             size += remainder;
             blocks.push(remainder);
         }
 
-        Self {
+        let mut iter = Self {
             parent: Box::new(parent),
             blocks,
             block_idx: 0,
             size,
+        };
+        let skipped = iter.advance(skipped);
+        (iter, skipped)
+    }
+
+    /// Updates the blocks after the parent iterator has been advanced by
+    /// `to_skip` values, and returns the number of copied values among them.
+    ///
+    /// If the parent stops within a skip block, the rest of the skip block is
+    /// consumed.
+    fn advance(&mut self, mut to_skip: usize) -> usize {
+        let mut skipped = 0;
+        // with no blocks there is nothing to copy
+        while let Some(current_block) = self.blocks.get_mut(self.block_idx) {
+            let len = to_skip.min(*current_block);
+            *current_block -= len;
+            to_skip -= len;
+            skipped += len;
+            // the values after the last copy block are not copied
+            if to_skip == 0 || self.block_idx + 1 >= self.blocks.len() {
+                break;
+            }
+            // the copy block is exhausted, so we go through the next skip block
+            let skip_block = self.blocks[self.block_idx + 1];
+            if to_skip < skip_block {
+                for _ in to_skip..skip_block {
+                    let node = self.parent.next();
+                    debug_assert!(node.is_some());
+                }
+                to_skip = 0;
+            } else {
+                to_skip -= skip_block;
+            }
+            self.block_idx += 2;
         }
+        self.size -= skipped;
+        skipped
     }
 }
 
@@ -97,3 +147,17 @@ impl<I: Iterator<Item = usize>> Iterator for MaskedIterator<I> {
         result
     }
 }
+
+impl<D: Decode> MaskedIterator<Succ<D>> {
+    /// Advances the iterator so that the next value returned, if any, is
+    /// greater than or equal to `min`, and returns the number of values
+    /// skipped.
+    ///
+    /// The parent iterator is advanced recursively, and the blocks are
+    /// updated using the number of values it skipped, so skipped values are
+    /// never enumerated.
+    pub(super) fn skip_to(&mut self, min: usize) -> usize {
+        let to_skip = self.parent.skip_to(min);
+        self.advance(to_skip)
+    }
+}
@@ -30,6 +30,10 @@ pub const EF_EXTENSION: &str = "ef";
 pub const LABELS_EXTENSION: &str = "labels";
 pub const LABELOFFSETS_EXTENSION: &str = "labeloffsets";
 pub const DEG_CUMUL_EXTENSION: &str = "dcf";
+pub const MODELS_EXTENSION: &str = "models";
+
+mod checksums;
+pub use checksums::*;
 
 mod offset_deg_iter;
 pub use offset_deg_iter::OffsetDegIter;
@@ -36,6 +36,7 @@ impl BvGraph<()> {
             basename: PathBuf::from(basename.as_ref()),
             graph_load_flags: Flags::empty(),
             offsets_load_flags: Flags::empty(),
+            build_offsets: false,
             _marker: std::marker::PhantomData,
         }
     }
@@ -225,16 +226,99 @@ where
     #[inline(always)]
     /// Returns a random access iterator over the successors of a node.
     fn labels(&self, node_id: usize) -> Succ<F::Decoder<'_>> {
-        let codes_reader = self
+        self.succ_from(node_id, 0).0
+    }
+}
+
+impl<F: SequentialDecoderFactory> BvGraph<F>
+where
+    for<'a> F::Decoder<'a>: Decode,
+{
+    #[inline(always)]
+    /// Creates an iterator specialized in the degrees of the nodes.
+    /// This is slightly faster because it can avoid decoding some of the nodes
+    /// and completely skip the merging step.
+    pub fn offset_deg_iter(&self) -> OffsetDegIter<F::Decoder<'_>> {
+        OffsetDegIter::new(
+            self.factory.new_decoder().unwrap(),
+            self.number_of_nodes,
+            self.compression_window,
+            self.min_interval_length,
+        )
+    }
+}
+
+impl<F: RandomAccessDecoderFactory> BvGraph<F>
+where
+    for<'a> F::Decoder<'a>: Decode,
+{
+    #[inline(always)]
+    /// Creates an iterator specialized in the degrees of the nodes starting
+    /// from a given node.
+    pub fn offset_deg_iter_from(&self, node: usize) -> OffsetDegIter<F::Decoder<'_>> {
+        let mut backrefs = vec![0; self.compression_window];
+        for node_id in node.saturating_sub(self.compression_window)..node {
+            backrefs[node_id % self.compression_window] = self.outdegree(node_id);
+        }
+        OffsetDegIter::new_from(
+            self.factory.new_decoder(node).unwrap(),
+            self.number_of_nodes,
+            self.compression_window,
+            self.min_interval_length,
+            node,
+            backrefs,
+        )
+    }
+}
+
+impl<F: RandomAccessDecoderFactory> BvGraph<F> {
+    /// Returns an iterator over the successors of a node that are greater
+    /// than or equal to `min`, in sorted order.
+    ///
+    /// Smaller successors are skipped without being enumerated whenever
+    /// possible: intervals ending before `min` are never stored, the decoding
+    /// of residuals stops at the first residual greater than or equal to
+    /// `min`, and the successors of the reference node are in turn obtained
+    /// from this method, so only the copy blocks following the skipped
+    /// successors of the reference node are materialized. If no copy block
+    /// reaches `min`, the iterator on the reference node is dropped.
+    #[inline(always)]
+    pub fn successors_from(&self, node_id: usize, min: usize) -> Succ<F::Decoder<'_>> {
+        self.succ_from(node_id, min).0
+    }
+
+    /// Returns an iterator over the successors of a node that are greater
+    /// than or equal to `min`, and the number of successors skipped.
+    fn succ_from(&self, node_id: usize, min: usize) -> (Succ<F::Decoder<'_>>, usize) {
+        let (codes_reader, degree) = self.reader_and_outdegree(node_id);
+        self.succ_from_reader(node_id, codes_reader, degree, min)
+    }
+
+    /// Returns a decoder positioned after the outdegree of a node, and the
+    /// outdegree.
+    #[inline(always)]
+    fn reader_and_outdegree(&self, node_id: usize) -> (F::Decoder<'_>, usize) {
+        let mut codes_reader = self
             .factory
             .new_decoder(node_id)
             .expect("Cannot create reader");
+        let degree = codes_reader.read_outdegree() as usize;
+        (codes_reader, degree)
+    }
 
+    /// Like [`succ_from`](BvGraph::succ_from), but using a decoder from
+    /// which the outdegree of the node has already been read.
+    fn succ_from_reader<'a>(
+        &'a self,
+        node_id: usize,
+        codes_reader: F::Decoder<'a>,
+        degree: usize,
+        min: usize,
+    ) -> (Succ<F::Decoder<'a>>, usize) {
         let mut result = Succ::new(codes_reader);
-        let degree = result.reader.read_outdegree() as usize;
         // no edges, we are done!
         if degree == 0 {
-            return result;
+            return (result, 0);
         }
         result.size = degree;
         let mut nodes_left_to_decode = degree;
@@ -248,28 +332,20 @@ where
         if ref_delta != 0 {
             // compute the node id of the reference
             let reference_node_id = node_id - ref_delta;
-            // retrieve the data
-            let neighbours = self.successors(reference_node_id);
-            debug_assert!(neighbours.len() != 0);
             // get the info on which destinations to copy
-            let number_of_blocks = result.reader.read_block_count() as usize;
-            // add +1 if the number of blocks is even, so we have capacity for
-            // the block that will be added in the masked iterator
-            let alloc_len = 1 + number_of_blocks - (number_of_blocks & 1);
-            let mut blocks = Vec::with_capacity(alloc_len);
-            if number_of_blocks != 0 {
-                // the first block could be zero
-                blocks.push(result.reader.read_block() as usize);
-                // while the other can't
-                for _ in 1..number_of_blocks {
-                    blocks.push(result.reader.read_block() as usize + 1);
-                }
+            let blocks = read_blocks(&mut result.reader);
+            // retrieve the data, skipping the successors smaller than min
+            let (neighbours, skipped) = self.succ_from(reference_node_id, min);
+            debug_assert!(neighbours.len() + skipped != 0);
+            // create the masked iterator, advancing the blocks past the
+            // skipped successors of the reference
+            let (res, copied_skipped) = MaskedIterator::new_skipped(neighbours, blocks, skipped);
+            nodes_left_to_decode -= res.len() + copied_skipped;
+            result.size -= copied_skipped;
+            // if no copy block reaches min we don't need the reference
+            if res.len() != 0 {
+                result.copied_nodes_iter = Some(res);
             }
-            // create the masked iterator
-            let res = MaskedIterator::new(neighbours, blocks);
-            nodes_left_to_decode -= res.len();
-
-            result.copied_nodes_iter = Some(res);
         };
 
         // if we still have to read nodes
@@ -286,7 +362,7 @@ where
                 let mut delta = result.reader.read_interval_len() as usize;
                 delta += self.min_interval_length;
                 // save the first interval
-                result.intervals.push((start, delta));
+                result.push_interval(start, delta, min);
                 start += delta;
                 nodes_left_to_decode -= delta;
                 // decode the intervals
@@ -295,13 +371,15 @@ where
                     delta = result.reader.read_interval_len() as usize;
                     delta += self.min_interval_length;
 
-                    result.intervals.push((start, delta));
+                    result.push_interval(start, delta, min);
                     start += delta;
                     nodes_left_to_decode -= delta;
                 }
-                // fake final interval to avoid checks in the implementation of
-                // `next`
-                result.intervals.push((usize::MAX - 1, 1));
+                if !result.intervals.is_empty() {
+                    // fake final interval to avoid checks in the
+                    // implementation of `next`
+                    result.intervals.push((usize::MAX - 1, 1));
+                }
             }
         }
 
@@ -329,51 +407,126 @@ where
             .and_then(|iter| iter.next())
             .unwrap_or(usize::MAX);
 
-        result
+        // skip the residuals smaller than min
+        result.skip_to(min);
+        let skipped = degree - result.size;
+        (result, skipped)
     }
 }
 
-impl<F: SequentialDecoderFactory> BvGraph<F>
-where
-    for<'a> F::Decoder<'a>: Decode,
-{
-    #[inline(always)]
-    /// Creates an iterator specialized in the degrees of the nodes.
-    /// This is slightly faster because it can avoid decoding some of the nodes
-    /// and completely skip the merging step.
-    pub fn offset_deg_iter(&self) -> OffsetDegIter<F::Decoder<'_>> {
-        OffsetDegIter::new(
-            self.factory.new_decoder().unwrap(),
-            self.number_of_nodes,
-            self.compression_window,
-            self.min_interval_length,
-        )
+/// Reads the copy blocks of a node, adding one to all blocks except the
+/// first one, which is the only one that can be zero.
+fn read_blocks(reader: &mut impl Decode) -> Vec<usize> {
+    let number_of_blocks = reader.read_block_count() as usize;
+    // add +1 if the number of blocks is even, so we have capacity for
+    // the block that will be added in the masked iterator
+    let alloc_len = 1 + number_of_blocks - (number_of_blocks & 1);
+    let mut blocks = Vec::with_capacity(alloc_len);
+    if number_of_blocks != 0 {
+        // the first block could be zero
+        blocks.push(reader.read_block() as usize);
+        // while the other can't
+        for _ in 1..number_of_blocks {
+            blocks.push(reader.read_block() as usize + 1);
+        }
     }
+    blocks
 }
 
-impl<F: RandomAccessDecoderFactory> BvGraph<F>
+impl<F> RandomAccessGraph for BvGraph<F>
 where
-    for<'a> F::Decoder<'a>: Decode,
+    F: RandomAccessDecoderFactory,
 {
-    #[inline(always)]
-    /// Creates an iterator specialized in the degrees of the nodes starting
-    /// from a given node.
-    pub fn offset_deg_iter_from(&self, node: usize) -> OffsetDegIter<F::Decoder<'_>> {
-        let mut backrefs = vec![0; self.compression_window];
-        for node_id in node.saturating_sub(self.compression_window)..node {
-            backrefs[node_id % self.compression_window] = self.outdegree(node_id);
+    /// Returns whether there is an arc going from `src_node_id` to
+    /// `dst_node_id`.
+    ///
+    /// The intervals and the residuals of the source node are examined
+    /// first, stopping at the first residual greater than or equal to
+    /// `dst_node_id`. The reference node is accessed, using
+    /// [`successors_from`](BvGraph::successors_from), only if
+    /// `dst_node_id` has not been found and it might be a copied successor.
+    fn has_arc(&self, src_node_id: usize, dst_node_id: usize) -> bool {
+        let (mut reader, degree) = self.reader_and_outdegree(src_node_id);
+        if degree == 0 {
+            return false;
         }
-        OffsetDegIter::new_from(
-            self.factory.new_decoder(node).unwrap(),
-            self.number_of_nodes,
-            self.compression_window,
-            self.min_interval_length,
-            node,
-            backrefs,
-        )
+        let mut nodes_left_to_decode = degree;
+        let ref_delta = if self.compression_window != 0 {
+            reader.read_reference_offset() as usize
+        } else {
+            0
+        };
+        let mut blocks = vec![];
+        // the decoder of the reference node, positioned after its outdegree
+        let mut reference = None;
+        if ref_delta != 0 {
+            blocks = read_blocks(&mut reader);
+            // we need the outdegree of the reference to count the copied
+            // successors if the last copy block is implicit
+            let mut copied = blocks.iter().step_by(2).sum::<usize>();
+            if blocks.len() % 2 == 0 {
+                let (ref_reader, ref_degree) = self.reader_and_outdegree(src_node_id - ref_delta);
+                copied += ref_degree - blocks.iter().sum::<usize>();
+                reference = Some((ref_reader, ref_degree));
+            }
+            nodes_left_to_decode -= copied;
+        }
+
+        if nodes_left_to_decode != 0 && self.min_interval_length != 0 {
+            let number_of_intervals = reader.read_interval_count() as usize;
+            let mut start = src_node_id;
+            for i in 0..number_of_intervals {
+                if i == 0 {
+                    start = (src_node_id as i64 + reader.read_interval_start().to_int()) as usize;
+                } else {
+                    start += 1 + reader.read_interval_start() as usize;
+                }
+                let len = reader.read_interval_len() as usize + self.min_interval_length;
+                if (start..start + len).contains(&dst_node_id) {
+                    return true;
+                }
+                start += len;
+                nodes_left_to_decode -= len;
+            }
+        }
+
+        if nodes_left_to_decode != 0 {
+            let mut residual =
+                (src_node_id as i64 + reader.read_first_residual().to_int()) as usize;
+            for _ in 1..nodes_left_to_decode {
+                if residual >= dst_node_id {
+                    break;
+                }
+                residual += 1 + reader.read_residual() as usize;
+            }
+            if residual == dst_node_id {
+                return true;
+            }
+        }
+
+        // the destination can only be a copied successor
+        if ref_delta == 0 {
+            return false;
+        }
+        let reference_node_id = src_node_id - ref_delta;
+        let (ref_reader, ref_degree) =
+            reference.unwrap_or_else(|| self.reader_and_outdegree(reference_node_id));
+        let (mut neighbours, rank) =
+            self.succ_from_reader(reference_node_id, ref_reader, ref_degree, dst_node_id);
+        if neighbours.next() != Some(dst_node_id) {
+            return false;
+        }
+        // check whether the position of the destination is in a copy block
+        let mut pos = 0;
+        for (i, block) in blocks.iter().enumerate() {
+            pos += block;
+            if rank < pos {
+                return i % 2 == 0;
+            }
+        }
+        blocks.len() % 2 == 0
     }
 }
-impl<F> RandomAccessGraph for BvGraph<F> where F: RandomAccessDecoderFactory {}
 
 /// The iterator returned from [`BvGraph`] that returns the successors of a
 /// node in sorted order.
@@ -426,6 +579,74 @@ impl<D: Decode> Succ<D> {
     }
 }
 
+impl<D: Decode> Succ<D> {
+    /// Stores an interval of successors, dropping the values smaller than
+    /// `min`.
+    #[inline(always)]
+    fn push_interval(&mut self, start: usize, len: usize, min: usize) {
+        if start + len <= min {
+            self.size -= len;
+        } else {
+            let skip = min.saturating_sub(start);
+            self.size -= skip;
+            self.intervals.push((start + skip, len - skip));
+        }
+    }
+
+    /// Advances the iterator so that the next successor returned, if any, is
+    /// greater than or equal to `min`, and returns the number of successors
+    /// skipped.
+    pub(super) fn skip_to(&mut self, min: usize) -> usize {
+        // no node can be larger than the fake final interval
+        let min = min.min(usize::MAX - 1);
+        let size = self.size;
+
+        if self.next_copied_node < min {
+            // the cached copied node has already been read from the iterator
+            self.size -= 1;
+            let copied_nodes_iter = self.copied_nodes_iter.as_mut().unwrap();
+            self.size -= copied_nodes_iter.skip_to(min);
+            self.next_copied_node = copied_nodes_iter.next().unwrap_or(usize::MAX);
+        }
+
+        if self.next_interval_node < min {
+            self.size -= 1;
+            // the last interval is a fake one starting at usize::MAX - 1, so
+            // we will stop before running out of intervals
+            loop {
+                let (start, len) = &mut self.intervals[self.intervals_idx];
+                if *start + *len <= min {
+                    self.size -= *len;
+                    self.intervals_idx += 1;
+                } else {
+                    let skip = min.saturating_sub(*start);
+                    self.size -= skip;
+                    *start += skip;
+                    *len -= skip;
+                    break;
+                }
+            }
+            let (start, len) = &mut self.intervals[self.intervals_idx];
+            *len -= 1;
+            self.next_interval_node = *start;
+            *start += 1;
+            self.intervals_idx += (*len == 0) as usize;
+        }
+
+        while self.next_residual_node < min {
+            self.size -= 1;
+            if self.residuals_to_go == 0 {
+                self.next_residual_node = usize::MAX;
+            } else {
+                self.residuals_to_go -= 1;
+                self.next_residual_node += 1 + self.reader.read_residual() as usize;
+            }
+        }
+
+        size - self.size
+    }
+}
+
 impl<D: Decode> Iterator for Succ<D> {
     type Item = usize;
 
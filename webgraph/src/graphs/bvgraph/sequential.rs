@@ -36,6 +36,7 @@ impl BvGraphSeq<()> {
             basename: PathBuf::from(basename.as_ref()),
             graph_load_flags: Flags::empty(),
             offsets_load_flags: Flags::empty(),
+            build_offsets: false,
             _marker: std::marker::PhantomData,
         }
     }
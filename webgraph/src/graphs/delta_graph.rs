@@ -0,0 +1,354 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::graphs::bvgraph::{store_ef, EF_EXTENSION, OFFSETS_EXTENSION};
+use crate::prelude::*;
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use std::fs::File;
+use std::io::BufWriter;
+use std::iter::Peekable;
+use std::path::Path;
+
+/// A mutable graph layering arc insertions and deletions over an immutable
+/// [`RandomAccessGraph`].
+///
+/// Inserted arcs not in the base graph and deleted arcs of the base graph are
+/// kept in two [`BTreeGraph`]s, and the successors of a node are obtained by
+/// merging lazily the successors in the base graph, minus the deleted ones,
+/// with the inserted ones. The successors of the base graph must be sorted.
+///
+/// Once enough changes have been accumulated, the resulting graph can be
+/// [compacted](DeltaGraph::compact) into a new [`BvGraph`], which can be
+/// then used as the base for further changes.
+///
+/// Note that checking whether an arc is in the base graph requires a scan of
+/// the successors of its source.
+///
+/// # Examples
+///
+/// ```
+/// # use webgraph::prelude::*;
+/// let base = VecGraph::from_arcs([(0, 1), (1, 2), (2, 0)]);
+/// let mut graph = DeltaGraph::new(base);
+/// graph.add_node(3);
+/// assert!(graph.add_arc(2, 3));
+/// assert!(graph.remove_arc(1, 2));
+/// assert!(!graph.remove_arc(1, 2));
+/// assert_eq!(graph.num_arcs(), 3);
+/// assert_eq!(graph.successors(2).collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeltaGraph<G> {
+    base: G,
+    /// The arcs not in the base graph that have been inserted.
+    added: BTreeGraph,
+    /// The arcs of the base graph that have been deleted.
+    removed: BTreeGraph,
+}
+
+impl<G: RandomAccessGraph> DeltaGraph<G> {
+    /// Creates a new mutable graph with no changes over a base graph.
+    pub fn new(base: G) -> Self {
+        let num_nodes = base.num_nodes();
+        Self {
+            base,
+            added: BTreeGraph::empty(num_nodes),
+            removed: BTreeGraph::empty(num_nodes),
+        }
+    }
+
+    /// Returns whether an arc is in the base graph.
+    fn base_has_arc(&self, src: usize, dst: usize) -> bool {
+        src < self.base.num_nodes() && self.base.has_arc(src, dst)
+    }
+
+    /// Add an isolated node to the graph and return true if it is a new node.
+    pub fn add_node(&mut self, node: usize) -> bool {
+        self.added.add_node(node)
+    }
+
+    /// Add an arc to the graph and return whether it is a new one.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if one of the given nodes is greater or equal
+    /// than the number of nodes in the graph.
+    pub fn add_arc(&mut self, src: usize, dst: usize) -> bool {
+        let max = src.max(dst);
+        if max >= self.added.num_nodes() {
+            panic!(
+                "Node {} does not exist (the graph has {} nodes)",
+                max,
+                self.added.num_nodes(),
+            );
+        }
+        if self.base_has_arc(src, dst) {
+            // The arc is new only if it had been deleted
+            self.removed.remove_arc(src, dst)
+        } else {
+            self.added.add_arc(src, dst)
+        }
+    }
+
+    /// Remove an arc from the graph and return whether it was present or not.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if one of the given nodes is greater or equal
+    /// than the number of nodes in the graph.
+    pub fn remove_arc(&mut self, src: usize, dst: usize) -> bool {
+        let max = src.max(dst);
+        if max >= self.added.num_nodes() {
+            panic!(
+                "Node {} does not exist (the graph has {} nodes)",
+                max,
+                self.added.num_nodes(),
+            );
+        }
+        if self.base_has_arc(src, dst) {
+            self.removed.add_arc(src, dst)
+        } else {
+            self.added.remove_arc(src, dst)
+        }
+    }
+
+    /// Add arcs from an [`IntoIterator`], adding new nodes as needed.
+    ///
+    /// The items must be pairs of the form `(usize, usize)` specifying an arc.
+    pub fn add_arcs(&mut self, arcs: impl IntoIterator<Item = (usize, usize)>) {
+        for (src, dst) in arcs {
+            self.add_node(src.max(dst));
+            self.add_arc(src, dst);
+        }
+    }
+
+    /// Remove arcs from an [`IntoIterator`].
+    ///
+    /// The items must be pairs of the form `(usize, usize)` specifying an arc.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if one of the given nodes is greater or equal
+    /// than the number of nodes in the graph.
+    pub fn remove_arcs(&mut self, arcs: impl IntoIterator<Item = (usize, usize)>) {
+        for (src, dst) in arcs {
+            self.remove_arc(src, dst);
+        }
+    }
+
+    /// Compresses the graph into a [`BvGraph`] with the given basename and
+    /// returns the length in bits of the graph bitstream.
+    ///
+    /// Offsets and their Elias–Fano representation are built, too, so the
+    /// result can be loaded as the base of a new [`DeltaGraph`]. To compress
+    /// the graph in parallel, use [`BvComp::parallel_graph`] instead.
+    pub fn compact<E: Endianness>(
+        &self,
+        basename: impl AsRef<Path>,
+        compression_flags: CompFlags,
+    ) -> Result<u64>
+    where
+        G: 'static,
+        for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+        BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: CodesWrite<E>,
+    {
+        let basename = basename.as_ref();
+        let num_nodes = self.added.num_nodes();
+        let graph_len = BvComp::single_thread::<E, _>(
+            basename,
+            self,
+            compression_flags,
+            true,
+            Some(num_nodes),
+        )?;
+        store_ef(
+            &basename.with_extension(OFFSETS_EXTENSION),
+            num_nodes,
+            graph_len,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+        Ok(graph_len)
+    }
+}
+
+impl<G> DeltaGraph<G> {
+    /// Returns the base graph.
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    /// Returns the arcs not in the base graph that have been inserted.
+    pub fn added(&self) -> &BTreeGraph {
+        &self.added
+    }
+
+    /// Returns the arcs of the base graph that have been deleted.
+    pub fn removed(&self) -> &BTreeGraph {
+        &self.removed
+    }
+
+    /// Returns the base graph, the inserted arcs and the deleted arcs.
+    pub fn into_inner(self) -> (G, BTreeGraph, BTreeGraph) {
+        (self.base, self.added, self.removed)
+    }
+}
+
+impl<G: RandomAccessGraph + 'static> SequentialLabeling for DeltaGraph<G>
+where
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type Label = usize;
+    type Lender<'a>
+        = IteratorImpl<'a, Self>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.added.num_nodes()
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        Some(self.num_arcs())
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        IteratorImpl {
+            labeling: self,
+            nodes: (from..self.num_nodes()),
+        }
+    }
+}
+
+impl<G: RandomAccessGraph + Sync + 'static> SplitLabeling for DeltaGraph<G>
+where
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type SplitLender<'a>
+        = split::ra::Lender<'a, DeltaGraph<G>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::ra::IntoIterator<'a, DeltaGraph<G>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::ra::Iter::new(self, how_many)
+    }
+}
+
+impl<G: RandomAccessGraph + 'static> SequentialGraph for DeltaGraph<G> where
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator
+{
+}
+
+impl<'a, G: RandomAccessGraph + 'static> IntoLender for &'a DeltaGraph<G>
+where
+    for<'b> <G::Labels<'b> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type Lender = <DeltaGraph<G> as SequentialLabeling>::Lender<'a>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<G: RandomAccessGraph + 'static> RandomAccessLabeling for DeltaGraph<G>
+where
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type Labels<'succ>
+        = Succ<'succ, <G::Labels<'succ> as IntoIterator>::IntoIter>
+    where
+        Self: 'succ;
+
+    #[inline(always)]
+    fn num_arcs(&self) -> u64 {
+        self.base.num_arcs() + self.added.num_arcs() - self.removed.num_arcs()
+    }
+
+    #[inline(always)]
+    fn outdegree(&self, node: usize) -> usize {
+        let base = if node < self.base.num_nodes() {
+            self.base.outdegree(node) - self.removed.outdegree(node)
+        } else {
+            0
+        };
+        base + self.added.outdegree(node)
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        let (base, removed) = if node < self.base.num_nodes() {
+            (
+                Some(self.base.successors(node).into_iter().peekable()),
+                Some(self.removed.successors(node).peekable()),
+            )
+        } else {
+            (None, None)
+        };
+        Succ {
+            base,
+            removed,
+            added: self.added.successors(node).peekable(),
+        }
+    }
+}
+
+impl<G: RandomAccessGraph + 'static> RandomAccessGraph for DeltaGraph<G> where
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator
+{
+}
+
+type BTreeSucc<'a> = <BTreeGraph as RandomAccessLabeling>::Labels<'a>;
+
+/// The successors of a node of a [`DeltaGraph`].
+pub struct Succ<'a, I: Iterator<Item = usize>> {
+    /// The successors in the base graph, if the node is in the base graph.
+    base: Option<Peekable<I>>,
+    /// The deleted successors, if the node is in the base graph.
+    removed: Option<Peekable<BTreeSucc<'a>>>,
+    /// The inserted successors.
+    added: Peekable<BTreeSucc<'a>>,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for Succ<'_, I> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip the deleted successors of the base graph, which are a
+        // sorted subsequence of the successors of the base graph
+        let next_base = match (&mut self.base, &mut self.removed) {
+            (Some(base), Some(removed)) => loop {
+                match base.peek() {
+                    None => break usize::MAX,
+                    Some(&succ) if removed.next_if_eq(&succ).is_some() => {
+                        base.next();
+                    }
+                    Some(&succ) => break succ,
+                }
+            },
+            _ => usize::MAX,
+        };
+        let next_added = self.added.peek().copied().unwrap_or(usize::MAX);
+        // Inserted arcs are not in the base graph
+        if next_base < next_added {
+            self.base.as_mut().and_then(Iterator::next)
+        } else {
+            self.added.next()
+        }
+    }
+}
+
+unsafe impl<I: Iterator<Item = usize> + SortedIterator> SortedIterator for Succ<'_, I> {}
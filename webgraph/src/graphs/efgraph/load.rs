@@ -0,0 +1,289 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::*;
+use crate::graphs::bvgraph::{EF_EXTENSION, GRAPH_EXTENSION, PROPERTIES_EXTENSION};
+use anyhow::{bail, ensure, Context, Result};
+use dsi_bitstream::prelude::*;
+use sealed::sealed;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// The graph class written in the `.properties` file.
+pub const EF_GRAPH_CLASS: &str = "it.unimi.dsi.webgraph.EFGraph";
+
+/// Returns the Java name of the byte order corresponding to an endianness.
+pub(super) fn byte_order<E: Endianness>() -> &'static str {
+    if E::IS_LITTLE {
+        "LITTLE_ENDIAN"
+    } else {
+        "BIG_ENDIAN"
+    }
+}
+
+/// A [`LoadMode`] that can be used to load the words of an [`EfGraph`].
+#[sealed]
+pub trait EfLoadMode: LoadMode {
+    type Words: AsRef<[u64]>;
+
+    fn load_words<P: AsRef<Path>>(graph: P, flags: MemoryFlags) -> Result<Self::Words>;
+}
+
+#[sealed]
+impl EfLoadMode for Mmap {
+    type Words = MmapHelper<u64>;
+
+    fn load_words<P: AsRef<Path>>(graph: P, flags: MemoryFlags) -> Result<Self::Words> {
+        MmapHelper::mmap(graph, flags.into())
+    }
+}
+
+#[sealed]
+impl EfLoadMode for LoadMem {
+    type Words = Box<[u64]>;
+
+    fn load_words<P: AsRef<Path>>(graph: P, _flags: MemoryFlags) -> Result<Self::Words> {
+        let path = graph.as_ref();
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)
+            .with_context(|| format!("Could not open {}", path.display()))?
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        // Zero-extend to a multiple of the word size, as mmap() does
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+}
+
+/// A load configuration for an [`EfGraph`].
+///
+/// A basic configuration is returned by [`EfGraph::with_basename`]. The
+/// configuration can then be customized using the methods of this struct.
+#[derive(Debug, Clone)]
+pub struct EfLoadConfig<E: Endianness, GLM: EfLoadMode, OLM: LoadMode> {
+    pub(crate) basename: PathBuf,
+    pub(crate) graph_load_flags: MemoryFlags,
+    pub(crate) offsets_load_flags: MemoryFlags,
+    pub(crate) _marker: PhantomData<(E, GLM, OLM)>,
+}
+
+impl EfGraph<(), (), ()> {
+    /// Returns a load configuration that can be customized.
+    ///
+    /// By default, the graph is little endian, as Java's `EFGraph` uses the
+    /// native byte order, and both the graph and the offsets are memory
+    /// mapped.
+    pub fn with_basename(basename: impl AsRef<Path>) -> EfLoadConfig<LE, Mmap, Mmap> {
+        EfLoadConfig {
+            basename: PathBuf::from(basename.as_ref()),
+            graph_load_flags: MemoryFlags::empty(),
+            offsets_load_flags: MemoryFlags::empty(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: Endianness, GLM: EfLoadMode, OLM: LoadMode> EfLoadConfig<E, GLM, OLM> {
+    /// Set the byte order of the graph file.
+    pub fn endianness<E2: Endianness>(self) -> EfLoadConfig<E2, GLM, OLM> {
+        EfLoadConfig {
+            basename: self.basename,
+            graph_load_flags: self.graph_load_flags,
+            offsets_load_flags: self.offsets_load_flags,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Choose the [`LoadMode`] for the graph and offsets.
+    pub fn mode<LM: EfLoadMode>(self) -> EfLoadConfig<E, LM, LM> {
+        EfLoadConfig {
+            basename: self.basename,
+            graph_load_flags: self.graph_load_flags,
+            offsets_load_flags: self.offsets_load_flags,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Choose the [`LoadMode`] for the graph only.
+    pub fn graph_mode<NGLM: EfLoadMode>(self) -> EfLoadConfig<E, NGLM, OLM> {
+        EfLoadConfig {
+            basename: self.basename,
+            graph_load_flags: self.graph_load_flags,
+            offsets_load_flags: self.offsets_load_flags,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Choose the [`LoadMode`] for the offsets only.
+    pub fn offsets_mode<NOLM: LoadMode>(self) -> EfLoadConfig<E, GLM, NOLM> {
+        EfLoadConfig {
+            basename: self.basename,
+            graph_load_flags: self.graph_load_flags,
+            offsets_load_flags: self.offsets_load_flags,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: Endianness> EfLoadConfig<E, Mmap, Mmap> {
+    /// Set flags for memory-mapping (both graph and offsets).
+    pub fn flags(self, flags: MemoryFlags) -> Self {
+        EfLoadConfig {
+            graph_load_flags: flags,
+            offsets_load_flags: flags,
+            ..self
+        }
+    }
+}
+
+impl<E: Endianness, OLM: LoadMode> EfLoadConfig<E, Mmap, OLM> {
+    /// Set flags for memory-mapping the graph.
+    pub fn graph_flags(self, flags: MemoryFlags) -> Self {
+        EfLoadConfig {
+            graph_load_flags: flags,
+            ..self
+        }
+    }
+}
+
+impl<E: Endianness, GLM: EfLoadMode> EfLoadConfig<E, GLM, Mmap> {
+    /// Set flags for memory-mapping the offsets.
+    pub fn offsets_flags(self, flags: MemoryFlags) -> Self {
+        EfLoadConfig {
+            offsets_load_flags: flags,
+            ..self
+        }
+    }
+}
+
+impl<E: Endianness, GLM: EfLoadMode, OLM: LoadMode> EfLoadConfig<E, GLM, OLM> {
+    /// Load the graph.
+    ///
+    /// The offsets are read from the `.ef` file, which is not written by
+    /// Java: for graphs written by Java, it can be built from the `.offsets`
+    /// file using `webgraph build ef`.
+    #[allow(clippy::type_complexity)]
+    pub fn load(mut self) -> Result<EfGraph<E, GLM::Words, OLM::Offsets>> {
+        self.basename.set_extension(PROPERTIES_EXTENSION);
+        let properties = parse_ef_properties::<E>(&self.basename).with_context(|| {
+            format!("Could not load properties file {}", self.basename.display())
+        })?;
+        self.basename.set_extension(GRAPH_EXTENSION);
+        let words = GLM::load_words(&self.basename, self.graph_load_flags)
+            .with_context(|| format!("Could not load graph file {}", self.basename.display()))?;
+        self.basename.set_extension(EF_EXTENSION);
+        let offsets =
+            OLM::load_offsets(&self.basename, self.offsets_load_flags).with_context(|| {
+                format!(
+                    "Could not load offsets file {} (use \"webgraph build ef\" to build it)",
+                    self.basename.display()
+                )
+            })?;
+
+        Ok(EfGraph::new(
+            words,
+            offsets,
+            properties.num_nodes,
+            properties.num_arcs,
+            properties.upper_bound,
+            properties.log2_quantum,
+        ))
+    }
+}
+
+/// The content of the `.properties` file of an [`EfGraph`].
+struct EfProperties {
+    num_nodes: usize,
+    num_arcs: u64,
+    upper_bound: usize,
+    log2_quantum: usize,
+}
+
+/// Reads the `.properties` file of an [`EfGraph`], checking the byte order
+/// against the expected one.
+fn parse_ef_properties<E: Endianness>(path: &Path) -> Result<EfProperties> {
+    let name = path.display();
+    let f =
+        std::fs::File::open(path).with_context(|| format!("Cannot open property file {}", name))?;
+    let map = java_properties::read(BufReader::new(f))
+        .with_context(|| format!("cannot parse {} as a java properties file", name))?;
+
+    let get = |key: &str| {
+        map.get(key)
+            .with_context(|| format!("Missing '{}' property in {}", key, name))
+    };
+
+    let graph_class = get("graphclass")?;
+    ensure!(
+        graph_class == EF_GRAPH_CLASS,
+        "Wrong graph class {} in {} (expected {})",
+        graph_class,
+        name,
+        EF_GRAPH_CLASS
+    );
+    let num_nodes = get("nodes")?
+        .parse::<usize>()
+        .with_context(|| format!("Cannot parse 'nodes' as usize in {}", name))?;
+    let num_arcs = get("arcs")?
+        .parse::<u64>()
+        .with_context(|| format!("Cannot parse 'arcs' as u64 in {}", name))?;
+    let upper_bound = match map.get("upperbound") {
+        Some(upper_bound) => upper_bound
+            .parse::<usize>()
+            .with_context(|| format!("Cannot parse 'upperbound' as usize in {}", name))?,
+        None => num_nodes,
+    };
+    let quantum = match map.get("quantum") {
+        Some(quantum) => quantum
+            .parse::<usize>()
+            .with_context(|| format!("Cannot parse 'quantum' as usize in {}", name))?,
+        None => 1 << DEFAULT_LOG2_QUANTUM,
+    };
+    ensure!(
+        quantum.is_power_of_two(),
+        "The quantum {} in {} is not a power of two",
+        quantum,
+        name
+    );
+    match map.get("byteorder").map(String::as_str) {
+        Some(found) if found == byte_order::<E>() => {}
+        Some(found) => bail!(
+            "Wrong byte order {} in {} (expected {})",
+            found,
+            name,
+            byte_order::<E>()
+        ),
+        None => bail!("Missing 'byteorder' property in {}", name),
+    }
+
+    Ok(EfProperties {
+        num_nodes,
+        num_arcs,
+        upper_bound,
+        log2_quantum: quantum.ilog2() as usize,
+    })
+}
+
+/// Returns the endianness of an [`EfGraph`], as specified by the `byteorder`
+/// property, in the format used by [`Endianness::NAME`].
+pub fn get_ef_endianness<P: AsRef<Path>>(basename: P) -> Result<String> {
+    let path = basename.as_ref().with_extension(PROPERTIES_EXTENSION);
+    let f = std::fs::File::open(&path)
+        .with_context(|| format!("Cannot open property file {}", path.display()))?;
+    let map = java_properties::read(BufReader::new(f))
+        .with_context(|| format!("cannot parse {} as a java properties file", path.display()))?;
+
+    match map.get("byteorder").map(String::as_str) {
+        Some("LITTLE_ENDIAN") => Ok(LE::NAME.to_string()),
+        Some("BIG_ENDIAN") => Ok(BE::NAME.to_string()),
+        Some(byte_order) => bail!("Unknown byte order {} in {}", byte_order, path.display()),
+        None => bail!("Missing 'byteorder' property in {}", path.display()),
+    }
+}
@@ -0,0 +1,509 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! An implementation of the Java `EFGraph` format.
+//!
+//! An [`EfGraph`] stores each successor list using the [Elias–Fano
+//! representation of monotone
+//! sequences](https://doi.org/10.1145/2433396.2433409) (quasi-succinct
+//! representation), which provides fast random access and fast
+//! [skipping](EfGraph::successors_from). The files follow the format of
+//! Java's `it.unimi.dsi.webgraph.EFGraph`:
+//!
+//! - the `.graph` file is a sequence of 64-bit words in the byte order
+//!   specified by the `byteorder` property, whose bits are read starting from
+//!   the least significant one;
+//! - the `.offsets` file contains, as in the case of a
+//!   [`BvGraph`](crate::graphs::bvgraph::BvGraph), the γ-coded differences
+//!   between the bit offsets of consecutive successor lists;
+//! - the `.properties` file contains the number of nodes and arcs, the upper
+//!   bound on successors (usually the number of nodes), and the indexing
+//!   quantum.
+//!
+//! The successor list of a node of outdegree *d* is stored at its offset as
+//! the γ code of *d* (using the little-endian conventions of
+//! [`dsi_bitstream`]), followed by:
+//!
+//! - the skip pointers, that is, for each *k* > 0 such that *kq* is at most
+//!   the upper bound shifted right by the number of lower bits, the position
+//!   in the upper bits following the *kq*-th zero, where *q* is the quantum,
+//!   or zero if the upper bits contain less than *kq* zeros;
+//! - the lower bits of the successors;
+//! - the upper bits of the successors, in which the *i*-th successor *x* is
+//!   represented by a one in position `(x >> l) + i`.
+//!
+//! Java stores the offsets in a serialized big list with extension `.obl`,
+//! which cannot be read from Rust; as in the case of a `BvGraph`, offsets are
+//! loaded from an `.ef` file, which is written by
+//! [`store`](EfGraph::store), and which can be built for graphs written by
+//! Java using `webgraph build ef`.
+
+use crate::prelude::*;
+use dsi_bitstream::traits::Endianness;
+use epserde::deser::MemCase;
+use lender::{IntoLender, Lend, Lender, Lending};
+use std::marker::PhantomData;
+use sux::traits::IndexedSeq;
+
+mod load;
+pub use load::*;
+
+mod store;
+
+/// The default base-two logarithm of the indexing quantum.
+pub const DEFAULT_LOG2_QUANTUM: usize = 8;
+
+/// Returns the number of lower bits of a list of given length and upper
+/// bound.
+#[inline(always)]
+fn lower_bits(len: usize, upper_bound: usize) -> usize {
+    if len == 0 || upper_bound < len {
+        0
+    } else {
+        (upper_bound / len).ilog2() as usize
+    }
+}
+
+/// Returns the number of skip pointers of a list of given length and upper
+/// bound.
+#[inline(always)]
+fn num_pointers(len: usize, upper_bound: usize, log2_quantum: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (upper_bound >> lower_bits(len, upper_bound)) >> log2_quantum
+    }
+}
+
+/// Returns the size in bits of the skip pointers of a list of given length
+/// and upper bound.
+///
+/// As in Java, this is the ceiling of the base-two logarithm of the length
+/// plus the upper bound shifted right by the number of lower bits.
+#[inline(always)]
+fn pointer_size(len: usize, upper_bound: usize) -> usize {
+    let max = (len + (upper_bound >> lower_bits(len, upper_bound))) as u64;
+    (u64::BITS - max.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// Returns the word of given index, or zero if the index is out of bounds.
+#[inline(always)]
+fn word<E: Endianness>(words: &[u64], index: usize) -> u64 {
+    let word = words.get(index).copied().unwrap_or(0);
+    if E::IS_LITTLE {
+        u64::from_le(word)
+    } else {
+        u64::from_be(word)
+    }
+}
+
+/// Returns the `width` bits starting at bit position `pos`.
+#[inline(always)]
+fn extract<E: Endianness>(words: &[u64], pos: u64, width: usize) -> u64 {
+    if width == 0 {
+        return 0;
+    }
+    let index = (pos / 64) as usize;
+    let bit = (pos % 64) as usize;
+    let mut result = word::<E>(words, index) >> bit;
+    if bit + width > 64 {
+        result |= word::<E>(words, index + 1) << (64 - bit);
+    }
+    if width < 64 {
+        result & ((1 << width) - 1)
+    } else {
+        result
+    }
+}
+
+/// Reads a γ code at bit position `pos`, returning its value and the position
+/// following the code.
+fn read_gamma<E: Endianness>(words: &[u64], pos: u64) -> (u64, u64) {
+    let mut index = (pos / 64) as usize;
+    let bit = pos % 64;
+    let mut window = word::<E>(words, index) >> bit;
+    let mut zeros = 0;
+    if window == 0 {
+        zeros = 64 - bit;
+        loop {
+            index += 1;
+            assert!(index < words.len(), "γ code at {} past end of graph", pos);
+            window = word::<E>(words, index);
+            if window != 0 {
+                break;
+            }
+            zeros += 64;
+        }
+    }
+    let msb = zeros + window.trailing_zeros() as u64;
+    let pos = pos + msb + 1;
+    let value = extract::<E>(words, pos, msb as usize) | (1 << msb);
+    (value - 1, pos + msb)
+}
+
+/// A graph in the Java `EFGraph` format.
+///
+/// The words of the `.graph` file are stored in `W`, the offsets in `OFF`,
+/// and `E` is the byte order of the words. Instances are usually loaded using
+/// [`with_basename`](EfGraph::with_basename), and written using
+/// [`store`](EfGraph::store).
+#[derive(Debug)]
+pub struct EfGraph<E, W, OFF> {
+    words: W,
+    offsets: MemCase<OFF>,
+    num_nodes: usize,
+    num_arcs: u64,
+    upper_bound: usize,
+    log2_quantum: usize,
+    _marker: PhantomData<E>,
+}
+
+impl<E, W, OFF> EfGraph<E, W, OFF> {
+    /// Creates a new graph from its components.
+    ///
+    /// # Arguments
+    /// - `words`: the words of the `.graph` file.
+    /// - `offsets`: the bit offsets of the successor lists, plus a final
+    ///   offset.
+    /// - `num_nodes`: the number of nodes.
+    /// - `num_arcs`: the number of arcs.
+    /// - `upper_bound`: a strict upper bound on the successors (usually the
+    ///   number of nodes).
+    /// - `log2_quantum`: the base-two logarithm of the indexing quantum.
+    pub fn new(
+        words: W,
+        offsets: MemCase<OFF>,
+        num_nodes: usize,
+        num_arcs: u64,
+        upper_bound: usize,
+        log2_quantum: usize,
+    ) -> Self {
+        Self {
+            words,
+            offsets,
+            num_nodes,
+            num_arcs,
+            upper_bound,
+            log2_quantum,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the strict upper bound on successors used to build the graph.
+    pub fn upper_bound(&self) -> usize {
+        self.upper_bound
+    }
+
+    /// Returns the base-two logarithm of the indexing quantum.
+    pub fn log2_quantum(&self) -> usize {
+        self.log2_quantum
+    }
+
+    /// Consumes the graph, returning its words and offsets.
+    pub fn into_inner(self) -> (W, MemCase<OFF>) {
+        (self.words, self.offsets)
+    }
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    EfGraph<E, W, OFF>
+{
+    /// Returns an iterator over the successors of a node that are greater
+    /// than or equal to `min`, in sorted order.
+    ///
+    /// Smaller successors are skipped using the skip pointers, so the cost
+    /// of skipping is proportional to the quantum rather than to the number
+    /// of skipped successors.
+    pub fn successors_from(&self, node_id: usize, min: usize) -> Succ<'_, E> {
+        let mut succ = self.successors(node_id);
+        succ.skip_to(min);
+        succ
+    }
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    SequentialLabeling for EfGraph<E, W, OFF>
+{
+    type Label = usize;
+    type Lender<'a>
+        = LenderImpl<'a, E, W, OFF>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        Some(self.num_arcs)
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        LenderImpl {
+            graph: self,
+            nodes: (from..self.num_nodes),
+        }
+    }
+}
+
+impl<'a, E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>> IntoLender
+    for &'a EfGraph<E, W, OFF>
+{
+    type Lender = <EfGraph<E, W, OFF> as SequentialLabeling>::Lender<'a>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>> SequentialGraph
+    for EfGraph<E, W, OFF>
+{
+}
+
+impl<E, W, OFF> SplitLabeling for EfGraph<E, W, OFF>
+where
+    E: Endianness,
+    W: AsRef<[u64]> + Send + Sync,
+    OFF: IndexedSeq<Input = usize, Output = usize> + Send + Sync,
+{
+    type SplitLender<'a>
+        = split::ra::Lender<'a, EfGraph<E, W, OFF>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::ra::IntoIterator<'a, EfGraph<E, W, OFF>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::ra::Iter::new(self, how_many)
+    }
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    RandomAccessLabeling for EfGraph<E, W, OFF>
+{
+    type Labels<'succ>
+        = Succ<'succ, E>
+    where
+        Self: 'succ;
+
+    #[inline(always)]
+    fn num_arcs(&self) -> u64 {
+        self.num_arcs
+    }
+
+    #[inline(always)]
+    fn outdegree(&self, node: usize) -> usize {
+        read_gamma::<E>(self.words.as_ref(), self.offsets.get(node) as u64).0 as usize
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        Succ::new(
+            self.words.as_ref(),
+            self.offsets.get(node) as u64,
+            self.upper_bound,
+            self.log2_quantum,
+        )
+    }
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    RandomAccessGraph for EfGraph<E, W, OFF>
+{
+    /// Returns whether there is an arc going from `src_node_id` to
+    /// `dst_node_id`.
+    ///
+    /// This implementation uses [`successors_from`](EfGraph::successors_from),
+    /// so it uses the skip pointers.
+    #[inline(always)]
+    fn has_arc(&self, src_node_id: usize, dst_node_id: usize) -> bool {
+        self.successors_from(src_node_id, dst_node_id).next() == Some(dst_node_id)
+    }
+}
+
+/// A sequential lender over the nodes of an [`EfGraph`] and their successors.
+#[derive(Debug, Clone)]
+pub struct LenderImpl<'a, E, W, OFF> {
+    graph: &'a EfGraph<E, W, OFF>,
+    nodes: core::ops::Range<usize>,
+}
+
+unsafe impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    SortedLender for LenderImpl<'_, E, W, OFF>
+{
+}
+
+impl<'a, E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>>
+    NodeLabelsLender<'_> for LenderImpl<'a, E, W, OFF>
+{
+    type Label = usize;
+    type IntoIterator = Succ<'a, E>;
+}
+
+impl<'a, E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>> Lending<'_>
+    for LenderImpl<'a, E, W, OFF>
+{
+    type Lend = (usize, Succ<'a, E>);
+}
+
+impl<E: Endianness, W: AsRef<[u64]>, OFF: IndexedSeq<Input = usize, Output = usize>> Lender
+    for LenderImpl<'_, E, W, OFF>
+{
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.nodes
+            .next()
+            .map(|node| (node, self.graph.successors(node)))
+    }
+}
+
+/// An iterator over the successors of a node of an [`EfGraph`], in sorted
+/// order.
+#[derive(Debug, Clone)]
+pub struct Succ<'a, E> {
+    words: &'a [u64],
+    /// The outdegree of the node.
+    outdegree: usize,
+    /// The index of the next successor.
+    index: usize,
+    /// The number of lower bits.
+    l: usize,
+    log2_quantum: usize,
+    num_pointers: usize,
+    pointer_size: usize,
+    pointers_start: u64,
+    lower_start: u64,
+    upper_start: u64,
+    /// The index of the current word of the upper bits.
+    curr: usize,
+    /// The current word of the upper bits, with the bits already
+    /// considered cleared.
+    window: u64,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, E: Endianness> Succ<'a, E> {
+    fn new(words: &'a [u64], pos: u64, upper_bound: usize, log2_quantum: usize) -> Self {
+        let (outdegree, pointers_start) = read_gamma::<E>(words, pos);
+        let outdegree = outdegree as usize;
+        let l = lower_bits(outdegree, upper_bound);
+        let num_pointers = num_pointers(outdegree, upper_bound, log2_quantum);
+        let pointer_size = pointer_size(outdegree, upper_bound);
+        let lower_start = pointers_start + (pointer_size * num_pointers) as u64;
+        let upper_start = lower_start + (l * outdegree) as u64;
+        let mut succ = Self {
+            words,
+            outdegree,
+            index: 0,
+            l,
+            log2_quantum,
+            num_pointers,
+            pointer_size,
+            pointers_start,
+            lower_start,
+            upper_start,
+            curr: 0,
+            window: 0,
+            _marker: PhantomData,
+        };
+        succ.set_upper_position(upper_start);
+        succ
+    }
+
+    /// Moves the scan of the upper bits to the given bit position.
+    #[inline(always)]
+    fn set_upper_position(&mut self, pos: u64) {
+        self.curr = (pos / 64) as usize;
+        self.window = word::<E>(self.words, self.curr) & (u64::MAX << (pos % 64));
+    }
+
+    /// Advances the iterator so that the next successor returned, if any, is
+    /// greater than or equal to `min`.
+    pub fn skip_to(&mut self, min: usize) {
+        if self.index >= self.outdegree {
+            return;
+        }
+        let block = ((min >> self.l) >> self.log2_quantum).min(self.num_pointers);
+        if block > 0 {
+            let skip = extract::<E>(
+                self.words,
+                self.pointers_start + ((block - 1) * self.pointer_size) as u64,
+                self.pointer_size,
+            );
+            let Some(index) = skip.checked_sub((block as u64) << self.log2_quantum) else {
+                // There are less than kq zeros, so all successors are smaller
+                // than min
+                self.index = self.outdegree;
+                return;
+            };
+            let index = index as usize;
+            if index > self.index {
+                self.index = index;
+                self.set_upper_position(self.upper_start + skip);
+            }
+        }
+        loop {
+            let (index, curr, window) = (self.index, self.curr, self.window);
+            match self.next() {
+                Some(succ) if succ < min => {}
+                Some(_) => {
+                    (self.index, self.curr, self.window) = (index, curr, window);
+                    return;
+                }
+                None => return,
+            }
+        }
+    }
+}
+
+impl<E: Endianness> Iterator for Succ<'_, E> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.outdegree {
+            return None;
+        }
+        while self.window == 0 {
+            self.curr += 1;
+            assert!(self.curr < self.words.len(), "Upper bits past end of graph");
+            self.window = word::<E>(self.words, self.curr);
+        }
+        let one = self.curr as u64 * 64 + self.window.trailing_zeros() as u64 - self.upper_start;
+        self.window &= self.window - 1;
+        let high = one - self.index as u64;
+        let low = extract::<E>(
+            self.words,
+            self.lower_start + (self.index * self.l) as u64,
+            self.l,
+        );
+        self.index += 1;
+        Some(((high << self.l) | low) as usize)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<E: Endianness> ExactSizeIterator for Succ<'_, E> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.outdegree - self.index
+    }
+}
+
+unsafe impl<E: Endianness> SortedIterator for Succ<'_, E> {}
@@ -0,0 +1,260 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::*;
+use crate::graphs::bvgraph::{
+    store_ef, EF_EXTENSION, GRAPH_EXTENSION, OFFSETS_EXTENSION, PROPERTIES_EXTENSION,
+};
+use anyhow::{ensure, Context, Result};
+use dsi_bitstream::prelude::*;
+use dsi_progress_logger::prelude::*;
+use lender::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// A bit writer filling 64-bit words starting from the least significant bit,
+/// and writing them in the byte order given by `E`.
+struct WordWriter<E: Endianness, W: Write> {
+    writer: W,
+    buffer: u64,
+    /// The number of bits used in `buffer`; always smaller than 64.
+    filled: usize,
+    /// The number of bits written so far.
+    written_bits: u64,
+    _marker: PhantomData<E>,
+}
+
+impl<E: Endianness, W: Write> WordWriter<E, W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: 0,
+            filled: 0,
+            written_bits: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn write_word(&mut self, word: u64) -> std::io::Result<()> {
+        if E::IS_LITTLE {
+            self.writer.write_all(&word.to_le_bytes())
+        } else {
+            self.writer.write_all(&word.to_be_bytes())
+        }
+    }
+
+    /// Writes the lowest `width` bits of `value`, which must be zero
+    /// elsewhere.
+    fn write_bits(&mut self, value: u64, width: usize) -> std::io::Result<()> {
+        debug_assert!(width <= 64);
+        debug_assert!(width == 64 || value >> width == 0);
+        if width == 0 {
+            return Ok(());
+        }
+        self.written_bits += width as u64;
+        self.buffer |= value << self.filled;
+        if self.filled + width < 64 {
+            self.filled += width;
+            return Ok(());
+        }
+        self.write_word(self.buffer)?;
+        self.buffer = if self.filled == 0 {
+            0
+        } else {
+            value >> (64 - self.filled)
+        };
+        self.filled = self.filled + width - 64;
+        Ok(())
+    }
+
+    fn write_zeros(&mut self, mut n: u64) -> std::io::Result<()> {
+        while n > 0 {
+            let width = n.min(64) as usize;
+            self.write_bits(0, width)?;
+            n -= width as u64;
+        }
+        Ok(())
+    }
+
+    fn write_gamma(&mut self, n: u64) -> std::io::Result<()> {
+        let n = n + 1;
+        let msb = n.ilog2() as usize;
+        self.write_zeros(msb as u64)?;
+        self.write_bits(1, 1)?;
+        self.write_bits(n & !(1 << msb), msb)
+    }
+
+    /// Writes the partially filled word, if any, and flushes the writer.
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.filled > 0 {
+            self.write_word(self.buffer)?;
+            self.buffer = 0;
+            self.filled = 0;
+        }
+        self.writer.flush()
+    }
+}
+
+/// Writes the successor list of a node, returning the number of bits
+/// written.
+fn write_successors<E: Endianness, W: Write>(
+    writer: &mut WordWriter<E, W>,
+    succ: &[usize],
+    upper_bound: usize,
+    log2_quantum: usize,
+) -> std::io::Result<u64> {
+    let start = writer.written_bits;
+    let len = succ.len();
+    writer.write_gamma(len as u64)?;
+    let l = lower_bits(len, upper_bound);
+    let pointer_size = pointer_size(len, upper_bound);
+    // The k-th pointer is the position in the upper bits following the
+    // (kq)-th zero, that is, kq plus the number of successors whose upper
+    // bits are smaller than kq; as in Java, it is zero if there is no such
+    // zero, that is, if all upper bits are smaller than kq
+    let mut index = 0;
+    for k in 1..=num_pointers(len, upper_bound, log2_quantum) {
+        let zeros = k << log2_quantum;
+        while index < len && succ[index] >> l < zeros {
+            index += 1;
+        }
+        let pointer = if index < len { zeros + index } else { 0 };
+        writer.write_bits(pointer as u64, pointer_size)?;
+    }
+    let mask = (1_u64 << l) - 1;
+    for &s in succ {
+        writer.write_bits(s as u64 & mask, l)?;
+    }
+    let mut last_high = 0;
+    for &s in succ {
+        let high = s >> l;
+        writer.write_zeros((high - last_high) as u64)?;
+        writer.write_bits(1, 1)?;
+        last_high = high;
+    }
+    Ok(writer.written_bits - start)
+}
+
+impl EfGraph<(), (), ()> {
+    /// Writes a graph in the Java `EFGraph` format with the given basename,
+    /// returning the length in bits of the `.graph` file.
+    ///
+    /// Besides the `.graph` and `.properties` files, this method writes the
+    /// `.offsets` file, as Java does, and the `.ef` file needed to load the
+    /// graph. The upper bound on successors is the number of nodes of the
+    /// graph, and `E` is the byte order of the graph file.
+    ///
+    /// # Arguments
+    /// - `basename`: the basename of the files.
+    /// - `graph`: the graph to write, whose successor lists must be sorted.
+    /// - `log2_quantum`: the base-two logarithm of the indexing quantum (see
+    ///   [`DEFAULT_LOG2_QUANTUM`]).
+    pub fn store<E: Endianness>(
+        basename: impl AsRef<Path>,
+        graph: &impl SequentialGraph,
+        log2_quantum: usize,
+    ) -> Result<u64> {
+        ensure!(
+            log2_quantum < 64,
+            "The base-two logarithm of the quantum must be smaller than 64"
+        );
+        let basename = basename.as_ref();
+        let num_nodes = graph.num_nodes();
+        let upper_bound = num_nodes;
+
+        let graph_path = basename.with_extension(GRAPH_EXTENSION);
+        let mut writer = WordWriter::<E, _>::new(BufWriter::with_capacity(
+            1 << 20,
+            File::create(&graph_path)
+                .with_context(|| format!("Could not create {}", graph_path.display()))?,
+        ));
+        let offsets_path = basename.with_extension(OFFSETS_EXTENSION);
+        let mut offsets_writer =
+            <BufBitWriter<BE, _>>::new(<WordAdapter<usize, _>>::new(BufWriter::with_capacity(
+                1 << 20,
+                File::create(&offsets_path)
+                    .with_context(|| format!("Could not create {}", offsets_path.display()))?,
+            )));
+        offsets_writer
+            .write_gamma(0)
+            .context("Could not write initial delta")?;
+
+        let mut pl = ProgressLogger::default();
+        pl.display_memory(true)
+            .item_name("node")
+            .expected_updates(Some(num_nodes));
+        pl.start("Writing successor lists...");
+
+        let mut num_arcs = 0;
+        let mut succ = Vec::new();
+        for_!((node, successors) in graph.iter() {
+            succ.clear();
+            succ.extend(successors);
+            for (i, &s) in succ.iter().enumerate() {
+                ensure!(
+                    s < upper_bound,
+                    "Successor {} of node {} is not smaller than the number of nodes {}",
+                    s,
+                    node,
+                    upper_bound
+                );
+                ensure!(
+                    i == 0 || succ[i - 1] < s,
+                    "The successors of node {} are not strictly increasing",
+                    node
+                );
+            }
+            num_arcs += succ.len() as u64;
+            let bits = write_successors(&mut writer, &succ, upper_bound, log2_quantum)
+                .with_context(|| format!("Could not write {}", graph_path.display()))?;
+            offsets_writer
+                .write_gamma(bits)
+                .context("Could not write delta")?;
+            pl.light_update();
+        });
+        pl.done();
+
+        let bitstream_len = writer.written_bits;
+        writer
+            .flush()
+            .with_context(|| format!("Could not flush {}", graph_path.display()))?;
+        BitWrite::flush(&mut offsets_writer).context("Could not flush offsets")?;
+        drop(offsets_writer);
+
+        log::info!("Writing the .properties file");
+        let properties = format!(
+            "#EFGraph properties\n\
+             graphclass={}\n\
+             version=0\n\
+             nodes={}\n\
+             arcs={}\n\
+             upperbound={}\n\
+             quantum={}\n\
+             byteorder={}\n",
+            EF_GRAPH_CLASS,
+            num_nodes,
+            num_arcs,
+            upper_bound,
+            1_u64 << log2_quantum,
+            byte_order::<E>(),
+        );
+        let properties_path = basename.with_extension(PROPERTIES_EXTENSION);
+        std::fs::write(&properties_path, properties)
+            .with_context(|| format!("Could not write {}", properties_path.display()))?;
+
+        log::info!("Building the Elias–Fano representation of the offsets");
+        store_ef(
+            &offsets_path,
+            num_nodes,
+            bitstream_len,
+            basename.with_extension(EF_EXTENSION),
+        )?;
+
+        Ok(bitstream_len)
+    }
+}
@@ -0,0 +1,288 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Deterministic families of graphs.
+//!
+//! All graphs in this module are undirected (i.e., every arc appears in both
+//! directions) and loopless, and their successors are computed on the fly, so
+//! they use constant space. They implement [`RandomAccessGraph`] and
+//! [`SplitLabeling`], so they can be compressed in parallel with
+//! [`BvComp::parallel_graph`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use webgraph::prelude::*;
+//! # use webgraph::graphs::families::Grid;
+//! let grid = Grid::new(2, 3);
+//! assert_eq!(grid.num_nodes(), 6);
+//! assert_eq!(grid.num_arcs(), 14);
+//! assert_eq!(grid.successors(1).collect::<Vec<_>>(), vec![0, 2, 4]);
+//! ```
+
+use crate::prelude::*;
+use std::iter::{Chain, Flatten};
+use std::ops::Range;
+
+/// The successors of a node with a bounded number of possible neighbors.
+pub type NeighborSucc<const N: usize> =
+    AssumeSortedIterator<Flatten<std::array::IntoIter<Option<usize>, N>>>;
+
+/// Returns the successors given by the elements set in `neighbors`, which
+/// must be sorted.
+#[inline(always)]
+fn neighbors<const N: usize>(neighbors: [Option<usize>; N]) -> NeighborSucc<N> {
+    unsafe { AssumeSortedIterator::new(neighbors.into_iter().flatten()) }
+}
+
+/// A two-dimensional grid with a given number of rows and columns.
+///
+/// Node `r * cols + c` is the node in row `r` and column `c`, and it is
+/// adjacent to the nodes immediately above, below, on the left and on the
+/// right, if they exist.
+#[derive(Debug, Clone, Copy)]
+pub struct Grid {
+    rows: usize,
+    cols: usize,
+    num_nodes: usize,
+}
+
+impl Grid {
+    /// Creates a new grid with the given number of rows and columns.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            num_nodes: rows * cols,
+        }
+    }
+}
+
+impl RandomAccessLabeling for Grid {
+    type Labels<'a> = NeighborSucc<4>;
+
+    fn num_arcs(&self) -> u64 {
+        let (rows, cols) = (self.rows as u64, self.cols as u64);
+        2 * (rows * cols.saturating_sub(1) + cols * rows.saturating_sub(1))
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        <Self as RandomAccessLabeling>::labels(self, node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> NeighborSucc<4> {
+        let (row, col) = (node / self.cols, node % self.cols);
+        neighbors([
+            (row > 0).then(|| node - self.cols),
+            (col > 0).then(|| node - 1),
+            (col + 1 < self.cols).then(|| node + 1),
+            (row + 1 < self.rows).then(|| node + self.cols),
+        ])
+    }
+}
+
+impl_ra_graph!(Grid);
+
+/// A path, in which node `x` is adjacent to nodes `x - 1` and `x + 1`, if
+/// they exist.
+#[derive(Debug, Clone, Copy)]
+pub struct Path {
+    num_nodes: usize,
+}
+
+impl Path {
+    /// Creates a new path with the given number of nodes.
+    pub fn new(num_nodes: usize) -> Self {
+        Self { num_nodes }
+    }
+}
+
+impl RandomAccessLabeling for Path {
+    type Labels<'a> = NeighborSucc<2>;
+
+    fn num_arcs(&self) -> u64 {
+        2 * self.num_nodes.saturating_sub(1) as u64
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        <Self as RandomAccessLabeling>::labels(self, node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> NeighborSucc<2> {
+        neighbors([
+            node.checked_sub(1),
+            (node + 1 < self.num_nodes).then_some(node + 1),
+        ])
+    }
+}
+
+impl_ra_graph!(Path);
+
+/// A cycle, in which node `x` is adjacent to nodes `x - 1` and `x + 1` modulo
+/// the number of nodes.
+///
+/// Since there are no loops or multiple arcs, cycles with less than three nodes
+/// are paths.
+#[derive(Debug, Clone, Copy)]
+pub struct Cycle {
+    num_nodes: usize,
+}
+
+impl Cycle {
+    /// Creates a new cycle with the given number of nodes.
+    pub fn new(num_nodes: usize) -> Self {
+        Self { num_nodes }
+    }
+}
+
+impl RandomAccessLabeling for Cycle {
+    type Labels<'a> = NeighborSucc<2>;
+
+    fn num_arcs(&self) -> u64 {
+        match self.num_nodes {
+            0 | 1 => 0,
+            2 => 2,
+            n => 2 * n as u64,
+        }
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        <Self as RandomAccessLabeling>::labels(self, node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> NeighborSucc<2> {
+        let n = self.num_nodes;
+        if n < 3 {
+            return <Path as RandomAccessLabeling>::labels(&Path::new(n), node);
+        }
+        let (pred, succ) = ((node + n - 1) % n, (node + 1) % n);
+        neighbors([Some(pred.min(succ)), Some(pred.max(succ))])
+    }
+}
+
+impl_ra_graph!(Cycle);
+
+/// A star, in which node 0 (the center) is adjacent to all other nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct Star {
+    num_nodes: usize,
+}
+
+impl Star {
+    /// Creates a new star with the given number of nodes, including the
+    /// center.
+    pub fn new(num_nodes: usize) -> Self {
+        Self { num_nodes }
+    }
+}
+
+impl RandomAccessLabeling for Star {
+    type Labels<'a> = AssumeSortedIterator<Range<usize>>;
+
+    fn num_arcs(&self) -> u64 {
+        2 * self.num_nodes.saturating_sub(1) as u64
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        if node == 0 {
+            self.num_nodes - 1
+        } else {
+            1
+        }
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> AssumeSortedIterator<Range<usize>> {
+        let range = if node == 0 { 1..self.num_nodes } else { 0..1 };
+        unsafe { AssumeSortedIterator::new(range) }
+    }
+}
+
+impl_ra_graph!(Star);
+
+/// A complete graph, in which every node is adjacent to all other nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct Complete {
+    num_nodes: usize,
+}
+
+impl Complete {
+    /// Creates a new complete graph with the given number of nodes.
+    pub fn new(num_nodes: usize) -> Self {
+        Self { num_nodes }
+    }
+}
+
+impl RandomAccessLabeling for Complete {
+    type Labels<'a> = AssumeSortedIterator<Chain<Range<usize>, Range<usize>>>;
+
+    fn num_arcs(&self) -> u64 {
+        let n = self.num_nodes as u64;
+        n * n.saturating_sub(1)
+    }
+
+    fn outdegree(&self, _node: usize) -> usize {
+        self.num_nodes - 1
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> AssumeSortedIterator<Chain<Range<usize>, Range<usize>>> {
+        unsafe { AssumeSortedIterator::new((0..node).chain(node + 1..self.num_nodes)) }
+    }
+}
+
+impl_ra_graph!(Complete);
+
+/// A complete bipartite graph, in which each of the first `left` nodes is
+/// adjacent to each of the following `right` nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct CompleteBipartite {
+    left: usize,
+    num_nodes: usize,
+}
+
+impl CompleteBipartite {
+    /// Creates a new complete bipartite graph with the given number of nodes
+    /// on each side.
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            left,
+            num_nodes: left + right,
+        }
+    }
+}
+
+impl RandomAccessLabeling for CompleteBipartite {
+    type Labels<'a> = AssumeSortedIterator<Range<usize>>;
+
+    fn num_arcs(&self) -> u64 {
+        2 * self.left as u64 * (self.num_nodes - self.left) as u64
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        if node < self.left {
+            self.num_nodes - self.left
+        } else {
+            self.left
+        }
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> AssumeSortedIterator<Range<usize>> {
+        let range = if node < self.left {
+            self.left..self.num_nodes
+        } else {
+            0..self.left
+        };
+        unsafe { AssumeSortedIterator::new(range) }
+    }
+}
+
+impl_ra_graph!(CompleteBipartite);
@@ -0,0 +1,382 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::graphs::arc_list_graph::Iter;
+use crate::labels::Left;
+use crate::traits::*;
+use anyhow::{ensure, Context, Result};
+use lender::*;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// The format of a file of arcs read by a [`FileArcListGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcFileFormat {
+    /// One arc per line, with source and target separated by whitespace
+    /// (e.g., TSV); empty lines and lines starting with `#` are ignored.
+    Text,
+    /// Pairs of big-endian `u32`.
+    U32,
+    /// Pairs of big-endian `u64`.
+    U64,
+}
+
+impl ArcFileFormat {
+    /// Returns the length in bytes of an arc in a binary format.
+    fn arc_len(&self) -> Option<u64> {
+        match self {
+            ArcFileFormat::Text => None,
+            ArcFileFormat::U32 => Some(8),
+            ArcFileFormat::U64 => Some(16),
+        }
+    }
+}
+
+/// An adapter exhibiting a file of arcs sorted by source as a [labeled
+/// sequential graph](LabeledSequentialGraph) with labels of type `()`.
+///
+/// Differently from an [`ArcListGraph`](super::arc_list_graph::ArcListGraph),
+/// the arcs are not kept in memory, but rather read from the file, which can be
+/// in any of the [supported formats](ArcFileFormat), at each iteration. The
+/// graph can be [split](SplitLabeling) by seeking to byte offsets at arc
+/// boundaries, so it can be compressed in parallel by
+/// [`BvComp::parallel_graph`](crate::graphs::bvgraph::BvComp::parallel_graph),
+/// or transposed by [`transpose_split`](crate::transform::transpose_split),
+/// without sorting the arcs.
+///
+/// The arcs must be sorted by source, and, for the successors of the graph
+/// to be sorted, by target within the same source; moreover, all sources
+/// must be smaller than the number of nodes. Errors while reading the file
+/// will cause a panic.
+///
+/// # Examples
+///
+/// ```
+/// # use webgraph::prelude::*;
+/// # use webgraph::graphs::file_arc_list_graph::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let dir = tempfile::tempdir()?;
+/// let path = dir.path().join("arcs.tsv");
+/// std::fs::write(&path, "# A small graph\n0\t1\n0\t2\n2\t0\n")?;
+/// let graph = FileArcListGraph::new(&path, ArcFileFormat::Text, 3)?;
+/// graph::eq(&graph, &VecGraph::from_arcs([(0, 1), (0, 2), (2, 0)]))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileArcListGraph {
+    path: PathBuf,
+    format: ArcFileFormat,
+    num_nodes: usize,
+    /// The length of the file in bytes.
+    len: u64,
+}
+
+impl FileArcListGraph {
+    /// Creates a new arc-list graph with the given number of nodes from a
+    /// file in the given format.
+    ///
+    /// # Implementation Notes
+    ///
+    /// Note that the resulting graph will be an arc-list graph labeled by the
+    /// unit type `()` wrapped into a [left
+    /// projection](crate::prelude::proj::Left).
+    pub fn new(
+        path: impl AsRef<Path>,
+        format: ArcFileFormat,
+        num_nodes: usize,
+    ) -> Result<Left<Self>> {
+        Ok(Left(Self::new_labeled(path, format, num_nodes)?))
+    }
+
+    /// Creates a new arc-list graph with the given number of nodes from a
+    /// file in the given format, labeling arcs with `()`.
+    pub fn new_labeled(
+        path: impl AsRef<Path>,
+        format: ArcFileFormat,
+        num_nodes: usize,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let len = std::fs::metadata(&path)
+            .with_context(|| format!("Could not stat {}", path.display()))?
+            .len();
+        if let Some(arc_len) = format.arc_len() {
+            ensure!(
+                len % arc_len == 0,
+                "The length of {} ({} bytes) is not a multiple of {}",
+                path.display(),
+                len,
+                arc_len
+            );
+        }
+        Ok(Self {
+            path,
+            format,
+            num_nodes,
+            len,
+        })
+    }
+
+    /// Returns the arcs in the given range of bytes.
+    fn arcs(&self, bytes: core::ops::Range<u64>) -> ArcRange<'_> {
+        ArcRange {
+            path: &self.path,
+            format: self.format,
+            bytes,
+        }
+    }
+
+    /// Returns the first position at or after `offset` at which the source
+    /// of the arcs changes, as a byte offset and a node: the arcs before the
+    /// byte offset have sources smaller than the node, and the remaining ones
+    /// have sources greater than or equal to the node.
+    fn boundary(&self, offset: u64) -> io::Result<(u64, usize)> {
+        if offset == 0 {
+            return Ok((0, 0));
+        }
+        let mut reader = match self.format.arc_len() {
+            Some(arc_len) => {
+                ArcReader::new(&self.path, self.format, offset.next_multiple_of(arc_len))?
+            }
+            None => {
+                // Move to the start of the next line
+                let mut reader = ArcReader::new(&self.path, self.format, offset - 1)?;
+                reader.skip_line()?;
+                reader
+            }
+        };
+        let end = (self.len, self.num_nodes);
+        let Some((_, src, _)) = reader.read_arc()? else {
+            return Ok(end);
+        };
+        while let Some((pos, next_src, _)) = reader.read_arc()? {
+            if next_src != src {
+                return Ok((pos, src + 1));
+            }
+        }
+        Ok(end)
+    }
+}
+
+impl SequentialLabeling for FileArcListGraph {
+    type Label = (usize, ());
+    type Lender<'node>
+        = Iter<(), ArcRange<'node>>
+    where
+        Self: 'node;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        self.format.arc_len().map(|arc_len| self.len / arc_len)
+    }
+
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        let mut iter = Iter::new(self.num_nodes, self.arcs(0..self.len).into_iter());
+        for _ in 0..from {
+            iter.next();
+        }
+        iter
+    }
+}
+
+impl LabeledSequentialGraph<()> for FileArcListGraph {}
+
+impl<'a> IntoLender for &'a FileArcListGraph {
+    type Lender = <FileArcListGraph as SequentialLabeling>::Lender<'a>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl SplitLabeling for FileArcListGraph {
+    type SplitLender<'a>
+        = Iter<(), ArcRange<'a>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = Vec<Self::SplitLender<'a>>
+    where
+        Self: 'a;
+
+    /// Returns `how_many` lenders, each reading about the same number of
+    /// bytes; some lenders might be empty if there are few sources.
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        let boundaries = (0..how_many)
+            .map(|i| self.boundary(self.len * i as u64 / how_many as u64))
+            .chain([Ok((self.len, self.num_nodes))])
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap_or_else(|e| panic!("Could not split {}: {}", self.path.display(), e));
+        boundaries
+            .windows(2)
+            .map(|w| {
+                let ((start, first_node), (end, last_node)) = (w[0], w[1]);
+                Iter::with_range(first_node..last_node, self.arcs(start..end).into_iter())
+            })
+            .collect()
+    }
+}
+
+/// The arcs of a [`FileArcListGraph`] starting in a range of bytes.
+#[derive(Debug, Clone)]
+pub struct ArcRange<'a> {
+    path: &'a Path,
+    format: ArcFileFormat,
+    bytes: core::ops::Range<u64>,
+}
+
+impl<'a> IntoIterator for ArcRange<'a> {
+    type Item = (usize, usize, ());
+    type IntoIter = Arcs<'a>;
+
+    fn into_iter(self) -> Arcs<'a> {
+        Arcs {
+            range: self,
+            reader: None,
+        }
+    }
+}
+
+/// An iterator on the arcs of a [`FileArcListGraph`] starting in a range of
+/// bytes.
+///
+/// The file is opened lazily, so that a large number of iterators can be
+/// created at the same time.
+#[derive(Debug)]
+pub struct Arcs<'a> {
+    range: ArcRange<'a>,
+    reader: Option<ArcReader>,
+}
+
+impl Iterator for Arcs<'_> {
+    type Item = (usize, usize, ());
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = &self.range;
+        let reader = self.reader.get_or_insert_with(|| {
+            ArcReader::new(range.path, range.format, range.bytes.start)
+                .unwrap_or_else(|e| panic!("Could not read {}: {}", range.path.display(), e))
+        });
+        if reader.pos >= range.bytes.end {
+            return None;
+        }
+        match reader.read_arc() {
+            Ok(Some((pos, src, dst))) if pos < range.bytes.end => Some((src, dst, ())),
+            Ok(_) => {
+                // Only ignored lines were left in the range
+                reader.pos = range.bytes.end;
+                None
+            }
+            Err(e) => panic!("Could not read {}: {}", range.path.display(), e),
+        }
+    }
+}
+
+// Arc files are required to be sorted
+unsafe impl SortedIterator for Arcs<'_> {}
+
+/// A reader of arcs from a file, keeping track of the current byte offset.
+#[derive(Debug)]
+struct ArcReader {
+    reader: BufReader<File>,
+    format: ArcFileFormat,
+    /// The byte offset of the next arc.
+    pos: u64,
+    line: String,
+}
+
+impl ArcReader {
+    fn new(path: &Path, format: ArcFileFormat, pos: u64) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(pos))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            format,
+            pos,
+            line: String::new(),
+        })
+    }
+
+    /// Skips the rest of the current line.
+    fn skip_line(&mut self) -> io::Result<()> {
+        self.line.clear();
+        self.pos += self.reader.read_line(&mut self.line)? as u64;
+        Ok(())
+    }
+
+    /// Returns the byte offset, the source and the target of the next arc,
+    /// or `None` at the end of the file.
+    fn read_arc(&mut self) -> io::Result<Option<(u64, usize, usize)>> {
+        let start = self.pos;
+        match self.format {
+            ArcFileFormat::Text => loop {
+                let start = self.pos;
+                self.skip_line()?;
+                if self.pos == start {
+                    return Ok(None);
+                }
+                let line = self.line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let mut fields = line.split_whitespace().map(str::parse::<usize>);
+                match (fields.next(), fields.next()) {
+                    (Some(Ok(src)), Some(Ok(dst))) => return Ok(Some((start, src, dst))),
+                    _ => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("Could not parse arc {:?} at byte {}", line, start),
+                        ))
+                    }
+                }
+            },
+            ArcFileFormat::U32 => {
+                let mut buf = [0; 8];
+                if !self.read_bytes(&mut buf)? {
+                    return Ok(None);
+                }
+                let (src, dst) = buf.split_at(4);
+                Ok(Some((
+                    start,
+                    u32::from_be_bytes(src.try_into().unwrap()) as usize,
+                    u32::from_be_bytes(dst.try_into().unwrap()) as usize,
+                )))
+            }
+            ArcFileFormat::U64 => {
+                let mut buf = [0; 16];
+                if !self.read_bytes(&mut buf)? {
+                    return Ok(None);
+                }
+                let (src, dst) = buf.split_at(8);
+                Ok(Some((
+                    start,
+                    u64::from_be_bytes(src.try_into().unwrap()) as usize,
+                    u64::from_be_bytes(dst.try_into().unwrap()) as usize,
+                )))
+            }
+        }
+    }
+
+    /// Fills `buf`, returning false at the end of the file.
+    fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<bool> {
+        match self.reader.read_exact(buf) {
+            Ok(()) => {
+                self.pos += buf.len() as u64;
+                Ok(true)
+            }
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
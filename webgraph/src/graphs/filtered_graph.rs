@@ -0,0 +1,336 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::prelude::*;
+use crate::utils::NumArcsCache;
+use lender::*;
+
+/// The label of a labeling representing a successor, possibly together with
+/// the label of the arc.
+///
+/// This trait is implemented by `usize`, the label of a [graph](SequentialGraph),
+/// in which case the arc label is `()`, and by `(usize, L)`, the label of a
+/// [labeled graph](LabeledSequentialGraph), in which case the arc label is `L`.
+pub trait SuccessorLabel {
+    /// The type of the label of the arc.
+    type ArcLabel;
+    /// Returns the successor.
+    fn successor(&self) -> usize;
+    /// Returns the label of the arc.
+    fn arc_label(&self) -> &Self::ArcLabel;
+}
+
+impl SuccessorLabel for usize {
+    type ArcLabel = ();
+
+    #[inline(always)]
+    fn successor(&self) -> usize {
+        *self
+    }
+
+    #[inline(always)]
+    fn arc_label(&self) -> &() {
+        &()
+    }
+}
+
+impl<L> SuccessorLabel for (usize, L) {
+    type ArcLabel = L;
+
+    #[inline(always)]
+    fn successor(&self) -> usize {
+        self.0
+    }
+
+    #[inline(always)]
+    fn arc_label(&self) -> &L {
+        &self.1
+    }
+}
+
+/// A wrapper keeping only the arcs of a (possibly labeled) graph satisfying a
+/// predicate.
+///
+/// The predicate is a closure receiving the source, the target and the label
+/// of an arc (`()` for graphs), and returning whether the arc should be kept.
+/// The order of nodes and of successors is preserved, so if the underlying
+/// graph is [sorted](SortedLender), so is the filtered graph.
+///
+/// Since we don't know how many arcs will be removed, the number of arcs is
+/// [computed lazily](NumArcsCache).
+///
+/// # Examples
+///
+/// ```
+/// # use webgraph::prelude::*;
+/// let graph = VecGraph::from_arcs([(0, 1), (1, 0), (1, 2), (2, 3)]);
+/// // Keep only arcs between nodes with the same parity
+/// let filtered = FilteredGraph::new(graph, |src, dst, _| (src ^ dst) & 1 == 0);
+/// assert_eq!(filtered.num_arcs(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FilteredGraph<G, F> {
+    graph: G,
+    filter: F,
+    num_arcs: NumArcsCache,
+}
+
+impl<G: SequentialLabeling, F> FilteredGraph<G, F>
+where
+    G::Label: SuccessorLabel,
+    F: Fn(usize, usize, &<G::Label as SuccessorLabel>::ArcLabel) -> bool,
+{
+    /// Creates a view of `graph` containing only the arcs satisfying
+    /// `filter`.
+    pub fn new(graph: G, filter: F) -> Self {
+        Self {
+            graph,
+            filter,
+            num_arcs: NumArcsCache::default(),
+        }
+    }
+}
+
+impl<G, F> FilteredGraph<G, F> {
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Returns the underlying graph and the filter.
+    pub fn into_inner(self) -> (G, F) {
+        (self.graph, self.filter)
+    }
+}
+
+impl<G: SequentialLabeling, F> SequentialLabeling for FilteredGraph<G, F>
+where
+    G::Label: SuccessorLabel,
+    F: Fn(usize, usize, &<G::Label as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Label = G::Label;
+    type Lender<'b>
+        = Iter<'b, G::Lender<'b>, F>
+    where
+        Self: 'b;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        self.num_arcs.get()
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        Iter {
+            iter: self.graph.iter_from(from),
+            filter: &self.filter,
+        }
+    }
+}
+
+impl<G: SequentialLabeling, F> SplitLabeling for FilteredGraph<G, F>
+where
+    G::Label: SuccessorLabel,
+    F: Fn(usize, usize, &<G::Label as SuccessorLabel>::ArcLabel) -> bool + Sync,
+    for<'a> G::Lender<'a>: Clone + Send + Sync,
+{
+    type SplitLender<'a>
+        = split::seq::Lender<'a, FilteredGraph<G, F>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::seq::IntoIterator<'a, FilteredGraph<G, F>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::seq::Iter::new(self.iter(), self.num_nodes(), how_many)
+    }
+}
+
+impl<G: SequentialGraph, F: Fn(usize, usize, &()) -> bool> SequentialGraph for FilteredGraph<G, F> {}
+
+impl<L, G: LabeledSequentialGraph<L>, F: Fn(usize, usize, &L) -> bool> LabeledSequentialGraph<L>
+    for FilteredGraph<G, F>
+{
+}
+
+impl<'b, G: SequentialLabeling, F> IntoLender for &'b FilteredGraph<G, F>
+where
+    G::Label: SuccessorLabel,
+    F: Fn(usize, usize, &<G::Label as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Lender = <FilteredGraph<G, F> as SequentialLabeling>::Lender<'b>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<G: RandomAccessLabeling, F> RandomAccessLabeling for FilteredGraph<G, F>
+where
+    G::Label: SuccessorLabel,
+    F: Fn(usize, usize, &<G::Label as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Labels<'succ>
+        = Succ<'succ, <G::Labels<'succ> as IntoIterator>::IntoIter, F>
+    where
+        Self: 'succ;
+
+    fn num_arcs(&self) -> u64 {
+        self.num_arcs.get_or_count(self)
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        <Self as RandomAccessLabeling>::labels(self, node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        Succ {
+            src: node,
+            iter: self.graph.labels(node).into_iter(),
+            filter: &self.filter,
+        }
+    }
+}
+
+impl<G: RandomAccessGraph, F: Fn(usize, usize, &()) -> bool> RandomAccessGraph
+    for FilteredGraph<G, F>
+{
+}
+
+impl<L, G: LabeledRandomAccessGraph<L>, F: Fn(usize, usize, &L) -> bool> LabeledRandomAccessGraph<L>
+    for FilteredGraph<G, F>
+{
+}
+
+/// An iterator over the nodes of a [`FilteredGraph`].
+#[derive(Debug)]
+pub struct Iter<'a, L, F> {
+    iter: L,
+    filter: &'a F,
+}
+
+impl<L: Clone, F> Clone for Iter<'_, L, F> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            filter: self.filter,
+        }
+    }
+}
+
+impl<'succ, L, F> NodeLabelsLender<'succ> for Iter<'_, L, F>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next>,
+    for<'next> LenderLabel<'next, L>: SuccessorLabel,
+    F: for<'next> Fn(usize, usize, &<LenderLabel<'next, L> as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Label = LenderLabel<'succ, L>;
+    type IntoIterator = Succ<'succ, LenderIntoIter<'succ, L>, F>;
+}
+
+impl<'succ, L, F> Lending<'succ> for Iter<'_, L, F>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next>,
+    for<'next> LenderLabel<'next, L>: SuccessorLabel,
+    F: for<'next> Fn(usize, usize, &<LenderLabel<'next, L> as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Lend = (usize, <Self as NodeLabelsLender<'succ>>::IntoIterator);
+}
+
+unsafe impl<L: SortedLender, F> SortedLender for Iter<'_, L, F>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next>,
+    for<'next> LenderLabel<'next, L>: SuccessorLabel,
+    F: for<'next> Fn(usize, usize, &<LenderLabel<'next, L> as SuccessorLabel>::ArcLabel) -> bool,
+{
+}
+
+impl<L, F> Lender for Iter<'_, L, F>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next>,
+    for<'next> LenderLabel<'next, L>: SuccessorLabel,
+    F: for<'next> Fn(usize, usize, &<LenderLabel<'next, L> as SuccessorLabel>::ArcLabel) -> bool,
+{
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        let filter = self.filter;
+        self.iter.next().map(|x| {
+            let (node, succ) = x.into_pair();
+            (
+                node,
+                Succ {
+                    src: node,
+                    iter: succ.into_iter(),
+                    filter,
+                },
+            )
+        })
+    }
+}
+
+impl<L: ExactSizeLender, F> ExactSizeLender for Iter<'_, L, F>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next>,
+    for<'next> LenderLabel<'next, L>: SuccessorLabel,
+    F: for<'next> Fn(usize, usize, &<LenderLabel<'next, L> as SuccessorLabel>::ArcLabel) -> bool,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// The successors of a node of a [`FilteredGraph`].
+#[derive(Debug)]
+pub struct Succ<'a, I, F> {
+    src: usize,
+    iter: I,
+    filter: &'a F,
+}
+
+impl<I: Clone, F> Clone for Succ<'_, I, F> {
+    fn clone(&self) -> Self {
+        Self {
+            src: self.src,
+            iter: self.iter.clone(),
+            filter: self.filter,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for Succ<'_, I, F>
+where
+    I::Item: SuccessorLabel,
+    F: Fn(usize, usize, &<I::Item as SuccessorLabel>::ArcLabel) -> bool,
+{
+    type Item = I::Item;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let label = self.iter.next()?;
+            if (self.filter)(self.src, label.successor(), label.arc_label()) {
+                return Some(label);
+            }
+        }
+    }
+}
+
+unsafe impl<I: SortedIterator, F> SortedIterator for Succ<'_, I, F>
+where
+    I::Item: SuccessorLabel,
+    F: Fn(usize, usize, &<I::Item as SuccessorLabel>::ArcLabel) -> bool,
+{
+}
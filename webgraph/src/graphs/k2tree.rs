@@ -0,0 +1,498 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! An implementation of k²-trees.
+//!
+//! A [k²-tree](https://doi.org/10.1016/j.is.2013.08.003) (Brisaboa, Ladra
+//! and Navarro) represents the adjacency matrix of a graph, padded to size
+//! *k*^*h* × *k*^*h*, as a *k*²-ary tree: each internal node corresponds to a
+//! submatrix, its children correspond to the *k*² submatrices obtained by
+//! splitting it into a *k* × *k* grid, and only children corresponding to
+//! nonempty submatrices are expanded. The tree is stored in level order as a
+//! bit vector with rank support, in which each nonempty submatrix is
+//! represented by a one, plus a bit vector of leaves containing the last
+//! level, that is, the cells of the matrix.
+//!
+//! The representation is symmetric, so besides successors it is possible to
+//! enumerate [predecessors](K2Tree::predecessors) and to [check for the
+//! presence of an arc](RandomAccessGraph::has_arc) with the same efficiency.
+
+use crate::prelude::*;
+use epserde::Epserde;
+use lender::{for_, IntoLender, Lend, Lender, Lending};
+use std::ops::Index;
+use sux::prelude::*;
+
+#[derive(Debug, Clone, Epserde)]
+/// A graph represented by a k²-tree.
+///
+/// The internal levels of the tree are stored in a bit vector with rank
+/// support `T`, and the last level in a bit vector `L`. Instances can be
+/// built from any [`SequentialGraph`] using
+/// [`from_seq_graph`](K2Tree::from_seq_graph), and can be serialized and
+/// memory-mapped using ε-serde.
+pub struct K2Tree<T = Rank9<BitVec<Box<[usize]>>>, L = BitVec<Box<[usize]>>> {
+    /// The number of nodes.
+    num_nodes: usize,
+    /// The number of arcs.
+    num_arcs: u64,
+    /// The arity of the tree along each dimension.
+    k: usize,
+    /// The number of levels of the tree, including the leaves.
+    height: usize,
+    /// The internal levels of the tree, in level order.
+    tree: T,
+    /// The last level of the tree, in level order.
+    leaves: L,
+}
+
+impl<T, L> K2Tree<T, L> {
+    /// Creates a new k²-tree from its components.
+    ///
+    /// # Safety
+    /// The bit vectors must represent a k²-tree of the given height over a
+    /// matrix with the given number of nodes and arcs.
+    pub unsafe fn from_parts(
+        num_nodes: usize,
+        num_arcs: u64,
+        k: usize,
+        height: usize,
+        tree: T,
+        leaves: L,
+    ) -> Self {
+        Self {
+            num_nodes,
+            num_arcs,
+            k,
+            height,
+            tree,
+            leaves,
+        }
+    }
+
+    /// Returns the arity of the tree along each dimension.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of levels of the tree, including the leaves.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the internal levels of the tree, in level order.
+    pub fn tree(&self) -> &T {
+        &self.tree
+    }
+
+    /// Returns the last level of the tree, in level order.
+    pub fn leaves(&self) -> &L {
+        &self.leaves
+    }
+
+    /// Consumes the k²-tree, returning its internal levels and its last
+    /// level.
+    pub fn into_inner(self) -> (T, L) {
+        (self.tree, self.leaves)
+    }
+}
+
+impl K2Tree {
+    /// Creates a new k²-tree with the given arity from a [`SequentialGraph`].
+    ///
+    /// Smaller values of `k` yield a more compact representation, whereas
+    /// larger values yield a shallower, and thus faster, tree.
+    ///
+    /// The tree is built one level at a time, with a pass over the graph for
+    /// each level: besides the tree itself, the construction uses space
+    /// proportional to its height. Duplicate arcs are represented, and
+    /// counted, once.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `k` is smaller than two.
+    pub fn from_seq_graph<G: SequentialGraph>(g: &G, k: usize) -> Self {
+        assert!(
+            k >= 2,
+            "The arity of a k²-tree must be at least 2 (got {})",
+            k
+        );
+        let num_nodes = g.num_nodes();
+        let mut height = 1;
+        let mut side = k;
+        while side < num_nodes {
+            side = side
+                .checked_mul(k)
+                .expect("The side of the adjacency matrix overflows usize");
+            height += 1;
+        }
+
+        // The side of the submatrices at each level
+        let mut subs = Vec::with_capacity(height);
+        let mut sub = side;
+        for _ in 0..height {
+            sub /= k;
+            subs.push(sub);
+        }
+        let child = |src: usize, dst: usize, sub: usize| (src / sub % k) * k + dst / sub % k;
+
+        let mut tree = BitVec::new(0);
+        let mut leaves = BitVec::new(0);
+        // The number of nonempty submatrices of the previous level; the root
+        // is always expanded
+        let mut parents = 1;
+        for level in 0..height {
+            if parents == 0 {
+                break;
+            }
+            let start = tree.len();
+            let tree_rank = Rank9::new(tree);
+            let mut bits = BitVec::new(parents * k * k);
+            // The positions in the tree of the submatrices containing the
+            // last arc at the previous levels
+            let mut path: Vec<usize> = Vec::with_capacity(level);
+            let mut last = None;
+            for_!((src, succ) in g.iter() {
+                for dst in succ {
+                    // Keeps the submatrices containing both the last arc
+                    // and this arc, which form a prefix of the path
+                    if let Some((last_src, last_dst)) = last {
+                        while let Some(&sub) = subs[..path.len()].last() {
+                            if src / sub == last_src / sub && dst / sub == last_dst / sub {
+                                break;
+                            }
+                            path.pop();
+                        }
+                    }
+                    while path.len() < level {
+                        let first_child = path.last().map_or(0, |&x| tree_rank.rank(x + 1) * k * k);
+                        path.push(first_child + child(src, dst, subs[path.len()]));
+                    }
+                    let first_child = path.last().map_or(0, |&x| tree_rank.rank(x + 1) * k * k - start);
+                    bits.set(first_child + child(src, dst, subs[level]), true);
+                    last = Some((src, dst));
+                }
+            });
+            tree = tree_rank.into_inner();
+            parents = bits.count_ones();
+            if level + 1 == height {
+                leaves = bits;
+            } else {
+                tree.extend(&bits);
+            }
+        }
+        let num_arcs = leaves.count_ones() as u64;
+
+        unsafe {
+            Self::from_parts(
+                num_nodes,
+                num_arcs,
+                k,
+                height,
+                Rank9::new(tree.into()),
+                leaves.into(),
+            )
+        }
+    }
+}
+
+impl<T, L> K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    /// Returns the side of the submatrices corresponding to the children of
+    /// the root.
+    fn root_sub(&self) -> usize {
+        self.k.pow(self.height as u32 - 1)
+    }
+
+    /// Returns the position of the first child of the internal node in
+    /// position `x`.
+    fn first_child(&self, x: usize) -> usize {
+        self.tree.rank(x + 1) * self.k * self.k
+    }
+
+    /// Returns an iterator over the predecessors of a node, in increasing
+    /// order.
+    pub fn predecessors(&self, node: usize) -> Neighbors<'_, T, L> {
+        Neighbors::new(self, node, true)
+    }
+}
+
+impl<T, L> SequentialLabeling for K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Label = usize;
+    type Lender<'a>
+        = LenderImpl<'a, T, L>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        Some(self.num_arcs)
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        LenderImpl {
+            graph: self,
+            nodes: (from..self.num_nodes),
+        }
+    }
+}
+
+impl<'a, T, L> IntoLender for &'a K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Lender = <K2Tree<T, L> as SequentialLabeling>::Lender<'a>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<T, L> SequentialGraph for K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+}
+
+impl<T, L> RandomAccessLabeling for K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Labels<'succ>
+        = Neighbors<'succ, T, L>
+    where
+        Self: 'succ;
+
+    #[inline(always)]
+    fn num_arcs(&self) -> u64 {
+        self.num_arcs
+    }
+
+    /// Returns the outdegree of a node.
+    ///
+    /// This method needs to enumerate the successors of the node.
+    fn outdegree(&self, node: usize) -> usize {
+        Neighbors::new(self, node, false).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        Neighbors::new(self, node, false)
+    }
+}
+
+impl<T, L> RandomAccessGraph for K2Tree<T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    /// Returns whether there is an arc going from `src_node` to `dst_node`.
+    ///
+    /// This method descends a single path of the tree. Nodes out of range
+    /// have no arcs.
+    fn has_arc(&self, src_node: usize, dst_node: usize) -> bool {
+        if src_node >= self.num_nodes || dst_node >= self.num_nodes {
+            return false;
+        }
+        let k = self.k;
+        let tree_len = self.tree.len();
+        let (mut src, mut dst) = (src_node, dst_node);
+        let mut sub = self.root_sub();
+        let mut x = (src / sub) * k + dst / sub;
+        loop {
+            if x >= tree_len {
+                return self.leaves[x - tree_len];
+            }
+            if !self.tree[x] {
+                return false;
+            }
+            src %= sub;
+            dst %= sub;
+            sub /= k;
+            x = self.first_child(x) + (src / sub) * k + dst / sub;
+        }
+    }
+}
+
+/// A sequential lender over the nodes of a [`K2Tree`] and their successors.
+#[derive(Debug, Clone)]
+pub struct LenderImpl<'a, T, L> {
+    graph: &'a K2Tree<T, L>,
+    nodes: core::ops::Range<usize>,
+}
+
+unsafe impl<T, L> SortedLender for LenderImpl<'_, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+}
+
+impl<'a, T, L> NodeLabelsLender<'_> for LenderImpl<'a, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Label = usize;
+    type IntoIterator = Neighbors<'a, T, L>;
+}
+
+impl<'a, T, L> Lending<'_> for LenderImpl<'a, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Lend = (usize, Neighbors<'a, T, L>);
+}
+
+impl<T, L> Lender for LenderImpl<'_, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.nodes
+            .next()
+            .map(|node| (node, Neighbors::new(self.graph, node, false)))
+    }
+}
+
+/// A frame of the depth-first visit performed by [`Neighbors`].
+#[derive(Debug, Clone)]
+struct Frame {
+    /// The position of the first child to consider.
+    first_child: usize,
+    /// The side of the submatrices corresponding to the children.
+    sub: usize,
+    /// The coordinate of the first child along the enumerated dimension.
+    offset: usize,
+    /// The index of the next child to consider.
+    next: usize,
+    /// The coordinate of the node relative to the children submatrices.
+    node: usize,
+}
+
+/// An iterator over the successors or the predecessors of a node of a
+/// [`K2Tree`], in increasing order.
+///
+/// The iterator performs a depth-first visit of the tree, keeping a stack
+/// of frames whose size is bounded by the height of the tree.
+#[derive(Debug, Clone)]
+pub struct Neighbors<'a, T, L> {
+    graph: &'a K2Tree<T, L>,
+    /// Whether we are enumerating predecessors (i.e., columns) rather than
+    /// successors (i.e., rows).
+    transposed: bool,
+    stack: Vec<Frame>,
+}
+
+impl<'a, T, L> Neighbors<'a, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    fn new(graph: &'a K2Tree<T, L>, node: usize, transposed: bool) -> Self {
+        let mut stack = Vec::with_capacity(graph.height);
+        if node < graph.num_nodes {
+            let sub = graph.root_sub();
+            stack.push(Frame {
+                first_child: Self::child_base(graph.k, node / sub, transposed),
+                sub,
+                offset: 0,
+                next: 0,
+                node: node % sub,
+            });
+        }
+        Self {
+            graph,
+            transposed,
+            stack,
+        }
+    }
+
+    /// Returns the offset of the first child in the row (or column, if
+    /// `transposed` is true) of index `index` of a *k* × *k* grid.
+    #[inline(always)]
+    fn child_base(k: usize, index: usize, transposed: bool) -> usize {
+        if transposed {
+            index
+        } else {
+            index * k
+        }
+    }
+}
+
+impl<T, L> Iterator for Neighbors<'_, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let k = self.graph.k;
+        let tree_len = self.graph.tree.len();
+        // Children in a row are contiguous; children in a column are k apart
+        let stride = if self.transposed { k } else { 1 };
+        loop {
+            let frame = self.stack.last_mut()?;
+            if frame.next == k {
+                self.stack.pop();
+                continue;
+            }
+            let x = frame.first_child + frame.next * stride;
+            let offset = frame.offset + frame.next * frame.sub;
+            frame.next += 1;
+
+            if x >= tree_len {
+                if self.graph.leaves[x - tree_len] {
+                    return Some(offset);
+                }
+                continue;
+            }
+            if !self.graph.tree[x] {
+                continue;
+            }
+            let sub = frame.sub / k;
+            let node = frame.node;
+            let first_child =
+                self.graph.first_child(x) + Self::child_base(k, node / sub, self.transposed);
+            self.stack.push(Frame {
+                first_child,
+                sub,
+                offset,
+                next: 0,
+                node: node % sub,
+            });
+        }
+    }
+}
+
+unsafe impl<T, L> SortedIterator for Neighbors<'_, T, L>
+where
+    T: Rank + Index<usize, Output = bool>,
+    L: Index<usize, Output = bool>,
+{
+}
@@ -8,13 +8,83 @@
 
 //! Implementations of graphs.
 
+/// Implements sequential access, splitting and [`IntoLender`](lender::IntoLender)
+/// for a type with a `num_nodes` field implementing
+/// [`RandomAccessLabeling`](crate::traits::RandomAccessLabeling) with `usize`
+/// labels, which is then a [`RandomAccessGraph`](crate::traits::RandomAccessGraph).
+macro_rules! impl_ra_graph {
+    ($ty:ty) => {
+        impl $crate::traits::SequentialLabeling for $ty {
+            type Label = usize;
+            type Lender<'a>
+                = $crate::traits::IteratorImpl<'a, Self>
+            where
+                Self: 'a;
+
+            #[inline(always)]
+            fn num_nodes(&self) -> usize {
+                self.num_nodes
+            }
+
+            #[inline(always)]
+            fn num_arcs_hint(&self) -> Option<u64> {
+                Some($crate::traits::RandomAccessLabeling::num_arcs(self))
+            }
+
+            #[inline(always)]
+            fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+                $crate::traits::IteratorImpl {
+                    labeling: self,
+                    nodes: (from..self.num_nodes),
+                }
+            }
+        }
+
+        impl $crate::traits::SplitLabeling for $ty {
+            type SplitLender<'a>
+                = $crate::traits::split::ra::Lender<'a, $ty>
+            where
+                Self: 'a;
+            type IntoIterator<'a>
+                = $crate::traits::split::ra::IntoIterator<'a, $ty>
+            where
+                Self: 'a;
+
+            fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+                $crate::traits::split::ra::Iter::new(self, how_many)
+            }
+        }
+
+        impl $crate::traits::SequentialGraph for $ty {}
+
+        impl $crate::traits::RandomAccessGraph for $ty {}
+
+        impl<'a> lender::IntoLender for &'a $ty {
+            type Lender = <$ty as $crate::traits::SequentialLabeling>::Lender<'a>;
+
+            #[inline(always)]
+            fn into_lender(self) -> Self::Lender {
+                $crate::traits::SequentialLabeling::iter(self)
+            }
+        }
+    };
+}
+
 pub mod arc_list_graph;
 pub mod btree_graph;
 pub mod bvgraph;
 pub mod csr_graph;
+pub mod delta_graph;
+pub mod efgraph;
+pub mod families;
+pub mod file_arc_list_graph;
+pub mod filtered_graph;
+pub mod k2tree;
 pub mod no_selfloops_graph;
 pub mod permuted_graph;
 pub mod random;
+pub mod subgraph;
+pub mod symmetrized_graph;
 pub mod union_graph;
 pub mod vec_graph;
 
@@ -22,8 +92,17 @@ pub mod prelude {
     pub use super::btree_graph::BTreeGraph;
     pub use super::bvgraph::*;
     pub use super::csr_graph::{CsrGraph, CsrSortedGraph};
+    pub use super::delta_graph::DeltaGraph;
+    pub use super::efgraph::EfGraph;
+    pub use super::file_arc_list_graph::{ArcFileFormat, FileArcListGraph};
+    pub use super::filtered_graph::FilteredGraph;
+    pub use super::k2tree::K2Tree;
     pub use super::no_selfloops_graph::NoSelfLoopsGraph;
-    pub use super::permuted_graph::PermutedGraph;
+    pub use super::permuted_graph::{
+        LabeledPermutedGraph, PermutedGraph, RandomAccessPermutedGraph,
+    };
+    pub use super::subgraph::Subgraph;
+    pub use super::symmetrized_graph::Symmetrized;
     pub use super::union_graph::UnionGraph;
     pub use super::vec_graph::VecGraph;
 }
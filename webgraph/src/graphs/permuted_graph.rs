@@ -154,6 +154,369 @@ impl<I: ExactSizeIterator<Item = usize>, P: BitFieldSlice<usize>> ExactSizeItera
     }
 }
 
+#[derive(Debug)]
+/// A random-access view of an underlying graph applying a permutation to its
+/// nodes.
+///
+/// Node `x` of the view corresponds to node `inv_perm[x]` of the underlying
+/// graph, and its successors are the images through `perm` of the successors
+/// of the latter: thus, `inv_perm` must be the inverse of `perm`.
+///
+/// To satisfy the contract of [`RandomAccessGraph`], successors are mapped
+/// and sorted on the fly, which requires an allocation at each call; if sorted
+/// successors are not needed, [`successors_unsorted`](Self::successors_unsorted)
+/// avoids sorting. The iterator on the graph, instead, is sorted.
+pub struct RandomAccessPermutedGraph<
+    'a,
+    G: RandomAccessGraph,
+    P: BitFieldSlice<usize> + ?Sized,
+    Q: BitFieldSlice<usize> + ?Sized,
+> {
+    pub graph: &'a G,
+    pub perm: &'a P,
+    pub inv_perm: &'a Q,
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize> + ?Sized, Q: BitFieldSlice<usize> + ?Sized> Clone
+    for RandomAccessPermutedGraph<'_, G, P, Q>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize> + ?Sized, Q: BitFieldSlice<usize> + ?Sized> Copy
+    for RandomAccessPermutedGraph<'_, G, P, Q>
+{
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>>
+    RandomAccessPermutedGraph<'_, G, P, Q>
+{
+    /// Returns the successors of a node in no particular order.
+    #[inline(always)]
+    pub fn successors_unsorted(
+        &self,
+        node: usize,
+    ) -> Succ<'_, <G::Labels<'_> as IntoIterator>::IntoIter, P> {
+        Succ {
+            iter: self.graph.successors(self.inv_perm.get(node)).into_iter(),
+            perm: self.perm,
+        }
+    }
+}
+
+impl<'a, G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> SequentialLabeling
+    for RandomAccessPermutedGraph<'a, G, P, Q>
+{
+    type Label = usize;
+    type Lender<'b>
+        = RandomAccessIter<'a, G, P, Q>
+    where
+        Self: 'b;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        Some(self.graph.num_arcs())
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        RandomAccessIter {
+            graph: *self,
+            nodes: (from..self.num_nodes()),
+        }
+    }
+}
+
+impl<
+        'b,
+        G: RandomAccessGraph + Sync,
+        P: BitFieldSlice<usize> + Sync,
+        Q: BitFieldSlice<usize> + Sync,
+    > SplitLabeling for RandomAccessPermutedGraph<'b, G, P, Q>
+{
+    type SplitLender<'a>
+        = split::ra::Lender<'a, RandomAccessPermutedGraph<'b, G, P, Q>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::ra::IntoIterator<'a, RandomAccessPermutedGraph<'b, G, P, Q>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::ra::Iter::new(self, how_many)
+    }
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> SequentialGraph
+    for RandomAccessPermutedGraph<'_, G, P, Q>
+{
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> RandomAccessLabeling
+    for RandomAccessPermutedGraph<'_, G, P, Q>
+{
+    type Labels<'succ>
+        = AssumeSortedIterator<std::vec::IntoIter<usize>>
+    where
+        Self: 'succ;
+
+    #[inline(always)]
+    fn num_arcs(&self) -> u64 {
+        self.graph.num_arcs()
+    }
+
+    #[inline(always)]
+    fn outdegree(&self, node: usize) -> usize {
+        self.graph.outdegree(self.inv_perm.get(node))
+    }
+
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        let mut succ = self.successors_unsorted(node).collect::<Vec<_>>();
+        succ.sort_unstable();
+        // this is safe as we just sorted the successors
+        unsafe { AssumeSortedIterator::new(succ.into_iter()) }
+    }
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> RandomAccessGraph
+    for RandomAccessPermutedGraph<'_, G, P, Q>
+{
+}
+
+impl<'a, 'b, G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> IntoLender
+    for &'b RandomAccessPermutedGraph<'a, G, P, Q>
+{
+    type Lender = <RandomAccessPermutedGraph<'a, G, P, Q> as SequentialLabeling>::Lender<'b>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+/// A sorted iterator over the nodes of a [`RandomAccessPermutedGraph`].
+#[derive(Debug, Clone)]
+pub struct RandomAccessIter<
+    'a,
+    G: RandomAccessGraph,
+    P: BitFieldSlice<usize>,
+    Q: BitFieldSlice<usize>,
+> {
+    graph: RandomAccessPermutedGraph<'a, G, P, Q>,
+    nodes: core::ops::Range<usize>,
+}
+
+unsafe impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> SortedLender
+    for RandomAccessIter<'_, G, P, Q>
+{
+}
+
+impl<'succ, G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>>
+    NodeLabelsLender<'succ> for RandomAccessIter<'_, G, P, Q>
+{
+    type Label = usize;
+    type IntoIterator = AssumeSortedIterator<std::vec::IntoIter<usize>>;
+}
+
+impl<'succ, G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> Lending<'succ>
+    for RandomAccessIter<'_, G, P, Q>
+{
+    type Lend = (usize, <Self as NodeLabelsLender<'succ>>::IntoIterator);
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> Lender
+    for RandomAccessIter<'_, G, P, Q>
+{
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.nodes
+            .next()
+            .map(|node| (node, self.graph.successors(node)))
+    }
+}
+
+impl<G: RandomAccessGraph, P: BitFieldSlice<usize>, Q: BitFieldSlice<usize>> ExactSizeLender
+    for RandomAccessIter<'_, G, P, Q>
+{
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A wrapper applying a permutation to the iterators of an underlying labeled
+/// graph, leaving the labels untouched.
+///
+/// As in the case of [`PermutedGraph`], nodes are simply remapped: thus,
+/// neither the iterator on the graph nor the successors are sorted.
+pub struct LabeledPermutedGraph<'a, G, P: BitFieldSlice<usize> + ?Sized> {
+    pub graph: &'a G,
+    pub perm: &'a P,
+}
+
+impl<L, G: SequentialLabeling<Label = (usize, L)>, P: BitFieldSlice<usize>> SequentialLabeling
+    for LabeledPermutedGraph<'_, G, P>
+{
+    type Label = (usize, L);
+    type Lender<'b>
+        = LabeledIter<'b, G::Lender<'b>, P>
+    where
+        Self: 'b;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        self.graph.num_arcs_hint()
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        LabeledIter {
+            iter: self.graph.iter_from(from),
+            perm: self.perm,
+        }
+    }
+}
+
+impl<
+        'b,
+        L,
+        G: SequentialLabeling<Label = (usize, L)> + SplitLabeling,
+        P: BitFieldSlice<usize> + Send + Sync + Clone,
+    > SplitLabeling for LabeledPermutedGraph<'b, G, P>
+where
+    for<'a> <G as SequentialLabeling>::Lender<'a>: Clone + ExactSizeLender + Send + Sync,
+{
+    type SplitLender<'a>
+        = split::seq::Lender<'a, LabeledPermutedGraph<'b, G, P>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::seq::IntoIterator<'a, LabeledPermutedGraph<'b, G, P>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::seq::Iter::new(self.iter(), self.num_nodes(), how_many)
+    }
+}
+
+impl<L, G: LabeledSequentialGraph<L>, P: BitFieldSlice<usize>> LabeledSequentialGraph<L>
+    for LabeledPermutedGraph<'_, G, P>
+{
+}
+
+impl<'a, 'b, L, G: SequentialLabeling<Label = (usize, L)>, P: BitFieldSlice<usize>> IntoLender
+    for &'b LabeledPermutedGraph<'a, G, P>
+{
+    type Lender = <LabeledPermutedGraph<'a, G, P> as SequentialLabeling>::Lender<'b>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+/// An iterator over the nodes of a labeled graph that applies on the fly a
+/// permutation of the nodes.
+#[derive(Debug, Clone)]
+pub struct LabeledIter<'node, I, P> {
+    iter: I,
+    perm: &'node P,
+}
+
+impl<
+        'succ,
+        L,
+        I: Lender + for<'next> NodeLabelsLender<'next, Label = (usize, L)>,
+        P: BitFieldSlice<usize>,
+    > NodeLabelsLender<'succ> for LabeledIter<'_, I, P>
+{
+    type Label = (usize, L);
+    type IntoIterator = LabeledSucc<'succ, LenderIntoIter<'succ, I>, P>;
+}
+
+impl<
+        'succ,
+        L,
+        I: Lender + for<'next> NodeLabelsLender<'next, Label = (usize, L)>,
+        P: BitFieldSlice<usize>,
+    > Lending<'succ> for LabeledIter<'_, I, P>
+{
+    type Lend = (usize, <Self as NodeLabelsLender<'succ>>::IntoIterator);
+}
+
+impl<
+        L,
+        I: Lender + for<'next> NodeLabelsLender<'next, Label = (usize, L)>,
+        P: BitFieldSlice<usize>,
+    > Lender for LabeledIter<'_, I, P>
+{
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        self.iter.next().map(|x| {
+            let (node, succ) = x.into_pair();
+            (
+                self.perm.get(node),
+                LabeledSucc {
+                    iter: succ.into_iter(),
+                    perm: self.perm,
+                },
+            )
+        })
+    }
+}
+
+impl<
+        L,
+        I: ExactSizeLender + for<'next> NodeLabelsLender<'next, Label = (usize, L)>,
+        P: BitFieldSlice<usize>,
+    > ExactSizeLender for LabeledIter<'_, I, P>
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LabeledSucc<'a, I, P> {
+    iter: I,
+    perm: &'a P,
+}
+
+impl<L, I: Iterator<Item = (usize, L)>, P: BitFieldSlice<usize>> Iterator
+    for LabeledSucc<'_, I, P>
+{
+    type Item = (usize, L);
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(succ, label)| (self.perm.get(succ), label))
+    }
+}
+
+impl<L, I: ExactSizeIterator<Item = (usize, L)>, P: BitFieldSlice<usize>> ExactSizeIterator
+    for LabeledSucc<'_, I, P>
+{
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn test_permuted_graph() -> anyhow::Result<()> {
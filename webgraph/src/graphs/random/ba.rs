@@ -0,0 +1,62 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::Csr;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+/// Barabási–Albert preferential-attachment random graphs.
+///
+/// The graph is undirected (i.e., every arc appears in both directions) and
+/// has no loops or multiple arcs. The first `m` nodes are initially isolated;
+/// then, each following node is connected to `m` distinct previous nodes,
+/// chosen with probability proportional to their degree (the first new node
+/// is connected to all initial nodes). The graph has thus
+/// 2`m`(`num_nodes` − `m`) arcs.
+///
+/// The graph is generated at construction time, and then it provides random
+/// access.
+#[derive(Debug, Clone)]
+pub struct BarabasiAlbert {
+    num_nodes: usize,
+    csr: Csr,
+}
+
+impl BarabasiAlbert {
+    /// Creates a new Barabási–Albert random graph, given the number of nodes,
+    /// the number of edges attaching each new node to the previous ones, and
+    /// a seed for the [pseudorandom number generator](SmallRng).
+    pub fn new(num_nodes: usize, m: usize, seed: u64) -> Self {
+        assert!(
+            m >= 1 && m < num_nodes,
+            "m must be positive and smaller than the number of nodes"
+        );
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut edges = Vec::with_capacity((num_nodes - m) * m);
+        // Each node appears once for each incident edge, so sampling
+        // uniformly from this list gives preferential attachment
+        let mut repeated = Vec::with_capacity(2 * (num_nodes - m) * m);
+        let mut targets = (0..m).collect::<Vec<_>>();
+        for src in m..num_nodes {
+            edges.extend(targets.iter().map(|&dst| (src, dst)));
+            repeated.extend_from_slice(&targets);
+            repeated.extend(std::iter::repeat_n(src, m));
+            targets.clear();
+            while targets.len() < m {
+                let dst = repeated[rng.random_range(0..repeated.len())];
+                if !targets.contains(&dst) {
+                    targets.push(dst);
+                }
+            }
+        }
+        Self {
+            num_nodes,
+            csr: Csr::from_edges(num_nodes, edges),
+        }
+    }
+}
+
+impl_csr_graph!(BarabasiAlbert);
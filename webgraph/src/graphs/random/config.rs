@@ -0,0 +1,53 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::Csr;
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+
+/// Random graphs with a given degree sequence generated by the configuration
+/// model.
+///
+/// Each node is given as many stubs as its prescribed degree, and stubs are
+/// then paired uniformly at random to form edges. The graph is undirected
+/// (i.e., every arc appears in both directions), and the loops and multiple
+/// edges generated by the pairing are discarded (this is sometimes called the
+/// _erased_ configuration model), so degrees can be smaller than prescribed.
+///
+/// The graph is generated at construction time, and then it provides random
+/// access.
+#[derive(Debug, Clone)]
+pub struct ConfigurationModel {
+    num_nodes: usize,
+    csr: Csr,
+}
+
+impl ConfigurationModel {
+    /// Creates a new random graph with the given degree sequence, whose sum
+    /// must be even, using the given seed for the [pseudorandom number
+    /// generator](SmallRng).
+    pub fn new(degrees: &[usize], seed: u64) -> Self {
+        let mut stubs = degrees
+            .iter()
+            .enumerate()
+            .flat_map(|(node, &degree)| std::iter::repeat_n(node, degree))
+            .collect::<Vec<_>>();
+        assert!(
+            stubs.len().is_multiple_of(2),
+            "The sum of the degrees must be even"
+        );
+        stubs.shuffle(&mut SmallRng::seed_from_u64(seed));
+        Self {
+            num_nodes: degrees.len(),
+            csr: Csr::from_edges(
+                degrees.len(),
+                stubs.chunks_exact(2).map(|pair| (pair[0], pair[1])),
+            ),
+        }
+    }
+}
+
+impl_csr_graph!(ConfigurationModel);
@@ -10,8 +10,8 @@ use lender::{Lend, Lender, Lending};
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 
 use crate::{
-    prelude::{NodeLabelsLender, SequentialGraph, SequentialLabeling},
-    traits::{SortedIterator, SortedLender},
+    prelude::{NodeLabelsLender, SequentialGraph, SequentialLabeling, SplitLabeling},
+    traits::{split, SortedIterator, SortedLender},
 };
 
 /// Provides a sequential implementation of Erdös-Rényi random graphs.
@@ -63,6 +63,15 @@ impl SequentialLabeling for ErdosRenyi {
     }
 }
 
+impl SplitLabeling for ErdosRenyi {
+    type SplitLender<'a> = split::seq::Lender<'a, ErdosRenyi>;
+    type IntoIterator<'a> = split::seq::IntoIterator<'a, ErdosRenyi>;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::seq::Iter::new(self.iter(), self.n, how_many)
+    }
+}
+
 unsafe impl SortedLender for Iter {}
 unsafe impl SortedIterator for <Succ as IntoIterator>::IntoIter {}
 
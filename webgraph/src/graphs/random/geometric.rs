@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::Csr;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+/// Random geometric graphs.
+///
+/// Nodes are placed uniformly at random in the unit square, and two distinct
+/// nodes are connected if their Euclidean distance is at most a given radius.
+/// The graph is undirected (i.e., every arc appears in both directions).
+///
+/// The graph is generated at construction time, using a grid of cells with
+/// side at least the radius so that only nearby nodes are compared, and then
+/// it provides random access.
+#[derive(Debug, Clone)]
+pub struct RandomGeometric {
+    num_nodes: usize,
+    positions: Box<[(f64, f64)]>,
+    csr: Csr,
+}
+
+impl RandomGeometric {
+    /// Creates a new random geometric graph, given the number of nodes, the
+    /// connection radius, and a seed for the [pseudorandom number
+    /// generator](SmallRng).
+    pub fn new(num_nodes: usize, radius: f64, seed: u64) -> Self {
+        assert!(radius >= 0.0, "The radius must be nonnegative");
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let positions = (0..num_nodes)
+            .map(|_| (rng.random::<f64>(), rng.random::<f64>()))
+            .collect::<Box<[_]>>();
+
+        // Cells have side at least the radius, but we use no more cells than
+        // nodes
+        let side = ((num_nodes as f64).sqrt().ceil() as usize)
+            .min((1.0 / radius).floor() as usize)
+            .max(1);
+        let cell = |(x, y): (f64, f64)| {
+            (
+                ((x * side as f64) as usize).min(side - 1),
+                ((y * side as f64) as usize).min(side - 1),
+            )
+        };
+        let mut cells = vec![vec![]; side * side];
+        for (node, &pos) in positions.iter().enumerate() {
+            let (cx, cy) = cell(pos);
+            cells[cx * side + cy].push(node);
+        }
+
+        let mut arcs = vec![];
+        for (node, &(x, y)) in positions.iter().enumerate() {
+            let (cx, cy) = cell((x, y));
+            for nx in cx.saturating_sub(1)..(cx + 2).min(side) {
+                for ny in cy.saturating_sub(1)..(cy + 2).min(side) {
+                    for &other in &cells[nx * side + ny] {
+                        let (ox, oy) = positions[other];
+                        if other != node
+                            && (x - ox) * (x - ox) + (y - oy) * (y - oy) <= radius * radius
+                        {
+                            arcs.push((node, other));
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            num_nodes,
+            positions,
+            csr: Csr::from_arcs(num_nodes, arcs),
+        }
+    }
+
+    /// Returns the positions of the nodes in the unit square.
+    pub fn positions(&self) -> &[(f64, f64)] {
+        &self.positions
+    }
+}
+
+impl_csr_graph!(RandomGeometric);
@@ -4,5 +4,107 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+//! Random graphs.
+//!
+//! All generators are deterministic given their seed. With the exception of
+//! [`ErdosRenyi`], which is generated lazily, generators build the successor
+//! lists of the graph at construction time and then provide random access to
+//! them; all generators implement [`SplitLabeling`](crate::traits::SplitLabeling),
+//! so they can be compressed in parallel with
+//! [`BvComp::parallel_graph`](crate::graphs::bvgraph::BvComp::parallel_graph).
+
+use crate::traits::AssumeSortedIterator;
+use std::iter::Copied;
+
+/// Implements [`RandomAccessLabeling`](crate::traits::RandomAccessLabeling),
+/// and all traits implemented by `impl_ra_graph!`, for a type with a
+/// `num_nodes` field and a `csr` field of type [`Csr`].
+macro_rules! impl_csr_graph {
+    ($ty:ty) => {
+        impl $crate::traits::RandomAccessLabeling for $ty {
+            type Labels<'a> = super::Succ<'a>;
+
+            #[inline(always)]
+            fn num_arcs(&self) -> u64 {
+                self.csr.successors.len() as u64
+            }
+
+            #[inline(always)]
+            fn outdegree(&self, node: usize) -> usize {
+                self.csr.offsets[node + 1] - self.csr.offsets[node]
+            }
+
+            #[inline(always)]
+            fn labels(&self, node: usize) -> super::Succ<'_> {
+                self.csr.successors(node)
+            }
+        }
+
+        impl_ra_graph!($ty);
+    };
+}
+
+mod ba;
+pub use ba::BarabasiAlbert;
+
+mod config;
+pub use config::ConfigurationModel;
+
 mod er;
 pub use er::ErdosRenyi;
+
+mod geometric;
+pub use geometric::RandomGeometric;
+
+mod rmat;
+pub use rmat::Rmat;
+
+mod ws;
+pub use ws::WattsStrogatz;
+
+/// The successors of a node of a random graph.
+pub type Succ<'a> = AssumeSortedIterator<Copied<std::slice::Iter<'a, usize>>>;
+
+/// The successor lists of a generated graph in compressed sparse row form.
+#[derive(Debug, Clone)]
+struct Csr {
+    offsets: Box<[usize]>,
+    successors: Box<[usize]>,
+}
+
+impl Csr {
+    /// Builds the successor lists of a graph with `num_nodes` nodes from a
+    /// list of arcs, which will be sorted and deduplicated.
+    fn from_arcs(num_nodes: usize, mut arcs: Vec<(usize, usize)>) -> Self {
+        arcs.sort_unstable();
+        arcs.dedup();
+        let mut offsets = vec![0; num_nodes + 1];
+        for &(src, _) in &arcs {
+            offsets[src + 1] += 1;
+        }
+        for node in 0..num_nodes {
+            offsets[node + 1] += offsets[node];
+        }
+        Self {
+            offsets: offsets.into_boxed_slice(),
+            successors: arcs.into_iter().map(|(_, dst)| dst).collect(),
+        }
+    }
+
+    /// Builds the successor lists of an undirected graph with `num_nodes`
+    /// nodes from a list of edges, discarding loops and multiple edges.
+    fn from_edges(num_nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let arcs = edges
+            .into_iter()
+            .filter(|(x, y)| x != y)
+            .flat_map(|(x, y)| [(x, y), (y, x)])
+            .collect();
+        Self::from_arcs(num_nodes, arcs)
+    }
+
+    #[inline(always)]
+    fn successors(&self, node: usize) -> Succ<'_> {
+        let succ = &self.successors[self.offsets[node]..self.offsets[node + 1]];
+        unsafe { AssumeSortedIterator::new(succ.iter().copied()) }
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::Csr;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+/// R-MAT random graphs.
+///
+/// R-MAT graphs are Kronecker graphs with a 2×2 initiator: each arc is sampled
+/// by descending recursively into one of the four quadrants of the adjacency
+/// matrix, chosen with probabilities `a`, `b`, `c` and `d` (top left, top
+/// right, bottom left and bottom right), until a single entry is reached. If
+/// the number of nodes is not a power of two, arcs falling outside the
+/// adjacency matrix are sampled again.
+///
+/// The graph is directed and might contain loops. Multiple arcs are
+/// discarded, so the number of arcs can be smaller than the number of samples.
+///
+/// The graph is generated at construction time, and then it provides random
+/// access.
+#[derive(Debug, Clone)]
+pub struct Rmat {
+    num_nodes: usize,
+    csr: Csr,
+}
+
+impl Rmat {
+    /// The probabilities used by the [Graph 500](https://graph500.org/)
+    /// benchmark.
+    pub const GRAPH500: [f64; 4] = [0.57, 0.19, 0.19, 0.05];
+
+    /// Creates a new R-MAT random graph, given the number of nodes, the number
+    /// of arcs to sample, the probabilities `[a, b, c, d]` of the four
+    /// quadrants, and a seed for the [pseudorandom number generator](SmallRng).
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the probabilities are not a distribution, if
+    /// arcs must be sampled in an empty graph, or if the probabilities make it
+    /// impossible to sample an arc inside the adjacency matrix (e.g., three
+    /// nodes and all mass on the bottom-right quadrant).
+    pub fn new(num_nodes: usize, num_samples: u64, probabilities: [f64; 4], seed: u64) -> Self {
+        assert!(
+            probabilities.iter().all(|p| (0.0..=1.0).contains(p)),
+            "Probabilities must be in [0..1]"
+        );
+        assert!(
+            (probabilities.iter().sum::<f64>() - 1.0).abs() < 1E-9,
+            "Probabilities must sum to one"
+        );
+        assert!(
+            num_nodes > 0 || num_samples == 0,
+            "Cannot sample arcs in an empty graph"
+        );
+        let [a, b, c, _] = probabilities;
+        let depth = num_nodes.next_power_of_two().trailing_zeros();
+        assert!(
+            num_samples == 0 || can_sample(num_nodes, depth, probabilities),
+            "Probabilities {:?} never sample an arc among {} nodes",
+            probabilities,
+            num_nodes
+        );
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut arcs = Vec::with_capacity(num_samples as usize);
+        while (arcs.len() as u64) < num_samples {
+            let (mut src, mut dst) = (0, 0);
+            for _ in 0..depth {
+                let r = rng.random::<f64>();
+                src <<= 1;
+                dst <<= 1;
+                if r < a {
+                } else if r < a + b {
+                    dst |= 1;
+                } else if r < a + b + c {
+                    src |= 1;
+                } else {
+                    src |= 1;
+                    dst |= 1;
+                }
+            }
+            if src < num_nodes && dst < num_nodes {
+                arcs.push((src, dst));
+            }
+        }
+        Self {
+            num_nodes,
+            csr: Csr::from_arcs(num_nodes, arcs),
+        }
+    }
+}
+
+/// Returns whether some descent with positive probability reaches an entry of
+/// the adjacency matrix of a graph with `num_nodes` nodes.
+///
+/// We follow the bits of the largest node from the most significant one,
+/// keeping track of which combinations of source and target being still equal
+/// to the prefix of the largest node (rather than smaller) are reachable.
+fn can_sample(num_nodes: usize, depth: u32, probabilities: [f64; 4]) -> bool {
+    let max = num_nodes - 1;
+    // reachable[src_eq][dst_eq]
+    let mut reachable = [[false, false], [false, true]];
+    for level in (0..depth).rev() {
+        let bit = (max >> level) & 1;
+        let mut next = [[false; 2]; 2];
+        for (src_eq, row) in reachable.iter().enumerate() {
+            for (dst_eq, _) in row.iter().enumerate().filter(|(_, &r)| r) {
+                for (quadrant, &p) in probabilities.iter().enumerate() {
+                    let (s, d) = (quadrant >> 1, quadrant & 1);
+                    if p == 0.0 || (src_eq == 1 && s > bit) || (dst_eq == 1 && d > bit) {
+                        continue;
+                    }
+                    next[src_eq & (s == bit) as usize][dst_eq & (d == bit) as usize] = true;
+                }
+            }
+        }
+        reachable = next;
+    }
+    reachable.iter().flatten().any(|&r| r)
+}
+
+impl_csr_graph!(Rmat);
@@ -0,0 +1,79 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::Csr;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+
+/// Watts–Strogatz small-world random graphs.
+///
+/// The generation starts from a ring lattice in which each node is connected
+/// to the `k` / 2 nearest nodes on each side; then, each lattice edge is
+/// rewired with probability `beta` by replacing its second endpoint with a node
+/// chosen uniformly at random, avoiding loops and multiple edges. The graph is
+/// undirected (i.e., every arc appears in both directions), and has
+/// `num_nodes` · `k` arcs.
+///
+/// The graph is generated at construction time, and then it provides random
+/// access.
+#[derive(Debug, Clone)]
+pub struct WattsStrogatz {
+    num_nodes: usize,
+    csr: Csr,
+}
+
+impl WattsStrogatz {
+    /// Creates a new Watts–Strogatz random graph, given the number of nodes,
+    /// the (even) degree `k` of the initial lattice, the rewiring probability
+    /// `beta`, and a seed for the [pseudorandom number generator](SmallRng).
+    pub fn new(num_nodes: usize, k: usize, beta: f64, seed: u64) -> Self {
+        assert!(k.is_multiple_of(2), "k must be even");
+        assert!(
+            k < num_nodes || k == 0,
+            "k must be smaller than the number of nodes"
+        );
+        assert!((0.0..=1.0).contains(&beta), "beta must be in [0..1]");
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut adj = vec![BTreeSet::new(); num_nodes];
+        for x in 0..num_nodes {
+            for j in 1..=k / 2 {
+                let y = (x + j) % num_nodes;
+                adj[x].insert(y);
+                adj[y].insert(x);
+            }
+        }
+        for j in 1..=k / 2 {
+            for x in 0..num_nodes {
+                if !rng.random_bool(beta) || adj[x].len() == num_nodes - 1 {
+                    continue;
+                }
+                let y = (x + j) % num_nodes;
+                let z = loop {
+                    let z = rng.random_range(0..num_nodes);
+                    if z != x && !adj[x].contains(&z) {
+                        break z;
+                    }
+                };
+                adj[x].remove(&y);
+                adj[y].remove(&x);
+                adj[x].insert(z);
+                adj[z].insert(x);
+            }
+        }
+        let arcs = adj
+            .iter()
+            .enumerate()
+            .flat_map(|(x, succ)| succ.iter().map(move |&y| (x, y)))
+            .collect();
+        Self {
+            num_nodes,
+            csr: Csr::from_arcs(num_nodes, arcs),
+        }
+    }
+}
+
+impl_csr_graph!(WattsStrogatz);
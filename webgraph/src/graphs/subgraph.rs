@@ -0,0 +1,409 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Subgraphs induced by a set of nodes.
+//!
+//! A [`Subgraph`] is a view of a graph restricted to a subset of its nodes,
+//! described by a bit vector with rank and select support: only arcs whose
+//! source and target both belong to the subset are kept. The surviving nodes
+//! can keep their original IDs, in which case the remaining nodes become
+//! isolated, or they can be renumbered densely, preserving their relative
+//! order; in this case, the [rank](Subgraph::new_id) and
+//! [select](Subgraph::old_id) operations of the node set map IDs back and
+//! forth.
+
+use crate::prelude::*;
+use crate::utils::NumArcsCache;
+use lender::*;
+use std::ops::Index;
+use sux::prelude::*;
+
+/// The default representation of the node set of a [`Subgraph`].
+pub type NodeSet = SelectAdaptConst<Rank9<BitVec<Box<[usize]>>>, Box<[usize]>, 12, 4>;
+
+/// A view of the subgraph of a graph induced by a set of nodes.
+///
+/// The set of nodes `N` is a bit vector of length equal to the number of
+/// nodes of the graph with rank and select support. If the subgraph is
+/// renumbered, its nodes are the ranks of the surviving nodes of the graph;
+/// otherwise, it has the same number of nodes of the graph, and nodes not in
+/// the set have no successors.
+///
+/// The number of arcs depends on how many successors of each node belong to
+/// the set, so it is [computed lazily](NumArcsCache).
+///
+/// Random access is available if the underlying graph is
+/// [random-access](RandomAccessGraph); in the renumbered case, it is
+/// necessary to select the original ID of a node, and to rank each successor.
+#[derive(Debug, Clone)]
+pub struct Subgraph<G, N = NodeSet> {
+    graph: G,
+    nodes: N,
+    renumber: bool,
+    num_arcs: NumArcsCache,
+}
+
+impl<G: SequentialGraph> Subgraph<G> {
+    /// Creates the subgraph induced by the nodes whose bit is set in `mask`,
+    /// renumbering them densely if `renumber` is true.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the length of `mask` is not the number of
+    /// nodes of `graph`.
+    pub fn from_mask(graph: G, mask: BitVec, renumber: bool) -> Self {
+        let nodes = SelectAdaptConst::<_, _, 12, 4>::new(Rank9::new(mask.into()));
+        Self::new(graph, nodes, renumber)
+    }
+
+    /// Creates the subgraph induced by the given nodes, renumbering them
+    /// densely if `renumber` is true.
+    ///
+    /// Nodes can be specified in any order, and duplicates are ignored.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if a node is not smaller than the number of
+    /// nodes of `graph`.
+    pub fn from_nodes(graph: G, nodes: impl IntoIterator<Item = usize>, renumber: bool) -> Self {
+        let num_nodes = graph.num_nodes();
+        let mut mask = BitVec::new(num_nodes);
+        for node in nodes {
+            assert!(
+                node < num_nodes,
+                "Node {} does not exist, as the graph has {} nodes",
+                node,
+                num_nodes
+            );
+            mask.set(node, true);
+        }
+        Self::from_mask(graph, mask, renumber)
+    }
+}
+
+impl<G: SequentialGraph, N: BitLength> Subgraph<G, N> {
+    /// Creates the subgraph induced by a node set with rank and select
+    /// support, renumbering its nodes densely if `renumber` is true.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the length of `nodes` is not the number of
+    /// nodes of `graph`.
+    pub fn new(graph: G, nodes: N, renumber: bool) -> Self {
+        assert_eq!(
+            nodes.len(),
+            graph.num_nodes(),
+            "The length of the node set ({}) is not the number of nodes of the graph ({})",
+            nodes.len(),
+            graph.num_nodes()
+        );
+        Self {
+            graph,
+            nodes,
+            renumber,
+            num_arcs: NumArcsCache::default(),
+        }
+    }
+}
+
+impl<G, N> Subgraph<G, N> {
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Returns the node set.
+    pub fn nodes(&self) -> &N {
+        &self.nodes
+    }
+
+    /// Returns whether the nodes of the subgraph are renumbered densely.
+    pub fn renumbered(&self) -> bool {
+        self.renumber
+    }
+
+    /// Returns the underlying graph and the node set.
+    pub fn into_inner(self) -> (G, N) {
+        (self.graph, self.nodes)
+    }
+}
+
+impl<G, N: Rank + Select + Index<usize, Output = bool>> Subgraph<G, N> {
+    /// Returns whether a node of the underlying graph belongs to the
+    /// subgraph.
+    #[inline(always)]
+    pub fn contains(&self, old_id: usize) -> bool {
+        self.nodes[old_id]
+    }
+
+    /// Returns the ID in the subgraph of a node of the underlying graph, or
+    /// `None` if the node does not belong to the subgraph.
+    #[inline(always)]
+    pub fn new_id(&self, old_id: usize) -> Option<usize> {
+        self.nodes[old_id].then(|| self.map(old_id))
+    }
+
+    /// Returns the ID in the underlying graph of a node of the subgraph.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_id` is not a node of the subgraph.
+    #[inline(always)]
+    pub fn old_id(&self, new_id: usize) -> usize {
+        if self.renumber {
+            self.nodes
+                .select(new_id)
+                .unwrap_or_else(|| panic!("Node {} does not exist in the subgraph", new_id))
+        } else {
+            assert!(
+                self.nodes[new_id],
+                "Node {} does not exist in the subgraph",
+                new_id
+            );
+            new_id
+        }
+    }
+
+    #[inline(always)]
+    fn map(&self, old_id: usize) -> usize {
+        if self.renumber {
+            self.nodes.rank(old_id)
+        } else {
+            old_id
+        }
+    }
+}
+
+impl<G: SequentialGraph, N: Rank + Select + Index<usize, Output = bool>> SequentialLabeling
+    for Subgraph<G, N>
+{
+    type Label = usize;
+    type Lender<'b>
+        = Iter<'b, G::Lender<'b>, N>
+    where
+        Self: 'b;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        if self.renumber {
+            self.nodes.num_ones()
+        } else {
+            self.graph.num_nodes()
+        }
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        self.num_arcs.get()
+    }
+
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        let pos = if self.renumber {
+            self.nodes.select(from).unwrap_or(self.graph.num_nodes())
+        } else {
+            from
+        };
+        Iter {
+            iter: self.graph.iter_from(pos),
+            nodes: &self.nodes,
+            renumber: self.renumber,
+            pos,
+        }
+    }
+}
+
+impl<G: SequentialGraph, N: Rank + Select + Index<usize, Output = bool> + Clone + Sync>
+    SplitLabeling for Subgraph<G, N>
+where
+    for<'a> G::Lender<'a>: Clone + Send + Sync,
+{
+    type SplitLender<'a>
+        = split::seq::Lender<'a, Subgraph<G, N>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::seq::IntoIterator<'a, Subgraph<G, N>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::seq::Iter::new(self.iter(), self.num_nodes(), how_many)
+    }
+}
+
+impl<G: SequentialGraph, N: Rank + Select + Index<usize, Output = bool>> SequentialGraph
+    for Subgraph<G, N>
+{
+}
+
+impl<'b, G: SequentialGraph, N: Rank + Select + Index<usize, Output = bool>> IntoLender
+    for &'b Subgraph<G, N>
+{
+    type Lender = <Subgraph<G, N> as SequentialLabeling>::Lender<'b>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<G: RandomAccessGraph, N: Rank + Select + Index<usize, Output = bool>> RandomAccessLabeling
+    for Subgraph<G, N>
+{
+    type Labels<'succ>
+        = Succ<'succ, <G::Labels<'succ> as IntoIterator>::IntoIter, N>
+    where
+        Self: 'succ;
+
+    fn num_arcs(&self) -> u64 {
+        self.num_arcs.get_or_count(self)
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        self.successors(node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        let iter = if self.renumber {
+            Some(self.graph.successors(self.old_id(node)).into_iter())
+        } else {
+            self.nodes[node].then(|| self.graph.successors(node).into_iter())
+        };
+        Succ {
+            iter,
+            nodes: &self.nodes,
+            renumber: self.renumber,
+        }
+    }
+}
+
+impl<G: RandomAccessGraph, N: Rank + Select + Index<usize, Output = bool>> RandomAccessGraph
+    for Subgraph<G, N>
+{
+}
+
+/// An iterator over the nodes of a [`Subgraph`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, L, N> {
+    iter: L,
+    nodes: &'a N,
+    renumber: bool,
+    /// The next node returned by `iter`.
+    pos: usize,
+}
+
+impl<'succ, L, N> NodeLabelsLender<'succ> for Iter<'_, L, N>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    N: Rank + Index<usize, Output = bool>,
+{
+    type Label = usize;
+    type IntoIterator = Succ<'succ, LenderIntoIter<'succ, L>, N>;
+}
+
+impl<'succ, L, N> Lending<'succ> for Iter<'_, L, N>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    N: Rank + Index<usize, Output = bool>,
+{
+    type Lend = (usize, <Self as NodeLabelsLender<'succ>>::IntoIterator);
+}
+
+unsafe impl<L, N> SortedLender for Iter<'_, L, N>
+where
+    L: SortedLender + Lender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    N: Rank + Index<usize, Output = bool>,
+{
+}
+
+impl<L, N> Lender for Iter<'_, L, N>
+where
+    L: Lender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    N: Rank + Index<usize, Output = bool>,
+{
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        if self.renumber {
+            // Skip to the next node in the set
+            let rank = self.nodes.rank(self.pos);
+            if rank == self.nodes.num_ones() {
+                return None;
+            }
+            while !self.nodes[self.pos] {
+                self.iter.next();
+                self.pos += 1;
+            }
+        }
+        let (node, succ) = self.iter.next()?.into_pair();
+        self.pos += 1;
+        let contains = self.nodes[node];
+        let node = if self.renumber {
+            self.nodes.rank(node)
+        } else {
+            node
+        };
+        Some((
+            node,
+            Succ {
+                iter: contains.then(|| succ.into_iter()),
+                nodes: self.nodes,
+                renumber: self.renumber,
+            },
+        ))
+    }
+}
+
+impl<L, N> ExactSizeLender for Iter<'_, L, N>
+where
+    L: ExactSizeLender + for<'next> NodeLabelsLender<'next, Label = usize>,
+    N: Rank + Index<usize, Output = bool>,
+{
+    fn len(&self) -> usize {
+        if self.renumber {
+            self.nodes.num_ones() - self.nodes.rank(self.pos)
+        } else {
+            self.iter.len()
+        }
+    }
+}
+
+/// The successors of a node of a [`Subgraph`].
+///
+/// Successors not in the node set are skipped, and the remaining ones are
+/// renumbered, if necessary.
+#[derive(Debug, Clone)]
+pub struct Succ<'a, I, N> {
+    /// The successors in the underlying graph, or `None` if the node is not
+    /// in the node set.
+    iter: Option<I>,
+    nodes: &'a N,
+    renumber: bool,
+}
+
+impl<I: Iterator<Item = usize>, N: Rank + Index<usize, Output = bool>> Iterator for Succ<'_, I, N> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let iter = self.iter.as_mut()?;
+        loop {
+            let dst = iter.next()?;
+            if self.nodes[dst] {
+                return Some(if self.renumber {
+                    self.nodes.rank(dst)
+                } else {
+                    dst
+                });
+            }
+        }
+    }
+}
+
+unsafe impl<I: Iterator<Item = usize> + SortedIterator, N: Rank + Index<usize, Output = bool>>
+    SortedIterator for Succ<'_, I, N>
+{
+}
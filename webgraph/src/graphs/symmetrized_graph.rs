@@ -0,0 +1,242 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::union_graph::{self, UnionGraph};
+use crate::prelude::*;
+use crate::utils::NumArcsCache;
+use lender::*;
+
+/// A wrapper exhibiting the symmetrization of a graph, that is, the union of
+/// the graph and of its transpose, possibly without self-loops.
+///
+/// The successors of the graph and of its transpose are merged lazily by a
+/// [`UnionGraph`], and arcs are deduplicated; if both graphs are
+/// [random-access](RandomAccessGraph), so is the symmetrized graph. The
+/// lenders and the successors of both graphs must be sorted.
+///
+/// An arc and its reverse are a single arc of the symmetrized graph, so the
+/// number of arcs is [computed lazily](NumArcsCache).
+#[derive(Debug, Clone)]
+pub struct Symmetrized<G: SequentialGraph, H: SequentialGraph> {
+    union: UnionGraph<G, H>,
+    selfloops: bool,
+    num_arcs: NumArcsCache,
+}
+
+impl<G: SequentialGraph, H: SequentialGraph> Symmetrized<G, H> {
+    /// Creates a symmetrized view of `graph` given its `transpose`; if
+    /// `selfloops` is false, self-loops are removed.
+    pub fn new(graph: G, transpose: H, selfloops: bool) -> Self {
+        Self {
+            union: UnionGraph(graph, transpose),
+            selfloops,
+            num_arcs: NumArcsCache::default(),
+        }
+    }
+
+    /// Returns the underlying graph and its transpose.
+    pub fn into_inner(self) -> (G, H) {
+        (self.union.0, self.union.1)
+    }
+}
+
+impl<G: SequentialGraph, H: SequentialGraph> SequentialLabeling for Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+{
+    type Label = usize;
+    type Lender<'b>
+        = Iter<union_graph::Iter<G::Lender<'b>, H::Lender<'b>>>
+    where
+        Self: 'b;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.union.num_nodes()
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        self.num_arcs.get()
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        Iter {
+            iter: self.union.iter_from(from),
+            selfloops: self.selfloops,
+        }
+    }
+}
+
+impl<G: SequentialGraph, H: SequentialGraph> SplitLabeling for Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender + Clone + Send + Sync,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender + Clone + Send + Sync,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+{
+    type SplitLender<'a>
+        = split::seq::Lender<'a, Symmetrized<G, H>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::seq::IntoIterator<'a, Symmetrized<G, H>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::seq::Iter::new(self.iter(), self.num_nodes(), how_many)
+    }
+}
+
+impl<G: SequentialGraph, H: SequentialGraph> SequentialGraph for Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+{
+}
+
+impl<'c, G: SequentialGraph, H: SequentialGraph> IntoLender for &'c Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+{
+    type Lender = <Symmetrized<G, H> as SequentialLabeling>::Lender<'c>;
+
+    #[inline(always)]
+    fn into_lender(self) -> Self::Lender {
+        self.iter()
+    }
+}
+
+impl<G: RandomAccessGraph, H: RandomAccessGraph> RandomAccessLabeling for Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+    for<'a> <H::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type Labels<'succ>
+        = Succ<<UnionGraph<G, H> as RandomAccessLabeling>::Labels<'succ>>
+    where
+        Self: 'succ;
+
+    fn num_arcs(&self) -> u64 {
+        self.num_arcs.get_or_count(self)
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        self.successors(node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        Succ::new(self.union.successors(node), node, self.selfloops)
+    }
+}
+
+impl<G: RandomAccessGraph, H: RandomAccessGraph> RandomAccessGraph for Symmetrized<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+    for<'a> <H::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+}
+
+/// An iterator over the nodes of a [`Symmetrized`] graph.
+#[derive(Debug, Clone)]
+pub struct Iter<I> {
+    iter: I,
+    selfloops: bool,
+}
+
+impl<'succ, I: Lender + for<'next> NodeLabelsLender<'next, Label = usize>> NodeLabelsLender<'succ>
+    for Iter<I>
+{
+    type Label = usize;
+    type IntoIterator = Succ<LenderIntoIter<'succ, I>>;
+}
+
+impl<'succ, I: Lender + for<'next> NodeLabelsLender<'next, Label = usize>> Lending<'succ>
+    for Iter<I>
+{
+    type Lend = (usize, <Self as NodeLabelsLender<'succ>>::IntoIterator);
+}
+
+unsafe impl<I: SortedLender + Lender + for<'next> NodeLabelsLender<'next, Label = usize>>
+    SortedLender for Iter<I>
+{
+}
+
+impl<L: Lender + for<'next> NodeLabelsLender<'next, Label = usize>> Lender for Iter<L> {
+    #[inline(always)]
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        let selfloops = self.selfloops;
+        self.iter.next().map(|x| {
+            let (node, succ) = x.into_pair();
+            (node, Succ::new(succ.into_iter(), node, selfloops))
+        })
+    }
+}
+
+impl<L: ExactSizeLender + for<'next> NodeLabelsLender<'next, Label = usize>> ExactSizeLender
+    for Iter<L>
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// The successors of a node of a [`Symmetrized`] graph.
+///
+/// Consecutive duplicates are skipped, as well as the node itself if
+/// self-loops have been removed.
+#[derive(Debug, Clone)]
+pub struct Succ<I: Iterator<Item = usize>> {
+    iter: I,
+    skip: Option<usize>,
+    last: Option<usize>,
+}
+
+impl<I: Iterator<Item = usize>> Succ<I> {
+    fn new(iter: I, node: usize, selfloops: bool) -> Self {
+        Self {
+            iter,
+            skip: (!selfloops).then_some(node),
+            last: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for Succ<I> {
+    type Item = usize;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let dst = self.iter.next()?;
+            if Some(dst) != self.last && Some(dst) != self.skip {
+                self.last = Some(dst);
+                return Some(dst);
+            }
+        }
+    }
+}
+
+unsafe impl<I: Iterator<Item = usize> + SortedIterator> SortedIterator for Succ<I> {}
@@ -9,6 +9,11 @@ use lender::*;
 
 #[derive(Debug, Clone)]
 /// A wrapper exhibiting the union of two graphs.
+///
+/// Arcs appearing in both graphs are counted once, so arcs and outdegrees
+/// must be counted by merging successor lists (see [`NumArcsCache`]). The
+/// number of arcs is not cached, so that a union can be built directly from
+/// its two graphs: use a [`NumArcsCache`] to avoid repeated passes.
 pub struct UnionGraph<G: SequentialGraph, H: SequentialGraph>(pub G, pub H);
 
 impl<G: SequentialGraph, H: SequentialGraph> SequentialLabeling for UnionGraph<G, H>
@@ -66,18 +71,18 @@ where
 
 impl<G: SequentialGraph, H: SequentialGraph> SequentialGraph for UnionGraph<G, H>
 where
-    for<'a> G::Lender<'a>: SortedLender + Clone,
+    for<'a> G::Lender<'a>: SortedLender,
     for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
-    for<'a> H::Lender<'a>: SortedLender + Clone,
+    for<'a> H::Lender<'a>: SortedLender,
     for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
 {
 }
 
 impl<'c, G: SequentialGraph, H: SequentialGraph> IntoLender for &'c UnionGraph<G, H>
 where
-    for<'a> G::Lender<'a>: SortedLender + Clone,
+    for<'a> G::Lender<'a>: SortedLender,
     for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
-    for<'a> H::Lender<'a>: SortedLender + Clone,
+    for<'a> H::Lender<'a>: SortedLender,
     for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
 {
     type Lender = <UnionGraph<G, H> as SequentialLabeling>::Lender<'c>;
@@ -88,6 +93,53 @@ where
     }
 }
 
+impl<G: RandomAccessGraph, H: RandomAccessGraph> RandomAccessLabeling for UnionGraph<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+    for<'a> <H::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+    type Labels<'succ>
+        = Succ<
+        <G::Labels<'succ> as IntoIterator>::IntoIter,
+        <H::Labels<'succ> as IntoIterator>::IntoIter,
+    >
+    where
+        Self: 'succ;
+
+    /// Returns the number of arcs of the union, which requires a full pass
+    /// at each call.
+    fn num_arcs(&self) -> u64 {
+        NumArcsCache::count(self)
+    }
+
+    fn outdegree(&self, node: usize) -> usize {
+        self.successors(node).count()
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> <Self as RandomAccessLabeling>::Labels<'_> {
+        Succ::new(
+            (node < self.0.num_nodes()).then(|| self.0.successors(node).into_iter()),
+            (node < self.1.num_nodes()).then(|| self.1.successors(node).into_iter()),
+        )
+    }
+}
+
+impl<G: RandomAccessGraph, H: RandomAccessGraph> RandomAccessGraph for UnionGraph<G, H>
+where
+    for<'a> G::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, G::Lender<'a>>: SortedIterator,
+    for<'a> H::Lender<'a>: SortedLender,
+    for<'a, 'b> LenderIntoIter<'b, H::Lender<'a>>: SortedIterator,
+    for<'a> <G::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+    for<'a> <H::Labels<'a> as IntoIterator>::IntoIter: SortedIterator,
+{
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct Iter<L, M>(L, M);
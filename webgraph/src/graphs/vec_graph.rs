@@ -263,6 +263,21 @@ impl<'a, L: Clone + 'static> IntoLender for &'a LabeledVecGraph<L> {
 
 impl<L: Clone + 'static> LabeledSequentialGraph<L> for LabeledVecGraph<L> {}
 
+impl<L: Clone + Send + Sync + 'static> SplitLabeling for LabeledVecGraph<L> {
+    type SplitLender<'a>
+        = split::ra::Lender<'a, LabeledVecGraph<L>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::ra::IntoIterator<'a, LabeledVecGraph<L>>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::ra::Iter::new(self, how_many)
+    }
+}
+
 impl<L: Clone + 'static> RandomAccessLabeling for LabeledVecGraph<L> {
     type Labels<'succ> = AssumeSortedIterator<
         core::iter::Map<
@@ -461,6 +476,21 @@ impl SequentialLabeling for VecGraph {
 
 impl SequentialGraph for VecGraph {}
 
+impl SplitLabeling for VecGraph {
+    type SplitLender<'a>
+        = split::ra::Lender<'a, VecGraph>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = split::ra::IntoIterator<'a, VecGraph>
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        split::ra::Iter::new(self, how_many)
+    }
+}
+
 impl RandomAccessLabeling for VecGraph {
     type Labels<'succ> = AssumeSortedIterator<
         core::iter::Map<
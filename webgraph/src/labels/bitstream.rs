@@ -11,13 +11,21 @@
 //! deserializer](BitDeserializer). An [`IndexedSeq`] provides pointers into the
 //! bitstream. Both sequential and random access are provided.
 //!
-//! See the examples for a complete implementation based on memory mapping.
+//! [`MmapReaderSupplier`] provides readers on a memory-mapped bitstream; it is
+//! used by [`load_labeled`](crate::graphs::bvgraph::load_labeled) to load the
+//! labels written by
+//! [`BvComp::single_thread_labeled`](crate::graphs::bvgraph::BvComp::single_thread_labeled).
 
 use crate::prelude::BitDeserializer;
 use crate::prelude::{NodeLabelsLender, RandomAccessLabeling, SequentialLabeling};
+use crate::utils::MmapHelper;
+use anyhow::{Context, Result};
+use dsi_bitstream::dispatch::CodesReaderFactory;
 use dsi_bitstream::traits::{BitRead, BitSeek, Endianness};
 use lender::*;
+use mmap_rs::MmapFlags;
 use std::ops::Deref;
+use std::path::Path;
 use sux::traits::{IndexedSeq, Types};
 
 /// A basic supplier trait.
@@ -31,6 +39,38 @@ pub trait Supply {
     fn request(&self) -> Self::Item<'_>;
 }
 
+/// A [`Supply`] of readers on a memory-mapped bitstream.
+pub struct MmapReaderSupplier<E: Endianness> {
+    backend: MmapHelper<u32>,
+    _marker: std::marker::PhantomData<E>,
+}
+
+impl<E: Endianness> MmapReaderSupplier<E> {
+    /// Memory-maps the given file.
+    pub fn mmap(path: impl AsRef<Path>, flags: MmapFlags) -> Result<Self> {
+        let path = path.as_ref();
+        Ok(Self {
+            backend: MmapHelper::<u32>::mmap(path, flags)
+                .with_context(|| format!("Could not mmap {}", path.display()))?,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<E: Endianness> Supply for MmapReaderSupplier<E>
+where
+    MmapHelper<u32>: CodesReaderFactory<E>,
+{
+    type Item<'a>
+        = <MmapHelper<u32> as CodesReaderFactory<E>>::CodesReader<'a>
+    where
+        Self: 'a;
+
+    fn request(&self) -> Self::Item<'_> {
+        self.backend.new_reader()
+    }
+}
+
 /// A labeling based on a bitstream of labels and an indexed sequence of offsets.
 pub struct BitStreamLabeling<E: Endianness, S: Supply, D, O>
 where
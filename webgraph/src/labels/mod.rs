@@ -15,3 +15,5 @@ pub use zip::*;
 
 pub mod proj;
 pub use proj::*;
+
+pub mod serializers;
@@ -0,0 +1,424 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Ready-made implementations of [`BitSerializer`] and [`BitDeserializer`].
+//!
+//! Each structure in this module implements both traits, so the same value
+//! can be used to write and to read labels; for example, it can be passed
+//! both as serializer and deserializer to
+//! [`SortPairs::new_labeled`](crate::utils::sort_pairs::SortPairs::new_labeled)
+//! or [`transpose_labeled`](crate::transform::transpose_labeled), or as
+//! deserializer to a [`BitStreamLabeling`](super::BitStreamLabeling).
+//!
+//! Structures can be combined: pairs, triples and quadruples of serializers
+//! are serializers of the corresponding tuples, and [`VecOf`] serializes
+//! vectors using a serializer for the elements. For example, a label made of
+//! a γ-coded integer and a list of 16-bit integers is handled by
+//! ```
+//! # use dsi_bitstream::dispatch::Codes;
+//! # use webgraph::labels::serializers::*;
+//! let serializer = (
+//!     CodedInt::<u64>::new(Codes::Gamma),
+//!     VecOf::new(Codes::Gamma, FixedWidthInt::<u16>::new(16)),
+//! );
+//! ```
+
+use crate::traits::{BitDeserializer, BitSerializer};
+use dsi_bitstream::dispatch::{Codes, CodesRead, CodesWrite};
+use dsi_bitstream::traits::{BitRead, BitWrite, Endianness};
+use std::marker::PhantomData;
+
+/// An unsigned integer type that can be (de)serialized by [`CodedInt`] and
+/// [`FixedWidthInt`].
+pub trait UnsignedInt: Copy {
+    /// The number of bits of the type.
+    const BITS: usize;
+    /// Converts the value to a `u64`.
+    fn to_u64(self) -> u64;
+    /// Converts a `u64` to a value of this type, truncating it if necessary.
+    fn from_u64(value: u64) -> Self;
+}
+
+/// A signed integer type that can be (de)serialized by [`ZigZagInt`].
+pub trait SignedInt: Copy {
+    /// Converts the value to a `u64` using the zigzag encoding.
+    fn to_zigzag(self) -> u64;
+    /// Converts a zigzag-encoded `u64` to a value of this type, truncating it
+    /// if necessary.
+    fn from_zigzag(value: u64) -> Self;
+}
+
+/// A floating-point type that can be (de)serialized by [`FixedWidthFloat`].
+pub trait Float: Copy {
+    /// The number of bits of the type.
+    const BITS: usize;
+    /// Returns the bit pattern of the value.
+    fn to_bits(self) -> u64;
+    /// Returns the value with the given bit pattern.
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($unsigned:ty, $signed:ty);*) => {$(
+        impl UnsignedInt for $unsigned {
+            const BITS: usize = <$unsigned>::BITS as usize;
+            #[inline(always)]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+            #[inline(always)]
+            fn from_u64(value: u64) -> Self {
+                value as $unsigned
+            }
+        }
+
+        impl SignedInt for $signed {
+            #[inline(always)]
+            fn to_zigzag(self) -> u64 {
+                ((self << 1) ^ (self >> (<$signed>::BITS - 1))) as $unsigned as u64
+            }
+            #[inline(always)]
+            fn from_zigzag(value: u64) -> Self {
+                let value = value as $unsigned;
+                ((value >> 1) as $signed) ^ -((value & 1) as $signed)
+            }
+        }
+    )*};
+}
+
+impl_int!(u8, i8; u16, i16; u32, i32; u64, i64; usize, isize);
+
+impl Float for f32 {
+    const BITS: usize = 32;
+    #[inline(always)]
+    fn to_bits(self) -> u64 {
+        f32::to_bits(self) as u64
+    }
+    #[inline(always)]
+    fn from_bits(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl Float for f64 {
+    const BITS: usize = 64;
+    #[inline(always)]
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+    #[inline(always)]
+    fn from_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+/// Unsigned integers written with an instantaneous code.
+///
+/// Note that most codes cannot write `u64::MAX`.
+#[derive(Debug)]
+pub struct CodedInt<T> {
+    code: Codes,
+    _marker: PhantomData<T>,
+}
+
+impl<T> CodedInt<T> {
+    /// Creates a serializer writing integers with the given code.
+    pub fn new(code: Codes) -> Self {
+        Self {
+            code,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for CodedInt<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CodedInt<T> {}
+
+impl<T: UnsignedInt, E: Endianness, BW: CodesWrite<E>> BitSerializer<E, BW> for CodedInt<T> {
+    type SerType = T;
+
+    #[inline(always)]
+    fn serialize(&self, value: &T, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        self.code.write(bitstream, value.to_u64())
+    }
+}
+
+impl<T: UnsignedInt, E: Endianness, BR: CodesRead<E>> BitDeserializer<E, BR> for CodedInt<T> {
+    type DeserType = T;
+
+    #[inline(always)]
+    fn deserialize(&self, bitstream: &mut BR) -> Result<T, BR::Error> {
+        Ok(T::from_u64(self.code.read(bitstream)?))
+    }
+}
+
+/// Signed integers mapped to unsigned integers by the zigzag encoding
+/// (0, -1, 1, -2, 2, … are mapped to 0, 1, 2, 3, 4, …) and written with an
+/// instantaneous code.
+///
+/// Note that `i64::MIN` is mapped to `u64::MAX`, which most codes cannot
+/// write.
+#[derive(Debug)]
+pub struct ZigZagInt<T> {
+    code: Codes,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ZigZagInt<T> {
+    /// Creates a serializer writing zigzag-encoded integers with the given
+    /// code.
+    pub fn new(code: Codes) -> Self {
+        Self {
+            code,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for ZigZagInt<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ZigZagInt<T> {}
+
+impl<T: SignedInt, E: Endianness, BW: CodesWrite<E>> BitSerializer<E, BW> for ZigZagInt<T> {
+    type SerType = T;
+
+    #[inline(always)]
+    fn serialize(&self, value: &T, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        self.code.write(bitstream, value.to_zigzag())
+    }
+}
+
+impl<T: SignedInt, E: Endianness, BR: CodesRead<E>> BitDeserializer<E, BR> for ZigZagInt<T> {
+    type DeserType = T;
+
+    #[inline(always)]
+    fn deserialize(&self, bitstream: &mut BR) -> Result<T, BR::Error> {
+        Ok(T::from_zigzag(self.code.read(bitstream)?))
+    }
+}
+
+/// Unsigned integers written using a fixed number of bits.
+///
+/// Values must fit in the given width; no check is performed.
+#[derive(Debug)]
+pub struct FixedWidthInt<T> {
+    width: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: UnsignedInt> FixedWidthInt<T> {
+    /// Creates a serializer writing integers using `width` bits.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is larger than the number of bits of `T`.
+    pub fn new(width: usize) -> Self {
+        assert!(
+            width <= T::BITS,
+            "The width ({}) is larger than the number of bits of the type ({})",
+            width,
+            T::BITS
+        );
+        Self {
+            width,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for FixedWidthInt<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FixedWidthInt<T> {}
+
+impl<T: UnsignedInt, E: Endianness, BW: BitWrite<E>> BitSerializer<E, BW> for FixedWidthInt<T> {
+    type SerType = T;
+
+    #[inline(always)]
+    fn serialize(&self, value: &T, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        bitstream.write_bits(value.to_u64(), self.width)
+    }
+}
+
+impl<T: UnsignedInt, E: Endianness, BR: BitRead<E>> BitDeserializer<E, BR> for FixedWidthInt<T> {
+    type DeserType = T;
+
+    #[inline(always)]
+    fn deserialize(&self, bitstream: &mut BR) -> Result<T, BR::Error> {
+        Ok(T::from_u64(bitstream.read_bits(self.width)?))
+    }
+}
+
+/// Floating-point numbers written as their bit pattern.
+#[derive(Debug)]
+pub struct FixedWidthFloat<T>(PhantomData<T>);
+
+impl<T> FixedWidthFloat<T> {
+    /// Creates a serializer writing floating-point numbers.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for FixedWidthFloat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for FixedWidthFloat<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FixedWidthFloat<T> {}
+
+impl<T: Float, E: Endianness, BW: BitWrite<E>> BitSerializer<E, BW> for FixedWidthFloat<T> {
+    type SerType = T;
+
+    #[inline(always)]
+    fn serialize(&self, value: &T, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        bitstream.write_bits(value.to_bits(), T::BITS)
+    }
+}
+
+impl<T: Float, E: Endianness, BR: BitRead<E>> BitDeserializer<E, BR> for FixedWidthFloat<T> {
+    type DeserType = T;
+
+    #[inline(always)]
+    fn deserialize(&self, bitstream: &mut BR) -> Result<T, BR::Error> {
+        Ok(T::from_bits(bitstream.read_bits(T::BITS)?))
+    }
+}
+
+/// Byte strings written as their length, using an instantaneous code,
+/// followed by their bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteString {
+    len_code: Codes,
+}
+
+impl ByteString {
+    /// Creates a serializer writing the length of byte strings with the
+    /// given code.
+    pub fn new(len_code: Codes) -> Self {
+        Self { len_code }
+    }
+}
+
+impl<E: Endianness, BW: CodesWrite<E>> BitSerializer<E, BW> for ByteString {
+    type SerType = Vec<u8>;
+
+    fn serialize(&self, value: &Vec<u8>, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        let mut written = self.len_code.write(bitstream, value.len() as u64)?;
+        for &byte in value {
+            written += bitstream.write_bits(byte as u64, 8)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<E: Endianness, BR: CodesRead<E>> BitDeserializer<E, BR> for ByteString {
+    type DeserType = Vec<u8>;
+
+    fn deserialize(&self, bitstream: &mut BR) -> Result<Vec<u8>, BR::Error> {
+        let len = self.len_code.read(bitstream)? as usize;
+        let mut value = Vec::with_capacity(len);
+        for _ in 0..len {
+            value.push(bitstream.read_bits(8)? as u8);
+        }
+        Ok(value)
+    }
+}
+
+/// Vectors written as their length, using an instantaneous code, followed by
+/// their elements, written using a serializer.
+#[derive(Debug, Clone, Copy)]
+pub struct VecOf<S> {
+    len_code: Codes,
+    inner: S,
+}
+
+impl<S> VecOf<S> {
+    /// Creates a serializer writing the length of vectors with the given code
+    /// and their elements with `inner`.
+    pub fn new(len_code: Codes, inner: S) -> Self {
+        Self { len_code, inner }
+    }
+}
+
+impl<E: Endianness, BW: CodesWrite<E>, S: BitSerializer<E, BW>> BitSerializer<E, BW> for VecOf<S> {
+    type SerType = Vec<S::SerType>;
+
+    fn serialize(&self, value: &Self::SerType, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        let mut written = self.len_code.write(bitstream, value.len() as u64)?;
+        for item in value {
+            written += self.inner.serialize(item, bitstream)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<E: Endianness, BR: CodesRead<E>, S: BitDeserializer<E, BR>> BitDeserializer<E, BR>
+    for VecOf<S>
+{
+    type DeserType = Vec<S::DeserType>;
+
+    fn deserialize(&self, bitstream: &mut BR) -> Result<Self::DeserType, BR::Error> {
+        let len = self.len_code.read(bitstream)? as usize;
+        let mut value = Vec::with_capacity(len);
+        for _ in 0..len {
+            value.push(self.inner.deserialize(bitstream)?);
+        }
+        Ok(value)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($s:ident $i:tt),*) => {
+        /// Tuples of serializers serialize tuples, one component after the
+        /// other.
+        impl<E: Endianness, BW: BitWrite<E>, $($s: BitSerializer<E, BW>),*> BitSerializer<E, BW>
+            for ($($s,)*)
+        {
+            type SerType = ($($s::SerType,)*);
+
+            fn serialize(&self, value: &Self::SerType, bitstream: &mut BW) -> Result<usize, BW::Error> {
+                Ok(0 $(+ self.$i.serialize(&value.$i, bitstream)?)*)
+            }
+        }
+
+        /// Tuples of deserializers deserialize tuples, one component after
+        /// the other.
+        impl<E: Endianness, BR: BitRead<E>, $($s: BitDeserializer<E, BR>),*> BitDeserializer<E, BR>
+            for ($($s,)*)
+        {
+            type DeserType = ($($s::DeserType,)*);
+
+            fn deserialize(&self, bitstream: &mut BR) -> Result<Self::DeserType, BR::Error> {
+                Ok(($(self.$i.deserialize(bitstream)?,)*))
+            }
+        }
+    };
+}
+
+impl_tuple!(S0 0, S1 1);
+impl_tuple!(S0 0, S1 1, S2 2);
+impl_tuple!(S0 0, S1 1, S2 2, S3 3);
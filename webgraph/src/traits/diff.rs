@@ -0,0 +1,502 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Structural differences between graphs.
+
+Differently from [`graph::eq`](super::graph::eq) and
+[`graph::eq_labeled`](super::graph::eq_labeled), which stop at the first
+difference, the functions in this module compute all differences between two
+graphs: for each node, the successors that have been removed (i.e., that
+appear only in the first graph), the successors that have been added (i.e.,
+that appear only in the second graph) and, for labeled graphs, the successors
+whose label has changed.
+
+The graphs must have [sorted lenders](SortedLender), so successor lists can
+be merged as they are returned; if a successor appears several times, the
+occurrences are matched in the order in which they are returned.
+
+[`diff`] and [`diff_labeled`] scan the graphs sequentially, whereas
+[`diff_split`] and [`diff_labeled_split`] scan in parallel graphs
+implementing [`SplitLabeling`]. All these functions return a [`Diff`]
+containing the differences of all nodes; to avoid keeping all differences in
+memory, [`diff_with`] and [`diff_labeled_with`] pass the differences of each
+node to a callback, and [`diff_split_with`] and [`diff_labeled_split_with`]
+pass to a callback the differences of each part as soon as it has been
+computed.
+
+*/
+
+use super::{
+    graph::UnitLender, labels::EqError, LabeledSequentialGraph, NodeLabelsLender, SequentialGraph,
+    SortedLender, SplitLabeling,
+};
+use crate::traits::Pair;
+use lender::*;
+use rayon::ThreadPool;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// The differences between the successors of a node in two graphs.
+///
+/// The three lists are sorted by successor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeDiff<L> {
+    /// The node.
+    pub node: usize,
+    /// The successors (and their labels) appearing only in the first graph.
+    pub removed: Vec<(usize, L)>,
+    /// The successors (and their labels) appearing only in the second graph.
+    pub added: Vec<(usize, L)>,
+    /// The successors appearing in both graphs with different labels, with
+    /// the label in the first and in the second graph.
+    pub changed: Vec<(usize, L, L)>,
+}
+
+impl<L> NodeDiff<L> {
+    /// Returns true if there is no difference.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Aggregate counts of the differences between two graphs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    /// The number of nodes with at least one difference.
+    pub nodes: usize,
+    /// The number of removed arcs.
+    pub removed: u64,
+    /// The number of added arcs.
+    pub added: u64,
+    /// The number of arcs whose label has changed.
+    pub changed: u64,
+}
+
+impl DiffStats {
+    fn update<L>(&mut self, node_diff: &NodeDiff<L>) {
+        self.nodes += 1;
+        self.removed += node_diff.removed.len() as u64;
+        self.added += node_diff.added.len() as u64;
+        self.changed += node_diff.changed.len() as u64;
+    }
+}
+
+impl core::ops::AddAssign for DiffStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.nodes += rhs.nodes;
+        self.removed += rhs.removed;
+        self.added += rhs.added;
+        self.changed += rhs.changed;
+    }
+}
+
+/// The differences between two graphs.
+///
+/// Note that only nodes with at least one difference are stored, so the
+/// memory used is proportional to the size of the difference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff<L> {
+    /// Aggregate counts of the differences.
+    pub stats: DiffStats,
+    /// The differences of each node with at least one difference, sorted by
+    /// node.
+    pub nodes: Vec<NodeDiff<L>>,
+}
+
+impl<L> Default for Diff<L> {
+    fn default() -> Self {
+        Self {
+            stats: DiffStats::default(),
+            nodes: Vec::new(),
+        }
+    }
+}
+
+impl<L> Diff<L> {
+    /// Returns true if the two graphs have the same arcs.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn push(&mut self, node_diff: NodeDiff<L>) {
+        self.stats.update(&node_diff);
+        self.nodes.push(node_diff);
+    }
+
+    fn append(&mut self, mut other: Self) {
+        self.stats += other.stats;
+        self.nodes.append(&mut other.nodes);
+    }
+}
+
+/// Computes the differences between two sorted successor lists.
+fn diff_succs<L: PartialEq>(
+    node: usize,
+    succ0: Vec<(usize, L)>,
+    succ1: Vec<(usize, L)>,
+) -> NodeDiff<L> {
+    let mut node_diff = NodeDiff {
+        node,
+        removed: Vec::new(),
+        added: Vec::new(),
+        changed: Vec::new(),
+    };
+    let mut succ0 = succ0.into_iter().peekable();
+    let mut succ1 = succ1.into_iter().peekable();
+    loop {
+        match (succ0.peek(), succ1.peek()) {
+            (None, None) => break,
+            (Some(_), None) => node_diff.removed.extend(succ0.by_ref()),
+            (None, Some(_)) => node_diff.added.extend(succ1.by_ref()),
+            (Some((s0, _)), Some((s1, _))) => {
+                if s0 < s1 {
+                    node_diff.removed.push(succ0.next().unwrap());
+                } else if s0 > s1 {
+                    node_diff.added.push(succ1.next().unwrap());
+                } else {
+                    let (s, l0) = succ0.next().unwrap();
+                    let (_, l1) = succ1.next().unwrap();
+                    if l0 != l1 {
+                        node_diff.changed.push((s, l0, l1));
+                    }
+                }
+            }
+        }
+    }
+    node_diff
+}
+
+/// Computes the differences between the nodes returned by two sorted
+/// lenders, which must return nodes in increasing order, passing the
+/// differences of each node with at least one difference to `f`.
+///
+/// If `aligned` is true, the lenders must return the same nodes.
+fn diff_lenders<M: PartialEq, L0, L1, E>(
+    mut iter0: L0,
+    mut iter1: L1,
+    aligned: bool,
+    mut f: impl FnMut(NodeDiff<M>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    L0: Lender + for<'next> NodeLabelsLender<'next, Label = (usize, M)>,
+    L1: Lender + for<'next> NodeLabelsLender<'next, Label = (usize, M)>,
+{
+    let mut stats = DiffStats::default();
+    let mut next0 = iter0
+        .next()
+        .map(|x| x.into_pair())
+        .map(|(node, succ)| (node, succ.into_iter().collect::<Vec<_>>()));
+    let mut next1 = iter1
+        .next()
+        .map(|x| x.into_pair())
+        .map(|(node, succ)| (node, succ.into_iter().collect::<Vec<_>>()));
+    loop {
+        if aligned {
+            assert_eq!(
+                next0.as_ref().map(|(node, _)| *node),
+                next1.as_ref().map(|(node, _)| *node),
+                "The parts of the two graphs contain different nodes"
+            );
+        }
+        let (node_diff, advance) = match (&mut next0, &mut next1) {
+            (None, None) => break,
+            (Some((node0, succ0)), Some((node1, _))) if *node0 < *node1 => (
+                diff_succs(*node0, core::mem::take(succ0), Vec::new()),
+                (true, false),
+            ),
+            (Some((node0, _)), Some((node1, succ1))) if *node0 > *node1 => (
+                diff_succs(*node1, Vec::new(), core::mem::take(succ1)),
+                (false, true),
+            ),
+            (Some((node, succ0)), Some((_, succ1))) => (
+                diff_succs(*node, core::mem::take(succ0), core::mem::take(succ1)),
+                (true, true),
+            ),
+            (Some((node, succ0)), None) => (
+                diff_succs(*node, core::mem::take(succ0), Vec::new()),
+                (true, false),
+            ),
+            (None, Some((node, succ1))) => (
+                diff_succs(*node, Vec::new(), core::mem::take(succ1)),
+                (false, true),
+            ),
+        };
+        if !node_diff.is_empty() {
+            stats.update(&node_diff);
+            f(node_diff)?;
+        }
+        if advance.0 {
+            next0 = iter0
+                .next()
+                .map(|x| x.into_pair())
+                .map(|(node, succ)| (node, succ.into_iter().collect::<Vec<_>>()));
+        }
+        if advance.1 {
+            next1 = iter1
+                .next()
+                .map(|x| x.into_pair())
+                .map(|(node, succ)| (node, succ.into_iter().collect::<Vec<_>>()));
+        }
+    }
+    Ok(stats)
+}
+
+/// Computes the differences between two labeled graphs with sorted lenders,
+/// passing the differences of each node with at least one difference to
+/// `f`, in increasing node order.
+///
+/// The graphs may have a different number of nodes: the successors of nodes
+/// appearing in just one graph are all removed or all added. The first error
+/// returned by `f`, if any, stops the computation and is returned.
+pub fn diff_labeled_with<M, G0, G1, E>(
+    g0: &G0,
+    g1: &G1,
+    f: impl FnMut(NodeDiff<M>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    G0: LabeledSequentialGraph<M>,
+    G1: LabeledSequentialGraph<M>,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+    M: PartialEq,
+{
+    diff_lenders(g0.iter(), g1.iter(), false, f)
+}
+
+/// Computes the differences between two labeled graphs with sorted lenders.
+///
+/// See [`diff_labeled_with`] for more details.
+pub fn diff_labeled<M, G0: LabeledSequentialGraph<M>, G1: LabeledSequentialGraph<M>>(
+    g0: &G0,
+    g1: &G1,
+) -> Diff<M>
+where
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+    M: PartialEq,
+{
+    let mut diff = Diff::default();
+    let Ok(_) = diff_labeled_with(g0, g1, |node_diff| {
+        diff.push(node_diff);
+        Ok::<_, Infallible>(())
+    });
+    diff
+}
+
+/// Computes the differences between two graphs with sorted lenders, passing
+/// the differences of each node with at least one difference to `f`.
+///
+/// The labels of the [`NodeDiff`] are `()`, and no successor is ever
+/// reported as changed. See [`diff_labeled_with`] for more details.
+pub fn diff_with<G0: SequentialGraph, G1: SequentialGraph, E>(
+    g0: &G0,
+    g1: &G1,
+    f: impl FnMut(NodeDiff<()>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+{
+    diff_lenders(UnitLender(g0.iter()), UnitLender(g1.iter()), false, f)
+}
+
+/// Computes the differences between two graphs with sorted lenders.
+///
+/// The labels of the resulting [`Diff`] are `()`, and no successor is ever
+/// reported as changed. See [`diff_labeled_with`] for more details.
+pub fn diff<G0: SequentialGraph, G1: SequentialGraph>(g0: &G0, g1: &G1) -> Diff<()>
+where
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+{
+    let mut diff = Diff::default();
+    let Ok(_) = diff_with(g0, g1, |node_diff| {
+        diff.push(node_diff);
+        Ok::<_, Infallible>(())
+    });
+    diff
+}
+
+/// Computes in parallel the differences between two labeled
+/// [splittable](SplitLabeling) graphs with sorted lenders, passing to `f` the
+/// differences of each part.
+///
+/// The two graphs are split in as many parts as there are threads in the
+/// pool, and corresponding parts are compared in parallel. The differences of
+/// each part are passed to `f` on the calling thread, in part order, as soon
+/// as they and those of all previous parts have been computed, so only the
+/// differences of parts waiting for previous ones are kept in memory.
+///
+/// The graphs must have the same number of nodes, or an
+/// [`EqError::NumNodes`] is returned; moreover, corresponding parts must
+/// contain the same nodes, in increasing order, and the same successor lists
+/// returned by the lenders of the graphs, which is true for the standard
+/// implementations of [`SplitLabeling`] based on
+/// [`split::seq`](super::split::seq) and [`split::ra`](super::split::ra).
+/// The first error returned by `f`, if any, stops the computation and is
+/// returned.
+///
+/// # Panics
+///
+/// If corresponding parts contain different nodes.
+pub fn diff_labeled_split_with<M, G0, G1, E>(
+    g0: &G0,
+    g1: &G1,
+    threads: &ThreadPool,
+    f: impl FnMut(Diff<M>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    G0: LabeledSequentialGraph<M> + SplitLabeling,
+    G1: LabeledSequentialGraph<M> + SplitLabeling,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+    M: PartialEq + Send,
+    E: From<EqError>,
+{
+    diff_parts_with(g0, g1, threads, f)
+}
+
+/// Implements [`diff_labeled_split_with`] without requiring sorted lenders,
+/// as the bound cannot be proved for [`UnitLabelGraph`](super::graph::UnitLabelGraph)s
+/// of references.
+fn diff_parts_with<M, G0, G1, E>(
+    g0: &G0,
+    g1: &G1,
+    threads: &ThreadPool,
+    mut f: impl FnMut(Diff<M>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    G0: LabeledSequentialGraph<M> + SplitLabeling,
+    G1: LabeledSequentialGraph<M> + SplitLabeling,
+    M: PartialEq + Send,
+    E: From<EqError>,
+{
+    if g0.num_nodes() != g1.num_nodes() {
+        return Err(EqError::NumNodes {
+            first: g0.num_nodes(),
+            second: g1.num_nodes(),
+        }
+        .into());
+    }
+    let num_threads = threads.current_num_threads();
+    threads.in_place_scope(|scope| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        for (part, (iter0, iter1)) in g0
+            .split_iter(num_threads)
+            .into_iter()
+            .zip(g1.split_iter(num_threads))
+            .enumerate()
+        {
+            let tx = tx.clone();
+            scope.spawn(move |_| {
+                let mut diff = Diff::default();
+                let Ok(_) = diff_lenders(iter0, iter1, true, |node_diff| {
+                    diff.push(node_diff);
+                    Ok::<_, Infallible>(())
+                });
+                // The receiver is dropped if f returns an error
+                let _ = tx.send((part, diff));
+            });
+        }
+        drop(tx);
+
+        let mut stats = DiffStats::default();
+        let mut next_part = 0;
+        let mut pending = BTreeMap::new();
+        for (part, diff) in rx {
+            pending.insert(part, diff);
+            while let Some(diff) = pending.remove(&next_part) {
+                stats += diff.stats;
+                f(diff)?;
+                next_part += 1;
+            }
+        }
+        Ok(stats)
+    })
+}
+
+/// Computes in parallel the differences between two labeled
+/// [splittable](SplitLabeling) graphs with sorted lenders.
+///
+/// See [`diff_labeled_split_with`] for more details.
+pub fn diff_labeled_split<M, G0, G1>(
+    g0: &G0,
+    g1: &G1,
+    threads: &ThreadPool,
+) -> Result<Diff<M>, EqError>
+where
+    G0: LabeledSequentialGraph<M> + SplitLabeling,
+    G1: LabeledSequentialGraph<M> + SplitLabeling,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+    M: PartialEq + Send,
+{
+    diff_parts(g0, g1, threads)
+}
+
+/// Implements [`diff_labeled_split`] using [`diff_parts_with`].
+fn diff_parts<M, G0, G1>(g0: &G0, g1: &G1, threads: &ThreadPool) -> Result<Diff<M>, EqError>
+where
+    G0: LabeledSequentialGraph<M> + SplitLabeling,
+    G1: LabeledSequentialGraph<M> + SplitLabeling,
+    M: PartialEq + Send,
+{
+    let mut diff = Diff::default();
+    diff_parts_with(g0, g1, threads, |part| {
+        diff.append(part);
+        Ok::<_, EqError>(())
+    })?;
+    Ok(diff)
+}
+
+/// Computes in parallel the differences between two
+/// [splittable](SplitLabeling) graphs with sorted lenders, passing to `f` the
+/// differences of each part.
+///
+/// The labels of the [`Diff`] are `()`, and no successor is ever reported as
+/// changed. See [`diff_labeled_split_with`] for more details.
+pub fn diff_split_with<G0, G1, E>(
+    g0: &G0,
+    g1: &G1,
+    threads: &ThreadPool,
+    f: impl FnMut(Diff<()>) -> Result<(), E>,
+) -> Result<DiffStats, E>
+where
+    G0: SequentialGraph + SplitLabeling,
+    G1: SequentialGraph + SplitLabeling,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+    E: From<EqError>,
+{
+    diff_parts_with(
+        &super::graph::UnitLabelGraph(g0),
+        &super::graph::UnitLabelGraph(g1),
+        threads,
+        f,
+    )
+}
+
+/// Computes in parallel the differences between two
+/// [splittable](SplitLabeling) graphs with sorted lenders.
+///
+/// The labels of the resulting [`Diff`] are `()`, and no successor is ever
+/// reported as changed. See [`diff_labeled_split_with`] for more details.
+pub fn diff_split<G0, G1>(g0: &G0, g1: &G1, threads: &ThreadPool) -> Result<Diff<()>, EqError>
+where
+    G0: SequentialGraph + SplitLabeling,
+    G1: SequentialGraph + SplitLabeling,
+    for<'a> G0::Lender<'a>: SortedLender,
+    for<'a> G1::Lender<'a>: SortedLender,
+{
+    diff_parts(
+        &super::graph::UnitLabelGraph(g0),
+        &super::graph::UnitLabelGraph(g1),
+        threads,
+    )
+}
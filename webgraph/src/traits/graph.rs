@@ -45,7 +45,7 @@ use lender::*;
 use super::{
     labels::EqError,
     lenders::{LenderIntoIter, NodeLabelsLender},
-    SortedIterator, SortedLender,
+    SortedIterator, SortedLender, SplitLabeling,
 };
 
 #[allow(non_camel_case_types)]
@@ -274,6 +274,24 @@ impl<G: SequentialGraph> SequentialLabeling for UnitLabelGraph<G> {
 
 impl<G: SequentialGraph> LabeledSequentialGraph<()> for UnitLabelGraph<G> {}
 
+impl<G: SequentialGraph + SplitLabeling> SplitLabeling for UnitLabelGraph<G> {
+    type SplitLender<'a>
+        = UnitLender<G::SplitLender<'a>>
+    where
+        Self: 'a;
+    type IntoIterator<'a>
+        = core::iter::Map<
+        <G::IntoIterator<'a> as IntoIterator>::IntoIter,
+        fn(G::SplitLender<'a>) -> Self::SplitLender<'a>,
+    >
+    where
+        Self: 'a;
+
+    fn split_iter(&self, how_many: usize) -> Self::IntoIterator<'_> {
+        self.0.split_iter(how_many).into_iter().map(UnitLender)
+    }
+}
+
 /// A labeled random-access graph.
 ///
 /// A labeled random-access graph is a random-access labeling whose labels are
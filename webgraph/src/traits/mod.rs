@@ -49,6 +49,9 @@ impl<T, U> Pair for (T, U) {
 pub mod graph;
 pub use graph::*;
 
+pub mod diff;
+pub use diff::*;
+
 pub mod labels;
 pub use labels::*;
 
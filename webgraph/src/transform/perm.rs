@@ -5,9 +5,10 @@
  */
 
 use crate::graphs::arc_list_graph;
-use crate::prelude::sort_pairs::{BatchIterator, KMergeIters};
+use crate::prelude::sort_pairs::{BatchIterator, BitReader, BitWriter, KMergeIters};
 use crate::prelude::*;
 use anyhow::{ensure, Context, Result};
+use dsi_bitstream::traits::NE;
 use dsi_progress_logger::prelude::*;
 use lender::*;
 use rayon::ThreadPool;
@@ -137,3 +138,149 @@ where
         edges,
     )))
 }
+
+/// Returns a [sequential](crate::traits::SequentialGraph) permuted labeled
+/// graph.
+///
+/// Note that if the graph is [splittable](SplitLabeling),
+/// [`permute_labeled_split`] will be much faster.
+///
+/// This assumes that the permutation is bijective. For the meaning of the
+/// additional parameters, see
+/// [`SortPairs`](crate::prelude::sort_pairs::SortPairs).
+#[allow(clippy::type_complexity)]
+pub fn permute_labeled<
+    S: BitSerializer<NE, BitWriter> + Clone,
+    D: BitDeserializer<NE, BitReader> + Clone + 'static,
+>(
+    graph: &impl LabeledSequentialGraph<S::SerType>,
+    perm: &impl BitFieldSlice<usize>,
+    batch_size: usize,
+    serializer: S,
+    deserializer: D,
+) -> Result<arc_list_graph::ArcListGraph<KMergeIters<BatchIterator<D>, D::DeserType>>>
+where
+    S::SerType: SortLabel,
+    D::DeserType: Clone,
+{
+    ensure!(
+        perm.len() == graph.num_nodes(),
+        "The given permutation has {} values and thus it's incompatible with a graph with {} nodes.",
+        perm.len(),
+        graph.num_nodes(),
+    );
+    let dir = Builder::new().prefix("permute_labeled_").tempdir()?;
+    log::info!(
+        "Creating a temporary directory for the sorted pairs: {}",
+        dir.path().display()
+    );
+
+    // create a stream where to dump the sorted pairs
+    let mut sorted = SortPairs::new_labeled(batch_size, dir.path(), serializer, deserializer)?;
+
+    // get a premuted view
+    let pgraph = LabeledPermutedGraph { graph, perm };
+
+    let mut pl = ProgressLogger::default();
+    pl.item_name("node")
+        .expected_updates(Some(graph.num_nodes()));
+    pl.start("Creating batches...");
+    // create batches of sorted edges
+    for_!( (src, succ) in pgraph.iter() {
+        for (dst, label) in succ {
+            sorted.push_labeled(src, dst, label)?;
+        }
+        pl.light_update();
+    });
+
+    // get a graph on the sorted data
+    let edges = sorted.iter().context("Could not read arcs")?;
+    let sorted = arc_list_graph::ArcListGraph::new_labeled(graph.num_nodes(), edges);
+    pl.done();
+
+    Ok(sorted)
+}
+
+/// Returns a [sequential](crate::traits::SequentialGraph) permuted labeled
+/// graph starting from a [splittable](SplitLabeling) labeled graph.
+///
+/// Note that if the graph is not [splittable](SplitLabeling) you must use
+/// [`permute_labeled`], albeit it will be slower.
+///
+/// This assumes that the permutation is bijective. For the meaning of the
+/// additional parameters, see
+/// [`SortPairs`](crate::prelude::sort_pairs::SortPairs); the batch size is
+/// divided among the threads.
+#[allow(clippy::type_complexity)]
+pub fn permute_labeled_split<S, D, G, P>(
+    graph: &G,
+    perm: &P,
+    batch_size: usize,
+    serializer: S,
+    deserializer: D,
+    threads: &ThreadPool,
+) -> Result<arc_list_graph::ArcListGraph<KMergeIters<BatchIterator<D>, D::DeserType>>>
+where
+    S: BitSerializer<NE, BitWriter> + Clone + Send,
+    D: BitDeserializer<NE, BitReader> + Clone + Send + 'static,
+    S::SerType: SortLabel,
+    D::DeserType: Clone + Send,
+    G: LabeledSequentialGraph<S::SerType> + SplitLabeling,
+    P: BitFieldSlice<usize> + Sync,
+{
+    ensure!(
+        perm.len() == graph.num_nodes(),
+        "The given permutation has {} values and thus it's incompatible with a graph with {} nodes.",
+        perm.len(),
+        graph.num_nodes(),
+    );
+
+    let num_threads = threads.current_num_threads();
+    let thread_batch_size = (batch_size / num_threads).max(1);
+    let mut dirs = vec![];
+
+    let edges = threads.in_place_scope(|scope| -> Result<_> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        for (thread_id, iter) in graph.split_iter(num_threads).into_iter().enumerate() {
+            let tx = tx.clone();
+            let dir = Builder::new()
+                .prefix(&format!("permute_labeled_split_{}_", thread_id))
+                .tempdir()
+                .context("Could not create a temporary directory")?;
+            let dir_path = dir.path().to_path_buf();
+            dirs.push(dir);
+            let (serializer, deserializer) = (serializer.clone(), deserializer.clone());
+            scope.spawn(move |_| {
+                log::debug!("Spawned thread {}", thread_id);
+                let permute = || -> Result<KMergeIters<BatchIterator<D>, D::DeserType>> {
+                    let mut sorted = SortPairs::new_labeled(
+                        thread_batch_size,
+                        dir_path,
+                        serializer,
+                        deserializer,
+                    )?;
+                    for_!( (src, succ) in iter {
+                        for (dst, label) in succ {
+                            sorted.push_labeled(perm.get(src), perm.get(dst), label)?;
+                        }
+                    });
+                    sorted.iter().context("Could not read arcs")
+                };
+                tx.send(permute()).expect("Could not send the sorted pairs");
+                log::debug!("Thread {} finished", thread_id);
+            });
+        }
+        drop(tx);
+
+        // get a graph on the sorted data
+        log::debug!("Waiting for threads to finish");
+        rx.iter().sum()
+    })?;
+
+    log::debug!("All threads finished");
+    Ok(arc_list_graph::ArcListGraph::new_labeled(
+        graph.num_nodes(),
+        edges,
+    ))
+}
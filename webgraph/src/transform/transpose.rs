@@ -5,15 +5,21 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
-use crate::graphs::arc_list_graph;
+use crate::graphs::arc_list_graph::{self, SplitArcListGraph};
 use crate::prelude::proj::Left;
-use crate::prelude::sort_pairs::{BatchIterator, BitReader, BitWriter, KMergeIters, SortPairs};
-use crate::prelude::{BitDeserializer, BitSerializer, LabeledSequentialGraph, SequentialGraph};
+use crate::prelude::sort_pairs::{
+    BatchIterator, BitReader, BitWriter, KMergeIters, SortLabel, SortPairs, Triple,
+};
+use crate::prelude::{
+    BitDeserializer, BitSerializer, LabeledSequentialGraph, SequentialGraph, SplitLabeling,
+};
 use crate::traits::graph::UnitLabelGraph;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use dsi_bitstream::traits::NE;
 use dsi_progress_logger::prelude::*;
 use lender::prelude::*;
+use rayon::ThreadPool;
+use std::path::Path;
 use tempfile::Builder;
 
 /// Returns the transpose of the provided labeled graph as a [sequential
@@ -32,8 +38,8 @@ pub fn transpose_labeled<
     deserializer: D,
 ) -> Result<arc_list_graph::ArcListGraph<KMergeIters<BatchIterator<D>, D::DeserType>>>
 where
-    S::SerType: Send + Sync + Copy,
-    D::DeserType: Clone + Copy,
+    S::SerType: SortLabel,
+    D::DeserType: Clone,
 {
     let dir = Builder::new().prefix("transpose_").tempdir()?;
     let mut sorted = SortPairs::new_labeled(batch_size, dir.path(), serializer, deserializer)?;
@@ -75,3 +81,156 @@ pub fn transpose(
         (),
     )?))
 }
+
+/// Sorts a batch of triples, dumps it in `dir` split according to the given
+/// boundaries of the parts, and adds the resulting iterators to `parts`.
+fn dump_parts<S: BitSerializer<NE, BitWriter>, D: BitDeserializer<NE, BitReader> + Clone>(
+    batch: &mut Vec<Triple<S::SerType>>,
+    boundaries: &[usize],
+    dir: &Path,
+    batch_id: usize,
+    serializer: &S,
+    deserializer: &D,
+    parts: &mut [Vec<BatchIterator<D>>],
+) -> Result<()>
+where
+    S::SerType: SortLabel,
+{
+    S::SerType::sort_triples(batch);
+    let mut start = 0;
+    for (part, iters) in parts.iter_mut().enumerate() {
+        let end = start + batch[start..].partition_point(|t| t.pair[0] < boundaries[part + 1]);
+        if end > start {
+            iters.push(BatchIterator::new_from_vec_sorted_labeled(
+                dir.join(format!("{:04x}_{:06x}", part, batch_id)),
+                &batch[start..end],
+                serializer,
+                deserializer.clone(),
+            )?);
+        }
+        start = end;
+    }
+    batch.clear();
+    Ok(())
+}
+
+/// Returns the transpose of the provided labeled [splittable](SplitLabeling)
+/// graph as a [splittable](SplitLabeling) [sequential
+/// graph](crate::traits::SequentialGraph).
+///
+/// The nodes of the transpose are divided in as many ranges (of equal size)
+/// as there are threads in the pool; each thread reads a part of the graph,
+/// sorting its arcs in separate batches for each range. The resulting graph
+/// can be [split](SplitLabeling::split_iter) along the ranges, and thus
+/// compressed directly using
+/// [`BvComp::parallel_iter`](crate::graphs::bvgraph::BvComp::parallel_iter).
+///
+/// Note that if the graph is not [splittable](SplitLabeling) you must use
+/// [`transpose_labeled`], albeit it will be slower. For the meaning of the
+/// additional parameters, see
+/// [`SortPairs`](crate::prelude::sort_pairs::SortPairs); the batch size is
+/// divided among the threads.
+#[allow(clippy::type_complexity)]
+pub fn transpose_labeled_split<
+    S: BitSerializer<NE, BitWriter> + Sync,
+    D: BitDeserializer<NE, BitReader> + Clone + Send + 'static,
+>(
+    graph: &(impl LabeledSequentialGraph<S::SerType> + SplitLabeling),
+    batch_size: usize,
+    serializer: S,
+    deserializer: D,
+    threads: &ThreadPool,
+) -> Result<SplitArcListGraph<KMergeIters<BatchIterator<D>, D::DeserType>>>
+where
+    S::SerType: SortLabel,
+    D::DeserType: Clone,
+{
+    let num_nodes = graph.num_nodes();
+    let num_threads = threads.current_num_threads();
+    let nodes_per_part = num_nodes.div_ceil(num_threads).max(1);
+    let boundaries = (0..=num_threads)
+        .map(|i| (i * nodes_per_part).min(num_nodes))
+        .collect::<Vec<_>>();
+    let thread_batch_size = (batch_size / num_threads).max(1);
+    let mut dirs = vec![];
+
+    let mut parts = threads.in_place_scope(|scope| -> Result<Vec<Vec<BatchIterator<D>>>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        for (thread_id, iter) in graph.split_iter(num_threads).into_iter().enumerate() {
+            let tx = tx.clone();
+            let dir = Builder::new()
+                .prefix(&format!("transpose_split_{}_", thread_id))
+                .tempdir()
+                .context("Could not create a temporary directory")?;
+            let dir_path = dir.path().to_path_buf();
+            dirs.push(dir);
+            let (boundaries, serializer) = (&boundaries, &serializer);
+            let deserializer = deserializer.clone();
+            scope.spawn(move |_| {
+                log::debug!("Spawned thread {}", thread_id);
+                let transpose = || -> Result<Vec<Vec<BatchIterator<D>>>> {
+                    let mut parts = (0..num_threads).map(|_| Vec::new()).collect::<Vec<_>>();
+                    let mut batch = Vec::with_capacity(thread_batch_size);
+                    let mut batch_id = 0;
+                    for_!( (src, succ) in iter {
+                        for (dst, label) in succ {
+                            batch.push(Triple {
+                                pair: [dst, src],
+                                label,
+                            });
+                            if batch.len() >= thread_batch_size {
+                                dump_parts(&mut batch, boundaries, &dir_path, batch_id, serializer, &deserializer, &mut parts)?;
+                                batch_id += 1;
+                            }
+                        }
+                    });
+                    if !batch.is_empty() {
+                        dump_parts(&mut batch, boundaries, &dir_path, batch_id, serializer, &deserializer, &mut parts)?;
+                    }
+                    Ok(parts)
+                };
+                tx.send(transpose())
+                    .expect("Could not send the sorted pairs");
+                log::debug!("Thread {} finished", thread_id);
+            });
+        }
+        drop(tx);
+
+        log::debug!("Waiting for threads to finish");
+        let mut parts = (0..num_threads).map(|_| Vec::new()).collect::<Vec<_>>();
+        for thread_parts in rx {
+            for (part, iters) in parts.iter_mut().zip(thread_parts?) {
+                part.extend(iters);
+            }
+        }
+        Ok(parts)
+    })?;
+
+    log::debug!("All threads finished");
+    Ok(SplitArcListGraph::new_labeled(
+        num_nodes,
+        boundaries,
+        parts.drain(..).map(KMergeIters::new).collect::<Vec<_>>(),
+    ))
+}
+
+/// Returns the transpose of the provided [splittable](SplitLabeling) graph as
+/// a [splittable](SplitLabeling) [sequential
+/// graph](crate::traits::SequentialGraph).
+///
+/// See [`transpose_labeled_split`] for more details.
+#[allow(clippy::type_complexity)]
+pub fn transpose_split(
+    graph: &(impl SequentialGraph + SplitLabeling),
+    batch_size: usize,
+    threads: &ThreadPool,
+) -> Result<Left<SplitArcListGraph<KMergeIters<BatchIterator<()>, ()>>>> {
+    Ok(Left(transpose_labeled_split(
+        &UnitLabelGraph(graph),
+        batch_size,
+        (),
+        (),
+        threads,
+    )?))
+}
@@ -35,6 +35,9 @@ pub fn temp_dir<P: AsRef<std::path::Path>>(base: P) -> anyhow::Result<PathBuf> {
 mod circular_buffer;
 pub(crate) use circular_buffer::*;
 
+mod num_arcs_cache;
+pub use num_arcs_cache::*;
+
 mod mmap_helper;
 pub use mmap_helper::*;
 
@@ -45,7 +48,7 @@ mod granularity;
 pub use granularity::*;
 
 pub mod sort_pairs;
-pub use sort_pairs::SortPairs;
+pub use sort_pairs::{SortLabel, SortPairs};
 
 use crate::graphs::bvgraph::{Decode, Encode};
 
@@ -0,0 +1,55 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use crate::traits::SequentialLabeling;
+use lender::*;
+use std::sync::OnceLock;
+
+/// A lazily computed number of arcs.
+///
+/// Views such as [`UnionGraph`](crate::graphs::union_graph::UnionGraph),
+/// [`Symmetrized`](crate::graphs::symmetrized_graph::Symmetrized),
+/// [`Subgraph`](crate::graphs::subgraph::Subgraph) and
+/// [`FilteredGraph`](crate::graphs::filtered_graph::FilteredGraph) cannot know
+/// how many arcs they have without enumerating them: arcs might appear in both
+/// graphs of a union, point outside the node set of a subgraph, or fail the
+/// predicate of a filter. Thus, counting the arcs of such a view requires a
+/// full sequential pass in which the successors of every node of the
+/// underlying graphs are merged, tested for membership or filtered, and, for
+/// the same reason, computing the outdegree of a node requires enumerating
+/// its successors.
+///
+/// This structure performs the pass the first time the number of arcs is
+/// requested and caches the result, which is then returned by the views as a
+/// [hint](SequentialLabeling::num_arcs_hint), too. Views whose fields are
+/// public, such as `UnionGraph`, do not cache their number of arcs, but you
+/// can keep a cache along with them.
+#[derive(Debug, Clone, Default)]
+pub struct NumArcsCache(OnceLock<u64>);
+
+impl NumArcsCache {
+    /// Returns the number of arcs, if it has already been computed.
+    #[inline(always)]
+    pub fn get(&self) -> Option<u64> {
+        self.0.get().copied()
+    }
+
+    /// Returns the number of arcs of `labeling`, counting them with a full
+    /// sequential pass the first time this method is called.
+    pub fn get_or_count<L: SequentialLabeling + ?Sized>(&self, labeling: &L) -> u64 {
+        *self.0.get_or_init(|| Self::count(labeling))
+    }
+
+    /// Counts the arcs of `labeling` with a full sequential pass.
+    pub fn count<L: SequentialLabeling + ?Sized>(labeling: &L) -> u64 {
+        let mut num_arcs = 0;
+        for_!((_, succ) in labeling.iter() {
+            num_arcs += succ.into_iter().count() as u64;
+        });
+        num_arcs
+    }
+}
@@ -30,12 +30,104 @@ pub type BitReader = BufBitReader<NE, MemWordReader<u32, ArcMmapHelper<u32>>>;
 /// Equality and order are defined only (lexicographically) on the pair of
 /// nodes.
 #[derive(Clone, Debug, Copy)]
-pub struct Triple<L: Copy> {
+#[repr(C)]
+pub struct Triple<L> {
     pub pair: [usize; 2],
     pub label: L,
 }
 
-impl<T: Copy> RadixKey for Triple<T> {
+/// A label that can be sorted, together with its pair, by [`SortPairs`].
+///
+/// [`Copy`] labels are sorted directly by [`radix_sort_triples`], which is
+/// what the implementations for primitive types and for tuples of [`Copy`]
+/// labels do. Since the radix sort needs [`Copy`] elements, the default
+/// implementation, used for [`Vec`] and [`String`], sorts instead the pairs
+/// tagged with their index, and then applies the resulting permutation to the
+/// triples by swapping them.
+pub trait SortLabel: Clone + Send + Sync {
+    /// Sorts triples by pair.
+    fn sort_triples(batch: &mut [Triple<Self>]) {
+        let mut perm = batch
+            .iter()
+            .enumerate()
+            .map(|(index, triple)| Triple {
+                pair: triple.pair,
+                label: index,
+            })
+            .collect::<Vec<_>>();
+        perm.radix_sort_unstable();
+        // The triple in position i must move to the position of the index i
+        // in perm; we follow the cycles of the permutation, marking the
+        // positions already filled as fixed points
+        for start in 0..perm.len() {
+            let mut curr = start;
+            loop {
+                let next = perm[curr].label;
+                perm[curr].label = curr;
+                if next == start || next == curr {
+                    break;
+                }
+                batch.swap(curr, next);
+                curr = next;
+            }
+        }
+    }
+}
+
+/// Sorts triples with [`Copy`] labels by pair using a radix sort.
+pub fn radix_sort_triples<L: Copy + Send + Sync>(batch: &mut [Triple<L>]) {
+    batch.radix_sort_unstable();
+}
+
+macro_rules! impl_copy_sort_label {
+    ($($ty:ty),*) => {$(
+        impl SortLabel for $ty {
+            fn sort_triples(batch: &mut [Triple<Self>]) {
+                radix_sort_triples(batch)
+            }
+        }
+    )*};
+}
+
+impl_copy_sort_label!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+macro_rules! impl_tuple_sort_label {
+    ($($l:ident),*) => {
+        impl<$($l: SortLabel + Copy),*> SortLabel for ($($l,)*) {
+            fn sort_triples(batch: &mut [Triple<Self>]) {
+                radix_sort_triples(batch)
+            }
+        }
+    };
+}
+
+impl_tuple_sort_label!(L0, L1);
+impl_tuple_sort_label!(L0, L1, L2);
+impl_tuple_sort_label!(L0, L1, L2, L3);
+
+impl<T: Clone + Send + Sync> SortLabel for Vec<T> {}
+
+impl SortLabel for String {}
+
+impl<T> RadixKey for Triple<T> {
     const LEVELS: usize = 16;
 
     fn get_level(&self, level: usize) -> u8 {
@@ -43,21 +135,21 @@ impl<T: Copy> RadixKey for Triple<T> {
     }
 }
 
-impl<T: Copy> PartialEq for Triple<T> {
+impl<T> PartialEq for Triple<T> {
     fn eq(&self, other: &Self) -> bool {
         self.pair == other.pair
     }
 }
 
-impl<T: Copy> Eq for Triple<T> {}
+impl<T> Eq for Triple<T> {}
 
-impl<T: Copy> PartialOrd for Triple<T> {
+impl<T> PartialOrd for Triple<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.pair.cmp(&other.pair))
+        Some(self.cmp(other))
     }
 }
 
-impl<T: Copy> Ord for Triple<T> {
+impl<T> Ord for Triple<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.pair.cmp(&other.pair)
     }
@@ -100,7 +192,7 @@ pub struct SortPairs<
     S: BitSerializer<NE, BitWriter> = (),
     D: BitDeserializer<NE, BitReader> + Clone = (),
 > where
-    S::SerType: Send + Sync + Copy,
+    S::SerType: SortLabel,
 {
     /// The batch size.
     batch_size: usize,
@@ -138,7 +230,7 @@ impl SortPairs<(), ()> {
 
 impl<S: BitSerializer<NE, BitWriter>, D: BitDeserializer<NE, BitReader> + Clone> SortPairs<S, D>
 where
-    S::SerType: Send + Sync + Copy,
+    S::SerType: SortLabel,
 {
     /// Creates a new `SortPairs` with labels.
     ///
@@ -286,10 +378,10 @@ impl<D: BitDeserializer<NE, BitReader>> BatchIterator<D> {
         deserializer: D,
     ) -> anyhow::Result<Self>
     where
-        S::SerType: Send + Sync + Copy,
+        S::SerType: SortLabel,
     {
         let start = std::time::Instant::now();
-        batch.radix_sort_unstable();
+        S::SerType::sort_triples(batch);
         debug!("Sorted {} arcs in {:?}", batch.len(), start.elapsed());
         Self::new_from_vec_sorted_labeled(file_path, batch, serializer, deserializer)
     }
@@ -303,7 +395,7 @@ impl<D: BitDeserializer<NE, BitReader>> BatchIterator<D> {
         deserializer: D,
     ) -> anyhow::Result<Self>
     where
-        S::SerType: Send + Sync + Copy,
+        S::SerType: SortLabel,
     {
         // create a batch file where to dump
         let file_path = file_path.as_ref();
@@ -438,7 +530,7 @@ impl<T, I: Iterator<Item = (usize, usize, T)>> Eq for HeadTail<T, I> {}
 impl<T, I: Iterator<Item = (usize, usize, T)>> PartialOrd for HeadTail<T, I> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some((other.head.0, other.head.1).cmp(&(self.head.0, self.head.1)))
+        Some(self.cmp(other))
     }
 }
 
@@ -677,4 +769,33 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_sort_triples() {
+        let pairs = (0..1000_usize).map(|i| [(i * 37) % 101, (i * 53) % 17]);
+        // Copy labels are radix sorted directly
+        let mut copy = pairs
+            .clone()
+            .map(|pair| Triple {
+                pair,
+                label: (pair[0] * 17 + pair[1]) as u64,
+            })
+            .collect::<Vec<_>>();
+        // Other labels are moved by following the sorting permutation
+        let mut clone = pairs
+            .map(|pair| Triple {
+                pair,
+                label: format!("{}", pair[0] * 17 + pair[1]),
+            })
+            .collect::<Vec<_>>();
+        SortLabel::sort_triples(&mut copy);
+        SortLabel::sort_triples(&mut clone);
+        assert!(copy.is_sorted());
+        assert!(clone.is_sorted());
+        for (c, s) in copy.iter().zip(&clone) {
+            assert_eq!(c.pair, s.pair);
+            assert_eq!(c.label, (c.pair[0] * 17 + c.pair[1]) as u64);
+            assert_eq!(s.label, c.label.to_string());
+        }
+    }
 }
@@ -0,0 +1,130 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::dispatch::factory::CodesReaderFactoryHelper;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use webgraph::graphs::bvgraph::{LoadModeCodesReader, Mmap};
+use webgraph::prelude::*;
+
+/// Compresses the first `split` nodes of `graph`, appends the remaining ones
+/// and checks that the result is identical to compressing `graph` at once.
+fn check_append<E: Endianness>(
+    graph: &VecGraph,
+    split: usize,
+    cf: CompFlags,
+    dir: &Path,
+) -> Result<()>
+where
+    MmapHelper<u32>: CodesReaderFactoryHelper<E>,
+    for<'a> LoadModeCodesReader<'a, E, Mmap>: BitSeek,
+    for<'a> MemBufReader<'a, E>: BitRead<E>,
+    BufBitWriter<E, WordAdapter<usize, BufWriter<File>>>: CodesWrite<E>,
+{
+    let basename = dir.join("whole");
+    BvComp::single_thread::<E, _>(&basename, graph, cf, true, None)?;
+
+    let appended = dir.join("appended");
+    let len = BvComp::single_thread::<E, _>(&appended, graph.iter().take(split), cf, true, None)?;
+    let appended_len = BvComp::append::<E, _>(&appended, graph.iter_from(split))?;
+
+    for extension in [GRAPH_EXTENSION, OFFSETS_EXTENSION, PROPERTIES_EXTENSION] {
+        assert_eq!(
+            std::fs::read(basename.with_extension(extension))?,
+            std::fs::read(appended.with_extension(extension))?,
+            "{} differs (split at {}, {:?})",
+            extension,
+            split,
+            cf
+        );
+    }
+    let properties = std::fs::read_to_string(appended.with_extension(PROPERTIES_EXTENSION))?;
+    assert!(
+        properties.contains(&format!("length={}\n", len + appended_len)),
+        "{} {} {}",
+        properties,
+        len,
+        appended_len
+    );
+
+    // the Elias–Fano representation of the offsets has been rebuilt
+    let appended_graph = BvGraph::with_basename(&appended).endianness::<E>().load()?;
+    graph::eq(graph, &appended_graph)?;
+    Ok(())
+}
+
+/// Returns a random graph whose arcs point to smaller nodes, so that each
+/// prefix of the graph is a valid graph.
+fn backward_graph(num_nodes: usize, p: f64) -> VecGraph {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut graph = VecGraph::empty(num_nodes);
+    for x in 0..num_nodes {
+        for y in 0..=x {
+            if rng.random_bool(p) {
+                graph.add_arc(x, y);
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+fn test_append() -> Result<()> {
+    let graph = backward_graph(100, 0.2);
+    let tmp = tempfile::tempdir()?;
+    for compression_window in [0, 1, 3, 7] {
+        for max_ref_count in [0, 1, 3, usize::MAX] {
+            for min_interval_length in [0, 2] {
+                let cf = CompFlags {
+                    compression_window,
+                    max_ref_count,
+                    min_interval_length,
+                    ..CompFlags::default()
+                };
+                for split in [0, 1, 5, 50, 99, 100] {
+                    check_append::<BE>(&graph, split, cf, tmp.path())?;
+                    check_append::<LE>(&graph, split, cf, tmp.path())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_append_cnr_2000() -> Result<()> {
+    let graph = VecGraph::from_lender(
+        BvGraphSeq::with_basename("../data/cnr-2000")
+            .endianness::<BE>()
+            .load()?
+            .iter(),
+    );
+    let tmp = tempfile::tempdir()?;
+    check_append::<BE>(&graph, 200_000, CompFlags::default(), tmp.path())
+}
+
+#[test]
+fn test_append_wrong_node() -> Result<()> {
+    let graph = backward_graph(10, 0.5);
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("graph");
+    BvComp::single_thread::<BE, _>(
+        &basename,
+        graph.iter().take(5),
+        CompFlags::default(),
+        true,
+        None,
+    )?;
+    // node 5 is missing
+    assert!(BvComp::append::<BE, _>(&basename, graph.iter_from(6)).is_err());
+    Ok(())
+}
@@ -74,8 +74,7 @@ fn test_arc_list_graph_cnr2000() {
     });
     assert_eq!(arcs.len(), graph.num_arcs() as _);
 
-    let arcgraph =
-        webgraph::graphs::arc_list_graph::ArcListGraph::new(graph.num_nodes(), arcs.into_iter());
+    let arcgraph = webgraph::graphs::arc_list_graph::ArcListGraph::new(graph.num_nodes(), arcs);
 
     assert_eq!(arcgraph.num_nodes(), graph.num_nodes());
     test_graph_iters(arcgraph.iter(), graph.iter());
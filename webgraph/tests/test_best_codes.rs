@@ -0,0 +1,40 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use webgraph::prelude::*;
+
+#[test]
+fn test_best_codes() -> Result<()> {
+    let graph = BvGraphSeq::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let mut comp_flags = CompFlags::default();
+    comp_flags.set_residuals(Codes::Gamma);
+
+    let best_flags = BvComp::best_codes(&graph, comp_flags)?;
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+    let par_best_flags = BvComp::parallel_best_codes(&graph, comp_flags, &thread_pool)?;
+    assert_eq!(best_flags, par_best_flags);
+    // γ is a bad code for residuals
+    assert_ne!(best_flags.residuals, Codes::Gamma);
+
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("gamma");
+    let len = BvComp::single_thread::<BE, _>(&basename, &graph, comp_flags, false, None)?;
+    let best_basename = tmp.path().join("best");
+    let best_len = BvComp::single_thread::<BE, _>(&best_basename, &graph, best_flags, false, None)?;
+    assert!(best_len < len);
+
+    // the codes are recorded in the properties, so dynamic dispatch works
+    let best_graph = BvGraphSeq::with_basename(&best_basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&graph, &best_graph)?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use webgraph::prelude::*;
+
+/// Checks that a length in bits is the one in the `.properties` file and the
+/// sum of the deltas in the `.offsets` file.
+fn check_len(basename: &Path, num_nodes: usize, len: u64) -> Result<()> {
+    let f = File::open(basename.with_extension(PROPERTIES_EXTENSION))?;
+    let map = java_properties::read(BufReader::new(f))?;
+    assert_eq!(map["length"].parse::<u64>()?, len);
+
+    let mut reader = <BufBitReader<BE, _>>::new(<WordAdapter<u32, _>>::new(BufReader::new(
+        File::open(basename.with_extension(OFFSETS_EXTENSION))?,
+    )));
+    let mut sum = 0;
+    for _ in 0..num_nodes + 1 {
+        sum += reader.read_gamma()?;
+    }
+    assert_eq!(sum, len);
+    Ok(())
+}
+
+#[test]
+fn test_bitstream_len() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let num_nodes = graph.num_nodes();
+    let tmp = tempfile::tempdir()?;
+
+    // Flushing must not count again the bits left in the buffer
+    let basename = tmp.path().join("be");
+    let len = BvComp::single_thread::<BE, _>(
+        &basename,
+        graph.iter(),
+        CompFlags::default(),
+        true,
+        Some(num_nodes),
+    )?;
+    check_len(&basename, num_nodes, len)?;
+    // The original graph has been compressed by Java with the same
+    // parameters, and its properties contain the bits of each component
+    let f = File::open("../data/cnr-2000.properties")?;
+    let map = java_properties::read(BufReader::new(f))?;
+    let java_len = map
+        .iter()
+        .filter(|(key, _)| key.starts_with("bitsfor"))
+        .map(|(_, value)| value.parse::<u64>())
+        .sum::<Result<u64, _>>()?;
+    assert_eq!(len, java_len);
+
+    let basename = tmp.path().join("le");
+    let len = BvComp::single_thread::<LE, _>(
+        &basename,
+        graph.iter(),
+        CompFlags::default(),
+        true,
+        Some(num_nodes),
+    )?;
+    check_len(&basename, num_nodes, len)?;
+
+    let basename = tmp.path().join("par");
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    let par_len = BvComp::parallel_graph::<BE>(
+        &basename,
+        &graph,
+        CompFlags::default(),
+        &threads,
+        tempfile::tempdir()?,
+    )?;
+    check_len(&basename, num_nodes, par_len)?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use epserde::prelude::*;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use sux::traits::IndexedSeq;
+use webgraph::graphs::bvgraph::{BuildMem, EF, EF_EXTENSION, OFFSETS_EXTENSION};
+use webgraph::prelude::*;
+
+/// Copies the graph and properties of cnr-2000 into `dir`, returning the
+/// basename of the copy.
+fn copy_graph(dir: &Path) -> Result<std::path::PathBuf> {
+    let basename = dir.join("cnr-2000");
+    std::fs::copy("../data/cnr-2000.graph", basename.with_extension("graph"))?;
+    std::fs::copy(
+        "../data/cnr-2000.properties",
+        basename.with_extension("properties"),
+    )?;
+    Ok(basename)
+}
+
+fn assert_same_offsets(basename: &Path) -> Result<()> {
+    let expected = EF::load_full("../data/cnr-2000.ef")?;
+    let ef = EF::load_full(basename.with_extension(EF_EXTENSION))?;
+    assert_eq!(ef.len(), expected.len());
+    for i in 0..ef.len() {
+        assert_eq!(ef.get(i), expected.get(i), "offset {}", i);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_build_offsets() -> Result<()> {
+    let expected = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+    let basename = copy_graph(tmp.path())?;
+
+    // Missing files
+    assert!(BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .load()
+        .is_err());
+    let graph = BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .build_offsets()
+        .load()?;
+    graph::eq(&expected, &graph)?;
+    labels::check_impl(&graph)?;
+    assert!(basename.with_extension(OFFSETS_EXTENSION).exists());
+    assert_same_offsets(&basename)?;
+
+    // Stale files
+    let ef_path = basename.with_extension(EF_EXTENSION);
+    std::fs::write(&ef_path, b"garbage")?;
+    std::fs::File::options()
+        .write(true)
+        .open(&ef_path)?
+        .set_modified(SystemTime::now() - Duration::from_secs(3600))?;
+    let graph = BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .build_offsets()
+        .load()?;
+    graph::eq(&expected, &graph)?;
+    assert_same_offsets(&basename)?;
+
+    // Up-to-date files are not rebuilt
+    let modified = ef_path.metadata()?.modified()?;
+    BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .build_offsets()
+        .load()?;
+    assert_eq!(ef_path.metadata()?.modified()?, modified);
+
+    Ok(())
+}
+
+#[test]
+fn test_build_mem() -> Result<()> {
+    let expected = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+    let basename = copy_graph(tmp.path())?;
+
+    let graph = BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .offsets_mode::<BuildMem>()
+        .load()?;
+    graph::eq(&expected, &graph)?;
+    labels::check_impl(&graph)?;
+    assert!(!basename.with_extension(EF_EXTENSION).exists());
+    assert!(!basename.with_extension(OFFSETS_EXTENSION).exists());
+
+    Ok(())
+}
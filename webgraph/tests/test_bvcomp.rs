@@ -56,16 +56,18 @@ where
                                         max_ref_count,
                                         min_interval_length,
                                     );
-                                    let compression_flags = CompFlags {
+                                    let mut compression_flags = CompFlags {
                                         outdegrees,
                                         references,
-                                        blocks,
-                                        intervals,
-                                        residuals,
                                         min_interval_length,
                                         compression_window,
                                         max_ref_count,
+                                        ref_selection: RefSelection::Greedy,
+                                        ..CompFlags::default()
                                     };
+                                    compression_flags.set_blocks(blocks);
+                                    compression_flags.set_intervals(intervals);
+                                    compression_flags.set_residuals(residuals);
 
                                     _test_body::<E, _>(tmp_path, &seq_graph, compression_flags)?;
                                 }
@@ -0,0 +1,90 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use webgraph::prelude::*;
+
+/// Computes the checksums of a graph with the given block size.
+fn checksums(graph: &impl SequentialGraph, block_size: usize) -> Checksums {
+    let mut checksums = Checksums::new(block_size);
+    for_!((node, succ) in graph.iter() {
+        checksums.add_successors(node, succ);
+    });
+    checksums
+}
+
+#[test]
+fn test_checksums() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let expected = checksums(&graph, CHECKSUM_BLOCK_SIZE);
+    assert_eq!(
+        expected.sums().len(),
+        graph.num_nodes().div_ceil(CHECKSUM_BLOCK_SIZE)
+    );
+
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("single");
+    BvComp::single_thread::<BE, _>(&basename, &graph, CompFlags::default(), true, None)?;
+    let found = Checksums::load(basename.with_extension(PROPERTIES_EXTENSION))?;
+    assert_eq!(found.as_ref(), Some(&expected));
+
+    let basename = tmp.path().join("parallel");
+    BvComp::parallel_graph::<BE>(
+        &basename,
+        &graph,
+        CompFlags::default(),
+        &rayon::ThreadPoolBuilder::new().num_threads(3).build()?,
+        tempfile::tempdir()?,
+    )?;
+    let found = Checksums::load(basename.with_extension(PROPERTIES_EXTENSION))?;
+    assert_eq!(found.as_ref(), Some(&expected));
+
+    // Graphs compressed before checksums were introduced have none
+    assert_eq!(Checksums::load("../data/cnr-2000.properties")?, None);
+    Ok(())
+}
+
+#[test]
+fn test_checksums_detect_changes() -> Result<()> {
+    let mut graph = VecGraph::from_arcs([(0, 1), (1, 2), (2, 0), (3, 4)]);
+    let checksums_before = checksums(&graph, 2);
+    assert_eq!(checksums_before.sums().len(), 3);
+
+    // Checksums are independent of the order in which nodes are added
+    let mut reversed = Checksums::new(2);
+    for node in (0..graph.num_nodes()).rev() {
+        reversed.add_successors(node, graph.successors(node));
+    }
+    assert_eq!(reversed, checksums_before);
+
+    // Partial checksums can be merged
+    let mut even = Checksums::new(2);
+    let mut odd = Checksums::new(2);
+    for node in 0..graph.num_nodes() {
+        let part = if node % 2 == 0 { &mut even } else { &mut odd };
+        part.add_successors(node, graph.successors(node));
+    }
+    even.merge(&odd);
+    assert_eq!(even, checksums_before);
+
+    graph.add_arc(2, 4);
+    let checksums_after = checksums(&graph, 2);
+    assert_eq!(checksums_before.sums()[0], checksums_after.sums()[0]);
+    assert_ne!(checksums_before.sums()[1], checksums_after.sums()[1]);
+    assert_eq!(checksums_before.sums()[2], checksums_after.sums()[2]);
+    assert_eq!(checksums_after.block_range(1, graph.num_nodes()), 2..4);
+
+    // Round trip through the properties format
+    let properties = checksums_after.to_properties();
+    let map = java_properties::read(properties.as_bytes())?;
+    assert_eq!(Checksums::from_properties(&map)?, Some(checksums_after));
+    Ok(())
+}
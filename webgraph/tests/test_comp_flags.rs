@@ -0,0 +1,223 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::dispatch::code_consts;
+use dsi_bitstream::prelude::*;
+use std::collections::HashMap;
+use webgraph::prelude::*;
+
+fn round_trip<E: Endianness>(cf: &CompFlags) -> Result<(String, CompFlags)> {
+    let properties = cf.to_properties::<E>(100, 1000, 10000)?;
+    let map: HashMap<String, String> = java_properties::read(properties.as_bytes())?;
+    Ok((properties, CompFlags::from_properties::<E>(&map)?))
+}
+
+#[test]
+fn test_code_str() {
+    let mut codes = vec![
+        Codes::Unary,
+        Codes::Gamma,
+        Codes::Delta,
+        Codes::Omega,
+        Codes::VByteBe,
+        Codes::VByteLe,
+    ];
+    for p in 1..=10 {
+        codes.push(Codes::Zeta { k: p });
+        codes.push(Codes::Pi { k: p });
+        codes.push(Codes::Golomb { b: p as u64 });
+        codes.push(Codes::ExpGolomb { k: p });
+        codes.push(Codes::Rice { log2_b: p });
+    }
+    for code in codes {
+        for k in [3, 5] {
+            let s = CompFlags::code_to_str(code, k).unwrap();
+            assert_eq!(CompFlags::code_from_str(&s, k), Some(code), "{}", s);
+        }
+    }
+    assert_eq!(
+        CompFlags::code_from_str("ZETA", 4),
+        Some(Codes::Zeta { k: 4 })
+    );
+    assert_eq!(CompFlags::code_from_str("PI", 4), None);
+    assert_eq!(CompFlags::code_from_str("FOO2", 4), None);
+}
+
+#[test]
+fn test_properties_java_compatible() -> Result<()> {
+    let mut cf = CompFlags {
+        outdegrees: Codes::Delta,
+        ..CompFlags::default()
+    };
+    cf.set_intervals(Codes::Zeta { k: 2 });
+    cf.set_residuals(Codes::Zeta { k: 2 });
+    let (properties, read_cf) = round_trip::<BE>(&cf)?;
+    assert!(cf.is_java_compatible());
+    assert!(properties.contains("version=0\n"));
+    assert!(!properties.contains("rustonly"));
+    assert!(properties.contains("zetak=2\n"));
+    assert!(properties.contains("compressionflags=OUTDEGREES_DELTA|INTERVALS_ZETA\n"));
+    assert_eq!(cf, read_cf);
+    Ok(())
+}
+
+#[test]
+fn test_properties_rust_only() -> Result<()> {
+    let mut cf = CompFlags {
+        outdegrees: Codes::Pi { k: 2 },
+        references: Codes::Rice { log2_b: 1 },
+        ..CompFlags::default()
+    };
+    cf.set_blocks(Codes::ExpGolomb { k: 1 });
+    cf.set_intervals(Codes::Zeta { k: 2 });
+    cf.set_residuals(Codes::Zeta { k: 5 });
+    assert!(!cf.is_java_compatible());
+    let (properties, read_cf) = round_trip::<BE>(&cf)?;
+    assert!(properties.contains("version=1\n"));
+    assert!(properties.contains("rustonly=true\n"));
+    assert!(properties.contains("zetak=5\n"));
+    assert_eq!(cf, read_cf);
+
+    let (properties, read_cf) = round_trip::<LE>(&cf)?;
+    assert!(properties.contains("version=1\n"));
+    assert_eq!(cf, read_cf);
+    Ok(())
+}
+
+#[test]
+fn test_properties_separate_codes() -> Result<()> {
+    // the blocks differ from the default, the block counts do not
+    let cf = CompFlags {
+        blocks: Codes::Delta,
+        interval_starts: Codes::Delta,
+        first_residuals: Codes::Gamma,
+        ..CompFlags::default()
+    };
+    assert!(!cf.is_java_compatible());
+    let (properties, read_cf) = round_trip::<BE>(&cf)?;
+    assert!(properties.contains("version=1\n"));
+    assert!(properties.contains(
+        "compressionflags=BLOCK_COUNTS_GAMMA|BLOCKS_DELTA|INTERVAL_COUNTS_GAMMA|INTERVAL_STARTS_DELTA|INTERVAL_LENS_GAMMA|FIRST_RESIDUALS_GAMMA|RESIDUALS_ZETA\n"
+    ));
+    assert_eq!(cf, read_cf);
+
+    // flags for single components override group flags, regardless of order
+    let mut map: HashMap<String, String> = java_properties::read(
+        CompFlags::default()
+            .to_properties::<BE>(100, 1000, 10000)?
+            .as_bytes(),
+    )?;
+    map.insert(
+        "compressionflags".into(),
+        "FIRST_RESIDUALS_DELTA|RESIDUALS_GAMMA|BLOCKS_UNARY".into(),
+    );
+    map.insert("rustonly".into(), "true".into());
+    let read_cf = CompFlags::from_properties::<BE>(&map)?;
+    assert_eq!(read_cf.first_residuals, Codes::Delta);
+    assert_eq!(read_cf.residuals, Codes::Gamma);
+    assert_eq!(read_cf.block_counts, Codes::Unary);
+    assert_eq!(read_cf.blocks, Codes::Unary);
+
+    // Rust-only codes must be marked as such
+    map.remove("rustonly");
+    assert!(CompFlags::from_properties::<BE>(&map).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_compress_separate_codes() -> Result<()> {
+    let graph = BvGraphSeq::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let cf = CompFlags {
+        outdegrees: Codes::ExpGolomb { k: 1 },
+        references: Codes::Golomb { b: 3 },
+        block_counts: Codes::Delta,
+        blocks: Codes::Omega,
+        interval_counts: Codes::Gamma,
+        interval_starts: Codes::Zeta { k: 2 },
+        interval_lens: Codes::Rice { log2_b: 2 },
+        first_residuals: Codes::Zeta { k: 4 },
+        residuals: Codes::Pi { k: 2 },
+        ..CompFlags::default()
+    };
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("cnr-2000");
+    BvComp::single_thread::<BE, _>(&basename, &graph, cf, false, None)?;
+
+    let dyn_graph = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&graph, &dyn_graph)?;
+
+    let static_graph = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .dispatch::<Static<
+            { code_consts::EXP_GOLOMB1 },
+            { code_consts::GOLOMB3 },
+            { code_consts::OMEGA },
+            { code_consts::RICE2 },
+            { code_consts::PI2 },
+            { code_consts::DELTA },
+            { code_consts::GAMMA },
+            { code_consts::ZETA2 },
+            { code_consts::ZETA4 },
+        >>()
+        .load()?;
+    graph::eq(&graph, &static_graph)?;
+    Ok(())
+}
+
+#[test]
+fn test_java_zetak() -> Result<()> {
+    // Java writes the parameter of ζ codes in the zetak property, and uses
+    // ζ codes for residuals unless otherwise specified
+    let properties =
+        "graphclass=it.unimi.dsi.webgraph.BVGraph\nversion=0\nzetak=2\ncompressionflags=\n";
+    let map: HashMap<String, String> = java_properties::read(properties.as_bytes())?;
+    let cf = CompFlags::from_properties::<BE>(&map)?;
+    assert_eq!(cf.residuals, Codes::Zeta { k: 2 });
+
+    let properties = "version=0\nzetak=5\ncompressionflags=OUTDEGREES_ZETA|RESIDUALS_GAMMA\n";
+    let map: HashMap<String, String> = java_properties::read(properties.as_bytes())?;
+    let cf = CompFlags::from_properties::<BE>(&map)?;
+    assert_eq!(cf.outdegrees, Codes::Zeta { k: 5 });
+    assert_eq!(cf.residuals, Codes::Gamma);
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_codes() -> Result<()> {
+    for s in [
+        "GOLOMB0",
+        "GOLOMB11",
+        "RICE11",
+        "EXPGOLOMB11",
+        "PI11",
+        "ZETA0",
+        "ZETA11",
+    ] {
+        assert_eq!(CompFlags::code_from_str(s, 3), None, "{}", s);
+    }
+    assert_eq!(CompFlags::code_from_str("ZETA", 11), None);
+    assert!(CompFlags::is_supported(Codes::Rice { log2_b: 0 }));
+    assert!(!CompFlags::is_supported(Codes::Golomb { b: 0 }));
+
+    // Errors are reported when parsing the properties
+    let read = |properties: &str| -> Result<CompFlags> {
+        let map: HashMap<String, String> = java_properties::read(properties.as_bytes())?;
+        CompFlags::from_properties::<BE>(&map)
+    };
+    assert!(read("version=1\nrustonly=true\ncompressionflags=OUTDEGREES_GOLOMB0\n").is_err());
+    assert!(read("version=1\nrustonly=true\ncompressionflags=BLOCKS_RICE11\n").is_err());
+    assert!(read("version=0\nzetak=11\ncompressionflags=\n").is_err());
+    // The ζ parameter is not used
+    let cf = read("version=0\nzetak=11\ncompressionflags=RESIDUALS_GAMMA\n")?;
+    assert_eq!(cf.residuals, Codes::Gamma);
+    Ok(())
+}
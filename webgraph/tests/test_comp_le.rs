@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use webgraph::graphs::random::ErdosRenyi;
+use webgraph::prelude::*;
+
+fn read_properties(basename: &Path) -> Result<HashMap<String, String>> {
+    let f = File::open(basename.with_extension(PROPERTIES_EXTENSION))?;
+    Ok(java_properties::read(BufReader::new(f))?)
+}
+
+#[test]
+fn test_single_thread_le() -> Result<()> {
+    let graph = ErdosRenyi::new(1000, 0.01, 0);
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("le");
+    let compression_flags = CompFlags {
+        outdegrees: Codes::Delta,
+        references: Codes::Gamma,
+        ..CompFlags::default()
+    };
+    BvComp::single_thread::<LE, _>(&basename, graph.iter(), compression_flags, true, Some(1000))?;
+
+    let read_flags = CompFlags::from_properties::<LE>(&read_properties(&basename)?)?;
+    assert_eq!(read_flags.outdegrees, Codes::Delta);
+    assert_eq!(read_flags.references, Codes::Gamma);
+    // The bitstream must use the codes in the properties
+    let seq = BvGraphSeq::with_basename(&basename)
+        .endianness::<LE>()
+        .load()?;
+    graph::eq(&graph, &seq)?;
+
+    // Offsets are big endian, whatever the endianness of the graph
+    let mut reader = <BufBitReader<BE, _>>::new(<WordAdapter<u32, _>>::new(BufReader::new(
+        File::open(basename.with_extension(OFFSETS_EXTENSION))?,
+    )));
+    let mut offset = reader.read_gamma()?;
+    for (expected, _) in seq.offset_deg_iter() {
+        assert_eq!(offset, expected);
+        offset += reader.read_gamma()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parallel_le() -> Result<()> {
+    let graph = BvGraphSeq::with_basename("../data/test")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("le");
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(2).build()?;
+    BvComp::parallel_graph::<LE>(
+        &basename,
+        &graph,
+        CompFlags::default(),
+        &threads,
+        tempfile::tempdir()?,
+    )?;
+
+    CompFlags::from_properties::<LE>(&read_properties(&basename)?)?;
+    let seq = BvGraphSeq::with_basename(&basename)
+        .endianness::<LE>()
+        .load()?;
+    graph::eq(&graph, &seq)?;
+    Ok(())
+}
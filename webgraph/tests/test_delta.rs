@@ -0,0 +1,81 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use webgraph::prelude::*;
+
+#[test]
+fn test_delta() -> Result<()> {
+    let arcs = [(0, 1), (0, 2), (1, 2), (2, 0), (3, 3)];
+    let mut graph = DeltaGraph::new(VecGraph::from_arcs(arcs));
+    // The same graph, mutated directly
+    let mut expected = BTreeGraph::from_arcs(arcs);
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let src = rng.random_range(0..6);
+        let dst = rng.random_range(0..6);
+        graph.add_node(src.max(dst));
+        expected.add_node(src.max(dst));
+        if rng.random_bool(0.5) {
+            assert_eq!(graph.add_arc(src, dst), expected.add_arc(src, dst));
+        } else {
+            assert_eq!(graph.remove_arc(src, dst), expected.remove_arc(src, dst));
+        }
+        assert_eq!(graph.num_arcs(), expected.num_arcs());
+        assert!(graph.successors(src).eq(expected.successors(src)));
+    }
+
+    labels::check_impl(&graph)?;
+    graph::eq(&graph, &expected)?;
+    for node in 0..graph.num_nodes() {
+        assert_eq!(graph.outdegree(node), expected.outdegree(node));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_delta_compact() -> Result<()> {
+    let load =
+        |basename: &std::path::Path| BvGraph::with_basename(basename).endianness::<BE>().load();
+    let mut graph = DeltaGraph::new(load("../data/cnr-2000".as_ref())?);
+    let mut expected = VecGraph::new();
+    let num_nodes = graph.num_nodes();
+    expected.add_node(num_nodes + 1);
+    let src = (1000..).find(|&node| graph.outdegree(node) > 0).unwrap();
+    let first = graph.successors(src).next().unwrap();
+    for_!((node, succ) in graph.base().iter() {
+        for dst in succ {
+            if (node, dst) != (src, first) {
+                expected.add_arc(node, dst);
+            }
+        }
+        if node == 0 {
+            expected.add_arc(0, num_nodes);
+        }
+    });
+    expected.add_arc(num_nodes + 1, 0);
+
+    graph.add_arcs([(0, num_nodes), (num_nodes + 1, 0)]);
+    graph.remove_arcs([(src, first), (src, num_nodes + 1)]);
+    assert_eq!(graph.num_arcs(), expected.num_arcs());
+
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("compact");
+    graph.compact::<BE>(&basename, CompFlags::default())?;
+    let compacted = load(&basename)?;
+    graph::eq(&compacted, &expected)?;
+
+    // The compacted graph can be used as a new base
+    let mut graph = DeltaGraph::new(compacted);
+    assert!(graph.add_arc(src, first));
+    assert!(graph.successors(src).any(|succ| succ == first));
+    Ok(())
+}
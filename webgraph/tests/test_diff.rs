@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use webgraph::graphs::vec_graph::LabeledVecGraph;
+use webgraph::prelude::*;
+
+#[test]
+fn test_diff() -> Result<()> {
+    let g0 = VecGraph::from_arcs([(0, 1), (0, 2), (1, 2), (2, 0), (3, 1), (3, 3)]);
+    let g1 = VecGraph::from_arcs([(0, 1), (0, 3), (1, 2), (2, 0), (3, 3), (4, 0)]);
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+
+    assert!(diff(&g0, &g0).is_empty());
+    assert!(diff_split(&g0, &g0, &threads)?.is_empty());
+
+    let expected = Diff {
+        stats: DiffStats {
+            nodes: 3,
+            removed: 2,
+            added: 2,
+            changed: 0,
+        },
+        nodes: vec![
+            NodeDiff {
+                node: 0,
+                removed: vec![(2, ())],
+                added: vec![(3, ())],
+                changed: vec![],
+            },
+            NodeDiff {
+                node: 3,
+                removed: vec![(1, ())],
+                added: vec![],
+                changed: vec![],
+            },
+            NodeDiff {
+                node: 4,
+                removed: vec![],
+                added: vec![(0, ())],
+                changed: vec![],
+            },
+        ],
+    };
+    // g0 has fewer nodes than g1
+    assert_eq!(diff(&g0, &g1), expected);
+    assert!(matches!(
+        diff_split(&g0, &g1, &threads),
+        Err(EqError::NumNodes { .. })
+    ));
+
+    let mut g0 = g0;
+    g0.add_node(4);
+    assert_eq!(diff(&g0, &g1), expected);
+    assert_eq!(diff_split(&g0, &g1, &threads)?, expected);
+
+    // The callbacks receive the same differences, in the same order
+    let mut nodes = vec![];
+    let stats = diff_with(&g0, &g1, |node_diff| {
+        nodes.push(node_diff);
+        Ok::<_, EqError>(())
+    })?;
+    assert_eq!(stats, expected.stats);
+    assert_eq!(nodes, expected.nodes);
+    let mut nodes = vec![];
+    let stats = diff_split_with(&g0, &g1, &threads, |part| {
+        nodes.extend(part.nodes);
+        Ok::<_, EqError>(())
+    })?;
+    assert_eq!(stats, expected.stats);
+    assert_eq!(nodes, expected.nodes);
+
+    // An error stops the computation
+    let mut calls = 0;
+    assert!(diff_with(&g0, &g1, |_| {
+        calls += 1;
+        Err(anyhow::anyhow!("stop"))
+    })
+    .is_err());
+    assert_eq!(calls, 1);
+    assert!(diff_split_with(&g0, &g1, &threads, |_| Err(anyhow::anyhow!("stop"))).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_diff_labeled() -> Result<()> {
+    let g0 = LabeledVecGraph::<u32>::from_arcs([(0, 1, 1), (0, 2, 2), (1, 0, 3), (2, 2, 4)]);
+    let g1 = LabeledVecGraph::<u32>::from_arcs([(0, 1, 1), (0, 2, 5), (1, 2, 3), (2, 2, 4)]);
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(2).build()?;
+
+    let diff = diff_labeled(&g0, &g1);
+    assert_eq!(diff, diff_labeled_split(&g0, &g1, &threads)?);
+    assert_eq!(
+        diff.stats,
+        DiffStats {
+            nodes: 2,
+            removed: 1,
+            added: 1,
+            changed: 1,
+        }
+    );
+    assert_eq!(diff.nodes[0].changed, vec![(2, 2, 5)]);
+    assert_eq!(diff.nodes[1].removed, vec![(0, 3)]);
+    assert_eq!(diff.nodes[1].added, vec![(2, 3)]);
+    Ok(())
+}
+
+#[test]
+fn test_diff_split() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    assert!(diff_split(&graph, &graph, &threads)?.is_empty());
+
+    // Remove the first successor of a few nodes
+    let mut modified = VecGraph::new();
+    modified.add_node(graph.num_nodes() - 1);
+    for_!((node, succ) in graph.iter() {
+        let skip = (node == 0 || node == 200_000) as usize;
+        for s in succ.into_iter().skip(skip) {
+            modified.add_arc(node, s);
+        }
+    });
+    let diff = diff_split(&graph, &modified, &threads)?;
+    assert_eq!(diff, webgraph::traits::diff(&graph, &modified));
+    assert_eq!(diff.stats.nodes, 2);
+    assert_eq!(diff.stats.removed, 2);
+    assert_eq!(diff.stats.added, 0);
+    assert_eq!(
+        diff.nodes[0].removed[0].0,
+        graph.successors(0).next().unwrap()
+    );
+    assert_eq!(diff.nodes[1].node, 200_000);
+    Ok(())
+}
@@ -0,0 +1,240 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use epserde::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use sux::prelude::*;
+use webgraph::graphs::arc_list_graph::ArcListGraph;
+use webgraph::graphs::bvgraph::{LoadMem, EF, OFFSETS_EXTENSION};
+use webgraph::graphs::efgraph::{get_ef_endianness, DEFAULT_LOG2_QUANTUM};
+use webgraph::prelude::*;
+
+/// Checks skipping against a reference graph on a sample of nodes.
+fn check_skip(expected: &impl RandomAccessGraph, graph: &impl RandomAccessGraph) {
+    for node in (0..expected.num_nodes()).step_by(97) {
+        let succ = expected.successors(node).into_iter().collect::<Vec<_>>();
+        for &s in succ.iter().step_by(7) {
+            assert!(graph.has_arc(node, s));
+            assert!(!graph.has_arc(node, s + 1) || succ.contains(&(s + 1)));
+        }
+    }
+}
+
+#[test]
+fn test_efgraph() -> Result<()> {
+    let expected = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+
+    let basename = tmp.path().join("le");
+    EfGraph::store::<LE>(&basename, &expected, DEFAULT_LOG2_QUANTUM)?;
+    assert!(basename.with_extension(OFFSETS_EXTENSION).exists());
+    assert_eq!(get_ef_endianness(&basename)?, LE::NAME);
+    let graph = EfGraph::with_basename(&basename).load()?;
+    assert_eq!(graph.num_arcs(), expected.num_arcs());
+    graph::eq(&expected, &graph)?;
+    labels::check_impl(&graph)?;
+    check_skip(&expected, &graph);
+    let graph = EfGraph::with_basename(&basename).mode::<LoadMem>().load()?;
+    graph::eq(&expected, &graph)?;
+    // The byte order must match
+    assert!(EfGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .load()
+        .is_err());
+
+    // A small quantum exercises the skip pointers
+    let basename = tmp.path().join("be");
+    EfGraph::store::<BE>(&basename, &expected, 2)?;
+    assert_eq!(get_ef_endianness(&basename)?, BE::NAME);
+    let graph = EfGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    assert_eq!(graph.log2_quantum(), 2);
+    graph::eq(&expected, &graph)?;
+    labels::check_impl(&graph)?;
+    check_skip(&expected, &graph);
+    Ok(())
+}
+
+#[test]
+fn test_efgraph_successors_from() -> Result<()> {
+    let expected =
+        VecGraph::from_arcs(
+            (0..2000)
+                .map(|i| (0, i * 3))
+                .chain([(1, 0), (1, 5999), (3, 2)]),
+        );
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("small");
+    EfGraph::store::<LE>(&basename, &expected, 1)?;
+    let graph = EfGraph::with_basename(&basename).load()?;
+    graph::eq(&expected, &graph)?;
+    assert_eq!(graph.outdegree(0), 2000);
+    assert_eq!(graph.outdegree(2), 0);
+
+    for min in [0, 1, 3, 4, 1000, 2999, 3000, 5997, 5998] {
+        let mut succ = graph.successors_from(0, min);
+        let found = succ.clone().collect::<Vec<_>>();
+        let expected = (0..2000)
+            .map(|i| i * 3)
+            .filter(|&s| s >= min)
+            .collect::<Vec<_>>();
+        assert_eq!(succ.len(), expected.len());
+        assert_eq!(found, expected);
+        // Skipping backwards has no effect
+        succ.skip_to(0);
+        assert_eq!(succ.next(), expected.first().copied());
+    }
+    assert_eq!(graph.successors_from(1, 1).collect::<Vec<_>>(), vec![5999]);
+    assert_eq!(graph.successors_from(1, 6000).next(), None);
+    assert!(graph.has_arc(3, 2));
+    assert!(!graph.has_arc(2, 2));
+
+    // Successors must be sorted
+    let unsorted = ArcListGraph::new(3, [(0, 2), (0, 1)]);
+    assert!(EfGraph::store::<LE>(tmp.path().join("unsorted"), &unsorted, 8).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_efgraph_layout() -> Result<()> {
+    // Node 0 has 4 successors with upper bound 8, so it has 1 lower bit and
+    // two skip pointers of ⌈log₂(4 + (8 >> 1))⌉ = 3 bits, as in Java; all
+    // upper bits are smaller than 2, so both pointers are zero
+    let graph = VecGraph::from_arcs([(0, 0), (0, 1), (0, 2), (0, 3), (7, 7)]);
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("layout");
+    assert_eq!(EfGraph::store::<LE>(&basename, &graph, 1)?, 20 + 6 + 7);
+
+    let bytes = std::fs::read(basename.with_extension("graph"))?;
+    let word = u64::from_le_bytes(bytes[..8].try_into()?);
+    // Bits are written starting from the least significant one
+    // (the skip pointers are the zero bits in positions [5..11))
+    let expected = 0b01100 // γ(4)
+        | 0b1010 << 11 // the lower bits of 0, 1, 2, 3
+        | 0b11011 << 15 // the upper bits of 0, 1, 2, 3
+        | 0b111111 << 20 // γ(0) for nodes 1 to 6
+        | 0b010 << 26 // γ(1) for node 7
+        | 0b111 << 29 // the lower bits of 7
+        | 0b1 << 32; // the upper bits of 7
+    assert_eq!(word, expected);
+
+    let graph = EfGraph::with_basename(&basename).load()?;
+    assert_eq!(graph.successors_from(0, 2).collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!(graph.successors_from(0, 6).next(), None);
+    assert!(!graph.has_arc(0, 6));
+    assert!(graph.has_arc(7, 7));
+    Ok(())
+}
+
+/// The graph of the `test-ef` fixture, which must be kept in sync with
+/// `data/efgraph.py`.
+fn fixture_graph() -> VecGraph {
+    let mut graph = VecGraph::empty(40);
+    let arcs = [
+        (0, vec![1, 2, 3, 5, 8, 13, 21, 34]),
+        (1, vec![0]),
+        (3, vec![39]),
+        (5, (0..40).collect()),
+        (6, vec![2, 4, 6, 7, 9, 10, 30, 31, 32, 33, 38]),
+        (39, vec![0, 39]),
+    ];
+    for (node, succ) in arcs {
+        for s in succ {
+            graph.add_arc(node, s);
+        }
+    }
+    graph
+}
+
+fn read_properties(path: impl AsRef<Path>) -> Result<HashMap<String, String>> {
+    Ok(java_properties::read(std::fs::File::open(path)?)?)
+}
+
+/// Builds the `.ef` file of a graph from its `.offsets` file, which, as in
+/// Java, is padded to a byte.
+fn build_ef(basename: &Path, num_nodes: usize) -> Result<()> {
+    let mut bytes = std::fs::read(basename.with_extension(OFFSETS_EXTENSION))?;
+    bytes.resize(bytes.len().next_multiple_of(4), 0);
+    let words = bytes
+        .chunks(4)
+        .map(|chunk| u32::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    let mut reader = <BufBitReader<BE, _>>::new(MemWordReader::new(&words));
+    let graph_len = std::fs::metadata(basename.with_extension("graph"))?.len();
+    let mut efb = EliasFanoBuilder::new(num_nodes + 1, 8 * graph_len as usize);
+    let mut offset = 0;
+    for _ in 0..num_nodes + 1 {
+        offset += reader.read_gamma()?;
+        efb.push(offset as usize);
+    }
+    let ef: EF = unsafe {
+        efb.build()
+            .map_high_bits(SelectAdaptConst::<_, _, 12, 4>::new)
+    };
+    ef.store(basename.with_extension("ef"))?;
+    Ok(())
+}
+
+/// Checks [`EfGraph::store`] and [`EfGraph::with_basename`] against the
+/// `test-ef` fixture.
+///
+/// The fixture follows the layout of Java's `EFGraph` with a quantum of 4,
+/// but it has been written by `data/efgraph.py`, independently of the Rust
+/// code, and checked by hand: it was not produced by the Java implementation,
+/// as none was available.
+#[test]
+fn test_efgraph_fixture() -> Result<()> {
+    let expected = fixture_graph();
+    let tmp = tempfile::tempdir()?;
+
+    let basename = tmp.path().join("test-ef");
+    EfGraph::store::<LE>(&basename, &expected, 2)?;
+    assert_eq!(
+        std::fs::read(basename.with_extension("graph"))?,
+        std::fs::read("../data/test-ef.graph")?
+    );
+    // Java pads offsets to a byte, whereas we pad them to a word
+    let offsets = std::fs::read(basename.with_extension(OFFSETS_EXTENSION))?;
+    let java_offsets = std::fs::read("../data/test-ef.offsets")?;
+    assert_eq!(&offsets[..java_offsets.len()], &java_offsets[..]);
+    assert!(offsets[java_offsets.len()..].iter().all(|&b| b == 0));
+    // Java properties have a date and no order
+    assert_eq!(
+        read_properties(basename.with_extension("properties"))?,
+        read_properties("../data/test-ef.properties")?
+    );
+
+    // Reads back the fixture, building the missing .ef file
+    let basename = tmp.path().join("java");
+    for extension in ["graph", "offsets", "properties"] {
+        std::fs::copy(
+            Path::new("../data/test-ef").with_extension(extension),
+            basename.with_extension(extension),
+        )?;
+    }
+    build_ef(&basename, expected.num_nodes())?;
+    let graph = EfGraph::with_basename(&basename).load()?;
+    assert_eq!(graph.num_arcs(), 63);
+    assert_eq!(graph.log2_quantum(), 2);
+    graph::eq(&expected, &graph)?;
+    labels::check_impl(&graph)?;
+    assert_eq!(
+        graph.successors_from(5, 37).collect::<Vec<_>>(),
+        [37, 38, 39]
+    );
+    assert_eq!(
+        graph.successors_from(6, 11).collect::<Vec<_>>(),
+        [30, 31, 32, 33, 38]
+    );
+    Ok(())
+}
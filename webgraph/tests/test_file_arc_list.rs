@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use webgraph::graphs::random::BarabasiAlbert;
+use webgraph::prelude::*;
+
+/// Writes the arcs of `graph` to `path` in the given format.
+fn write_arcs(graph: &impl SequentialGraph, path: &Path, format: ArcFileFormat) -> Result<()> {
+    let mut file = BufWriter::new(std::fs::File::create(path)?);
+    if format == ArcFileFormat::Text {
+        writeln!(file, "# Source\tTarget")?;
+    }
+    for_!((src, succ) in graph.iter() {
+        for dst in succ {
+            match format {
+                ArcFileFormat::Text => writeln!(file, "{}\t{}", src, dst)?,
+                ArcFileFormat::U32 => {
+                    file.write_all(&(src as u32).to_be_bytes())?;
+                    file.write_all(&(dst as u32).to_be_bytes())?;
+                }
+                ArcFileFormat::U64 => {
+                    file.write_all(&(src as u64).to_be_bytes())?;
+                    file.write_all(&(dst as u64).to_be_bytes())?;
+                }
+            }
+        }
+        if format == ArcFileFormat::Text && src % 1000 == 0 {
+            writeln!(file)?;
+            writeln!(file, "# Node {}", src)?;
+        }
+    });
+    file.flush()?;
+    Ok(())
+}
+
+/// Checks that the concatenation of the lenders returned by `split_iter`
+/// is equal to `graph`.
+fn check_split(file_graph: &FileArcListGraph, graph: &impl SequentialGraph, how_many: usize) {
+    let mut expected = graph.iter();
+    let mut next_node = 0;
+    for lender in file_graph.split_iter(how_many) {
+        for_!((node, succ) in lender {
+            assert_eq!(node, next_node);
+            next_node += 1;
+            let (expected_node, expected_succ) = expected.next().unwrap();
+            assert_eq!(node, expected_node);
+            assert!(succ.into_iter().map(|(dst, _)| dst).eq(expected_succ));
+        });
+    }
+    assert_eq!(next_node, graph.num_nodes());
+}
+
+#[test]
+fn test_file_arc_list() -> Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let graph = BarabasiAlbert::new(5000, 3, 0);
+    // Add isolated nodes at the end
+    let mut vec_graph = VecGraph::from_lender(graph.iter());
+    vec_graph.add_node(5009);
+
+    for format in [ArcFileFormat::Text, ArcFileFormat::U32, ArcFileFormat::U64] {
+        let path = tmp.path().join("arcs");
+        write_arcs(&vec_graph, &path, format)?;
+        let file_graph = FileArcListGraph::new(&path, format, 5010)?;
+        graph::eq(&file_graph, &vec_graph)?;
+        if format != ArcFileFormat::Text {
+            assert_eq!(file_graph.num_arcs_hint(), Some(vec_graph.num_arcs()));
+        }
+
+        let file_graph = FileArcListGraph::new_labeled(&path, format, 5010)?;
+        for how_many in [1, 2, 3, 7, 100, 20_000] {
+            check_split(&file_graph, &vec_graph, how_many);
+        }
+
+        // Lenders starting in the middle of the graph
+        let mut iter = file_graph.iter_from(4999);
+        let (node, succ) = iter.next().unwrap();
+        assert_eq!(node, 4999);
+        assert!(succ
+            .into_iter()
+            .map(|(dst, _)| dst)
+            .eq(vec_graph.successors(4999)));
+    }
+
+    // Files containing only comments or truncated pairs
+    let path = tmp.path().join("empty");
+    std::fs::write(&path, "# Nothing here\n\n")?;
+    let file_graph = FileArcListGraph::new_labeled(&path, ArcFileFormat::Text, 3)?;
+    check_split(&file_graph, &VecGraph::empty(3), 4);
+    std::fs::write(&path, [0; 12])?;
+    assert!(FileArcListGraph::new(&path, ArcFileFormat::U32, 3).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_file_arc_list_compress() -> Result<()> {
+    let graph = BvGraphSeq::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+    let path = tmp.path().join("cnr-2000.tsv");
+    write_arcs(&graph, &path, ArcFileFormat::Text)?;
+    let file_graph = FileArcListGraph::new(&path, ArcFileFormat::Text, graph.num_nodes())?;
+
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    let basename = tmp.path().join("cnr-2000");
+    BvComp::parallel_graph::<BE>(
+        &basename,
+        &file_graph,
+        CompFlags::default(),
+        &thread_pool,
+        tempfile::tempdir()?,
+    )?;
+    let compressed = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&compressed, &graph)?;
+
+    let transposed = transpose_split(&file_graph, 100_000, &thread_pool)?;
+    let expected = transpose(&graph, 100_000)?;
+    graph::eq(&transposed, &expected)?;
+    Ok(())
+}
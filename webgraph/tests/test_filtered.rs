@@ -0,0 +1,76 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use webgraph::graphs::vec_graph::LabeledVecGraph;
+use webgraph::prelude::*;
+
+#[test]
+fn test_filtered() -> Result<()> {
+    let graph = VecGraph::from_arcs([(0, 1), (0, 2), (1, 1), (1, 3), (2, 0), (3, 2), (3, 3)]);
+    let filtered = FilteredGraph::new(graph, |src, dst, _| src < dst);
+    assert_eq!(filtered.num_nodes(), 4);
+    assert_eq!(filtered.num_arcs(), 3);
+    labels::check_impl(&filtered)?;
+    assert_eq!(filtered.successors(0).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(filtered.successors(3).collect::<Vec<_>>(), vec![]);
+    assert_eq!(filtered.outdegree(1), 1);
+    graph::eq(&filtered, &VecGraph::from_arcs([(0, 1), (0, 2), (1, 3)]))?;
+    assert_eq!(filtered.num_arcs_hint(), Some(3));
+    Ok(())
+}
+
+#[test]
+fn test_filtered_labeled() -> Result<()> {
+    let graph =
+        LabeledVecGraph::<u32>::from_arcs([(0, 1, 5), (0, 2, 1), (1, 0, 2), (1, 2, 7), (2, 2, 3)]);
+    let filtered = FilteredGraph::new(graph, |_, _, &label| label >= 3);
+    assert_eq!(filtered.num_arcs(), 3);
+    labels::check_impl(&filtered)?;
+    assert_eq!(filtered.successors(1).collect::<Vec<_>>(), vec![(2, 7)]);
+    graph::eq_labeled(
+        &filtered,
+        &LabeledVecGraph::<u32>::from_arcs([(0, 1, 5), (1, 2, 7), (2, 2, 3)]),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_filtered_compress() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    // Remove arcs between nearby nodes, which are most likely intra-host
+    let far = |src: usize, dst: usize| src.abs_diff(dst) > 100;
+
+    let mut expected = VecGraph::new();
+    expected.add_node(graph.num_nodes() - 1);
+    for_!((node, succ) in graph.iter() {
+        for dst in succ.into_iter().filter(|&dst| far(node, dst)) {
+            expected.add_arc(node, dst);
+        }
+    });
+
+    let filtered = FilteredGraph::new(graph, |src, dst, _| far(src, dst));
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("filtered");
+    BvComp::parallel_graph::<BE>(
+        &basename,
+        &filtered,
+        CompFlags::default(),
+        &rayon::ThreadPoolBuilder::new().num_threads(3).build()?,
+        tempfile::tempdir()?,
+    )?;
+    let compressed = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&compressed, &expected)?;
+    assert_eq!(filtered.num_arcs(), expected.num_arcs());
+    Ok(())
+}
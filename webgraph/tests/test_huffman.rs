@@ -0,0 +1,141 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use webgraph::graphs::bvgraph::Huffman;
+use webgraph::prelude::*;
+use webgraph::traits::labels;
+
+#[test]
+fn test_huffman_cnr_2000() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let tmp = tempfile::tempdir()?;
+    let cf = CompFlags::default();
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    let models = BvComp::parallel_huffman_models(&graph, cf, &threads)?;
+
+    let basename = tmp.path().join("cnr-2000");
+    let huffman_len = BvComp::parallel_huffman::<BE>(
+        &basename,
+        &graph,
+        cf,
+        &models,
+        &threads,
+        tempfile::tempdir()?,
+    )?;
+    let instantaneous_len =
+        BvComp::single_thread::<BE, _>(tmp.path().join("instantaneous"), &graph, cf, false, None)?;
+    assert!(
+        huffman_len < instantaneous_len,
+        "{} >= {}",
+        huffman_len,
+        instantaneous_len
+    );
+
+    let seq_graph = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .dispatch::<Huffman>()
+        .load()?;
+    graph::eq(&graph, &seq_graph)?;
+    let huffman_graph = BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .dispatch::<Huffman>()
+        .load()?;
+    labels::check_impl(&huffman_graph)?;
+    graph::eq(&graph, &huffman_graph)?;
+
+    // the default dispatch reads the models, too
+    let dyn_graph = BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    labels::check_impl(&dyn_graph)?;
+    graph::eq(&graph, &dyn_graph)?;
+    let dyn_seq_graph = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&graph, &dyn_seq_graph)?;
+    let stream_graph = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load_from_path(basename.with_extension(GRAPH_EXTENSION))?;
+    graph::eq(&graph, &stream_graph)?;
+
+    // but the graph cannot be read with static dispatch
+    assert!(BvGraph::with_basename(&basename)
+        .endianness::<BE>()
+        .dispatch::<Static>()
+        .load()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_huffman() -> Result<()> {
+    // arcs towards random, possibly very distant, nodes, so that all tokens
+    // are used
+    let mut rng = SmallRng::seed_from_u64(0);
+    let num_nodes = 1000;
+    let mut graph = VecGraph::empty(num_nodes);
+    for x in 0..num_nodes {
+        let log_outdegree = rng.random_range(0..8);
+        let mut succ = (0..rng.random_range(0..1 << log_outdegree))
+            .map(|_| rng.random_range(0..num_nodes))
+            .collect::<Vec<_>>();
+        succ.sort();
+        succ.dedup();
+        for y in succ {
+            graph.add_arc(x, y);
+        }
+    }
+    // models built on a different graph can encode any value
+    let models = BvComp::huffman_models(&VecGraph::empty(1), CompFlags::default())?;
+
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("graph");
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    for compression_window in [0, 7] {
+        let cf = CompFlags {
+            compression_window,
+            ..CompFlags::default()
+        };
+        for models in [&models, &BvComp::huffman_models(&graph, cf)?] {
+            BvComp::parallel_huffman::<BE>(
+                &basename,
+                &graph,
+                cf,
+                models,
+                &threads,
+                tempfile::tempdir()?,
+            )?;
+            let huffman_graph = BvGraph::with_basename(&basename)
+                .endianness::<BE>()
+                .dispatch::<Huffman>()
+                .load()?;
+            graph::eq(&graph, &huffman_graph)?;
+            labels::check_impl(&huffman_graph)?;
+
+            BvComp::parallel_huffman::<LE>(
+                &basename,
+                &graph,
+                cf,
+                models,
+                &threads,
+                tempfile::tempdir()?,
+            )?;
+            let huffman_graph = BvGraph::with_basename(&basename)
+                .endianness::<LE>()
+                .dispatch::<Huffman>()
+                .load()?;
+            graph::eq(&graph, &huffman_graph)?;
+            labels::check_impl(&huffman_graph)?;
+        }
+    }
+    Ok(())
+}
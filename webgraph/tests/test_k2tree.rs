@@ -0,0 +1,130 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use epserde::prelude::*;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use webgraph::graphs::arc_list_graph::ArcListGraph;
+use webgraph::prelude::*;
+use webgraph::traits::labels;
+
+/// Returns a random graph with the given number of nodes.
+fn random_graph(num_nodes: usize, p: f64) -> VecGraph {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut graph = VecGraph::empty(num_nodes);
+    for x in 0..num_nodes {
+        for y in 0..num_nodes {
+            if rng.random_bool(p) {
+                graph.add_arc(x, y);
+            }
+        }
+    }
+    graph
+}
+
+/// Checks successors, predecessors and arcs of a k²-tree against `graph`.
+fn check_k2tree(graph: &VecGraph, k2tree: &K2Tree) -> Result<()> {
+    graph::eq(graph, k2tree)?;
+    labels::check_impl(k2tree)?;
+    assert_eq!(k2tree.num_arcs(), graph.num_arcs());
+
+    let transpose = VecGraph::from_arcs(
+        (0..graph.num_nodes()).flat_map(|x| graph.successors(x).map(move |y| (y, x))),
+    );
+    for node in 0..graph.num_nodes() {
+        assert_eq!(k2tree.outdegree(node), graph.outdegree(node));
+        let pred = k2tree.predecessors(node).collect::<Vec<_>>();
+        let expected = if node < transpose.num_nodes() {
+            transpose.successors(node).collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        assert_eq!(pred, expected, "predecessors of {}", node);
+        for dst in 0..graph.num_nodes() {
+            assert_eq!(
+                k2tree.has_arc(node, dst),
+                graph.successors(node).any(|s| s == dst),
+                "arc {} -> {}",
+                node,
+                dst
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_k2tree() -> Result<()> {
+    for num_nodes in [0, 1, 2, 5, 16, 17, 100] {
+        for p in [0.0, 0.05, 0.5, 1.0] {
+            let graph = random_graph(num_nodes, p);
+            for k in [2, 3, 4, 8] {
+                check_k2tree(&graph, &K2Tree::from_seq_graph(&graph, k))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_k2tree_out_of_range() {
+    // 5 nodes are padded to a side of 8 when k = 2
+    let graph = VecGraph::from_arcs([(0, 4), (4, 0), (4, 4)]);
+    let k2tree = K2Tree::from_seq_graph(&graph, 2);
+    assert!(k2tree.has_arc(4, 4));
+    for (src, dst) in [(4, 5), (5, 4), (7, 7), (8, 0), (0, 100), (usize::MAX, 0)] {
+        assert!(!k2tree.has_arc(src, dst), "({}, {})", src, dst);
+    }
+    let k2tree = K2Tree::from_seq_graph(&VecGraph::empty(0), 2);
+    assert!(!k2tree.has_arc(0, 0));
+}
+
+#[test]
+fn test_k2tree_duplicates() -> Result<()> {
+    let graph = ArcListGraph::new(5, [(0, 1), (0, 1), (0, 3), (4, 4), (4, 4), (4, 4)]);
+    let k2tree = K2Tree::from_seq_graph(&graph, 2);
+    assert_eq!(k2tree.num_arcs(), 3);
+    graph::eq(&VecGraph::from_arcs([(0, 1), (0, 3), (4, 4)]), &k2tree)?;
+    Ok(())
+}
+
+#[test]
+fn test_k2tree_cnr_2000() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let k2tree = K2Tree::from_seq_graph(&graph, 4);
+    graph::eq(&graph, &k2tree)?;
+    for node in (0..graph.num_nodes()).step_by(1009) {
+        for succ in graph.successors(node) {
+            assert!(k2tree.has_arc(node, succ));
+            assert!(k2tree.predecessors(succ).any(|pred| pred == node));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_k2tree_epserde() -> Result<()> {
+    let graph = random_graph(50, 0.1);
+    let k2tree = K2Tree::from_seq_graph(&graph, 2);
+
+    let mut file = std::io::Cursor::new(vec![]);
+    k2tree.serialize(&mut file)?;
+    let data = file.into_inner();
+    // This is presently needed because of limitations of the borrow checker
+    let data = unsafe { std::mem::transmute::<&'_ [u8], &'static [u8]>(&data) };
+    let deserialized = <K2Tree>::deserialize_eps(data)?;
+    graph::eq(&k2tree, &deserialized)?;
+    for node in 0..graph.num_nodes() {
+        assert!(k2tree
+            .predecessors(node)
+            .eq(deserialized.predecessors(node)));
+    }
+    Ok(())
+}
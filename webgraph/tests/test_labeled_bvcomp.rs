@@ -0,0 +1,147 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use webgraph::graphs::vec_graph::LabeledVecGraph;
+use webgraph::prelude::*;
+
+#[derive(Clone)]
+struct GammaSer;
+
+impl<E: Endianness, BW: GammaWrite<E>> BitSerializer<E, BW> for GammaSer {
+    type SerType = u64;
+    fn serialize(&self, value: &u64, bitstream: &mut BW) -> Result<usize, BW::Error> {
+        bitstream.write_gamma(*value)
+    }
+}
+
+#[derive(Clone)]
+struct GammaDeser;
+
+impl<E: Endianness, BR: GammaRead<E>> BitDeserializer<E, BR> for GammaDeser {
+    type DeserType = u64;
+    fn deserialize(&self, bitstream: &mut BR) -> Result<u64, BR::Error> {
+        bitstream.read_gamma()
+    }
+}
+
+#[test]
+fn test_labeled_bvcomp() -> Result<()> {
+    let arcs = [
+        (0, 1, 10),
+        (0, 2, 0),
+        (1, 2, 7),
+        (1, 3, 1),
+        (2, 4, 100),
+        (3, 4, 3),
+        (4, 0, 1000),
+        (4, 1, 5),
+    ];
+    let graph = LabeledVecGraph::<u64>::from_arcs(arcs.iter().copied());
+
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("graph");
+    let labels_basename = tmp.path().join("graph-labelled");
+
+    BvComp::single_thread_labeled::<BE, _, _>(
+        &basename,
+        &labels_basename,
+        &graph,
+        GammaSer,
+        "it.unimi.dsi.webgraph.labelling.GammaCodedIntLabel(weight)",
+        CompFlags::default(),
+    )?;
+
+    let properties = std::fs::read_to_string(labels_basename.with_extension(PROPERTIES_EXTENSION))?;
+    assert!(properties.contains("underlyinggraph=graph\n"));
+
+    let labeled = load_labeled::<BE, _, _>(&labels_basename, GammaDeser)?;
+    assert_eq!(labeled.num_nodes(), 5);
+    graph::eq_labeled(&graph, &labeled)?;
+
+    for node in 0..graph.num_nodes() {
+        assert_eq!(
+            RandomAccessLabeling::labels(&labeled, node).collect::<Vec<_>>(),
+            RandomAccessLabeling::labels(&graph, node).collect::<Vec<_>>(),
+        );
+    }
+
+    // The labeled graph can be permuted in parallel
+    let perm = [3, 0, 4, 1, 2];
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+    let expected = permute_labeled(&graph, &perm.as_slice(), 2, GammaSer, GammaDeser)?;
+    let permuted = permute_labeled_split(
+        &labeled,
+        &perm.as_slice(),
+        2,
+        GammaSer,
+        GammaDeser,
+        &threads,
+    )?;
+    graph::eq_labeled(&expected, &permuted)?;
+    Ok(())
+}
+
+#[test]
+fn test_parallel_labeled_bvcomp() -> Result<()> {
+    let arcs = (0..1000_usize).flat_map(|src| {
+        (0..src % 7).map(move |i| (src, (src * 31 + i * 97) % 1000, (src ^ i) as u64))
+    });
+    let graph = LabeledVecGraph::<u64>::from_arcs(arcs);
+
+    let tmp = tempfile::tempdir()?;
+    let seq_basename = tmp.path().join("seq");
+    let seq_labels_basename = tmp.path().join("seq-labelled");
+    let par_basename = tmp.path().join("par");
+    let par_labels_basename = tmp.path().join("par-labelled");
+    let label_spec = "it.unimi.dsi.webgraph.labelling.GammaCodedIntLabel(weight)";
+
+    let seq_lens = BvComp::single_thread_labeled::<BE, _, _>(
+        &seq_basename,
+        &seq_labels_basename,
+        &graph,
+        GammaSer,
+        label_spec,
+        CompFlags::default(),
+    )?;
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+    let par_lens = BvComp::parallel_labeled::<BE, _, _>(
+        &par_basename,
+        &par_labels_basename,
+        &graph,
+        GammaSer,
+        label_spec,
+        CompFlags::default(),
+        &threads,
+        tempfile::tempdir()?,
+    )?;
+    assert_eq!(seq_lens, par_lens);
+
+    for (seq, par, extension) in [
+        (&seq_basename, &par_basename, GRAPH_EXTENSION),
+        (&seq_basename, &par_basename, OFFSETS_EXTENSION),
+        (&seq_basename, &par_basename, PROPERTIES_EXTENSION),
+        (&seq_labels_basename, &par_labels_basename, LABELS_EXTENSION),
+        (
+            &seq_labels_basename,
+            &par_labels_basename,
+            LABELOFFSETS_EXTENSION,
+        ),
+    ] {
+        assert_eq!(
+            std::fs::read(seq.with_extension(extension))?,
+            std::fs::read(par.with_extension(extension))?,
+            "{}",
+            extension
+        );
+    }
+
+    let labeled = load_labeled::<BE, _, _>(&par_labels_basename, GammaDeser)?;
+    graph::eq_labeled(&graph, &labeled)?;
+    Ok(())
+}
@@ -10,7 +10,7 @@ use webgraph::prelude::*;
 
 #[test]
 fn test_eq() -> anyhow::Result<()> {
-    let arcs = vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 4), (3, 4)];
+    let arcs = [(0, 1), (0, 2), (1, 2), (1, 3), (2, 4), (3, 4)];
     let g0 = VecGraph::from_arcs(arcs.iter().copied());
     let mut g1 = g0.clone();
     labels::eq_sorted(&g0, &g1)?;
@@ -19,7 +19,7 @@ fn test_eq() -> anyhow::Result<()> {
     assert!(labels::eq_sorted(&g0, &g1).is_err());
     assert!(graph::eq(&g0, &g1).is_err());
 
-    let arcs = vec![
+    let arcs = [
         (0, 1, 0),
         (0, 2, 1),
         (1, 2, 2),
@@ -40,8 +40,8 @@ fn test_eq() -> anyhow::Result<()> {
 #[test]
 fn test_graph_eq_error() -> anyhow::Result<()> {
     // Test eq function with different successors
-    let arcs1 = vec![(0, 0), (0, 2), (1, 2)];
-    let arcs2 = vec![(0, 0), (0, 1), (1, 2)]; // Different successor for node 0
+    let arcs1 = [(0, 0), (0, 2), (1, 2)];
+    let arcs2 = [(0, 0), (0, 1), (1, 2)]; // Different successor for node 0
     let g1 = VecGraph::from_arcs(arcs1.iter().copied());
     let mut g2 = VecGraph::from_arcs(arcs2.iter().copied());
 
@@ -65,8 +65,8 @@ fn test_graph_eq_error() -> anyhow::Result<()> {
     }
 
     // Test eq_labeled function with different labels
-    let labeled_arcs1 = vec![(0, 1, "a"), (0, 2, "b"), (1, 2, "c")];
-    let labeled_arcs2 = vec![(0, 1, "a"), (0, 2, "x"), (1, 2, "c")]; // Different label for arc (0,2)
+    let labeled_arcs1 = [(0, 1, "a"), (0, 2, "b"), (1, 2, "c")];
+    let labeled_arcs2 = [(0, 1, "a"), (0, 2, "x"), (1, 2, "c")]; // Different label for arc (0,2)
     let lg1 = LabeledVecGraph::from_arcs(labeled_arcs1.iter().copied());
     let lg2 = LabeledVecGraph::from_arcs(labeled_arcs2.iter().copied());
 
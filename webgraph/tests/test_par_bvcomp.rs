@@ -21,13 +21,27 @@ fn test_par_bvcomp() -> Result<()> {
         .filter_level(log::LevelFilter::Debug)
         .try_init()?;
 
-    _test_par_bvcomp("../data/cnr-2000")?;
+    _test_par_bvcomp("../data/cnr-2000", "-par", CompFlags::default(), 1..10)?;
     Ok(())
 }
 
-fn _test_par_bvcomp(basename: &str) -> Result<()> {
-    let comp_flags = CompFlags::default();
-    let tmp_basename = PathBuf::from(String::from(basename) + "-par");
+#[test]
+fn test_par_bvcomp_optimal_refs() -> Result<()> {
+    let comp_flags = CompFlags {
+        ref_selection: RefSelection::Optimal { chunk_size: 1000 },
+        ..CompFlags::default()
+    };
+    _test_par_bvcomp("../data/cnr-2000", "-par-opt", comp_flags, [1, 4])?;
+    Ok(())
+}
+
+fn _test_par_bvcomp(
+    basename: &str,
+    suffix: &str,
+    comp_flags: CompFlags,
+    thread_nums: impl IntoIterator<Item = usize>,
+) -> Result<()> {
+    let tmp_basename = PathBuf::from(String::from(basename) + suffix);
 
     // load the graph
     let graph = webgraph::graphs::bvgraph::sequential::BvGraphSeq::with_basename(basename)
@@ -38,7 +52,7 @@ fn _test_par_bvcomp(basename: &str) -> Result<()> {
     graph_filename.set_extension(GRAPH_EXTENSION);
 
     let expected_size = graph_filename.metadata()?.len();
-    for thread_num in 1..10 {
+    for thread_num in thread_nums {
         log::info!("Testing with {} threads", thread_num);
         // create a threadpool and make the compression use it, this way
         // we can test with different number of threads
@@ -5,7 +5,11 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
 use lender::*;
+use webgraph::graphs::vec_graph::LabeledVecGraph;
+use webgraph::labels::serializers::CodedInt;
 use webgraph::prelude::*;
 
 #[test]
@@ -48,3 +52,107 @@ fn test_permuted() {
         ]
     );
 }
+
+#[test]
+fn test_labeled_permuted() {
+    let graph = LabeledVecGraph::<u32>::from_arcs([(0, 1, 10), (0, 2, 20), (2, 0, 30)]);
+    let perm = [2, 0, 1];
+    let permuted_graph = LabeledPermutedGraph {
+        graph: &graph,
+        perm: &perm,
+    };
+
+    assert_eq!(
+        permuted_graph
+            .iter()
+            .map_into_iter(|(node, successors)| (node, successors.into_iter().collect()))
+            .collect::<Vec<_>>(),
+        vec![(2, vec![(0, 10), (1, 20)]), (0, vec![]), (1, vec![(2, 30)])]
+    );
+}
+
+#[test]
+fn test_permute_labeled() -> Result<()> {
+    let graph = LabeledVecGraph::<u32>::from_arcs([
+        (0, 1, 1),
+        (0, 3, 2),
+        (1, 2, 3),
+        (2, 0, 4),
+        (2, 4, 5),
+        (3, 3, 6),
+        (4, 1, 7),
+    ]);
+    let perm = [3, 0, 4, 1, 2];
+    let mut arcs = vec![];
+    for_!((node, succ) in graph.iter() {
+        for (dst, label) in succ {
+            arcs.push((perm[node], perm[dst], label));
+        }
+    });
+    arcs.sort();
+    let expected = LabeledVecGraph::<u32>::from_arcs(arcs);
+    let serde = CodedInt::<u32>::new(Codes::Gamma);
+
+    let permuted = permute_labeled(&graph, &perm.as_slice(), 2, serde, serde)?;
+    graph::eq_labeled(&expected, &permuted)?;
+
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+    let permuted = permute_labeled_split(&graph, &perm.as_slice(), 2, serde, serde, &threads)?;
+    graph::eq_labeled(&expected, &permuted)?;
+
+    // The identity permutation leaves the graph unchanged
+    let identity = [0, 1, 2, 3, 4];
+    let permuted = permute_labeled(&graph, &identity.as_slice(), 10, serde, serde)?;
+    graph::eq_labeled(&graph, &permuted)?;
+    assert!(permute_labeled(&graph, &[0, 1].as_slice(), 10, serde, serde).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_random_access_permuted() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let num_nodes = graph.num_nodes();
+    // Reverse the nodes, swapping the first and the second half
+    let perm = (0..num_nodes)
+        .map(|x| (num_nodes - 1 - x + num_nodes / 2) % num_nodes)
+        .collect::<Vec<_>>();
+    let mut inv_perm = vec![0; num_nodes];
+    for (x, &p) in perm.iter().enumerate() {
+        inv_perm[p] = x;
+    }
+    let permuted = RandomAccessPermutedGraph {
+        graph: &graph,
+        perm: &perm.as_slice(),
+        inv_perm: &inv_perm.as_slice(),
+    };
+
+    assert_eq!(permuted.num_nodes(), num_nodes);
+    assert_eq!(permuted.num_arcs(), graph.num_arcs());
+    labels::check_impl(&permuted)?;
+
+    let sorted = permute(&graph, &perm.as_slice(), 1_000_000)?;
+    let sorted = VecGraph::from_lender(sorted.iter());
+    for node in 0..num_nodes {
+        assert!(permuted.successors(node).eq(sorted.successors(node)));
+    }
+
+    let mut next = 0;
+    for iter in permuted.split_iter(4) {
+        for_!((node, succ) in iter {
+            assert_eq!(node, next);
+            assert!(succ.eq(sorted.successors(node)));
+            next += 1;
+        });
+    }
+    assert_eq!(next, num_nodes);
+
+    for node in [0, 1, 1000, num_nodes - 1] {
+        let mut succ = permuted.successors_unsorted(node).collect::<Vec<_>>();
+        assert_eq!(succ.len(), permuted.outdegree(node));
+        succ.sort();
+        assert_eq!(succ, permuted.successors(node).collect::<Vec<_>>());
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use webgraph::graphs::families::{Complete, CompleteBipartite, Cycle, Grid, Path, Star};
+use webgraph::graphs::random::{
+    BarabasiAlbert, ConfigurationModel, ErdosRenyi, RandomGeometric, Rmat, WattsStrogatz,
+};
+use webgraph::prelude::*;
+
+/// Returns a graph containing each edge in both directions.
+fn undirected(num_nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> BTreeGraph {
+    let mut graph = BTreeGraph::empty(num_nodes);
+    for (x, y) in edges {
+        graph.add_arc(x, y);
+        graph.add_arc(y, x);
+    }
+    graph
+}
+
+/// Checks that a graph is loopless and symmetric.
+fn check_undirected(graph: &impl RandomAccessGraph) {
+    for_!((node, succ) in graph.iter() {
+        for succ in succ {
+            assert_ne!(node, succ);
+            assert!(graph.has_arc(succ, node));
+        }
+    });
+}
+
+#[test]
+fn test_families() -> Result<()> {
+    graph::eq(
+        &Grid::new(2, 3),
+        &undirected(6, [(0, 1), (1, 2), (3, 4), (4, 5), (0, 3), (1, 4), (2, 5)]),
+    )?;
+    graph::eq(&Path::new(4), &undirected(4, [(0, 1), (1, 2), (2, 3)]))?;
+    graph::eq(
+        &Cycle::new(4),
+        &undirected(4, [(0, 1), (1, 2), (2, 3), (3, 0)]),
+    )?;
+    graph::eq(&Cycle::new(2), &undirected(2, [(0, 1)]))?;
+    graph::eq(&Star::new(4), &undirected(4, [(0, 1), (0, 2), (0, 3)]))?;
+    graph::eq(&Complete::new(3), &undirected(3, [(0, 1), (0, 2), (1, 2)]))?;
+    graph::eq(
+        &CompleteBipartite::new(2, 2),
+        &undirected(4, [(0, 2), (0, 3), (1, 2), (1, 3)]),
+    )?;
+
+    for n in 0..6 {
+        labels::check_impl(Path::new(n))?;
+        labels::check_impl(Cycle::new(n))?;
+        labels::check_impl(Star::new(n))?;
+        labels::check_impl(Complete::new(n))?;
+        labels::check_impl(Grid::new(n, 3))?;
+        labels::check_impl(Grid::new(3, n))?;
+        labels::check_impl(CompleteBipartite::new(n, 2))?;
+        labels::check_impl(CompleteBipartite::new(2, n))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_barabasi_albert() -> Result<()> {
+    let graph = BarabasiAlbert::new(1000, 3, 0);
+    assert_eq!(graph.num_arcs(), 2 * 3 * 997);
+    labels::check_impl(&graph)?;
+    check_undirected(&graph);
+    for node in 3..1000 {
+        assert!(graph.outdegree(node) >= 3);
+    }
+    graph::eq(&graph, &BarabasiAlbert::new(1000, 3, 0))?;
+    assert!(graph::eq(&graph, &BarabasiAlbert::new(1000, 3, 1)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rmat() -> Result<()> {
+    let graph = Rmat::new(1000, 10000, Rmat::GRAPH500, 0);
+    assert!(graph.num_arcs() <= 10000);
+    assert!(graph.num_arcs() > 5000);
+    labels::check_impl(&graph)?;
+    // The top-left quadrant is the densest
+    assert!(graph.outdegree(0) > graph.outdegree(999));
+    graph::eq(&graph, &Rmat::new(1000, 10000, Rmat::GRAPH500, 0))?;
+    // All mass on the bottom-right quadrant: only the last node of a power
+    // of two is reachable
+    let graph = Rmat::new(4, 10, [0.0, 0.0, 0.0, 1.0], 0);
+    assert_eq!(graph.num_arcs(), 1);
+    assert!(graph.has_arc(3, 3));
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "never sample an arc")]
+fn test_rmat_out_of_matrix() {
+    Rmat::new(3, 10, [0.0, 0.0, 0.0, 1.0], 0);
+}
+
+#[test]
+fn test_watts_strogatz() -> Result<()> {
+    // Without rewiring we get the ring lattice
+    let lattice = WattsStrogatz::new(10, 4, 0.0, 0);
+    for node in 0..10 {
+        let mut expected = [1, 2, 8, 9].map(|d| (node + d) % 10);
+        expected.sort();
+        assert_eq!(lattice.successors(node).collect::<Vec<_>>(), expected);
+    }
+
+    let graph = WattsStrogatz::new(1000, 6, 0.2, 0);
+    assert_eq!(graph.num_arcs(), 6000);
+    labels::check_impl(&graph)?;
+    check_undirected(&graph);
+    graph::eq(&graph, &WattsStrogatz::new(1000, 6, 0.2, 0))?;
+    Ok(())
+}
+
+#[test]
+fn test_configuration_model() -> Result<()> {
+    let degrees = (0..1000).map(|node| 1 + node % 8).collect::<Vec<_>>();
+    let graph = ConfigurationModel::new(&degrees, 0);
+    labels::check_impl(&graph)?;
+    check_undirected(&graph);
+    for (node, &degree) in degrees.iter().enumerate() {
+        assert!(graph.outdegree(node) <= degree);
+    }
+    // Few stubs are lost to loops and multiple edges
+    assert!(graph.num_arcs() > degrees.iter().sum::<usize>() as u64 * 9 / 10);
+    graph::eq(&graph, &ConfigurationModel::new(&degrees, 0))?;
+    Ok(())
+}
+
+#[test]
+fn test_random_geometric() -> Result<()> {
+    let radius = 0.05;
+    let graph = RandomGeometric::new(1000, radius, 0);
+    labels::check_impl(&graph)?;
+    check_undirected(&graph);
+    let pos = graph.positions();
+    let close = |x: usize, y: usize| {
+        let (dx, dy) = (pos[x].0 - pos[y].0, pos[x].1 - pos[y].1);
+        dx * dx + dy * dy <= radius * radius
+    };
+    for x in 0..1000 {
+        let succ = graph.successors(x).collect::<Vec<_>>();
+        let expected = (0..1000)
+            .filter(|&y| y != x && close(x, y))
+            .collect::<Vec<_>>();
+        assert_eq!(succ, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_random_compress() -> Result<()> {
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("random");
+
+    let graph = BarabasiAlbert::new(10000, 5, 0);
+    BvComp::parallel_graph::<BE>(
+        &basename,
+        &graph,
+        CompFlags::default(),
+        &thread_pool,
+        tempfile::tempdir()?,
+    )?;
+    let compressed = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&compressed, &graph)?;
+
+    let graph = ErdosRenyi::new(100, 0.1, 0);
+    BvComp::parallel_graph::<BE>(
+        &basename,
+        &graph,
+        CompFlags::default(),
+        &thread_pool,
+        tempfile::tempdir()?,
+    )?;
+    let compressed = BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&compressed, &graph)?;
+    Ok(())
+}
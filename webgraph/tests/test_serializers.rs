@@ -0,0 +1,158 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use webgraph::graphs::vec_graph::LabeledVecGraph;
+use webgraph::labels::serializers::*;
+use webgraph::prelude::*;
+
+/// Serializes the values, checking the number of bits written, and
+/// deserializes them back.
+fn round_trip<S>(serde: &S, values: &[S::SerType]) -> Result<()>
+where
+    S: BitSerializer<BE, BufBitWriter<BE, MemWordWriterVec<u64, Vec<u64>>>>
+        + for<'a> BitDeserializer<
+            BE,
+            BufBitReader<BE, MemWordReader<u64, &'a [u64]>>,
+            DeserType = S::SerType,
+        >,
+    S::SerType: PartialEq + std::fmt::Debug,
+{
+    let mut writer = BufBitWriter::<BE, _>::new(MemWordWriterVec::new(Vec::new()));
+    let mut written = 0;
+    for value in values {
+        written += serde.serialize(value, &mut writer)?;
+    }
+    writer.write_bits(1, 1)?;
+    let data = writer.into_inner()?.into_inner();
+
+    let mut reader = BufBitReader::<BE, _>::new(MemWordReader::new(data.as_slice()));
+    for value in values {
+        assert_eq!(&serde.deserialize(&mut reader)?, value);
+    }
+    assert_eq!(reader.bit_pos()?, written as u64);
+    assert_eq!(reader.read_bits(1)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    round_trip(
+        &CodedInt::<u32>::new(Codes::Gamma),
+        &[0, 1, 2, 1000, u32::MAX],
+    )?;
+    round_trip(
+        &CodedInt::<u64>::new(Codes::Zeta { k: 3 }),
+        &[0, 7, 1 << 40],
+    )?;
+    round_trip(&CodedInt::<u8>::new(Codes::Delta), &[0, 1, 255])?;
+    round_trip(
+        &ZigZagInt::<i64>::new(Codes::Delta),
+        &[0, -1, 1, -2, i64::MIN + 1, i64::MAX],
+    )?;
+    round_trip(&ZigZagInt::<i8>::new(Codes::Gamma), &[0, -128, 127])?;
+    round_trip(&FixedWidthInt::<u16>::new(11), &[0, 1, 2047])?;
+    round_trip(
+        &FixedWidthFloat::<f32>::new(),
+        &[0.0, -1.5, f32::MAX, f32::INFINITY],
+    )?;
+    round_trip(
+        &FixedWidthFloat::<f64>::new(),
+        &[0.0, f64::EPSILON, f64::NEG_INFINITY],
+    )?;
+    round_trip(
+        &ByteString::new(Codes::Gamma),
+        &[vec![], b"webgraph".to_vec(), vec![0, 255]],
+    )?;
+    round_trip(
+        &VecOf::new(Codes::Gamma, ZigZagInt::<i32>::new(Codes::Gamma)),
+        &[vec![], vec![-3, 0, 7]],
+    )?;
+    round_trip(
+        &(
+            CodedInt::<usize>::new(Codes::Gamma),
+            FixedWidthFloat::<f64>::new(),
+        ),
+        &[(0, 0.5), (17, -2.0)],
+    )?;
+    round_trip(
+        &(
+            CodedInt::<u32>::new(Codes::Unary),
+            ByteString::new(Codes::Delta),
+            VecOf::new(Codes::Gamma, FixedWidthInt::<u64>::new(64)),
+        ),
+        &[(3, b"a".to_vec(), vec![u64::MAX, 0])],
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_zigzag() {
+    // The zigzag encoding interleaves negative and nonnegative integers
+    assert_eq!(
+        [0_i32, -1, 1, -2, 2].map(SignedInt::to_zigzag),
+        [0, 1, 2, 3, 4]
+    );
+    assert_eq!(i16::MIN.to_zigzag(), u16::MAX as u64);
+}
+
+#[test]
+fn test_transpose_with_serializers() -> Result<()> {
+    let arcs = [
+        (0, 1, (1, -1.0)),
+        (0, 2, (2, 0.5)),
+        (1, 2, (3, f64::INFINITY)),
+        (2, 0, (4, -0.0)),
+        (2, 1, (5, 1E100)),
+    ];
+    let g = LabeledVecGraph::<(u32, f64)>::from_arcs(arcs);
+    let serde = (
+        CodedInt::<u32>::new(Codes::Gamma),
+        FixedWidthFloat::<f64>::new(),
+    );
+
+    let trans = transpose_labeled(&g, 2, serde, serde)?;
+    let t = LabeledVecGraph::<(u32, f64)>::from_lender(trans.iter());
+    let trans = transpose_labeled(&t, 2, serde, serde)?;
+    let tt = LabeledVecGraph::<(u32, f64)>::from_lender(trans.iter());
+    graph::eq_labeled(&g, &tt)?;
+    Ok(())
+}
+
+#[test]
+fn test_sort_pairs_byte_string() -> Result<()> {
+    let serde = ByteString::new(Codes::Gamma);
+    let mut arcs = (0..100_usize)
+        .map(|i| {
+            let (x, y) = ((i * 37) % 11, (i * 53) % 100);
+            (x, y, format!("{}->{}", x, y).into_bytes())
+        })
+        .collect::<Vec<_>>();
+    let dir = tempfile::tempdir()?;
+    let mut sort_pairs = SortPairs::new_labeled(7, dir.path(), serde, serde)?;
+    for (x, y, label) in &arcs {
+        sort_pairs.push_labeled(*x, *y, label.clone())?;
+    }
+    arcs.sort();
+    assert_eq!(sort_pairs.iter()?.collect::<Vec<_>>(), arcs);
+
+    let arcs = [
+        (0, 1, vec![1_u16, 2]),
+        (1, 0, vec![]),
+        (1, 2, vec![3]),
+        (2, 0, vec![4, 5, 6]),
+    ];
+    let g = LabeledVecGraph::<Vec<u16>>::from_arcs(arcs);
+    let serde = VecOf::new(Codes::Gamma, FixedWidthInt::<u16>::new(16));
+    let trans = transpose_labeled(&g, 2, serde, serde)?;
+    let t = LabeledVecGraph::<Vec<u16>>::from_lender(trans.iter());
+    let trans = transpose_labeled(&t, 2, serde, serde)?;
+    let tt = LabeledVecGraph::<Vec<u16>>::from_lender(trans.iter());
+    graph::eq_labeled(&g, &tt)?;
+    Ok(())
+}
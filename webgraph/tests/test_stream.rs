@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use webgraph::prelude::*;
+
+#[test]
+fn test_stream() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let data = std::fs::read("../data/cnr-2000.graph")?;
+
+    // Uncompressed data, not padded to a multiple of four bytes
+    let mut unpadded = data.clone();
+    while unpadded.last() == Some(&0) {
+        unpadded.pop();
+    }
+    let stream_graph = BvGraphSeq::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load_from_reader(std::io::Cursor::new(unpadded))?;
+    graph::eq(&graph, &stream_graph)?;
+
+    // A reader can be consumed just once
+    let (_, _, cf) = parse_properties::<BE>("../data/cnr-2000.properties")?;
+    let factory = StreamDecoderFactory::<BE>::new(std::io::Cursor::new(data), cf, None)?;
+    let mut decoder = factory.new_decoder()?;
+    assert_eq!(decoder.read_outdegree(), graph.outdegree(0) as u64);
+    assert!(factory.new_decoder().is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn test_stream_zstd() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let data = std::fs::read("../data/cnr-2000.graph")?;
+    let compressed = zstd::encode_all(&data[..], 0)?;
+
+    let tmp = tempfile::tempdir()?;
+    let path = tmp.path().join("cnr-2000.graph.zst");
+    std::fs::write(&path, &compressed)?;
+
+    // A file can be iterated upon many times
+    let zst_graph = BvGraphSeq::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load_from_path(&path)?;
+    graph::eq(&graph, &zst_graph)?;
+    graph::eq(&graph, &zst_graph)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "zstd"))]
+fn test_stream_no_zstd() -> Result<()> {
+    let data = std::fs::read("../data/cnr-2000.graph")?;
+    let compressed = zstd::encode_all(&data[..], 0)?;
+    let (_, _, cf) = parse_properties::<BE>("../data/cnr-2000.properties")?;
+    let factory = StreamDecoderFactory::<BE>::new(std::io::Cursor::new(compressed), cf, None)?;
+    assert!(factory.new_decoder().is_err());
+    Ok(())
+}
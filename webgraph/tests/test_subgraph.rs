@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use lender::*;
+use sux::bits::BitVec;
+use webgraph::prelude::*;
+
+#[test]
+fn test_subgraph() -> Result<()> {
+    let g = VecGraph::from_arcs([(0, 1), (0, 2), (1, 3), (2, 0), (2, 4), (3, 1), (4, 4)]);
+
+    let sub = Subgraph::from_nodes(g.clone(), [4, 0, 2], false);
+    assert_eq!(sub.num_nodes(), 5);
+    assert_eq!(sub.num_arcs(), 4);
+    labels::check_impl(&sub)?;
+    assert_eq!(sub.successors(0).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(sub.successors(1).collect::<Vec<_>>(), vec![]);
+    assert_eq!(sub.successors(2).collect::<Vec<_>>(), vec![0, 4]);
+    assert_eq!(sub.outdegree(4), 1);
+    assert_eq!(sub.new_id(2), Some(2));
+    assert_eq!(sub.new_id(3), None);
+    assert_eq!(sub.old_id(4), 4);
+
+    let mut mask = BitVec::new(5);
+    for node in [0, 2, 4] {
+        mask.set(node, true);
+    }
+    let sub = Subgraph::from_mask(g, mask, true);
+    assert_eq!(sub.num_nodes(), 3);
+    assert_eq!(sub.num_arcs(), 4);
+    labels::check_impl(&sub)?;
+    assert_eq!(sub.new_id(4), Some(2));
+    assert_eq!(sub.new_id(1), None);
+    assert_eq!(sub.old_id(1), 2);
+    graph::eq(&sub, &VecGraph::from_arcs([(0, 1), (1, 0), (1, 2), (2, 2)]))?;
+
+    let mut iter = sub.iter_from(1);
+    let (node, succ) = iter.next().unwrap().into_pair();
+    assert_eq!((node, succ.collect::<Vec<_>>()), (1, vec![0, 2]));
+    let (node, succ) = iter.next().unwrap().into_pair();
+    assert_eq!((node, succ.collect::<Vec<_>>()), (2, vec![2]));
+    assert!(iter.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_subgraph_cnr_2000() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let num_nodes = graph.num_nodes();
+    let keep = |node: usize| !node.is_multiple_of(3) && node < num_nodes - 1000;
+
+    // Build the expected subgraph by hand
+    let mut expected = VecGraph::new();
+    let new_id = |node: usize| node - node / 3 - 1;
+    expected.add_node(new_id(num_nodes - 1001));
+    for_!((node, succ) in graph.iter() {
+        if keep(node) {
+            for dst in succ.into_iter().filter(|&x| keep(x)) {
+                expected.add_arc(new_id(node), new_id(dst));
+            }
+        }
+    });
+
+    let sub = Subgraph::from_nodes(graph, (0..num_nodes).filter(|&x| keep(x)), true);
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    assert!(diff_split(&sub, &expected, &threads)?.is_empty());
+    assert_eq!(sub.num_nodes(), expected.num_nodes());
+    assert_eq!(sub.num_arcs(), expected.num_arcs());
+    for node in [0, 1, 1000, sub.num_nodes() - 1] {
+        assert!(sub.successors(node).eq(expected.successors(node)));
+        assert_eq!(sub.new_id(sub.old_id(node)), Some(node));
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use std::cell::Cell;
+use webgraph::prelude::*;
+
+#[test]
+fn test_successors_from() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    for node in (0..graph.num_nodes()).step_by(7) {
+        let succ = graph.successors(node).collect::<Vec<_>>();
+        let mut mins = vec![0, node, usize::MAX];
+        for &s in &succ {
+            mins.extend([s.saturating_sub(1), s, s + 1]);
+        }
+        for min in mins {
+            let expected = succ
+                .iter()
+                .copied()
+                .filter(|&s| s >= min)
+                .collect::<Vec<_>>();
+            let succ_from = graph.successors_from(node, min);
+            assert_eq!(succ_from.len(), expected.len(), "node {} min {}", node, min);
+            assert_eq!(
+                succ_from.collect::<Vec<_>>(),
+                expected,
+                "node {} min {}",
+                node,
+                min
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_has_arc() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    for node in (0..graph.num_nodes()).step_by(11) {
+        let succ = graph.successors(node).collect::<Vec<_>>();
+        for &s in &succ {
+            assert!(graph.has_arc(node, s), "arc {} -> {}", node, s);
+        }
+        let mut candidates = vec![0, node, graph.num_nodes() - 1];
+        for &s in &succ {
+            candidates.extend([s.saturating_sub(1), s + 1]);
+        }
+        for dst in candidates {
+            assert_eq!(
+                graph.has_arc(node, dst),
+                succ.binary_search(&dst).is_ok(),
+                "arc {} -> {}",
+                node,
+                dst
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A decoder factory counting the decoders it creates, that is, the nodes
+/// whose successor list is decoded, and the values read.
+struct CountingFactory<F> {
+    factory: F,
+    decoders: Cell<usize>,
+    reads: Cell<usize>,
+}
+
+struct CountingDecoder<'a, D> {
+    decoder: D,
+    reads: &'a Cell<usize>,
+}
+
+fn count(reads: &Cell<usize>, value: u64) -> u64 {
+    reads.set(reads.get() + 1);
+    value
+}
+
+impl<D: Decode> Decode for CountingDecoder<'_, D> {
+    fn read_outdegree(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_outdegree())
+    }
+    fn read_reference_offset(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_reference_offset())
+    }
+    fn read_block_count(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_block_count())
+    }
+    fn read_block(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_block())
+    }
+    fn read_interval_count(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_interval_count())
+    }
+    fn read_interval_start(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_interval_start())
+    }
+    fn read_interval_len(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_interval_len())
+    }
+    fn read_first_residual(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_first_residual())
+    }
+    fn read_residual(&mut self) -> u64 {
+        count(self.reads, self.decoder.read_residual())
+    }
+}
+
+impl<F: RandomAccessDecoderFactory> RandomAccessDecoderFactory for CountingFactory<F> {
+    type Decoder<'a>
+        = CountingDecoder<'a, F::Decoder<'a>>
+    where
+        Self: 'a;
+
+    fn new_decoder(&self, node: usize) -> Result<Self::Decoder<'_>> {
+        self.decoders.set(self.decoders.get() + 1);
+        Ok(CountingDecoder {
+            decoder: self.factory.new_decoder(node)?,
+            reads: &self.reads,
+        })
+    }
+}
+
+impl<F> CountingFactory<F> {
+    /// Returns the number of decoders created and of values read since the
+    /// last call.
+    fn take(&self) -> (usize, usize) {
+        (self.decoders.take(), self.reads.take())
+    }
+}
+
+#[test]
+fn test_decoded_nodes() -> Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let (num_nodes, num_arcs) = (graph.num_nodes(), graph.num_arcs());
+    let (_, _, cf) = parse_properties::<BE>("../data/cnr-2000.properties")?;
+    let counting = CountingFactory {
+        factory: graph.into_inner(),
+        decoders: Cell::new(0),
+        reads: Cell::new(0),
+    };
+    let graph = BvGraph::new(
+        &counting,
+        num_nodes,
+        num_arcs,
+        cf.compression_window,
+        cf.min_interval_length,
+    );
+
+    // decoders and reads used by successors, has_arc and successors_from
+    let (mut full, mut arc, mut from) = ((0, 0), (0, 0), (0, 0));
+    for node in (0..num_nodes).step_by(13) {
+        let succ = graph.successors(node).collect::<Vec<_>>();
+        counting.take();
+        for &s in &succ {
+            let mut iter = graph.successors(node);
+            assert!(iter.any(|x| x == s));
+            let (succ_decoders, succ_reads) = counting.take();
+            full = (full.0 + succ_decoders, full.1 + succ_reads);
+
+            assert!(graph.has_arc(node, s));
+            let (decoders, reads) = counting.take();
+            // the reference is visited only when the arc is copied
+            assert!(decoders <= succ_decoders, "arc {} -> {}", node, s);
+            arc = (arc.0 + decoders, arc.1 + reads);
+
+            assert_eq!(graph.successors_from(node, s).next(), Some(s));
+            let (decoders, reads) = counting.take();
+            assert!(decoders <= succ_decoders, "arc {} -> {}", node, s);
+            from = (from.0 + decoders, from.1 + reads);
+        }
+    }
+    assert!(arc.0 < full.0, "{:?} >= {:?}", arc, full);
+    assert!(arc.1 < full.1, "{:?} >= {:?}", arc, full);
+    // successors_from decodes the same lists as successors, but it never
+    // stores intervals or copy blocks preceding the target
+    assert!(from.0 <= full.0, "{:?} > {:?}", from, full);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use anyhow::Result;
+use dsi_bitstream::prelude::*;
+use webgraph::prelude::*;
+use webgraph::transform::simplify;
+
+#[test]
+fn test_union_random_access() -> Result<()> {
+    let g0 = VecGraph::from_arcs([(0, 1), (0, 3), (1, 2), (2, 0), (2, 2), (3, 1)]);
+    let g1 = VecGraph::from_arcs([(0, 3), (1, 0), (2, 2), (4, 1), (4, 4)]);
+    let union = UnionGraph(g0, g1);
+
+    assert_eq!(union.num_nodes(), 5);
+    assert_eq!(union.num_arcs(), 9);
+    labels::check_impl(&union)?;
+    assert_eq!(union.successors(0).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(union.successors(2).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(union.successors(4).collect::<Vec<_>>(), vec![1, 4]);
+    assert_eq!(union.outdegree(1), 2);
+    assert_eq!(union.outdegree(3), 1);
+    Ok(())
+}
+
+#[test]
+fn test_symmetrized() -> Result<()> {
+    let arcs = [(0, 1), (1, 0), (1, 2), (2, 2), (3, 0)];
+    let graph = VecGraph::from_arcs(arcs);
+    let transpose = VecGraph::from_arcs(arcs.map(|(x, y)| (y, x)));
+
+    let symm = Symmetrized::new(graph.clone(), transpose.clone(), true);
+    assert_eq!(symm.num_arcs(), 7);
+    labels::check_impl(&symm)?;
+    assert_eq!(symm.successors(0).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(symm.successors(2).collect::<Vec<_>>(), vec![1, 2]);
+
+    let symm = Symmetrized::new(graph, transpose, false);
+    assert_eq!(symm.num_arcs(), 6);
+    labels::check_impl(&symm)?;
+    assert_eq!(symm.successors(2).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(symm.outdegree(2), 1);
+    assert_eq!(symm.num_arcs_hint(), Some(6));
+    Ok(())
+}
+
+#[test]
+fn test_symmetrized_cnr_2000() -> Result<()> {
+    let load = |basename| BvGraph::with_basename(basename).endianness::<BE>().load();
+    let symm = Symmetrized::new(
+        load("../data/cnr-2000")?,
+        load("../data/cnr-2000-t")?,
+        false,
+    );
+    let simplified = simplify(&load("../data/cnr-2000")?, 1_000_000)?;
+    graph::eq(&symm, &simplified)?;
+
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    let simplified = VecGraph::from_lender(simplified.iter());
+    assert!(diff_split(&symm, &simplified, &threads)?.is_empty());
+    assert_eq!(symm.num_arcs(), simplified.num_arcs());
+    for node in [0, 1, 1000, symm.num_nodes() - 1] {
+        assert!(symm.successors(node).eq(simplified.successors(node)));
+    }
+    Ok(())
+}
@@ -6,17 +6,22 @@
  */
 
 use dsi_bitstream::codes::{GammaRead, GammaWrite};
+use dsi_bitstream::traits::BE;
 use dsi_bitstream::traits::NE;
 use dsi_bitstream::traits::{BitRead, BitWrite};
-use webgraph::graphs::vec_graph::LabeledVecGraph;
-use webgraph::prelude::{transpose, transpose_labeled};
+use lender::*;
+use webgraph::graphs::bvgraph::{BvComp, BvGraph, CompFlags};
+use webgraph::graphs::vec_graph::{LabeledVecGraph, VecGraph};
+use webgraph::prelude::{
+    transpose, transpose_labeled, transpose_labeled_split, transpose_split, SplitLabeling,
+};
 use webgraph::traits::labels::SequentialLabeling;
+use webgraph::traits::RandomAccessGraph;
 use webgraph::traits::{graph, BitDeserializer, BitSerializer};
-use webgraph::utils::sort_pairs::{BitReader, BitWriter};
+use webgraph::utils::sort_pairs::{radix_sort_triples, BitReader, BitWriter, SortLabel, Triple};
 
 #[test]
 fn test_transpose() -> anyhow::Result<()> {
-    use webgraph::graphs::vec_graph::VecGraph;
     let arcs = vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 4), (3, 4)];
     let g = VecGraph::from_arcs(arcs);
 
@@ -35,6 +40,12 @@ fn test_transpose_labeled() -> anyhow::Result<()> {
     #[derive(Clone, Copy, PartialEq, Debug)]
     struct Payload(f64);
 
+    impl SortLabel for Payload {
+        fn sort_triples(batch: &mut [Triple<Self>]) {
+            radix_sort_triples(batch)
+        }
+    }
+
     #[derive(Clone, Copy, PartialEq, Debug)]
     struct BD {}
 
@@ -96,5 +107,69 @@ fn test_transpose_labeled() -> anyhow::Result<()> {
     let g4 = LabeledVecGraph::from_lender(g.iter());
 
     graph::eq_labeled(&g3, &g4)?;
+
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(2).build()?;
+    let trans = transpose_labeled_split(&g, 2, BS {}, BD {}, &threads)?;
+    let g5 = LabeledVecGraph::<Payload>::from_lender(trans.iter());
+    graph::eq_labeled(&g2, &g5)?;
+    Ok(())
+}
+
+#[test]
+fn test_transpose_split() -> anyhow::Result<()> {
+    let graph = BvGraph::with_basename("../data/cnr-2000")
+        .endianness::<BE>()
+        .load()?;
+    let expected = VecGraph::from_lender(&transpose(&graph, 100_000)?);
+
+    let threads = rayon::ThreadPoolBuilder::new().num_threads(3).build()?;
+    let trans = transpose_split(&graph, 100_000, &threads)?;
+    assert_eq!(trans.num_nodes(), graph.num_nodes());
+    graph::eq(&expected, &trans)?;
+
+    // Lenders starting in the middle of the graph
+    for from in [0, 1000, 108_519, 325_556, 325_557] {
+        let mut iter = trans.iter_from(from);
+        let mut expected_iter = expected.iter_from(from);
+        for _ in 0..10 {
+            match (iter.next(), expected_iter.next()) {
+                (Some((node, succ)), Some((expected_node, expected_succ))) => {
+                    assert_eq!(node, expected_node);
+                    assert!(succ.into_iter().eq(expected_succ));
+                }
+                (None, None) => break,
+                _ => panic!("Different number of nodes from {}", from),
+            }
+        }
+    }
+
+    // Any number of parts can be requested
+    for how_many in [1, 2, 3, 5] {
+        let mut next_node = 0;
+        for mut lender in trans.split_iter(how_many) {
+            while let Some((node, succ)) = lender.next() {
+                assert_eq!(node, next_node);
+                assert!(succ.into_iter().eq(expected.successors(node)));
+                next_node += 1;
+            }
+        }
+        assert_eq!(next_node, graph.num_nodes());
+    }
+
+    // The result can be compressed in parallel directly
+    let tmp = tempfile::tempdir()?;
+    let basename = tmp.path().join("transpose");
+    BvComp::parallel_iter::<BE, _>(
+        &basename,
+        trans.split_iter(3),
+        trans.num_nodes(),
+        CompFlags::default(),
+        &threads,
+        tempfile::tempdir()?,
+    )?;
+    let compressed = webgraph::graphs::bvgraph::BvGraphSeq::with_basename(&basename)
+        .endianness::<BE>()
+        .load()?;
+    graph::eq(&expected, &compressed)?;
     Ok(())
 }